@@ -0,0 +1,419 @@
+//! aichat adapter.
+//!
+//! Parses aichat session logs and converts them to Spool format.
+//!
+//! aichat stores each saved session as a JSONL file:
+//! - `~/.config/aichat/sessions/<name>.jsonl`
+//!
+//! The first line is session metadata (model, temperature, etc.), and every
+//! line after that is one message with a `role` and a `content` that is
+//! either plain text or a tool call (name, arguments, and result bundled
+//! together rather than split across separate request/response lines the
+//! way Claude Code and Codex record them).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use spool_format::{
+    Entry, EntryId, PromptEntry, ResponseEntry, SessionEndState, SessionEntry, ToolCallEntry,
+    ToolOutput, ToolResultEntry,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::{AdapterRegistration, AgentType, SessionInfo};
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Find all aichat sessions on the system.
+pub fn find_sessions() -> Result<Vec<SessionInfo>> {
+    let mut sessions: Vec<SessionInfo> = list_session_paths()?
+        .into_iter()
+        .filter_map(|path| session_info_for(&path).ok())
+        .collect();
+
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(sessions)
+}
+
+/// Cheaply list every aichat session file, without reading any of them.
+pub fn list_session_paths() -> Result<Vec<PathBuf>> {
+    let sessions_dir = get_aichat_dir()?.join("sessions");
+
+    if !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&sessions_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Read a single session file's metadata and build its [`SessionInfo`].
+/// This is the expensive half of session discovery - it reads and parses
+/// the whole file to find the first user prompt for the title.
+pub fn session_info_for(path: &Path) -> Result<SessionInfo> {
+    let metadata = fs::metadata(path).ok();
+    let modified_at = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .map(DateTime::<Utc>::from);
+    let created_at = metadata
+        .as_ref()
+        .and_then(|m| m.created().ok())
+        .map(DateTime::<Utc>::from);
+
+    let first_prompt = read_first_prompt(path);
+    let title = first_prompt
+        .as_ref()
+        .map(|p| truncate_first_prompt(p, 200))
+        .filter(|t| !t.is_empty());
+
+    Ok(SessionInfo {
+        path: path.to_path_buf(),
+        agent: AgentType::Aichat,
+        created_at,
+        modified_at,
+        title,
+        project_dir: None,
+        message_count: None,
+    })
+}
+
+/// Convert an aichat session to Spool format.
+pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
+    let raw_lines = read_raw_lines(&session.path)?;
+
+    let session_meta = raw_lines.iter().find_map(|line| match line {
+        RawLine::Meta(m) => Some(m.clone()),
+        _ => None,
+    });
+
+    let first_prompt_text = raw_lines.iter().find_map(|line| match line {
+        RawLine::Message(m) if m.role == "user" => match &m.content {
+            MessageContent::Text(text) if !text.trim().is_empty() => Some(text.clone()),
+            _ => None,
+        },
+        _ => None,
+    });
+
+    let session_start = session
+        .created_at
+        .or(session.modified_at)
+        .unwrap_or_else(Utc::now);
+
+    let title = session.title.clone().or_else(|| {
+        first_prompt_text
+            .as_ref()
+            .map(|t| truncate_first_prompt(t, 200))
+    });
+
+    let mut extra = HashMap::new();
+    if let Some(ref meta) = session_meta {
+        if let Some(ref model) = meta.model {
+            extra.insert(
+                "x_model".to_string(),
+                serde_json::Value::String(model.clone()),
+            );
+        }
+        if let Some(temperature) = meta.temperature {
+            extra.insert("x_temperature".to_string(), serde_json::json!(temperature));
+        }
+    }
+
+    let session_entry = SessionEntry {
+        id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+        ts: 0,
+        version: "1.0".to_string(),
+        agent: "aichat".to_string(),
+        recorded_at: session_start,
+        agent_version: None,
+        title,
+        author: None,
+        tags: None,
+        duration_ms: None,
+        entry_count: None,
+        tools_used: None,
+        files_modified: None,
+        tool_time_ms: None,
+        tool_invocations: None,
+        subagent_time_ms: None,
+        file_changes: None,
+        first_prompt: first_prompt_text.map(|t| truncate_first_prompt(&t, 200)),
+        schema_url: None,
+        trimmed: None,
+        ended: Some(SessionEndState::Unknown),
+        content_hash: None,
+        extra,
+    };
+
+    let mut entries = vec![Entry::Session(session_entry)];
+    let mut tool_id_map: HashMap<String, EntryId> = HashMap::new();
+    let mut tools_used: Vec<String> = Vec::new();
+    let mut ts: u64 = 0;
+
+    for line in raw_lines {
+        let RawLine::Message(msg) = line else {
+            continue;
+        };
+
+        match msg.content {
+            MessageContent::Text(text) => {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                match msg.role.as_str() {
+                    "user" => {
+                        entries.push(Entry::Prompt(PromptEntry {
+                            id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                            ts,
+                            content: text,
+                            subagent_id: None,
+                            attachments: None,
+                            extra: HashMap::new(),
+                        }));
+                    }
+                    "assistant" => {
+                        entries.push(Entry::Response(ResponseEntry {
+                            id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                            ts,
+                            content: text,
+                            truncated: None,
+                            original_bytes: None,
+                            model: None,
+                            token_usage: None,
+                            subagent_id: None,
+                            extra: HashMap::new(),
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+            MessageContent::ToolCall {
+                tool,
+                arguments,
+                result,
+                is_error,
+            } => {
+                let call_id = tool_id_map
+                    .entry(format!("{}:{}", tool, ts))
+                    .or_insert_with(|| Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)))
+                    .to_owned();
+                tools_used.push(tool.clone());
+
+                entries.push(Entry::ToolCall(ToolCallEntry {
+                    id: call_id,
+                    ts,
+                    tool,
+                    input: arguments,
+                    subagent_id: None,
+                    extra: HashMap::new(),
+                }));
+
+                let result_entry = if is_error {
+                    ToolResultEntry {
+                        id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                        ts,
+                        call_id,
+                        output: None,
+                        error: Some(result),
+                        truncated: None,
+                        original_bytes: None,
+                        subagent_id: None,
+                        redacted: None,
+                        extra: HashMap::new(),
+                    }
+                } else {
+                    ToolResultEntry {
+                        id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                        ts,
+                        call_id,
+                        output: Some(ToolOutput::Text(result)),
+                        error: None,
+                        truncated: None,
+                        original_bytes: None,
+                        subagent_id: None,
+                        redacted: None,
+                        extra: HashMap::new(),
+                    }
+                };
+                entries.push(Entry::ToolResult(result_entry));
+            }
+        }
+
+        // aichat doesn't record per-message timestamps, so entries are
+        // ordered but evenly spaced to keep `ts` strictly non-decreasing.
+        ts += 1;
+    }
+
+    let entry_count = entries.len();
+    if let Entry::Session(ref mut s) = entries[0] {
+        s.entry_count = Some(entry_count);
+        s.duration_ms = Some(ts);
+        if !tools_used.is_empty() {
+            tools_used.sort();
+            tools_used.dedup();
+            s.tools_used = Some(tools_used);
+        }
+        s.ended = Some(SessionEndState::Completed);
+    }
+
+    let session = match &entries[0] {
+        Entry::Session(s) => s.clone(),
+        _ => unreachable!(),
+    };
+
+    let entry_spans = vec![None; entries.len()];
+    Ok(spool_format::SpoolFile {
+        session,
+        entries,
+        unparsed_lines: Vec::new(),
+        entry_spans,
+    })
+}
+
+/// [`AdapterRegistration`] for aichat: its lines have no `type` field, so a
+/// line is recognized as aichat only once the other agents (which all key
+/// off `type`) have had a chance to claim it - a `model` or `role` field is
+/// what distinguishes an aichat meta/message line from something unknown.
+/// aichat has no skill/command installation location yet.
+pub struct AichatRegistration;
+
+impl AdapterRegistration for AichatRegistration {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Aichat
+    }
+
+    fn detect(&self, first_line: &serde_json::Value) -> bool {
+        first_line.get("type").is_none()
+            && (first_line.get("model").is_some() || first_line.get("role").is_some())
+    }
+
+    fn find_sessions(&self) -> Result<Vec<SessionInfo>> {
+        find_sessions()
+    }
+
+    fn list_session_paths(&self) -> Result<Vec<PathBuf>> {
+        list_session_paths()
+    }
+
+    fn session_info_for(&self, path: &Path) -> Result<SessionInfo> {
+        session_info_for(path)
+    }
+
+    fn convert(&self, session: &SessionInfo) -> Result<spool_format::SpoolFile> {
+        convert(session)
+    }
+
+    fn skill_install_dir(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+// ============================================================================
+// Raw aichat JSONL format types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawLine {
+    // Tried first: a meta line has neither `role` nor `content`, so it
+    // fails to deserialize as a message and falls through to `Meta` below.
+    Message(RawMessage),
+    Meta(RawSessionMeta),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawSessionMeta {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    temperature: Option<f64>,
+    #[serde(default)]
+    top_p: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawMessage {
+    role: String,
+    content: MessageContent,
+}
+
+/// A message's content: plain text, or a bundled tool call with its result.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MessageContent {
+    Text(String),
+    ToolCall {
+        tool: String,
+        #[serde(default)]
+        arguments: serde_json::Value,
+        #[serde(default)]
+        result: String,
+        #[serde(rename = "isError", default)]
+        is_error: bool,
+    },
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn read_raw_lines(path: &Path) -> Result<Vec<RawLine>> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open session file: {:?}", path))?;
+    let reader = BufReader::new(file);
+
+    let mut raw_lines = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RawLine>(trimmed) {
+            Ok(parsed) => raw_lines.push(parsed),
+            Err(_) => continue,
+        }
+    }
+
+    Ok(raw_lines)
+}
+
+fn read_first_prompt(path: &Path) -> Option<String> {
+    let raw_lines = read_raw_lines(path).ok()?;
+    raw_lines.into_iter().find_map(|line| match line {
+        RawLine::Message(m) if m.role == "user" => match m.content {
+            MessageContent::Text(text) if !text.trim().is_empty() => Some(text),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn truncate_first_prompt(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &text[..end])
+}
+
+fn get_aichat_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    Ok(home.join(".config").join("aichat"))
+}
@@ -12,19 +12,25 @@
 //! - `sessions-index.json` — quick metadata index for all sessions
 //! - `<session-id>/subagents/agent-<id>.jsonl` — subagent session files
 //! - `agent-<id>.jsonl` — older-format subagent files at project root
+//!
+//! When a `Task` tool call is converted, its subagent transcript is located
+//! via one of the two paths above, recursively converted the same way as
+//! the parent session, and spliced into the entry stream between the
+//! `SubagentStart`/`SubagentEnd` pair so the subagent's own prompts,
+//! responses, and tool calls show up inline rather than as an empty gap.
 
-use crate::{AgentType, SessionInfo};
+use crate::{AdapterRegistration, AgentAdapter, AgentType, SessionInfo, SubagentSpec};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use spool_format::{
     Entry, EntryId, PromptEntry, ResponseEntry, SessionEntry, SubagentEndEntry, SubagentStartEntry,
-    SubagentStatus, ThinkingEntry, TokenUsage, ToolCallEntry, ToolOutput, ToolResultEntry,
+    SubagentStatus, ThinkingEntry, Timestamp, TokenUsage, ToolCallEntry, ToolOutput, ToolResultEntry,
 };
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 // ============================================================================
@@ -33,103 +39,264 @@ use uuid::Uuid;
 
 /// Find all Claude Code sessions on the system.
 pub fn find_sessions() -> Result<Vec<SessionInfo>> {
-    let base_dir = get_claude_dir()?;
-    let projects_dir = base_dir.join("projects");
+    let project_dirs = list_project_dirs()?;
 
-    if !projects_dir.exists() {
-        return Ok(Vec::new());
+    let mut sessions = Vec::new();
+    for project_path in &project_dirs {
+        sessions.extend(scan_project(project_path)?);
     }
 
-    let mut sessions = Vec::new();
+    // Sort by modified time, newest first
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
 
-    for project_entry in fs::read_dir(&projects_dir)? {
-        let project_entry = project_entry?;
-        let project_path = project_entry.path();
+    Ok(sessions)
+}
 
-        if !project_path.is_dir() {
-            continue;
-        }
+/// Like [`find_sessions`], but fans project directories out across a pool
+/// of worker threads sized to the available hardware parallelism. Useful
+/// on machines with hundreds of project directories, where the sequential
+/// scan is dominated by `stat`/`read_dir` latency rather than CPU work.
+///
+/// Project directories are split into contiguous chunks (one per worker)
+/// rather than pulled from a shared queue, so joining the chunk results
+/// back in order is enough to keep output deterministic.
+pub fn find_sessions_parallel() -> Result<Vec<SessionInfo>> {
+    let project_dirs = list_project_dirs()?;
+    if project_dirs.is_empty() {
+        return Ok(Vec::new());
+    }
 
-        // Try to use sessions-index.json for fast metadata
-        let index = read_sessions_index(&project_path);
+    let chunk_size = project_dirs.len().div_ceil(crate::worker_count());
+
+    let mut sessions: Vec<SessionInfo> = std::thread::scope(|scope| {
+        let handles: Vec<_> = project_dirs
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut found = Vec::new();
+                    for project_path in chunk {
+                        if let Ok(scanned) = scan_project(project_path) {
+                            found.extend(scanned);
+                        }
+                    }
+                    found
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(sessions)
+}
 
-        // Find .jsonl session files directly in the project directory
-        for file_entry in fs::read_dir(&project_path)? {
+/// Cheaply list every Claude Code session file across all project
+/// directories, without reading any of them or consulting
+/// `sessions-index.json`.
+pub fn list_session_paths() -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for project_path in &list_project_dirs()? {
+        for file_entry in fs::read_dir(project_path)? {
             let file_entry = file_entry?;
             let file_path = file_entry.path();
-
-            // Only .jsonl files
             if file_path.extension().map(|e| e == "jsonl").unwrap_or(false) {
-                // Skip subagent files (agent-*.jsonl). This also covers
-                // prompt-suggestion files (agent-aprompt_suggestion-*.jsonl).
                 let filename = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
                 if filename.starts_with("agent-") {
                     continue;
                 }
-
-                let session_id = filename.to_string();
-                let metadata = fs::metadata(&file_path).ok();
-                let modified_at = metadata
-                    .as_ref()
-                    .and_then(|m| m.modified().ok())
-                    .map(DateTime::<Utc>::from);
-                let created_at = metadata
-                    .as_ref()
-                    .and_then(|m| m.created().ok())
-                    .map(DateTime::<Utc>::from);
-
-                // Look up metadata from index if available
-                let index_entry = index
-                    .as_ref()
-                    .and_then(|idx| idx.entries.iter().find(|e| e.session_id == session_id));
-
-                let title = index_entry.and_then(|e| {
-                    // Prefer summary over firstPrompt
-                    e.summary
-                        .clone()
-                        .or_else(|| e.first_prompt.clone())
-                        .filter(|s| s != "No prompt")
-                });
-
-                let project_dir = index_entry
-                    .and_then(|e| e.project_path.as_ref().map(PathBuf::from))
-                    .or_else(|| Some(project_path.clone()));
-
-                // Use index timestamps if available (more accurate)
-                let created_at = index_entry
-                    .and_then(|e| e.created.as_ref())
-                    .and_then(|s| s.parse::<DateTime<Utc>>().ok())
-                    .or(created_at);
-                let modified_at = index_entry
-                    .and_then(|e| e.modified.as_ref())
-                    .and_then(|s| s.parse::<DateTime<Utc>>().ok())
-                    .or(modified_at);
-
-                let message_count = index_entry.and_then(|e| e.message_count);
-
-                sessions.push(SessionInfo {
-                    path: file_path,
-                    agent: AgentType::ClaudeCode,
-                    created_at,
-                    modified_at,
-                    title,
-                    project_dir,
-                    message_count,
-                });
+                paths.push(file_path);
             }
         }
     }
+    Ok(paths)
+}
 
-    // Sort by modified time, newest first
-    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+/// Build the [`SessionInfo`] for a single session file, by rescanning its
+/// project directory (cheap - `sessions-index.json` plus a `stat`, no
+/// full-file JSONL parse) and picking out the matching entry.
+pub fn session_info_for(path: &Path) -> Result<SessionInfo> {
+    let project_path = path
+        .parent()
+        .with_context(|| format!("Session file has no parent directory: {:?}", path))?;
+    scan_project(project_path)?
+        .into_iter()
+        .find(|s| s.path == path)
+        .with_context(|| format!("Session not found while rescanning: {:?}", path))
+}
+
+/// List the project directories under `~/.claude/projects/`.
+fn list_project_dirs() -> Result<Vec<PathBuf>> {
+    let base_dir = get_claude_dir()?;
+    let projects_dir = base_dir.join("projects");
+
+    if !projects_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_dir(&projects_dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect())
+}
+
+/// Scan a single project directory for Claude Code session files, using
+/// `sessions-index.json` for fast metadata when present.
+fn scan_project(project_path: &Path) -> Result<Vec<SessionInfo>> {
+    let mut sessions = Vec::new();
+
+    // Try to use sessions-index.json for fast metadata
+    let index = read_sessions_index(project_path);
+
+    // Find .jsonl session files directly in the project directory
+    for file_entry in fs::read_dir(project_path)? {
+        let file_entry = file_entry?;
+        let file_path = file_entry.path();
+
+        // Only .jsonl files
+        if file_path.extension().map(|e| e == "jsonl").unwrap_or(false) {
+            // Skip subagent files (agent-*.jsonl). This also covers
+            // prompt-suggestion files (agent-aprompt_suggestion-*.jsonl).
+            let filename = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            if filename.starts_with("agent-") {
+                continue;
+            }
+
+            let session_id = filename.to_string();
+            let metadata = fs::metadata(&file_path).ok();
+            let modified_at = metadata
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .map(DateTime::<Utc>::from);
+            let created_at = metadata
+                .as_ref()
+                .and_then(|m| m.created().ok())
+                .map(DateTime::<Utc>::from);
+
+            // Look up metadata from index if available
+            let index_entry = index
+                .as_ref()
+                .and_then(|idx| idx.entries.iter().find(|e| e.session_id == session_id));
+
+            let title = index_entry.and_then(|e| {
+                // Prefer summary over firstPrompt
+                e.summary
+                    .clone()
+                    .or_else(|| e.first_prompt.clone())
+                    .filter(|s| s != "No prompt")
+            });
+
+            let project_dir = index_entry
+                .and_then(|e| e.project_path.as_ref().map(PathBuf::from))
+                .or_else(|| Some(project_path.to_path_buf()));
+
+            // Use index timestamps if available (more accurate)
+            let created_at = index_entry
+                .and_then(|e| e.created.as_ref())
+                .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                .or(created_at);
+            let modified_at = index_entry
+                .and_then(|e| e.modified.as_ref())
+                .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                .or(modified_at);
+
+            let message_count = index_entry.and_then(|e| e.message_count);
+
+            sessions.push(SessionInfo {
+                path: file_path,
+                agent: AgentType::ClaudeCode,
+                created_at,
+                modified_at,
+                title,
+                project_dir,
+                message_count,
+            });
+        }
+    }
 
     Ok(sessions)
 }
 
 /// Convert a Claude Code session to Spool format.
 pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
-    let file = fs::File::open(&session.path)
-        .with_context(|| format!("Failed to open session file: {:?}", session.path))?;
+    let raw_lines = read_raw_lines(&session.path)?;
+    convert_raw_lines(&raw_lines, session, &ClaudeCodeAdapter)
+}
+
+/// Claude Code's tool vocabulary and prompt conventions: `Write`/`Edit`
+/// family tools for file modification, `Task` for subagents, and
+/// `<system-reminder>` as the injected tag stripped from prompt text.
+pub struct ClaudeCodeAdapter;
+
+impl AgentAdapter for ClaudeCodeAdapter {
+    fn modified_path(&self, tool: &str, input: &serde_json::Value) -> Option<String> {
+        extract_modified_path(tool, input)
+    }
+
+    fn is_subagent_tool(&self, name: &str) -> Option<SubagentSpec> {
+        if name == "Task" {
+            Some(SubagentSpec {
+                type_key: "subagent_type",
+                description_key: "description",
+            })
+        } else {
+            None
+        }
+    }
+
+    fn system_tags(&self) -> &[&str] {
+        &["system-reminder"]
+    }
+}
+
+/// [`AdapterRegistration`] for Claude Code: lines whose `type` is one of
+/// `user`/`assistant`/`progress`/`summary`/`system` belong to this agent,
+/// and its skill/command helpers live under `~/.claude/commands`.
+pub struct ClaudeCodeRegistration;
+
+impl AdapterRegistration for ClaudeCodeRegistration {
+    fn agent_type(&self) -> AgentType {
+        AgentType::ClaudeCode
+    }
+
+    fn detect(&self, first_line: &serde_json::Value) -> bool {
+        matches!(
+            first_line.get("type").and_then(|v| v.as_str()),
+            Some("user" | "assistant" | "progress" | "summary" | "system")
+        )
+    }
+
+    fn find_sessions(&self) -> Result<Vec<SessionInfo>> {
+        find_sessions()
+    }
+
+    fn list_session_paths(&self) -> Result<Vec<PathBuf>> {
+        list_session_paths()
+    }
+
+    fn session_info_for(&self, path: &Path) -> Result<SessionInfo> {
+        session_info_for(path)
+    }
+
+    fn convert(&self, session: &SessionInfo) -> Result<spool_format::SpoolFile> {
+        convert(session)
+    }
+
+    fn skill_install_dir(&self) -> Option<PathBuf> {
+        Some(dirs::home_dir()?.join(".claude").join("commands"))
+    }
+}
+
+/// Read and parse a Claude Code JSONL transcript, skipping unparseable lines
+/// for forward compatibility. Used for both the top-level session file and
+/// subagent transcripts.
+fn read_raw_lines(path: &Path) -> Result<Vec<RawLine>> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open session file: {:?}", path))?;
     let reader = BufReader::new(file);
 
     let mut raw_lines: Vec<RawLine> = Vec::new();
@@ -145,7 +312,27 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
         }
     }
 
-    convert_raw_lines(&raw_lines, session)
+    Ok(raw_lines)
+}
+
+/// Locate a subagent's transcript file, checking both the newer
+/// `<session-id>/subagents/agent-<id>.jsonl` layout and the older
+/// `agent-<id>.jsonl` layout at the project root.
+fn find_subagent_file(project_dir: &Path, session_id: &str, agent_id: &str) -> Option<PathBuf> {
+    let nested = project_dir
+        .join(session_id)
+        .join("subagents")
+        .join(format!("agent-{}.jsonl", agent_id));
+    if nested.is_file() {
+        return Some(nested);
+    }
+
+    let root = project_dir.join(format!("agent-{}.jsonl", agent_id));
+    if root.is_file() {
+        return Some(root);
+    }
+
+    None
 }
 
 // ============================================================================
@@ -362,14 +549,11 @@ struct RawTextBlock {
 // Conversion logic
 // ============================================================================
 
-fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_format::SpoolFile> {
-    let mut entries: Vec<Entry> = Vec::new();
-
-    // Track tool call IDs: Claude's tool_use id -> our spool EntryId
-    let mut tool_id_map: HashMap<String, EntryId> = HashMap::new();
-    // Track Task tool calls: Claude's tool_use id -> SubagentStart EntryId
-    let mut task_subagent_map: HashMap<String, EntryId> = HashMap::new();
-
+fn convert_raw_lines(
+    raw_lines: &[RawLine],
+    info: &SessionInfo,
+    adapter: &dyn AgentAdapter,
+) -> Result<spool_format::SpoolFile> {
     // Parse timestamps to compute relative ms
     let mut first_timestamp: Option<DateTime<Utc>> = None;
     let mut summary_text: Option<String> = None;
@@ -400,7 +584,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                     && !text.contains("<local-command-stdout>")
                                     && !text.contains("<local-command-caveat>")
                                 {
-                                    let clean = strip_system_tags(text);
+                                    let clean = strip_system_tags(text, adapter.system_tags());
                                     if !clean.is_empty() {
                                         first_prompt_text =
                                             Some(truncate_first_prompt(&clean, 200));
@@ -433,7 +617,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
         .title
         .clone()
         .or(summary_text)
-        .or_else(|| extract_title_from_lines(raw_lines));
+        .or_else(|| extract_title_from_lines(raw_lines, adapter));
 
     let session_entry = SessionEntry {
         id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
@@ -449,10 +633,15 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
         entry_count: None,
         tools_used: None,
         files_modified: None,
+        tool_time_ms: None,
+        tool_invocations: None,
+        subagent_time_ms: None,
+        file_changes: None,
         first_prompt: first_prompt_text,
         schema_url: None,
         trimmed: None,
         ended: Some(spool_format::SessionEndState::Unknown),
+        content_hash: None,
         extra: if let Some(ref model) = model_name {
             let mut m = HashMap::new();
             m.insert(
@@ -464,16 +653,203 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
             HashMap::new()
         },
     };
-    entries.push(Entry::Session(session_entry));
+    let mut entries: Vec<Entry> = vec![Entry::Session(session_entry)];
+
+    // Second pass: convert entries, splicing in any Task subagent transcripts.
+    let project_dir = info.path.parent().unwrap_or_else(|| Path::new("."));
+    let session_id = info
+        .path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_string();
+    let (converted, _last_response) = convert_entries(
+        raw_lines,
+        &session_start,
+        None,
+        project_dir,
+        &session_id,
+        adapter,
+    );
+    entries.extend(converted);
+
+    // Compute final metadata
+    let last_ts = entries
+        .iter()
+        .filter_map(|e| e.timestamp())
+        .max()
+        .unwrap_or(0);
+    let entry_count = entries.len();
+    let tools_used = {
+        let mut tools: Vec<String> = entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::ToolCall(tc) => Some(tc.tool.clone()),
+                _ => None,
+            })
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        tools.sort();
+        tools
+    };
+
+    // Collect files modified by file-writing tool calls, including paths
+    // inferred from `Bash` commands (redirects, `mv`/`cp`/`rm`/`sed -i`/`tee`).
+    let files_modified = {
+        let mut paths: Vec<String> = entries
+            .iter()
+            .flat_map(|e| match e {
+                Entry::ToolCall(tc) => tool_call_file_changes(&tc.tool, &tc.input, adapter)
+                    .into_iter()
+                    .map(|c| c.path)
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    // Aggregate line-level edit stats per path. The first tool call seen
+    // for a path decides `created`: a `Write`-family call (or a Bash
+    // redirect/`tee` overwrite) creates it, anything else means it must
+    // already have existed.
+    let file_changes = {
+        let mut by_path: std::collections::BTreeMap<String, spool_format::FileChangeSummary> =
+            std::collections::BTreeMap::new();
+        for entry in &entries {
+            let Entry::ToolCall(tc) = entry else {
+                continue;
+            };
+            for change in tool_call_file_changes(&tc.tool, &tc.input, adapter) {
+                let summary =
+                    by_path
+                        .entry(change.path.clone())
+                        .or_insert_with(|| spool_format::FileChangeSummary {
+                            path: change.path.clone(),
+                            edits: 0,
+                            lines_added: 0,
+                            lines_removed: 0,
+                            created: None,
+                            last_touched_ts: tc.ts,
+                        });
+                if summary.edits == 0 {
+                    summary.created = Some(change.is_write);
+                }
+                summary.edits += 1;
+                summary.lines_added += change.lines_added;
+                summary.lines_removed += change.lines_removed;
+                summary.last_touched_ts = summary.last_touched_ts.max(tc.ts);
+            }
+        }
+        by_path.into_values().collect::<Vec<_>>()
+    };
+
+    // Per-tool and per-subagent time accounting: pair each ToolCall with its
+    // matching ToolResult (by `call_id`) and attribute `result_ts - call_ts`
+    // (clamped at 0) to the tool's name and the call's owning subagent.
+    // A call with no matching result contributes nothing; concurrent calls
+    // are each measured independently, so totals can exceed `duration_ms`.
+    let mut tool_invocations: HashMap<String, u64> = HashMap::new();
+    let mut open_calls: HashMap<EntryId, (String, Option<EntryId>, Timestamp)> = HashMap::new();
+    for entry in &entries {
+        if let Entry::ToolCall(tc) = entry {
+            *tool_invocations.entry(tc.tool.clone()).or_insert(0) += 1;
+            open_calls.insert(tc.id, (tc.tool.clone(), tc.subagent_id, tc.ts));
+        }
+    }
+
+    let mut tool_time_ms: HashMap<String, u64> = HashMap::new();
+    let mut subagent_time_ms: HashMap<String, u64> = HashMap::new();
+    for entry in &entries {
+        if let Entry::ToolResult(tr) = entry {
+            if let Some((tool, subagent_id, call_ts)) = open_calls.get(&tr.call_id) {
+                let elapsed = tr.ts.saturating_sub(*call_ts);
+                *tool_time_ms.entry(tool.clone()).or_insert(0) += elapsed;
+                if let Some(subagent_id) = subagent_id {
+                    *subagent_time_ms.entry(subagent_id.to_string()).or_insert(0) += elapsed;
+                }
+            }
+        }
+    }
+
+    // Update session entry with computed metadata
+    if let Entry::Session(ref mut s) = entries[0] {
+        s.duration_ms = Some(last_ts);
+        s.entry_count = Some(entry_count);
+        if !tools_used.is_empty() {
+            s.tools_used = Some(tools_used);
+        }
+        if !files_modified.is_empty() {
+            s.files_modified = Some(files_modified);
+        }
+        if !file_changes.is_empty() {
+            s.file_changes = Some(file_changes);
+        }
+        if !tool_invocations.is_empty() {
+            s.tool_invocations = Some(tool_invocations);
+        }
+        if !tool_time_ms.is_empty() {
+            s.tool_time_ms = Some(tool_time_ms);
+        }
+        if !subagent_time_ms.is_empty() {
+            s.subagent_time_ms = Some(subagent_time_ms);
+        }
+        s.ended = Some(spool_format::SessionEndState::Completed);
+    }
+
+    let session = match &entries[0] {
+        Entry::Session(s) => s.clone(),
+        _ => unreachable!(),
+    };
+
+    let entry_spans = vec![None; entries.len()];
+    Ok(spool_format::SpoolFile {
+        session,
+        entries,
+        unparsed_lines: Vec::new(),
+        entry_spans,
+    })
+}
+
+/// Convert a slice of raw lines into Spool entries, optionally tagging every
+/// produced entry with `subagent_id` (used when recursively converting a
+/// subagent transcript). Returns the entries plus the text of the last
+/// non-empty assistant response, which callers use as a `SubagentEnd` summary
+/// when converting a subagent's own transcript.
+///
+/// `project_dir` and `session_id` are only needed to locate Task subagent
+/// transcripts on disk; they're threaded through recursive calls unchanged
+/// since subagent files are addressed relative to the top-level session.
+fn convert_entries(
+    raw_lines: &[RawLine],
+    session_start: &DateTime<Utc>,
+    subagent_id: Option<EntryId>,
+    project_dir: &Path,
+    session_id: &str,
+    adapter: &dyn AgentAdapter,
+) -> (Vec<Entry>, Option<String>) {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    // Track tool call IDs: Claude's tool_use id -> our spool EntryId
+    let mut tool_id_map: HashMap<String, EntryId> = HashMap::new();
+    // Track Task tool calls: Claude's tool_use id -> SubagentStart EntryId
+    let mut task_subagent_map: HashMap<String, EntryId> = HashMap::new();
+    // Track Task tool calls: Claude's tool_use id -> subagent's final response text
+    let mut task_subagent_summary: HashMap<String, String> = HashMap::new();
+
+    let mut last_response_text: Option<String> = None;
 
-    // Second pass: convert entries
     for line in raw_lines {
         match line {
             RawLine::User(u) => {
                 if u.is_meta {
                     continue;
                 }
-                let ts = compute_relative_ts(&u.timestamp, &session_start);
+                let ts = compute_relative_ts(&u.timestamp, session_start);
 
                 if let Some(ref msg) = u.message {
                     match &msg.content {
@@ -486,13 +862,13 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                 continue;
                             }
 
-                            let clean = strip_system_tags(text);
+                            let clean = strip_system_tags(text, adapter.system_tags());
                             if !clean.is_empty() {
                                 entries.push(Entry::Prompt(PromptEntry {
                                     id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
                                     ts,
                                     content: clean,
-                                    subagent_id: None,
+                                    subagent_id,
                                     attachments: None,
                                     extra: HashMap::new(),
                                 }));
@@ -528,7 +904,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                             error: Some(content_text),
                                             truncated: None,
                                             original_bytes: None,
-                                            subagent_id: subagent_start_id,
+                                            subagent_id: subagent_start_id.or(subagent_id),
                                             redacted: None,
                                             extra: HashMap::new(),
                                         }
@@ -541,7 +917,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                             error: None,
                                             truncated: None,
                                             original_bytes: None,
-                                            subagent_id: subagent_start_id,
+                                            subagent_id: subagent_start_id.or(subagent_id),
                                             redacted: None,
                                             extra: HashMap::new(),
                                         }
@@ -555,11 +931,12 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                         } else {
                                             Some(SubagentStatus::Completed)
                                         };
+                                        let summary = task_subagent_summary.remove(tool_use_id);
                                         entries.push(Entry::SubagentEnd(SubagentEndEntry {
                                             id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
                                             ts,
                                             start_id,
-                                            summary: None,
+                                            summary,
                                             status,
                                             extra: HashMap::new(),
                                         }));
@@ -572,7 +949,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                 }
             }
             RawLine::Assistant(a) => {
-                let ts = compute_relative_ts(&a.timestamp, &session_start);
+                let ts = compute_relative_ts(&a.timestamp, session_start);
 
                 if let Some(ref msg) = a.message {
                     // Extract model and token_usage once per message.
@@ -611,9 +988,10 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                             original_bytes: None,
                                             model,
                                             token_usage,
-                                            subagent_id: None,
+                                            subagent_id,
                                             extra: HashMap::new(),
                                         }));
+                                        last_response_text = Some(text.clone());
                                     }
                                 }
                                 RawContentBlock::Thinking { thinking, .. } => {
@@ -625,7 +1003,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                             collapsed: Some(true),
                                             truncated: None,
                                             original_bytes: None,
-                                            subagent_id: None,
+                                            subagent_id,
                                             extra: HashMap::new(),
                                         }));
                                     }
@@ -635,15 +1013,19 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                         Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext));
                                     tool_id_map.insert(id.clone(), entry_id);
 
-                                    // For Task tool calls, emit a SubagentStart entry
-                                    let subagent_id = if name == "Task" {
+                                    // For subagent-spawning tool calls, emit a SubagentStart
+                                    // entry, then locate and splice in the subagent's own
+                                    // transcript.
+                                    let call_subagent_id = if let Some(spec) =
+                                        adapter.is_subagent_tool(name)
+                                    {
                                         let subagent_type = input
-                                            .get("subagent_type")
+                                            .get(spec.type_key)
                                             .and_then(|v| v.as_str())
                                             .unwrap_or("unknown")
                                             .to_string();
                                         let description = input
-                                            .get("description")
+                                            .get(spec.description_key)
                                             .and_then(|v| v.as_str())
                                             .map(|s| s.to_string());
 
@@ -654,14 +1036,35 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                             ts,
                                             agent: subagent_type,
                                             context: description,
-                                            parent_subagent_id: None,
+                                            parent_subagent_id: subagent_id,
                                             extra: HashMap::new(),
                                         }));
 
                                         task_subagent_map.insert(id.clone(), start_id);
+
+                                        if let Some(subagent_path) =
+                                            find_subagent_file(project_dir, session_id, id)
+                                        {
+                                            if let Ok(sub_raw_lines) = read_raw_lines(&subagent_path) {
+                                                let (sub_entries, sub_summary) = convert_entries(
+                                                    &sub_raw_lines,
+                                                    session_start,
+                                                    Some(start_id),
+                                                    project_dir,
+                                                    session_id,
+                                                    adapter,
+                                                );
+                                                entries.extend(sub_entries);
+                                                if let Some(summary) = sub_summary {
+                                                    task_subagent_summary
+                                                        .insert(id.clone(), summary);
+                                                }
+                                            }
+                                        }
+
                                         Some(start_id)
                                     } else {
-                                        None
+                                        subagent_id
                                     };
 
                                     entries.push(Entry::ToolCall(ToolCallEntry {
@@ -669,7 +1072,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
                                         ts,
                                         tool: name.clone(),
                                         input: input.clone(),
-                                        subagent_id,
+                                        subagent_id: call_subagent_id,
                                         extra: HashMap::new(),
                                     }));
                                 }
@@ -684,65 +1087,7 @@ fn convert_raw_lines(raw_lines: &[RawLine], info: &SessionInfo) -> Result<spool_
         }
     }
 
-    // Compute final metadata
-    let last_ts = entries
-        .iter()
-        .filter_map(|e| e.timestamp())
-        .max()
-        .unwrap_or(0);
-    let entry_count = entries.len();
-    let tools_used = {
-        let mut tools: Vec<String> = entries
-            .iter()
-            .filter_map(|e| match e {
-                Entry::ToolCall(tc) => Some(tc.tool.clone()),
-                _ => None,
-            })
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-        tools.sort();
-        tools
-    };
-
-    // Collect files modified by file-writing tool calls
-    let files_modified = {
-        let mut paths: Vec<String> = entries
-            .iter()
-            .filter_map(|e| match e {
-                Entry::ToolCall(tc) => extract_modified_path(&tc.tool, &tc.input),
-                _ => None,
-            })
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-        paths.sort();
-        paths
-    };
-
-    // Update session entry with computed metadata
-    if let Entry::Session(ref mut s) = entries[0] {
-        s.duration_ms = Some(last_ts);
-        s.entry_count = Some(entry_count);
-        if !tools_used.is_empty() {
-            s.tools_used = Some(tools_used);
-        }
-        if !files_modified.is_empty() {
-            s.files_modified = Some(files_modified);
-        }
-        s.ended = Some(spool_format::SessionEndState::Completed);
-    }
-
-    let session = match &entries[0] {
-        Entry::Session(s) => s.clone(),
-        _ => unreachable!(),
-    };
-
-    Ok(spool_format::SpoolFile {
-        session,
-        entries,
-        unparsed_lines: Vec::new(),
-    })
+    (entries, last_response_text)
 }
 
 /// Compute relative timestamp in milliseconds from session start.
@@ -770,20 +1115,21 @@ fn extract_tool_result_text(content: &Option<RawToolResultContent>) -> String {
     }
 }
 
-/// Strip system-injected XML tags from user messages to get clean prompt text.
-fn strip_system_tags(text: &str) -> String {
-    // Remove common system-injected tags
+/// Strip agent-injected XML tags (e.g. `<system-reminder>...</system-reminder>`)
+/// from user messages to get clean prompt text. `tags` are bare tag names,
+/// as returned by [`AgentAdapter::system_tags`].
+fn strip_system_tags(text: &str, tags: &[&str]) -> String {
     let mut result = text.to_string();
-    // Remove <system-reminder>...</system-reminder> blocks
-    while let (Some(start), Some(end)) = (
-        result.find("<system-reminder>"),
-        result.find("</system-reminder>"),
-    ) {
-        let end = end + "</system-reminder>".len();
-        if start < end {
-            result.replace_range(start..end, "");
-        } else {
-            break;
+    for tag in tags {
+        let open = format!("<{}>", tag);
+        let close = format!("</{}>", tag);
+        while let (Some(start), Some(end)) = (result.find(&open), result.find(&close)) {
+            let end = end + close.len();
+            if start < end {
+                result.replace_range(start..end, "");
+            } else {
+                break;
+            }
         }
     }
     result.trim().to_string()
@@ -810,6 +1156,285 @@ fn extract_modified_path(tool: &str, input: &serde_json::Value) -> Option<String
     }
 }
 
+/// A single file-mutating tool call, with enough detail to compute
+/// line-level edit stats. Line counts come from `str::lines()`, which
+/// splits on `\n` regardless of multi-byte content, so non-ASCII text is
+/// counted correctly.
+struct FileChange {
+    path: String,
+    /// Whether this call wrote the whole file (`true`, a `Write`-family
+    /// tool) or edited part of it (`false`, `Edit`/`NotebookEdit`).
+    is_write: bool,
+    lines_added: u64,
+    lines_removed: u64,
+}
+
+/// How a shell command affected a file, as inferred by
+/// [`infer_bash_file_changes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BashFileOp {
+    Create,
+    Overwrite,
+    Append,
+    Delete,
+}
+
+/// Split `s` on the first delimiter from `delims` (checked in the given
+/// order at each position) found outside single/double quotes.
+fn split_top_level<'a>(s: &'a str, delims: &[&str]) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0usize;
+    let mut quote: Option<char> = None;
+    let mut iter = s.char_indices().peekable();
+    while let Some((i, c)) = iter.next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c == '\'' || c == '"' {
+            quote = Some(c);
+            continue;
+        }
+        if let Some(d) = delims.iter().find(|d| s[i..].starts_with(**d)) {
+            parts.push(&s[start..i]);
+            for _ in 0..d.chars().count() - 1 {
+                iter.next();
+            }
+            start = i + d.len();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Tokenize a shell command stage: split on whitespace, strip quotes, and
+/// emit `>`/`>>` as their own tokens even when glued to an adjacent word
+/// (e.g. `cmd>out.txt`). Not a full shell lexer — no variable expansion,
+/// globbing, or `$()`/backtick handling.
+fn shell_tokens(stage: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = stage.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            } else {
+                current.push(c);
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => quote = Some(c),
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            '>' => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    tokens.push(">>".to_string());
+                } else {
+                    tokens.push(">".to_string());
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// A redirect/command-argument target worth reporting: non-empty, not a
+/// file descriptor duplication (`&1`, `&2`), and not an obvious non-file
+/// sink.
+fn valid_bash_target(target: &str) -> Option<String> {
+    if target.is_empty() || target.starts_with('&') {
+        return None;
+    }
+    if matches!(target, "/dev/null" | "/dev/stdout" | "/dev/stderr") {
+        return None;
+    }
+    Some(target.to_string())
+}
+
+/// Conservatively recognize file-mutating effects of a `Bash` tool call's
+/// `command` string: output redirections (`>` overwrite, `>>` append) and
+/// a short list of common file-mutating commands (`mv`, `cp`, `rm`,
+/// `sed -i`, `tee`). `command` is split on `;`, `&&`, `||` into
+/// statements and each statement on `|` into pipeline stages, so a
+/// redirect on one stage of a pipeline isn't attributed to another
+/// stage's command. This is not a shell parser — it errs toward
+/// reporting nothing over reporting a wrong path (no variable expansion,
+/// globbing, multi-source `mv`/`cp`, or subshells).
+fn infer_bash_file_changes(command: &str) -> Vec<(String, BashFileOp)> {
+    let mut changes = Vec::new();
+
+    for statement in split_top_level(command, &["&&", "||", ";"]) {
+        for stage in split_top_level(statement, &["|"]) {
+            let tokens = shell_tokens(stage);
+            if tokens.is_empty() {
+                continue;
+            }
+
+            for i in 0..tokens.len() {
+                let op = match tokens[i].as_str() {
+                    ">" => Some(BashFileOp::Overwrite),
+                    ">>" => Some(BashFileOp::Append),
+                    _ => None,
+                };
+                if let (Some(op), Some(target)) = (op, tokens.get(i + 1)) {
+                    if let Some(path) = valid_bash_target(target) {
+                        changes.push((path, op));
+                    }
+                }
+            }
+
+            let name = tokens[0].rsplit('/').next().unwrap_or(&tokens[0]);
+            let args = &tokens[1..];
+            match name {
+                "rm" => {
+                    for arg in args.iter().filter(|a| !a.starts_with('-')) {
+                        if let Some(path) = valid_bash_target(arg) {
+                            changes.push((path, BashFileOp::Delete));
+                        }
+                    }
+                }
+                "mv" | "cp" => {
+                    let non_flags: Vec<&String> =
+                        args.iter().filter(|a| !a.starts_with('-')).collect();
+                    // Only the simple `mv|cp SRC DEST` form is handled —
+                    // `mv a b DEST_DIR/` has an ambiguous final filename.
+                    if let [src, dest] = non_flags[..] {
+                        if let Some(dest) = valid_bash_target(dest) {
+                            changes.push((dest, BashFileOp::Overwrite));
+                        }
+                        if name == "mv" {
+                            if let Some(src) = valid_bash_target(src) {
+                                changes.push((src, BashFileOp::Delete));
+                            }
+                        }
+                    }
+                }
+                "sed" => {
+                    let in_place = args.iter().any(|a| a.starts_with("-i"));
+                    if in_place {
+                        if let Some(file) = args.iter().filter(|a| !a.starts_with('-')).last() {
+                            if let Some(path) = valid_bash_target(file) {
+                                changes.push((path, BashFileOp::Overwrite));
+                            }
+                        }
+                    }
+                }
+                "tee" => {
+                    let append = args.iter().any(|a| a == "-a" || a == "--append");
+                    let op = if append {
+                        BashFileOp::Append
+                    } else {
+                        BashFileOp::Overwrite
+                    };
+                    for arg in args.iter().filter(|a| !a.starts_with('-')) {
+                        if let Some(path) = valid_bash_target(arg) {
+                            changes.push((path, op));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    changes
+}
+
+/// Extract line-level edit stats from a file-mutating tool call, for
+/// aggregation into [`spool_format::FileChangeSummary`]. Returns `None`
+/// for tools that don't modify files, per `adapter`'s own tool
+/// recognition ([`AgentAdapter::modified_path`]).
+fn extract_file_change(
+    tool: &str,
+    input: &serde_json::Value,
+    adapter: &dyn AgentAdapter,
+) -> Option<FileChange> {
+    let path = adapter.modified_path(tool, input)?;
+    match tool {
+        "Write" | "write" | "write_file" => {
+            let lines_added = input
+                .get("content")
+                .and_then(|v| v.as_str())
+                .map(|s| s.lines().count() as u64)
+                .unwrap_or(0);
+            Some(FileChange {
+                path,
+                is_write: true,
+                lines_added,
+                lines_removed: 0,
+            })
+        }
+        "Edit" | "edit" | "edit_file" => {
+            let lines_removed = input
+                .get("old_string")
+                .and_then(|v| v.as_str())
+                .map(|s| s.lines().count() as u64)
+                .unwrap_or(0);
+            let lines_added = input
+                .get("new_string")
+                .and_then(|v| v.as_str())
+                .map(|s| s.lines().count() as u64)
+                .unwrap_or(0);
+            Some(FileChange {
+                path,
+                is_write: false,
+                lines_added,
+                lines_removed,
+            })
+        }
+        "NotebookEdit" | "notebook_edit" => Some(FileChange {
+            path,
+            is_write: false,
+            lines_added: 0,
+            lines_removed: 0,
+        }),
+        _ => None,
+    }
+}
+
+/// File changes for a single tool call, covering both regular
+/// file-writing tools ([`extract_file_change`]) and `Bash`/`bash`
+/// commands whose shell effects are inferred by
+/// [`infer_bash_file_changes`]. Bash changes have no line-level stats
+/// (the tool call only gives us a command string, not the content
+/// written), so `lines_added`/`lines_removed` are always 0.
+fn tool_call_file_changes(
+    tool: &str,
+    input: &serde_json::Value,
+    adapter: &dyn AgentAdapter,
+) -> Vec<FileChange> {
+    if tool == "Bash" || tool == "bash" {
+        let command = input.get("command").and_then(|v| v.as_str()).unwrap_or("");
+        return infer_bash_file_changes(command)
+            .into_iter()
+            .map(|(path, op)| FileChange {
+                path,
+                is_write: matches!(op, BashFileOp::Create | BashFileOp::Overwrite),
+                lines_added: 0,
+                lines_removed: 0,
+            })
+            .collect();
+    }
+
+    extract_file_change(tool, input, adapter).into_iter().collect()
+}
+
 /// Truncate text for the first_prompt field, respecting UTF-8 boundaries.
 fn truncate_first_prompt(text: &str, max_bytes: usize) -> String {
     if text.len() <= max_bytes {
@@ -823,7 +1448,7 @@ fn truncate_first_prompt(text: &str, max_bytes: usize) -> String {
 }
 
 /// Extract a title from the first real user prompt.
-fn extract_title_from_lines(lines: &[RawLine]) -> Option<String> {
+fn extract_title_from_lines(lines: &[RawLine], adapter: &dyn AgentAdapter) -> Option<String> {
     for line in lines {
         if let RawLine::User(u) = line {
             if u.is_meta {
@@ -837,7 +1462,7 @@ fn extract_title_from_lines(lines: &[RawLine]) -> Option<String> {
                     {
                         continue;
                     }
-                    let clean = strip_system_tags(text);
+                    let clean = strip_system_tags(text, adapter.system_tags());
                     if !clean.is_empty() {
                         let first_line = clean.lines().next().unwrap_or(&clean);
                         let title = if first_line.len() > 60 {
@@ -870,13 +1495,13 @@ mod tests {
     #[test]
     fn test_strip_system_tags() {
         let input = "Hello world";
-        assert_eq!(strip_system_tags(input), "Hello world");
+        assert_eq!(strip_system_tags(input, &["system-reminder"]), "Hello world");
 
         let input = "Before <system-reminder>hidden</system-reminder> After";
-        assert_eq!(strip_system_tags(input), "Before  After");
+        assert_eq!(strip_system_tags(input, &["system-reminder"]), "Before  After");
 
         let input = "<system-reminder>all hidden</system-reminder>";
-        assert_eq!(strip_system_tags(input), "");
+        assert_eq!(strip_system_tags(input, &["system-reminder"]), "");
     }
 
     #[test]
@@ -946,7 +1571,7 @@ mod tests {
             }),
         ];
         assert_eq!(
-            extract_title_from_lines(&lines),
+            extract_title_from_lines(&lines, &ClaudeCodeAdapter),
             Some("Fix the auth bug in login.py".to_string())
         );
     }
@@ -967,7 +1592,7 @@ mod tests {
             is_meta: false,
             tool_use_result: None,
         })];
-        let result = extract_title_from_lines(&lines).unwrap();
+        let result = extract_title_from_lines(&lines, &ClaudeCodeAdapter).unwrap();
         assert!(result.ends_with("..."));
         // Should not panic and should be valid UTF-8
         assert!(result.len() <= 60);
@@ -989,7 +1614,7 @@ mod tests {
             is_meta: false,
             tool_use_result: None,
         })];
-        let result = extract_title_from_lines(&lines).unwrap();
+        let result = extract_title_from_lines(&lines, &ClaudeCodeAdapter).unwrap();
         assert!(result.ends_with("..."));
         // The '→' starts at byte 56 and ends at 59. Truncating at 57 would be mid-char.
         // The fix should walk back to 56.
@@ -1043,7 +1668,7 @@ mod tests {
             message_count: None,
         };
 
-        let spool = convert_raw_lines(&lines, &info).unwrap();
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
 
         // Find all response entries
         let responses: Vec<&spool_format::ResponseEntry> = spool
@@ -1124,7 +1749,7 @@ mod tests {
             message_count: None,
         };
 
-        let spool = convert_raw_lines(&lines, &info).unwrap();
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
         assert_eq!(
             spool.session.first_prompt.as_deref(),
             Some("Fix the authentication bug in login.py")
@@ -1208,7 +1833,7 @@ mod tests {
             message_count: None,
         };
 
-        let spool = convert_raw_lines(&lines, &info).unwrap();
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
         let files = spool.session.files_modified.unwrap();
 
         // Should contain Write, Edit, NotebookEdit targets but not Read
@@ -1276,4 +1901,387 @@ mod tests {
         assert!(result.ends_with("..."));
         assert!(result.is_char_boundary(result.len() - 3));
     }
+
+    #[test]
+    fn test_nested_subagent_parent_id_threading() {
+        // A Task encountered while already inside a subagent (subagent_id is
+        // `Some`) should record the enclosing subagent as its parent, so
+        // `convert_entries`'s recursion — not an explicit stack — is what
+        // reconstructs the call tree for sub-subagents.
+        let lines = vec![RawLine::Assistant(RawAssistantLine {
+            message: Some(RawApiMessage {
+                model: None,
+                content: Some(vec![RawContentBlock::ToolUse {
+                    id: "toolu_nested".to_string(),
+                    name: "Task".to_string(),
+                    input: serde_json::json!({
+                        "subagent_type": "general-purpose",
+                        "description": "nested work",
+                    }),
+                }]),
+                usage: None,
+                stop_reason: None,
+            }),
+            timestamp: None,
+            uuid: None,
+        })];
+
+        let session_start: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+        let outer_subagent_id = Uuid::new_v4();
+
+        let (entries, _) = convert_entries(
+            &lines,
+            &session_start,
+            Some(outer_subagent_id),
+            Path::new("/tmp/does-not-exist"),
+            "session",
+            &ClaudeCodeAdapter,
+        );
+
+        let start = entries
+            .iter()
+            .find_map(|e| match e {
+                Entry::SubagentStart(s) => Some(s),
+                _ => None,
+            })
+            .expect("expected a SubagentStart entry");
+
+        assert_eq!(start.parent_subagent_id, Some(outer_subagent_id));
+    }
+
+    #[test]
+    fn test_tool_time_accounting() {
+        let lines = vec![
+            RawLine::Assistant(RawAssistantLine {
+                message: Some(RawApiMessage {
+                    model: None,
+                    content: Some(vec![
+                        RawContentBlock::ToolUse {
+                            id: "toolu_1".to_string(),
+                            name: "Bash".to_string(),
+                            input: serde_json::json!({"command": "ls"}),
+                        },
+                        RawContentBlock::ToolUse {
+                            id: "toolu_2".to_string(),
+                            name: "Bash".to_string(),
+                            input: serde_json::json!({"command": "sleep 1"}),
+                        },
+                    ]),
+                    usage: None,
+                    stop_reason: None,
+                }),
+                timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+                uuid: None,
+            }),
+            RawLine::User(RawUserLine {
+                message: Some(RawMessage {
+                    role: Some("user".to_string()),
+                    content: Some(RawContent::Blocks(vec![RawToolResultBlock {
+                        block_type: Some("tool_result".to_string()),
+                        tool_use_id: Some("toolu_1".to_string()),
+                        content: Some(RawToolResultContent::Text("file.txt".to_string())),
+                        is_error: None,
+                    }])),
+                }),
+                timestamp: Some("2026-01-01T00:00:02Z".to_string()),
+                uuid: None,
+                is_meta: false,
+                tool_use_result: None,
+            }),
+            // toolu_2 never gets a matching result (open/crashed session).
+        ];
+
+        let info = SessionInfo {
+            path: PathBuf::from("/tmp/test.jsonl"),
+            agent: AgentType::ClaudeCode,
+            created_at: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            modified_at: None,
+            title: None,
+            project_dir: None,
+            message_count: None,
+        };
+
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
+
+        assert_eq!(spool.session.tool_invocations.unwrap().get("Bash"), Some(&2));
+        assert_eq!(spool.session.tool_time_ms.unwrap().get("Bash"), Some(&2000));
+    }
+
+    #[test]
+    fn test_extract_file_change() {
+        // Write — counts total lines written, marks created = true
+        let change = extract_file_change(
+            "Write",
+            &serde_json::json!({"file_path": "/a.rs", "content": "line1\nline2\nline3"}),
+            &ClaudeCodeAdapter,
+        )
+        .unwrap();
+        assert_eq!(change.path, "/a.rs");
+        assert!(change.is_write);
+        assert_eq!(change.lines_added, 3);
+        assert_eq!(change.lines_removed, 0);
+
+        // Edit — old/new string line counts, marks created = false
+        let change = extract_file_change(
+            "Edit",
+            &serde_json::json!({
+                "file_path": "/a.rs",
+                "old_string": "one\ntwo",
+                "new_string": "one\ntwo\nthree\nfour",
+            }),
+            &ClaudeCodeAdapter,
+        )
+        .unwrap();
+        assert!(!change.is_write);
+        assert_eq!(change.lines_removed, 2);
+        assert_eq!(change.lines_added, 4);
+
+        // Multi-byte content is counted by lines, not bytes
+        let change = extract_file_change(
+            "Write",
+            &serde_json::json!({"file_path": "/b.rs", "content": "héllo\n世界\n"}),
+            &ClaudeCodeAdapter,
+        )
+        .unwrap();
+        assert_eq!(change.lines_added, 2);
+
+        // Bash is handled separately by `infer_bash_file_changes`, not by
+        // this tool-name-based extractor.
+        assert!(extract_file_change(
+            "Bash",
+            &serde_json::json!({"command": "ls"}),
+            &ClaudeCodeAdapter
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_file_changes_aggregation() {
+        let lines = vec![
+            RawLine::Assistant(RawAssistantLine {
+                message: Some(RawApiMessage {
+                    model: None,
+                    content: Some(vec![
+                        RawContentBlock::ToolUse {
+                            id: "toolu_1".to_string(),
+                            name: "Write".to_string(),
+                            input: serde_json::json!({
+                                "file_path": "/a.rs",
+                                "content": "line1\nline2",
+                            }),
+                        },
+                        RawContentBlock::ToolUse {
+                            id: "toolu_2".to_string(),
+                            name: "Edit".to_string(),
+                            input: serde_json::json!({
+                                "file_path": "/a.rs",
+                                "old_string": "line1",
+                                "new_string": "line1 updated\nextra",
+                            }),
+                        },
+                    ]),
+                    usage: None,
+                    stop_reason: None,
+                }),
+                timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+                uuid: None,
+            }),
+            RawLine::Assistant(RawAssistantLine {
+                message: Some(RawApiMessage {
+                    model: None,
+                    content: Some(vec![RawContentBlock::ToolUse {
+                        id: "toolu_3".to_string(),
+                        name: "Read".to_string(),
+                        input: serde_json::json!({"file_path": "/a.rs"}),
+                    }]),
+                    usage: None,
+                    stop_reason: None,
+                }),
+                timestamp: Some("2026-01-01T00:00:05Z".to_string()),
+                uuid: None,
+            }),
+        ];
+
+        let info = SessionInfo {
+            path: PathBuf::from("/tmp/test.jsonl"),
+            agent: AgentType::ClaudeCode,
+            created_at: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            modified_at: None,
+            title: None,
+            project_dir: None,
+            message_count: None,
+        };
+
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
+        let changes = spool.session.file_changes.unwrap();
+        assert_eq!(changes.len(), 1);
+        let change = &changes[0];
+        assert_eq!(change.path, "/a.rs");
+        assert_eq!(change.edits, 2);
+        assert_eq!(change.created, Some(true)); // first touch was the Write
+        assert_eq!(change.lines_added, 2 + 2); // Write's 2 lines + Edit's new_string 2 lines
+        assert_eq!(change.lines_removed, 1); // Edit's old_string 1 line
+    }
+
+    #[test]
+    fn test_infer_bash_file_changes_redirects() {
+        let changes = infer_bash_file_changes("echo hi > out.txt");
+        assert_eq!(changes, vec![("out.txt".to_string(), BashFileOp::Overwrite)]);
+
+        let changes = infer_bash_file_changes("echo hi >> out.txt");
+        assert_eq!(changes, vec![("out.txt".to_string(), BashFileOp::Append)]);
+
+        // Quoted path with a space
+        let changes = infer_bash_file_changes(r#"echo hi > "my file.txt""#);
+        assert_eq!(
+            changes,
+            vec![("my file.txt".to_string(), BashFileOp::Overwrite)]
+        );
+
+        // /dev/null and fd duplication are not real files
+        assert!(infer_bash_file_changes("echo hi > /dev/null").is_empty());
+        assert_eq!(
+            infer_bash_file_changes("cmd > out.txt 2>&1"),
+            vec![("out.txt".to_string(), BashFileOp::Overwrite)]
+        );
+    }
+
+    #[test]
+    fn test_infer_bash_file_changes_pipeline_attributes_last_stage_only() {
+        // The redirect belongs to the final stage of the pipeline; grep's
+        // own arguments aren't file targets.
+        let changes = infer_bash_file_changes("cat access.log | grep ERROR > errors.txt");
+        assert_eq!(
+            changes,
+            vec![("errors.txt".to_string(), BashFileOp::Overwrite)]
+        );
+    }
+
+    #[test]
+    fn test_infer_bash_file_changes_statements() {
+        let changes = infer_bash_file_changes("echo a > a.txt && echo b >> b.txt; echo c > c.txt");
+        assert_eq!(
+            changes,
+            vec![
+                ("a.txt".to_string(), BashFileOp::Overwrite),
+                ("b.txt".to_string(), BashFileOp::Append),
+                ("c.txt".to_string(), BashFileOp::Overwrite),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_bash_file_changes_common_commands() {
+        assert_eq!(
+            infer_bash_file_changes("rm old.txt"),
+            vec![("old.txt".to_string(), BashFileOp::Delete)]
+        );
+        assert_eq!(
+            infer_bash_file_changes("rm -f old.txt"),
+            vec![("old.txt".to_string(), BashFileOp::Delete)]
+        );
+        assert_eq!(
+            infer_bash_file_changes("mv a.txt b.txt"),
+            vec![
+                ("b.txt".to_string(), BashFileOp::Overwrite),
+                ("a.txt".to_string(), BashFileOp::Delete),
+            ]
+        );
+        assert_eq!(
+            infer_bash_file_changes("cp a.txt b.txt"),
+            vec![("b.txt".to_string(), BashFileOp::Overwrite)]
+        );
+        assert_eq!(
+            infer_bash_file_changes("sed -i 's/foo/bar/' config.yml"),
+            vec![("config.yml".to_string(), BashFileOp::Overwrite)]
+        );
+        assert_eq!(
+            infer_bash_file_changes("tee out.txt"),
+            vec![("out.txt".to_string(), BashFileOp::Overwrite)]
+        );
+        assert_eq!(
+            infer_bash_file_changes("tee -a out.txt"),
+            vec![("out.txt".to_string(), BashFileOp::Append)]
+        );
+
+        // Conservative: mv/cp with more than one source (ambiguous dest
+        // filename inside a target directory) reports nothing.
+        assert!(infer_bash_file_changes("mv a.txt b.txt dir/").is_empty());
+
+        // Read-only commands report nothing.
+        assert!(infer_bash_file_changes("cat file.txt").is_empty());
+        assert!(infer_bash_file_changes("ls -la").is_empty());
+    }
+
+    #[test]
+    fn test_bash_tool_call_feeds_files_modified() {
+        let lines = vec![RawLine::Assistant(RawAssistantLine {
+            message: Some(RawApiMessage {
+                model: None,
+                content: Some(vec![RawContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "Bash".to_string(),
+                    input: serde_json::json!({"command": "echo hi >> /tmp/log.txt"}),
+                }]),
+                usage: None,
+                stop_reason: None,
+            }),
+            timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+            uuid: None,
+        })];
+
+        let info = SessionInfo {
+            path: PathBuf::from("/tmp/test.jsonl"),
+            agent: AgentType::ClaudeCode,
+            created_at: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            modified_at: None,
+            title: None,
+            project_dir: None,
+            message_count: None,
+        };
+
+        let spool = convert_raw_lines(&lines, &info, &ClaudeCodeAdapter).unwrap();
+        assert_eq!(
+            spool.session.files_modified,
+            Some(vec!["/tmp/log.txt".to_string()])
+        );
+        let changes = spool.session.file_changes.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "/tmp/log.txt");
+        assert_eq!(changes[0].created, Some(false)); // append, not a creating write
+    }
+
+    #[test]
+    fn test_unmatched_tool_result_does_not_emit_subagent_end() {
+        // A tool_result whose tool_use_id was never registered as a Task
+        // (e.g. the subagent map was never populated) must not spuriously
+        // close an agent that was never opened.
+        let lines = vec![RawLine::User(RawUserLine {
+            message: Some(RawMessage {
+                role: Some("user".to_string()),
+                content: Some(RawContent::Blocks(vec![RawToolResultBlock {
+                    block_type: Some("tool_result".to_string()),
+                    tool_use_id: Some("toolu_unknown".to_string()),
+                    content: Some(RawToolResultContent::Text("done".to_string())),
+                    is_error: None,
+                }])),
+            }),
+            timestamp: None,
+            uuid: None,
+            is_meta: false,
+            tool_use_result: None,
+        })];
+
+        let session_start: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+        let (entries, _) = convert_entries(
+            &lines,
+            &session_start,
+            None,
+            Path::new("/tmp"),
+            "s",
+            &ClaudeCodeAdapter,
+        );
+
+        assert!(!entries.iter().any(|e| matches!(e, Entry::SubagentEnd(_))));
+    }
 }
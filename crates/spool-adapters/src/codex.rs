@@ -11,15 +11,15 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use spool_format::{
     Entry, EntryId, PromptEntry, ResponseEntry, SessionEndState, SessionEntry, ThinkingEntry,
-    ToolCallEntry, ToolOutput, ToolResultEntry,
+    TokenUsage, ToolCallEntry, ToolOutput, ToolResultEntry,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::{AgentType, SessionInfo};
+use crate::{AdapterRegistration, AgentType, SessionInfo};
 
 // ============================================================================
 // Public API
@@ -27,60 +27,102 @@ use crate::{AgentType, SessionInfo};
 
 /// Find all Codex CLI sessions on the system.
 pub fn find_sessions() -> Result<Vec<SessionInfo>> {
-    let base_dir = get_codex_dir()?.join("sessions");
+    let paths = list_rollout_paths()?;
+    let mut sessions = Vec::with_capacity(paths.len());
+    for path in &paths {
+        sessions.push(session_info_for(path)?);
+    }
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(sessions)
+}
 
-    if !base_dir.exists() {
+/// Like [`find_sessions`], but fans the per-file metadata reads out across a
+/// pool of worker threads sized by [`crate::worker_count`] (capped via
+/// `SPOOL_MAX_WORKERS`). Useful when `~/.codex/sessions` holds years of
+/// daily rollout folders, where the scan is dominated by opening and
+/// reading each rollout file's first couple of lines rather than CPU work.
+///
+/// Rollout paths are split into contiguous chunks (one per worker) rather
+/// than pulled from a shared queue, so joining the chunk results back in
+/// order is enough to keep output deterministic.
+pub fn find_sessions_parallel() -> Result<Vec<SessionInfo>> {
+    let paths = list_rollout_paths()?;
+    if paths.is_empty() {
         return Ok(Vec::new());
     }
 
-    let mut sessions = Vec::new();
-    let pattern = format!("{}/**/*.jsonl", base_dir.display());
-
-    for entry in glob::glob(&pattern)? {
-        let path = match entry {
-            Ok(path) => path,
-            Err(_) => continue,
-        };
-
-        if !path.is_file() {
-            continue;
-        }
-
-        let metadata = fs::metadata(&path).ok();
-        let modified_at = metadata
-            .as_ref()
-            .and_then(|m| m.modified().ok())
-            .map(DateTime::<Utc>::from);
-
-        let (session_meta, first_prompt) = read_session_meta_and_prompt(&path)?;
-
-        let created_at = session_meta
-            .as_ref()
-            .and_then(|m| parse_timestamp(&m.timestamp));
+    let chunk_size = paths.len().div_ceil(crate::worker_count());
+
+    let mut sessions: Vec<SessionInfo> = std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|p| session_info_for(p).ok())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
 
-        let project_dir = session_meta
-            .as_ref()
-            .and_then(|m| m.cwd.as_ref())
-            .map(PathBuf::from);
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(sessions)
+}
 
-        let title = first_prompt
-            .as_ref()
-            .map(|p| truncate_first_prompt(p, 200))
-            .filter(|t| !t.is_empty());
-
-        sessions.push(SessionInfo {
-            path,
-            agent: AgentType::Codex,
-            created_at,
-            modified_at,
-            title,
-            project_dir,
-            message_count: None,
-        });
+/// Glob every `rollout-*.jsonl` file under `~/.codex/sessions`.
+pub fn list_rollout_paths() -> Result<Vec<PathBuf>> {
+    let base_dir = get_codex_dir()?.join("sessions");
+    if !base_dir.exists() {
+        return Ok(Vec::new());
     }
 
-    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
-    Ok(sessions)
+    let pattern = format!("{}/**/*.jsonl", base_dir.display());
+    Ok(glob::glob(&pattern)?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+/// Read a single rollout file's metadata and build its [`SessionInfo`].
+pub fn session_info_for(path: &Path) -> Result<SessionInfo> {
+    let metadata = fs::metadata(path).ok();
+    let modified_at = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .map(DateTime::<Utc>::from);
+
+    let (session_meta, first_prompt) = read_session_meta_and_prompt(path)?;
+
+    let created_at = session_meta
+        .as_ref()
+        .and_then(|m| parse_timestamp(&m.timestamp));
+
+    let project_dir = session_meta
+        .as_ref()
+        .and_then(|m| m.cwd.as_ref())
+        .map(PathBuf::from);
+
+    let title = first_prompt
+        .as_ref()
+        .map(|p| truncate_first_prompt(p, 200))
+        .filter(|t| !t.is_empty());
+
+    Ok(SessionInfo {
+        path: path.to_path_buf(),
+        agent: AgentType::Codex,
+        created_at,
+        modified_at,
+        title,
+        project_dir,
+        message_count: None,
+    })
 }
 
 /// Convert a Codex CLI session to Spool format.
@@ -203,10 +245,15 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
         entry_count: None,
         tools_used: None,
         files_modified: None,
+        tool_time_ms: None,
+        tool_invocations: None,
+        subagent_time_ms: None,
+        file_changes: None,
         first_prompt: first_prompt_text.map(|t| truncate_first_prompt(&t, 200)),
         schema_url: None,
         trimmed: None,
         ended: Some(SessionEndState::Unknown),
+        content_hash: None,
         extra,
     };
 
@@ -216,6 +263,21 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
     let mut files_modified: BTreeSet<String> = BTreeSet::new();
 
     let mut current_model = last_model;
+    let mut current_token_usage: Option<TokenUsage> = None;
+    let mut total_token_usage: Option<RawTokenUsage> = None;
+    // Tracks whichever terminal-ish marker was seen most recently, so a
+    // later `turn_aborted` overrides an earlier `agent_message` (the
+    // session ended interrupted) and vice versa (an aborted turn was
+    // followed by a completed one, e.g. after resuming).
+    let mut end_state = SessionEndState::Unknown;
+    let mut abort_reason: Option<String> = None;
+    // Content already emitted from an `event_msg` for this session, so the
+    // `response_item` reconciliation pass below can tell a genuine
+    // duplicate (same turn, recorded twice) from a response/reasoning
+    // block that only ever showed up as a `response_item` (e.g. an
+    // interrupted turn whose `event_msg` was never flushed).
+    let mut emitted_response_texts: HashSet<String> = HashSet::new();
+    let mut emitted_thinking_texts: HashSet<String> = HashSet::new();
 
     for line in raw_lines {
         let ts = compute_relative_ts(&line.timestamp, &session_start);
@@ -244,7 +306,10 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                             }
                         }
                         RawEventMsg::AgentMessage { message } => {
+                            end_state = SessionEndState::Completed;
+                            abort_reason = None;
                             if !message.trim().is_empty() {
+                                emitted_response_texts.insert(message.trim().to_string());
                                 entries.push(Entry::Response(ResponseEntry {
                                     id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
                                     ts,
@@ -252,7 +317,7 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                                     truncated: None,
                                     original_bytes: None,
                                     model: current_model.clone(),
-                                    token_usage: None,
+                                    token_usage: current_token_usage.clone(),
                                     subagent_id: None,
                                     extra: HashMap::new(),
                                 }));
@@ -260,6 +325,7 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                         }
                         RawEventMsg::AgentReasoning { text } => {
                             if !text.trim().is_empty() {
+                                emitted_thinking_texts.insert(text.trim().to_string());
                                 entries.push(Entry::Thinking(ThinkingEntry {
                                     id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
                                     ts,
@@ -272,7 +338,23 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                                 }));
                             }
                         }
-                        _ => {}
+                        RawEventMsg::TokenCount { info } => {
+                            if let Some(info) = info {
+                                if let Some(last) = info.last_token_usage.as_ref() {
+                                    current_token_usage = Some(TokenUsage::from(last));
+                                }
+                                if info.total_token_usage.is_some() {
+                                    total_token_usage = info.total_token_usage;
+                                }
+                            }
+                            end_state = SessionEndState::Completed;
+                            abort_reason = None;
+                        }
+                        RawEventMsg::TurnAborted { reason } => {
+                            end_state = SessionEndState::Cancelled;
+                            abort_reason = reason;
+                        }
+                        RawEventMsg::Unknown => entries.push(Entry::Unknown),
                     }
                 }
             }
@@ -323,8 +405,18 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                             tools_used.insert(name.clone());
                             tool_id_map.insert(call_id, entry_id);
                             let input_value = parse_json_or_string(&input);
+                            let mut tool_extra = HashMap::new();
                             if name == "apply_patch" {
-                                collect_patch_paths(&input, &mut files_modified);
+                                let changes = parse_apply_patch(&input);
+                                for change in &changes {
+                                    files_modified.insert(change.path.clone());
+                                    if let Some(dest) = change.renamed_to.as_ref() {
+                                        files_modified.insert(dest.clone());
+                                    }
+                                }
+                                if let Ok(value) = serde_json::to_value(&changes) {
+                                    tool_extra.insert("x_patch_files".to_string(), value);
+                                }
                             }
                             entries.push(Entry::ToolCall(ToolCallEntry {
                                 id: entry_id,
@@ -332,7 +424,7 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                                 tool: name,
                                 input: input_value,
                                 subagent_id: None,
-                                extra: HashMap::new(),
+                                extra: tool_extra,
                             }));
                         }
                         RawResponseItem::CustomToolCallOutput { call_id, output } => {
@@ -363,7 +455,80 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
                                 extra: HashMap::new(),
                             }));
                         }
-                        _ => {}
+                        // `event_msg`'s `agent_message`/`agent_reasoning` already cover
+                        // the common case, but an interrupted turn can leave the final
+                        // assistant message or reasoning block recorded only as a
+                        // `response_item` (no matching `event_msg` was ever flushed) —
+                        // reassemble those the same way rather than dropping the turn's
+                        // last output.
+                        RawResponseItem::Message { role, content } => {
+                            if !matches!(role.as_deref(), Some(r) if r != "assistant") {
+                                let text = content
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .filter_map(|item| match item {
+                                        RawContentItem::OutputText { text } => Some(text),
+                                        _ => None,
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .join("");
+                                let trimmed = text.trim();
+                                if !trimmed.is_empty()
+                                    && !emitted_response_texts.contains(trimmed)
+                                {
+                                    entries.push(Entry::Response(ResponseEntry {
+                                        id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                                        ts,
+                                        content: text,
+                                        truncated: None,
+                                        original_bytes: None,
+                                        model: current_model.clone(),
+                                        token_usage: current_token_usage.clone(),
+                                        subagent_id: None,
+                                        extra: HashMap::new(),
+                                    }));
+                                }
+                            }
+                        }
+                        RawResponseItem::Reasoning {
+                            summary,
+                            content,
+                            encrypted_content,
+                        } => {
+                            let text = content.unwrap_or_else(|| {
+                                summary
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .filter_map(|b| b.text)
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            });
+                            let trimmed = text.trim();
+                            if (!trimmed.is_empty() || encrypted_content.is_some())
+                                && !emitted_thinking_texts.contains(trimmed)
+                            {
+                                let mut extra = HashMap::new();
+                                if let Some(encrypted) = encrypted_content {
+                                    extra.insert(
+                                        "x_encrypted_reasoning".to_string(),
+                                        serde_json::Value::String(encrypted),
+                                    );
+                                }
+                                entries.push(Entry::Thinking(ThinkingEntry {
+                                    id: Uuid::new_v7(uuid::Timestamp::now(uuid::NoContext)),
+                                    ts,
+                                    content: text,
+                                    collapsed: None,
+                                    truncated: None,
+                                    original_bytes: None,
+                                    subagent_id: None,
+                                    extra,
+                                }));
+                            }
+                        }
+                        RawResponseItem::Unknown => {
+                            entries.push(Entry::Unknown);
+                        }
                     }
                 }
             }
@@ -388,8 +553,27 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
             entry.files_modified = Some(files_modified.into_iter().collect());
         }
         entry.entry_count = Some(entry_count);
+        entry.ended = Some(end_state);
+        if let Some(reason) = abort_reason {
+            entry
+                .extra
+                .insert("x_abort_reason".to_string(), serde_json::Value::String(reason));
+        }
+        if let Some(totals) = total_token_usage.as_ref() {
+            entry.extra.insert(
+                "x_token_usage".to_string(),
+                serde_json::json!({
+                    "input_tokens": totals.input_tokens,
+                    "output_tokens": totals.output_tokens,
+                    "cached_input_tokens": totals.cached_input_tokens,
+                    "reasoning_output_tokens": totals.reasoning_output_tokens,
+                    "total_tokens": totals.total_tokens,
+                }),
+            );
+        }
     }
 
+    let entry_spans = vec![None; entries.len()];
     Ok(spool_format::SpoolFile {
         session: match &entries[0] {
             Entry::Session(s) => s.clone(),
@@ -397,9 +581,45 @@ pub fn convert(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
         },
         entries,
         unparsed_lines: Vec::new(),
+        entry_spans,
     })
 }
 
+/// [`AdapterRegistration`] for Codex CLI: the first line of a rollout file
+/// is always a `session_meta` record. Codex has no skill/command
+/// installation location yet.
+pub struct CodexRegistration;
+
+impl AdapterRegistration for CodexRegistration {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Codex
+    }
+
+    fn detect(&self, first_line: &serde_json::Value) -> bool {
+        first_line.get("type").and_then(|v| v.as_str()) == Some("session_meta")
+    }
+
+    fn find_sessions(&self) -> Result<Vec<SessionInfo>> {
+        find_sessions_parallel()
+    }
+
+    fn list_session_paths(&self) -> Result<Vec<PathBuf>> {
+        list_rollout_paths()
+    }
+
+    fn session_info_for(&self, path: &Path) -> Result<SessionInfo> {
+        session_info_for(path)
+    }
+
+    fn convert(&self, session: &SessionInfo) -> Result<spool_format::SpoolFile> {
+        convert(session)
+    }
+
+    fn skill_install_dir(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
 // ============================================================================
 // Raw Codex JSONL format types
 // ============================================================================
@@ -499,11 +719,46 @@ enum RawEventMsg {
     #[serde(rename = "turn_aborted")]
     TurnAborted { reason: Option<String> },
     #[serde(rename = "token_count")]
-    TokenCount { info: Option<serde_json::Value> },
+    TokenCount { info: Option<RawTokenCountInfo> },
     #[serde(other)]
     Unknown,
 }
 
+/// `info` payload of a `token_count` event: `last_token_usage` covers just
+/// the turn that finished, `total_token_usage` is the session's running
+/// total so far - both already cumulative as reported by Codex, not deltas
+/// we need to sum ourselves.
+#[derive(Debug, Clone, Deserialize)]
+struct RawTokenCountInfo {
+    total_token_usage: Option<RawTokenUsage>,
+    last_token_usage: Option<RawTokenUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawTokenUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cached_input_tokens: u64,
+    #[serde(default)]
+    reasoning_output_tokens: u64,
+    #[serde(default)]
+    total_tokens: u64,
+}
+
+impl From<&RawTokenUsage> for TokenUsage {
+    fn from(raw: &RawTokenUsage) -> Self {
+        TokenUsage {
+            input_tokens: raw.input_tokens,
+            output_tokens: raw.output_tokens,
+            cache_read_tokens: Some(raw.cached_input_tokens),
+            cache_creation_tokens: None,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Serialize)]
 struct RawWebSearchAction {
@@ -636,16 +891,83 @@ fn parse_json_or_string(input: &str) -> serde_json::Value {
     serde_json::from_str(input).unwrap_or_else(|_| serde_json::Value::String(input.to_string()))
 }
 
-fn collect_patch_paths(patch: &str, files_modified: &mut BTreeSet<String>) {
+/// One file's changes within an `apply_patch` call's custom diff body.
+#[derive(Debug, Clone, Serialize)]
+struct PatchFileChange {
+    path: String,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    renamed_to: Option<String>,
+    added_lines: usize,
+    removed_lines: usize,
+}
+
+/// Parse an `apply_patch` tool call's body into per-file change stats.
+/// `*** Update/Add/Delete File:` headers start a new file's hunk, `***
+/// Move to:` turns the current file into a rename, `@@ ...` lines are
+/// context markers (not counted), and a header followed by no `+`/`-`
+/// lines (a binary or empty-file patch) still produces a zero-count entry
+/// rather than being dropped.
+fn parse_apply_patch(patch: &str) -> Vec<PatchFileChange> {
+    let mut changes = Vec::new();
+    let mut current: Option<PatchFileChange> = None;
+
+    let start_file = |changes: &mut Vec<PatchFileChange>, current: &mut Option<PatchFileChange>| {
+        if let Some(c) = current.take() {
+            changes.push(c);
+        }
+    };
+
     for line in patch.lines() {
         if let Some(path) = line.strip_prefix("*** Update File: ") {
-            files_modified.insert(path.trim().to_string());
+            start_file(&mut changes, &mut current);
+            current = Some(PatchFileChange {
+                path: path.trim().to_string(),
+                kind: "update",
+                renamed_to: None,
+                added_lines: 0,
+                removed_lines: 0,
+            });
         } else if let Some(path) = line.strip_prefix("*** Add File: ") {
-            files_modified.insert(path.trim().to_string());
+            start_file(&mut changes, &mut current);
+            current = Some(PatchFileChange {
+                path: path.trim().to_string(),
+                kind: "add",
+                renamed_to: None,
+                added_lines: 0,
+                removed_lines: 0,
+            });
         } else if let Some(path) = line.strip_prefix("*** Delete File: ") {
-            files_modified.insert(path.trim().to_string());
+            start_file(&mut changes, &mut current);
+            current = Some(PatchFileChange {
+                path: path.trim().to_string(),
+                kind: "delete",
+                renamed_to: None,
+                added_lines: 0,
+                removed_lines: 0,
+            });
+        } else if let Some(dest) = line.strip_prefix("*** Move to: ") {
+            if let Some(c) = current.as_mut() {
+                c.kind = "rename";
+                c.renamed_to = Some(dest.trim().to_string());
+            }
+        } else if line.starts_with("@@")
+            || line.starts_with("*** End of File")
+            || line == "*** End Patch"
+            || line == "*** Begin Patch"
+        {
+            // Context/structural markers - not line changes.
+        } else if let Some(c) = current.as_mut() {
+            if line.starts_with('+') {
+                c.added_lines += 1;
+            } else if line.starts_with('-') {
+                c.removed_lines += 1;
+            }
         }
     }
+
+    start_file(&mut changes, &mut current);
+    changes
 }
 
 fn get_codex_dir() -> Result<PathBuf> {
@@ -4,6 +4,7 @@
 //!
 //! This crate provides adapters for various AI coding agents:
 //! - Claude Code
+//! - aichat
 //! - Codex CLI (planned)
 //! - Cursor (planned)
 //! - Aider (planned)
@@ -21,16 +22,18 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+pub mod aichat;
 pub mod claude_code;
-// pub mod codex; // TODO
+pub mod codex;
 // pub mod cursor; // TODO
 // pub mod aider; // TODO
 
 use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Information about a discovered agent session.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
     /// Path to the session log file
     pub path: PathBuf,
@@ -49,9 +52,10 @@ pub struct SessionInfo {
 }
 
 /// Supported agent types.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentType {
     ClaudeCode,
+    Aichat,
     Codex,
     Cursor,
     Aider,
@@ -64,6 +68,7 @@ impl AgentType {
     pub fn as_str(&self) -> &'static str {
         match self {
             AgentType::ClaudeCode => "claude-code",
+            AgentType::Aichat => "aichat",
             AgentType::Codex => "codex",
             AgentType::Cursor => "cursor",
             AgentType::Aider => "aider",
@@ -81,3 +86,140 @@ pub trait Adapter {
     /// Convert a session to Spool format.
     fn convert(session: &SessionInfo) -> anyhow::Result<spool_format::SpoolFile>;
 }
+
+/// One agent's complete integration with the rest of spool: how to
+/// recognize its log format from the first JSON line, find its sessions,
+/// convert them, and (if it supports one) where to install a skill/command
+/// helper for it. Unlike [`Adapter`], this is object-safe - callers hold a
+/// `Vec<Box<dyn AdapterRegistration>>` from [`registry`] and dispatch
+/// through it, so adding a new agent (a new module implementing this trait
+/// plus one line in `registry`) doesn't require touching `detect_agent_from_log`,
+/// `find_all_sessions`, `convert_session`, or `spool skill install`.
+pub trait AdapterRegistration: Send + Sync {
+    /// The agent type this registration handles.
+    fn agent_type(&self) -> AgentType;
+
+    /// Inspect the first non-blank JSON line of a log file and decide
+    /// whether it belongs to this agent.
+    fn detect(&self, first_line: &serde_json::Value) -> bool;
+
+    /// Find all sessions for this agent on disk.
+    fn find_sessions(&self) -> anyhow::Result<Vec<SessionInfo>>;
+
+    /// Cheaply list this agent's session log paths, without extracting any
+    /// per-session metadata - just enough of a directory walk to know what
+    /// exists. Used by a persistent session catalog to decide, file by
+    /// file, whether a cached [`SessionInfo`] can be reused or must be
+    /// rebuilt.
+    fn list_session_paths(&self) -> anyhow::Result<Vec<PathBuf>>;
+
+    /// Build the [`SessionInfo`] for a single session log path (the
+    /// per-file counterpart to [`AdapterRegistration::find_sessions`],
+    /// for catalog entries whose cache was invalidated).
+    fn session_info_for(&self, path: &Path) -> anyhow::Result<SessionInfo>;
+
+    /// Convert a session to Spool format.
+    fn convert(&self, session: &SessionInfo) -> anyhow::Result<spool_format::SpoolFile>;
+
+    /// Directory this agent reads user-installed command/skill files from,
+    /// if it supports them (e.g. Claude Code's `~/.claude/commands`).
+    /// `None` means this agent has no such integration (yet).
+    fn skill_install_dir(&self) -> Option<PathBuf>;
+}
+
+/// All known adapters, in detection-priority order (first match wins).
+pub fn registry() -> Vec<Box<dyn AdapterRegistration>> {
+    vec![
+        Box::new(claude_code::ClaudeCodeRegistration),
+        Box::new(codex::CodexRegistration),
+        Box::new(aichat::AichatRegistration),
+    ]
+}
+
+/// Describes a subagent-spawning tool call recognized by
+/// [`AgentAdapter::is_subagent_tool`]: which input keys hold the subagent's
+/// type/role and task description.
+#[derive(Debug, Clone, Copy)]
+pub struct SubagentSpec {
+    /// Input key holding the subagent's type/role (e.g. `"subagent_type"`).
+    pub type_key: &'static str,
+    /// Input key holding a human-readable task description.
+    pub description_key: &'static str,
+}
+
+/// Per-agent tool vocabulary and text conventions, so a single conversion
+/// pipeline (the Claude Code adapter's internal conversion functions) can
+/// be reused across agents whose raw log shape matches but whose tool
+/// names, subagent convention, and injected prompt tags differ.
+/// [`claude_code::ClaudeCodeAdapter`] is the first implementation.
+pub trait AgentAdapter {
+    /// Extract the file path a tool call modifies, if `tool` is a
+    /// file-writing tool for this agent.
+    fn modified_path(&self, tool: &str, input: &serde_json::Value) -> Option<String>;
+
+    /// If `name` is this agent's tool for spawning a subagent, describe
+    /// where to find its type/role and description in the tool's input.
+    fn is_subagent_tool(&self, name: &str) -> Option<SubagentSpec>;
+
+    /// XML-ish tag names this agent injects into prompt text that should
+    /// be stripped before storing it (e.g. `"system-reminder"`).
+    fn system_tags(&self) -> &[&str];
+}
+
+/// Env var capping [`worker_count`], for CI runners and other constrained
+/// environments where hardware parallelism overstates the usable core count.
+pub const MAX_WORKERS_ENV_VAR: &str = "SPOOL_MAX_WORKERS";
+
+/// Number of worker threads to use for parallel scanning/conversion, sized
+/// to the available hardware parallelism (falling back to 4 when it can't
+/// be determined), capped by [`MAX_WORKERS_ENV_VAR`] when set to a valid
+/// positive integer.
+pub(crate) fn worker_count() -> usize {
+    let default = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    match std::env::var(MAX_WORKERS_ENV_VAR)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+    {
+        Some(max) if max > 0 => default.min(max),
+        _ => default,
+    }
+}
+
+/// Convert many sessions in parallel, returning one result per input
+/// session in the same order as `sessions`.
+///
+/// Sessions are split into `worker_count()` contiguous chunks and each
+/// chunk is converted on its own thread; chunking (rather than a
+/// work-stealing queue) keeps output ordering trivial to reason about.
+/// Callers that want streaming/incremental behavior should keep calling
+/// each adapter's `convert` directly instead.
+pub fn convert_many(sessions: &[SessionInfo]) -> Vec<anyhow::Result<spool_format::SpoolFile>> {
+    if sessions.is_empty() {
+        return Vec::new();
+    }
+
+    let chunk_size = sessions.len().div_ceil(worker_count());
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = sessions
+            .chunks(chunk_size.max(1))
+            .map(|chunk| scope.spawn(move || chunk.iter().map(convert_one).collect::<Vec<_>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+fn convert_one(session: &SessionInfo) -> anyhow::Result<spool_format::SpoolFile> {
+    match session.agent {
+        AgentType::ClaudeCode => claude_code::convert(session),
+        AgentType::Aichat => aichat::convert(session),
+        _ => anyhow::bail!("Unsupported agent: {}", session.agent.as_str()),
+    }
+}
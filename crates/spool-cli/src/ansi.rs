@@ -0,0 +1,399 @@
+//! ANSI SGR escape sequence parsing for tool output.
+//!
+//! Agent tool output (cargo, git, test runner output) often carries ANSI
+//! color codes. [`parse_ansi_spans`] turns an escape-laden string into
+//! styled segments with the escape bytes removed, the same shape
+//! [`crate::highlight::highlight_to_spans`] produces for syntax
+//! highlighting, so `spool view` and the TUI preview can render real
+//! colors instead of literal escape bytes breaking the `│`-bordered boxes.
+
+use ratatui::style::{Color, Modifier, Style};
+
+/// SGR (Select Graphic Rendition) state accumulated while scanning a
+/// string; reset by parameter `0`, updated in place by everything else.
+/// `pub(crate)` so [`crate::vt`]'s grid emulator can reuse the same SGR
+/// parameter table instead of duplicating it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct SgrState {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl SgrState {
+    pub(crate) fn to_style(self) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg);
+        }
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.dim {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        if self.italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if self.underline {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        style
+    }
+
+    /// Apply one `ESC [ ... m` sequence's already-split parameters.
+    pub(crate) fn apply_sgr(&mut self, params: &[u32]) {
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                0 => *self = SgrState::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                39 => self.fg = None,
+                49 => self.bg = None,
+                n @ 30..=37 => self.fg = Some(named_color((n - 30) as u8)),
+                n @ 90..=97 => self.fg = Some(bright_named_color((n - 90) as u8)),
+                n @ 40..=47 => self.bg = Some(named_color((n - 40) as u8)),
+                n @ 100..=107 => self.bg = Some(bright_named_color((n - 100) as u8)),
+                n @ (38 | 48) => {
+                    let is_fg = n == 38;
+                    match params.get(i + 1) {
+                        Some(5) => {
+                            if let Some(&idx) = params.get(i + 2) {
+                                let color = Color::Indexed(idx as u8);
+                                if is_fg {
+                                    self.fg = Some(color);
+                                } else {
+                                    self.bg = Some(color);
+                                }
+                                i += 2;
+                            }
+                        }
+                        Some(2) => {
+                            if let (Some(&r), Some(&g), Some(&b)) =
+                                (params.get(i + 2), params.get(i + 3), params.get(i + 4))
+                            {
+                                let color = Color::Rgb(r as u8, g as u8, b as u8);
+                                if is_fg {
+                                    self.fg = Some(color);
+                                } else {
+                                    self.bg = Some(color);
+                                }
+                                i += 4;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+fn named_color(n: u8) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+fn bright_named_color(n: u8) -> Color {
+    match n {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+/// Scan `text` for CSI sequences (`ESC [ ... <final byte>`), returning
+/// styled segments with every escape sequence removed from the visible
+/// text. Only SGR sequences (final byte `m`) affect style; any other CSI
+/// sequence (cursor movement, clear-line, etc.) is stripped without
+/// changing it, since those carry no information relevant to a one-shot
+/// text dump.
+pub fn parse_ansi_spans(text: &str) -> Vec<(Style, String)> {
+    let mut spans = Vec::new();
+    let mut state = SgrState::default();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' || chars.peek() != Some(&'[') {
+            current.push(c);
+            continue;
+        }
+        chars.next(); // consume '['
+
+        let mut raw = String::new();
+        let mut final_byte = None;
+        for c2 in chars.by_ref() {
+            if c2.is_ascii_alphabetic() {
+                final_byte = Some(c2);
+                break;
+            }
+            raw.push(c2);
+        }
+
+        if final_byte == Some('m') {
+            if !current.is_empty() {
+                spans.push((state.to_style(), std::mem::take(&mut current)));
+            }
+            let params: Vec<u32> = if raw.is_empty() {
+                vec![0]
+            } else {
+                raw.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+            };
+            state.apply_sgr(&params);
+        }
+        // Any other final byte (or an unterminated sequence at EOF): the
+        // escape bytes are already dropped by not having been pushed to
+        // `current`; style is left unchanged.
+    }
+    if !current.is_empty() {
+        spans.push((state.to_style(), current));
+    }
+    spans
+}
+
+/// Strip all CSI escape sequences from `text`, returning plain visible
+/// text. Strip-only mode for callers (e.g. truncation) that need to count
+/// visible characters rather than raw escape-laden bytes.
+pub fn strip_ansi(text: &str) -> String {
+    parse_ansi_spans(text)
+        .into_iter()
+        .map(|(_, s)| s)
+        .collect()
+}
+
+/// Split a flat span list (as returned by [`parse_ansi_spans`]) on
+/// embedded newlines into one span list per visual line, preserving each
+/// segment's style.
+fn split_spans_into_lines(spans: Vec<(Style, String)>) -> Vec<Vec<(Style, String)>> {
+    let mut lines: Vec<Vec<(Style, String)>> = vec![Vec::new()];
+    for (style, text) in spans {
+        let mut parts = text.split('\n');
+        if let Some(first) = parts.next() {
+            if !first.is_empty() {
+                lines.last_mut().unwrap().push((style, first.to_string()));
+            }
+        }
+        for part in parts {
+            lines.push(Vec::new());
+            if !part.is_empty() {
+                lines.last_mut().unwrap().push((style, part.to_string()));
+            }
+        }
+    }
+    lines
+}
+
+/// Parse `text` into one span list per line, for the ratatui preview —
+/// the ANSI analog of [`crate::highlight::highlight_to_spans`].
+pub fn parse_ansi_lines(text: &str) -> Vec<Vec<(Style, String)>> {
+    split_spans_into_lines(parse_ansi_spans(text))
+}
+
+/// Truncate `spans` to at most `max_chars` *visible* characters (counting
+/// by char, not escape byte, so a cut never lands inside what used to be
+/// an escape sequence — those are already gone by the time spans exist).
+/// Returns the truncated spans and whether truncation actually happened.
+pub fn truncate_spans(spans: Vec<(Style, String)>, max_chars: usize) -> (Vec<(Style, String)>, bool) {
+    let mut out = Vec::new();
+    let mut used = 0usize;
+    for (style, text) in spans {
+        if used >= max_chars {
+            return (out, true);
+        }
+        let remaining = max_chars - used;
+        let count = text.chars().count();
+        if count <= remaining {
+            used += count;
+            out.push((style, text));
+        } else {
+            out.push((style, text.chars().take(remaining).collect()));
+            return (out, true);
+        }
+    }
+    (out, false)
+}
+
+/// Re-render `spans` as a `spool view` ANSI terminal string, honoring
+/// `NO_COLOR` (<https://no-color.org>) by falling back to plain text.
+pub fn spans_to_ansi(spans: &[(Style, String)]) -> String {
+    if std::env::var_os("NO_COLOR").is_some() {
+        return spans.iter().map(|(_, s)| s.as_str()).collect();
+    }
+
+    let mut out = String::new();
+    for (style, text) in spans {
+        let prefix = style_to_sgr(*style);
+        if !prefix.is_empty() {
+            out.push_str(&prefix);
+        }
+        out.push_str(text);
+        if !prefix.is_empty() {
+            out.push_str("\x1b[0m");
+        }
+    }
+    out
+}
+
+fn style_to_sgr(style: Style) -> String {
+    let mut codes: Vec<String> = Vec::new();
+    if let Some(color) = style.fg {
+        codes.extend(color_to_sgr(color, false));
+    }
+    if let Some(color) = style.bg {
+        codes.extend(color_to_sgr(color, true));
+    }
+    if style.add_modifier.contains(Modifier::BOLD) {
+        codes.push("1".to_string());
+    }
+    if style.add_modifier.contains(Modifier::ITALIC) {
+        codes.push("3".to_string());
+    }
+    if style.add_modifier.contains(Modifier::UNDERLINED) {
+        codes.push("4".to_string());
+    }
+    if codes.is_empty() {
+        return String::new();
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_to_sgr(color: Color, bg: bool) -> Vec<String> {
+    let base = if bg { 48 } else { 38 };
+    match color {
+        Color::Rgb(r, g, b) => vec![format!("{};2;{};{};{}", base, r, g, b)],
+        Color::Indexed(n) => vec![format!("{};5;{}", base, n)],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ansi_spans_strips_reset_and_splits_on_color_change() {
+        let spans = parse_ansi_spans("\x1b[31mred\x1b[0m plain");
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].1, "red");
+        assert_eq!(spans[0].0, Style::default().fg(Color::Red));
+        assert_eq!(spans[1].1, " plain");
+        assert_eq!(spans[1].0, Style::default());
+    }
+
+    #[test]
+    fn parse_ansi_spans_maps_bright_and_background_colors() {
+        let spans = parse_ansi_spans("\x1b[92;44mtext\x1b[0m");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(
+            spans[0].0,
+            Style::default().fg(Color::LightGreen).bg(Color::Blue)
+        );
+    }
+
+    #[test]
+    fn parse_ansi_spans_maps_256_color_and_truecolor() {
+        let indexed = parse_ansi_spans("\x1b[38;5;200mx\x1b[0m");
+        assert_eq!(indexed[0].0, Style::default().fg(Color::Indexed(200)));
+
+        let truecolor = parse_ansi_spans("\x1b[38;2;10;20;30mx\x1b[0m");
+        assert_eq!(truecolor[0].0, Style::default().fg(Color::Rgb(10, 20, 30)));
+    }
+
+    #[test]
+    fn parse_ansi_spans_applies_bold_italic_underline() {
+        let spans = parse_ansi_spans("\x1b[1;3;4mstyled\x1b[0m");
+        assert_eq!(
+            spans[0].0,
+            Style::default().add_modifier(Modifier::BOLD | Modifier::ITALIC | Modifier::UNDERLINED)
+        );
+    }
+
+    #[test]
+    fn parse_ansi_spans_applies_dim() {
+        let spans = parse_ansi_spans("\x1b[2mfaint\x1b[0m");
+        assert_eq!(spans[0].0, Style::default().add_modifier(Modifier::DIM));
+    }
+
+    #[test]
+    fn parse_ansi_spans_strips_non_sgr_csi_sequences() {
+        // Cursor-up (`A`) carries no style info; it should just vanish.
+        let spans = parse_ansi_spans("a\x1b[2Ab");
+        let text: String = spans.iter().map(|(_, s)| s.as_str()).collect();
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn strip_ansi_removes_all_escapes() {
+        assert_eq!(strip_ansi("\x1b[31mred\x1b[0m"), "red");
+    }
+
+    #[test]
+    fn parse_ansi_lines_splits_styled_text_on_newlines() {
+        let lines = parse_ansi_lines("\x1b[31mred\nstill red\x1b[0m");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], vec![(Style::default().fg(Color::Red), "red".to_string())]);
+        assert_eq!(
+            lines[1],
+            vec![(Style::default().fg(Color::Red), "still red".to_string())]
+        );
+    }
+
+    #[test]
+    fn truncate_spans_cuts_mid_segment_and_reports_truncation() {
+        let spans = vec![
+            (Style::default(), "hello ".to_string()),
+            (Style::default().fg(Color::Red), "world".to_string()),
+        ];
+        let (truncated, was_truncated) = truncate_spans(spans, 8);
+        assert!(was_truncated);
+        let text: String = truncated.iter().map(|(_, s)| s.as_str()).collect();
+        assert_eq!(text, "hello wo");
+    }
+
+    #[test]
+    fn truncate_spans_reports_no_truncation_when_it_fits() {
+        let spans = vec![(Style::default(), "short".to_string())];
+        let (truncated, was_truncated) = truncate_spans(spans, 10);
+        assert!(!was_truncated);
+        assert_eq!(truncated, vec![(Style::default(), "short".to_string())]);
+    }
+
+    #[test]
+    fn spans_to_ansi_round_trips_color() {
+        let spans = vec![(Style::default().fg(Color::Red), "red".to_string())];
+        let rendered = spans_to_ansi(&spans);
+        assert!(rendered.contains("red"));
+        assert!(rendered.contains("\x1b["));
+    }
+}
@@ -0,0 +1,231 @@
+//! Content-defined chunking and a blake3-addressed blob store, shared by
+//! the session cache ([`crate::commands::cache`]) and the session
+//! repository ([`crate::commands::repo`]).
+//!
+//! Splits a byte stream into content-defined chunks with a buzhash rolling
+//! hash over a sliding window, so a payload that recurs across many
+//! sessions (file contents, diffs, near-identical tool output) rechunks to
+//! the same boundaries wherever it appears and dedups against chunks
+//! already on disk under their blake3 hash.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Average chunk size target is 2^13 bytes (~8 KiB).
+const CHUNK_MASK: u64 = (1u64 << 13) - 1;
+/// Lower bound on chunk size, so a run of boundary-triggering bytes can't
+/// fragment the store into a flood of tiny chunks.
+const MIN_CHUNK: usize = 2 * 1024;
+/// Upper bound on chunk size, so a long stretch that never triggers a
+/// boundary doesn't produce one unbounded chunk.
+const MAX_CHUNK: usize = 64 * 1024;
+/// Width of the rolling hash's window, in bytes.
+const WINDOW: usize = 64;
+
+/// Deterministic pseudo-random table for the buzhash rolling hash, built
+/// once per process with a splitmix64 generator (no need for true
+/// randomness - the table just has to mix bytes well).
+fn buzhash_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u64; 256];
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        for slot in table.iter_mut() {
+            state = state.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^= z >> 31;
+            *slot = z;
+        }
+        table
+    })
+}
+
+/// Split `data` into content-defined chunk boundaries using a buzhash
+/// rolling hash over a [`WINDOW`]-byte window: a boundary falls wherever
+/// `hash & CHUNK_MASK == 0`, targeting an ~8 KiB average chunk size and
+/// clamped to `[MIN_CHUNK, MAX_CHUNK]`.
+pub fn chunk_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = buzhash_table();
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u64 = 0;
+    let mut window = [0u8; WINDOW];
+    let mut window_len = 0usize;
+    let mut window_pos = 0usize;
+
+    for (i, &byte) in data.iter().enumerate() {
+        if window_len < WINDOW {
+            hash = hash.rotate_left(1) ^ table[byte as usize];
+            window[window_pos] = byte;
+            window_len += 1;
+        } else {
+            let outgoing = window[window_pos];
+            hash = hash.rotate_left(1)
+                ^ table[outgoing as usize].rotate_left(WINDOW as u32)
+                ^ table[byte as usize];
+            window[window_pos] = byte;
+        }
+        window_pos = (window_pos + 1) % WINDOW;
+
+        let chunk_len = i - start + 1;
+        let at_end = i == data.len() - 1;
+        let at_boundary =
+            chunk_len >= MIN_CHUNK && (hash & CHUNK_MASK == 0 || chunk_len >= MAX_CHUNK);
+
+        if at_boundary || at_end {
+            boundaries.push((start, i + 1));
+            start = i + 1;
+            hash = 0;
+            window_len = 0;
+            window_pos = 0;
+        }
+    }
+
+    boundaries
+}
+
+/// Result of [`write_chunks`]: the ordered hashes that reassemble into the
+/// original bytes, plus how many of them were newly written (the rest
+/// already existed in the store under the same hash - the dedup payoff).
+pub struct ChunkWriteStats {
+    pub hashes: Vec<String>,
+    pub new_chunks: usize,
+}
+
+/// Split `bytes` into content-defined chunks, writing each one to
+/// `chunks_dir` under its blake3 hash (skipping chunks already present),
+/// and return the ordered list of chunk hashes.
+pub fn write_chunks(chunks_dir: &Path, bytes: &[u8]) -> Result<ChunkWriteStats> {
+    let mut hashes = Vec::with_capacity(bytes.len() / MIN_CHUNK + 1);
+    let mut new_chunks = 0;
+    for (start, end) in chunk_boundaries(bytes) {
+        let chunk = &bytes[start..end];
+        let hash = blake3::hash(chunk).to_hex().to_string();
+        let chunk_path = chunks_dir.join(&hash);
+        if !chunk_path.exists() {
+            write_atomic(&chunk_path, chunk)?;
+            new_chunks += 1;
+        }
+        hashes.push(hash);
+    }
+    Ok(ChunkWriteStats { hashes, new_chunks })
+}
+
+/// Write `bytes` to `path` via a `.tmp` sibling and `fs::rename`, so a
+/// reader never observes a partially-written file (a chunk store hit on a
+/// half-written chunk would silently read back corrupt content under a
+/// content-addressed name that looks valid).
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, bytes).with_context(|| format!("Failed to write {:?}", tmp_path))?;
+    fs::rename(&tmp_path, path).with_context(|| format!("Failed to replace {:?}", path))?;
+    Ok(())
+}
+
+/// Reassemble bytes from an ordered list of chunk hashes, trusting the
+/// filename with no hash re-check - for a cache this process itself wrote
+/// and is the only writer of.
+pub fn read_chunks(chunks_dir: &Path, hashes: &[String]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for hash in hashes {
+        bytes.extend_from_slice(&fs::read(chunks_dir.join(hash)).ok()?);
+    }
+    Some(bytes)
+}
+
+/// Reassemble bytes from an ordered list of chunk hashes, recomputing each
+/// chunk's blake3 hash and erroring if it doesn't match the name it's
+/// stored under - for a repository restore, where the chunk store isn't
+/// necessarily trusted (could have been copied between machines, touched
+/// by another process, or bit-rotted on disk).
+pub fn read_chunks_verified(chunks_dir: &Path, hashes: &[String]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for hash in hashes {
+        let chunk_path = chunks_dir.join(hash);
+        let chunk =
+            fs::read(&chunk_path).with_context(|| format!("Missing chunk {:?}", chunk_path))?;
+        let actual = blake3::hash(&chunk).to_hex().to_string();
+        if actual != *hash {
+            anyhow::bail!("chunk {} failed integrity check (recomputed hash {})", hash, actual);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_boundaries_cover_the_whole_input_contiguously() {
+        let data = vec![7u8; 200_000];
+        let boundaries = chunk_boundaries(&data);
+        assert_eq!(boundaries.first().unwrap().0, 0);
+        assert_eq!(boundaries.last().unwrap().1, data.len());
+        for window in boundaries.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+    }
+
+    #[test]
+    fn empty_input_has_no_boundaries() {
+        assert!(chunk_boundaries(&[]).is_empty());
+    }
+
+    #[test]
+    fn write_then_read_chunks_round_trips() {
+        let dir = std::env::temp_dir().join(format!("spool-chunking-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let data = b"hello world, this is some test content to chunk".repeat(1000);
+
+        let stats = write_chunks(&dir, &data).unwrap();
+        let restored = read_chunks(&dir, &stats.hashes).unwrap();
+        assert_eq!(restored, data);
+
+        let verified = read_chunks_verified(&dir, &stats.hashes).unwrap();
+        assert_eq!(verified, data);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn identical_chunks_across_two_writes_are_not_duplicated() {
+        let dir = std::env::temp_dir().join(format!("spool-chunking-test-dedup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let data = b"repeated payload ".repeat(5000);
+
+        let first = write_chunks(&dir, &data).unwrap();
+        assert!(first.new_chunks > 0);
+        let second = write_chunks(&dir, &data).unwrap();
+        assert_eq!(second.new_chunks, 0);
+        assert_eq!(first.hashes, second.hashes);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_tampered_chunk_fails_verified_read() {
+        let dir = std::env::temp_dir().join(format!("spool-chunking-test-tamper-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let data = b"some content that will be tampered with after storing".repeat(100);
+
+        let stats = write_chunks(&dir, &data).unwrap();
+        let tampered_path = dir.join(&stats.hashes[0]);
+        fs::write(&tampered_path, b"corrupted").unwrap();
+
+        assert!(read_chunks_verified(&dir, &stats.hashes).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,13 +1,31 @@
 //! Shared helpers for detecting agent logs, discovering sessions, and converting to Spool.
 
 use anyhow::{Context, Result};
-use spool_adapters::{claude_code, codex, AgentType, SessionInfo};
-use spool_format::SpoolFile;
+use spool_adapters::{AgentType, SessionInfo};
+use spool_format::{decrypt_spool_file, is_encrypted, SpoolFile};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+/// Env var holding the passphrase for an encrypted `.spool.enc` file, so
+/// scripts/CI can encrypt/decrypt without an interactive prompt. Checked
+/// before falling back to a terminal prompt in [`passphrase_for_decrypt`]
+/// and [`passphrase_for_encrypt`].
+pub const PASSPHRASE_ENV_VAR: &str = "SPOOL_PASSPHRASE";
+
 pub fn load_spool_or_log(path: &Path) -> Result<SpoolFile> {
+    let is_enc_extension = path
+        .extension()
+        .map(|e| e == "enc")
+        .unwrap_or(false);
+
+    if is_enc_extension || looks_encrypted(path)? {
+        let data = std::fs::read(path).with_context(|| format!("Failed to read: {:?}", path))?;
+        let passphrase = passphrase_for_decrypt()?;
+        return decrypt_spool_file(&data, &passphrase)
+            .with_context(|| format!("Failed to decrypt: {:?}", path));
+    }
+
     if path.extension().map(|e| e == "spool").unwrap_or(false) {
         return SpoolFile::from_path(path).with_context(|| format!("Failed to read: {:?}", path));
     }
@@ -23,19 +41,58 @@ pub fn load_spool_or_log(path: &Path) -> Result<SpoolFile> {
         message_count: None,
     };
 
-    match agent {
-        AgentType::ClaudeCode => claude_code::convert(&session_info)
-            .with_context(|| format!("Failed to convert session: {:?}", path)),
-        AgentType::Codex => codex::convert(&session_info)
-            .with_context(|| format!("Failed to convert session: {:?}", path)),
-        _ => anyhow::bail!("Unsupported agent log: {:?}", path),
+    convert_session(&session_info).with_context(|| format!("Failed to convert session: {:?}", path))
+}
+
+/// Sniff `path`'s first few bytes for the encrypted-container magic, so a
+/// `.spool.enc` that got renamed (or any extension at all) is still
+/// transparently detected rather than failing to parse as JSONL.
+fn looks_encrypted(path: &Path) -> Result<bool> {
+    let mut buf = [0u8; 8];
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Ok(false),
+    };
+    use std::io::Read;
+    match file.read_exact(&mut buf) {
+        Ok(()) => Ok(is_encrypted(&buf)),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Get the passphrase to decrypt an encrypted container: from
+/// [`PASSPHRASE_ENV_VAR`] if set, otherwise an interactive, non-echoing
+/// terminal prompt.
+pub fn passphrase_for_decrypt() -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password("Passphrase: ").context("Failed to read passphrase")
+}
+
+/// Get the passphrase to encrypt a new container: from [`PASSPHRASE_ENV_VAR`]
+/// if set, otherwise an interactive prompt with confirmation (entered twice,
+/// to catch typos that would otherwise lock the caller out of their own
+/// export).
+pub fn passphrase_for_encrypt() -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(passphrase);
+    }
+    let passphrase =
+        rpassword::prompt_password("Passphrase: ").context("Failed to read passphrase")?;
+    let confirm = rpassword::prompt_password("Confirm passphrase: ")
+        .context("Failed to read passphrase")?;
+    if passphrase != confirm {
+        anyhow::bail!("Passphrases did not match");
     }
+    Ok(passphrase)
 }
 
 pub fn detect_agent_from_log(path: &Path) -> Result<AgentType> {
     let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
     let mut reader = BufReader::new(file);
     let mut line = String::new();
+    let adapters = spool_adapters::registry();
     loop {
         line.clear();
         if reader.read_line(&mut line)? == 0 {
@@ -46,12 +103,11 @@ pub fn detect_agent_from_log(path: &Path) -> Result<AgentType> {
         }
         let value: serde_json::Value = serde_json::from_str(&line)
             .with_context(|| format!("Failed to parse JSON line in {:?}", path))?;
-        let kind = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        return Ok(match kind {
-            "session_meta" => AgentType::Codex,
-            "user" | "assistant" | "progress" | "summary" | "system" => AgentType::ClaudeCode,
-            _ => AgentType::Unknown,
-        });
+        return Ok(adapters
+            .iter()
+            .find(|a| a.detect(&value))
+            .map(|a| a.agent_type())
+            .unwrap_or(AgentType::Unknown));
     }
     Ok(AgentType::Unknown)
 }
@@ -59,9 +115,14 @@ pub fn detect_agent_from_log(path: &Path) -> Result<AgentType> {
 /// Discover all sessions from all known agent log locations.
 /// Returns sessions sorted by modified_at (newest first), filtering out empty sessions.
 pub fn find_all_sessions() -> Result<Vec<SessionInfo>> {
-    let mut sessions =
-        claude_code::find_sessions().context("Failed to discover Claude Code sessions")?;
-    sessions.extend(codex::find_sessions().context("Failed to discover Codex sessions")?);
+    let mut sessions = Vec::new();
+    for adapter in spool_adapters::registry() {
+        sessions.extend(
+            adapter
+                .find_sessions()
+                .with_context(|| format!("Failed to discover {} sessions", adapter.agent_type().as_str()))?,
+        );
+    }
     sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
     sessions.retain(|s| s.message_count.map(|c| c > 0).unwrap_or(true));
     Ok(sessions)
@@ -69,9 +130,9 @@ pub fn find_all_sessions() -> Result<Vec<SessionInfo>> {
 
 /// Convert a SessionInfo into a SpoolFile using the appropriate adapter.
 pub fn convert_session(session: &SessionInfo) -> Result<SpoolFile> {
-    match session.agent {
-        AgentType::ClaudeCode => claude_code::convert(session),
-        AgentType::Codex => codex::convert(session),
-        _ => anyhow::bail!("Unsupported agent: {}", session.agent.as_str()),
-    }
+    spool_adapters::registry()
+        .into_iter()
+        .find(|a| a.agent_type() == session.agent)
+        .map(|a| a.convert(session))
+        .unwrap_or_else(|| anyhow::bail!("Unsupported agent: {}", session.agent.as_str()))
 }
@@ -62,6 +62,7 @@ fn draw_session_list(f: &mut Frame, area: Rect, app: &App) {
                 "codex" => "CX",
                 "cursor" => "CU",
                 "aider" => "AI",
+                "aichat" => "AC",
                 _ => "??",
             };
 
@@ -317,6 +318,21 @@ fn draw_preview(f: &mut Frame, area: Rect, app: &App) {
                 )));
                 lines.push(Line::from(""));
             }
+            Entry::Terminal(t) => {
+                lines.push(Line::from(Span::styled(
+                    "TERMINAL",
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                let text = String::from_utf8_lossy(&t.decoded_bytes()).into_owned();
+                let preview_lines: Vec<&str> = text.lines().take(3).collect();
+                for line in preview_lines {
+                    let truncated = truncate_str(line, area.width as usize - 4);
+                    lines.push(Line::from(format!("  {}", truncated)));
+                }
+                lines.push(Line::from(""));
+            }
             Entry::Unknown => {}
         }
     }
@@ -1,38 +1,87 @@
 //! Session cache - stores converted SpoolFiles to avoid re-parsing unchanged logs.
 //!
-//! Cache entries are stored in `~/.cache/spool/` (or platform equivalent) as `.spool` files,
-//! named by a hash of the source path. Each entry includes a metadata sidecar with the
-//! source file's mtime, so we can detect when the cache is stale.
+//! Cache entries are stored in `~/.cache/spool/` (or platform equivalent).
+//! Converted sessions are split into content-defined chunks under
+//! `chunks/`, keyed by the blake3 hash of their bytes, so payloads that
+//! recur across sessions (file contents, diffs, near-identical tool
+//! output) are only stored once; `sessions/<key>.manifest` records the
+//! ordered list of chunk hashes that reassemble into the session, and
+//! `sessions/<key>.meta` holds the source file's mtime/size, so we can
+//! detect when the cache is stale.
 
+pub mod catalog;
+
+use crate::chunking::{read_chunks, write_atomic, write_chunks};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use spool_format::SpoolFile;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-/// Metadata stored alongside each cached spool file.
+/// Env var that, when set to any value, makes cache hit/miss/stale
+/// decisions log a line per source path to stderr.
+pub const CACHE_DEBUG_ENV_VAR: &str = "SPOOL_CACHE_DEBUG";
+
+fn debug_enabled() -> bool {
+    std::env::var_os(CACHE_DEBUG_ENV_VAR).is_some()
+}
+
+fn debug_log(source: &Path, decision: &str) {
+    if debug_enabled() {
+        eprintln!("[spool cache] {}: {:?}", decision, source);
+    }
+}
+
+/// Metadata stored alongside each cached session's manifest.
 #[derive(Serialize, Deserialize)]
 struct CacheMeta {
     /// Modification time of the source file when cached.
     source_mtime_secs: u64,
     /// Size of the source file when cached.
     source_size: u64,
+    /// blake3 hash of the source file's bytes when cached, used to
+    /// disambiguate same-second edits that mtime+size alone can't catch
+    /// (coarse filesystem mtime granularity, or a same-size rewrite).
+    /// `None` for entries cached before this field existed.
+    source_hash: Option<String>,
 }
 
-/// Get the cache directory, creating it if necessary.
-fn cache_dir() -> Result<PathBuf> {
+/// Ordered list of chunk hashes (hex blake3) that reassemble into the
+/// cached session's serialized bytes.
+#[derive(Serialize, Deserialize)]
+struct ChunkManifest {
+    chunks: Vec<String>,
+}
+
+/// Get the root cache directory (`~/.cache/spool` or platform equivalent).
+fn cache_root() -> Result<PathBuf> {
     let base = dirs::cache_dir()
         .or_else(dirs::data_local_dir)
         .unwrap_or_else(|| PathBuf::from(".cache"));
-    let cache_path = base.join("spool").join("sessions");
-    if !cache_path.exists() {
-        fs::create_dir_all(&cache_path)
-            .with_context(|| format!("Failed to create cache directory: {:?}", cache_path))?;
+    Ok(base.join("spool"))
+}
+
+/// Get a cache subdirectory, creating it if necessary.
+fn ensure_dir(path: PathBuf) -> Result<PathBuf> {
+    if !path.exists() {
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create cache directory: {:?}", path))?;
     }
-    Ok(cache_path)
+    Ok(path)
+}
+
+/// Directory holding per-session manifests and metadata sidecars.
+fn sessions_dir() -> Result<PathBuf> {
+    ensure_dir(cache_root()?.join("sessions"))
+}
+
+/// Directory holding the deduplicated, content-addressed chunk store.
+fn chunks_dir() -> Result<PathBuf> {
+    ensure_dir(cache_root()?.join("chunks"))
 }
 
 /// Generate a cache key from a source path.
@@ -57,57 +106,92 @@ fn source_metadata(source: &Path) -> Result<(u64, u64)> {
 /// Try to load a cached SpoolFile for the given source path.
 /// Returns None if not cached or if the cache is stale.
 pub fn load_cached(source: &Path) -> Option<SpoolFile> {
-    let cache_path = cache_dir().ok()?;
+    let sessions_path = sessions_dir().ok()?;
+    let chunks_path = chunks_dir().ok()?;
     let key = cache_key(source);
-    let spool_path = cache_path.join(format!("{}.spool", key));
-    let meta_path = cache_path.join(format!("{}.meta", key));
+    let manifest_path = sessions_path.join(format!("{}.manifest", key));
+    let meta_path = sessions_path.join(format!("{}.meta", key));
 
-    // Check if cache files exist
-    if !spool_path.exists() || !meta_path.exists() {
+    if !manifest_path.exists() || !meta_path.exists() {
+        debug_log(source, "miss (no cache entry)");
         return None;
     }
 
-    // Load and validate metadata
     let meta_json = fs::read_to_string(&meta_path).ok()?;
     let meta: CacheMeta = serde_json::from_str(&meta_json).ok()?;
 
     // Check if source has changed
     let (current_mtime, current_size) = source_metadata(source).ok()?;
     if meta.source_mtime_secs != current_mtime || meta.source_size != current_size {
-        // Cache is stale, remove it
-        let _ = fs::remove_file(&spool_path);
+        debug_log(source, "stale (mtime/size changed)");
+        // Cache is stale, remove it (the chunks it references are left for
+        // gc() to reclaim, since other manifests may still need them)
+        let _ = fs::remove_file(&manifest_path);
         let _ = fs::remove_file(&meta_path);
         return None;
     }
 
-    // Load the cached spool file
-    SpoolFile::from_path(&spool_path).ok()
+    // mtime+size alone can't tell apart a same-second rewrite on
+    // filesystems with coarse mtime granularity, so when we have a source
+    // hash on record, recheck it before trusting the cache.
+    if let Some(expected_hash) = &meta.source_hash {
+        let current_hash = blake3::hash(&fs::read(source).ok()?).to_hex().to_string();
+        if *expected_hash != current_hash {
+            debug_log(source, "stale (content hash mismatch)");
+            let _ = fs::remove_file(&manifest_path);
+            let _ = fs::remove_file(&meta_path);
+            return None;
+        }
+    }
+
+    let manifest_json = fs::read_to_string(&manifest_path).ok()?;
+    let manifest: ChunkManifest = serde_json::from_str(&manifest_json).ok()?;
+    let bytes = read_chunks(&chunks_path, &manifest.chunks)?;
+
+    let result = SpoolFile::from_reader(std::io::Cursor::new(bytes)).ok();
+    debug_log(
+        source,
+        if result.is_some() { "hit" } else { "miss (corrupt cache entry)" },
+    );
+    result
 }
 
 /// Save a SpoolFile to the cache for the given source path.
 pub fn save_cached(source: &Path, spool: &SpoolFile) -> Result<()> {
-    let cache_path = cache_dir()?;
+    let sessions_path = sessions_dir()?;
+    let chunks_path = chunks_dir()?;
     let key = cache_key(source);
-    let spool_path = cache_path.join(format!("{}.spool", key));
-    let meta_path = cache_path.join(format!("{}.meta", key));
+    let manifest_path = sessions_path.join(format!("{}.manifest", key));
+    let meta_path = sessions_path.join(format!("{}.meta", key));
 
     // Get source metadata
     let (mtime, size) = source_metadata(source)?;
+    let source_hash = fs::read(source)
+        .ok()
+        .map(|bytes| blake3::hash(&bytes).to_hex().to_string());
     let meta = CacheMeta {
         source_mtime_secs: mtime,
         source_size: size,
+        source_hash,
     };
 
-    // Write the spool file
+    let mut bytes = Vec::new();
     spool
-        .write_to_path(&spool_path)
-        .with_context(|| format!("Failed to write cache: {:?}", spool_path))?;
+        .write_to(&mut bytes)
+        .with_context(|| "Failed to serialize session for caching")?;
+
+    let chunks = write_chunks(&chunks_path, &bytes)?.hashes;
+    let manifest = ChunkManifest { chunks };
+
+    let manifest_json = serde_json::to_string(&manifest)?;
+    write_atomic(&manifest_path, manifest_json.as_bytes())
+        .with_context(|| format!("Failed to write cache manifest: {:?}", manifest_path))?;
 
-    // Write the metadata
     let meta_json = serde_json::to_string(&meta)?;
-    fs::write(&meta_path, meta_json)
+    write_atomic(&meta_path, meta_json.as_bytes())
         .with_context(|| format!("Failed to write cache meta: {:?}", meta_path))?;
 
+    debug_log(source, "saved");
     Ok(())
 }
 
@@ -138,3 +222,40 @@ where
 
     Ok(spool)
 }
+
+/// Delete chunks in the chunk store that are no longer referenced by any
+/// session manifest. Returns the number of chunks removed.
+pub fn gc() -> Result<usize> {
+    let sessions_path = sessions_dir()?;
+    let chunks_path = chunks_dir()?;
+
+    let mut referenced: HashSet<String> = HashSet::new();
+    for entry in fs::read_dir(&sessions_path)
+        .with_context(|| format!("Failed to read {:?}", sessions_path))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map(|e| e == "manifest").unwrap_or(false) {
+            if let Ok(json) = fs::read_to_string(&path) {
+                if let Ok(manifest) = serde_json::from_str::<ChunkManifest>(&json) {
+                    referenced.extend(manifest.chunks);
+                }
+            }
+        }
+    }
+
+    let mut removed = 0;
+    for entry in
+        fs::read_dir(&chunks_path).with_context(|| format!("Failed to read {:?}", chunks_path))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !referenced.contains(&name) {
+            fs::remove_file(entry.path())
+                .with_context(|| format!("Failed to remove unreferenced chunk: {:?}", name))?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
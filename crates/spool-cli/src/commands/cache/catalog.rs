@@ -0,0 +1,170 @@
+//! Persistent session catalog - avoids re-walking and re-parsing every
+//! session log on every cold start.
+//!
+//! [`scan`] discovers every session across all registered agents, but
+//! only calls an adapter's (often expensive, full-file-parsing)
+//! `session_info_for` for files whose mtime and size have changed since
+//! the catalog was last written; everything else reuses the cached
+//! [`spool_adapters::SessionInfo`] row as-is. This turns a cold-start
+//! library load into roughly one `stat` per session file in the common
+//! case of nothing having changed, rather than a full re-parse of every
+//! log.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use spool_adapters::{AdapterRegistration, SessionInfo};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One catalog row: the stat values a session's file had when it was last
+/// parsed, plus the metadata that parse produced.
+#[derive(Serialize, Deserialize, Clone)]
+struct CatalogEntry {
+    modified_secs: u64,
+    size: u64,
+    session: SessionInfo,
+}
+
+/// The full on-disk catalog, keyed by session path.
+#[derive(Serialize, Deserialize, Default)]
+struct Catalog {
+    entries: HashMap<String, CatalogEntry>,
+}
+
+fn catalog_path() -> Result<PathBuf> {
+    Ok(super::cache_root()?.join("catalog.json"))
+}
+
+fn load_catalog() -> Catalog {
+    catalog_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Write the catalog back atomically: serialize to a temp file in the
+/// same directory, then rename over the real path, so a crash or a
+/// concurrently running `spool` never observes a half-written catalog.
+fn save_catalog(catalog: &Catalog) -> Result<()> {
+    let path = catalog_path()?;
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string(catalog)?;
+    fs::write(&tmp_path, &json).with_context(|| format!("Failed to write {:?}", tmp_path))?;
+    fs::rename(&tmp_path, &path).with_context(|| format!("Failed to replace {:?}", path))?;
+    Ok(())
+}
+
+fn file_stat(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let modified_secs = meta
+        .modified()
+        .ok()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some((modified_secs, meta.len()))
+}
+
+/// Reuse the catalog row for `path` if its mtime+size are unchanged,
+/// otherwise fall back to `adapter.session_info_for`.
+fn refresh_one(
+    adapter: &dyn AdapterRegistration,
+    path: &Path,
+    catalog: &Catalog,
+) -> Option<SessionInfo> {
+    let (modified_secs, size) = file_stat(path)?;
+
+    if let Some(cached) = catalog.entries.get(&path_key(path)) {
+        if cached.modified_secs == modified_secs && cached.size == size {
+            return Some(cached.session.clone());
+        }
+    }
+
+    adapter.session_info_for(path).ok()
+}
+
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// Discover every session across all registered agents, reusing cached
+/// metadata from the persistent catalog wherever possible. Paths are
+/// listed cheaply per-adapter, then each one's freshness check (and, on a
+/// miss, its re-parse) runs in parallel across a fixed worker pool sized
+/// to the available hardware parallelism. The merged catalog is written
+/// back to disk before returning.
+pub fn scan() -> Result<Vec<SessionInfo>> {
+    let mut catalog = load_catalog();
+    let adapters = spool_adapters::registry();
+
+    let mut paths: Vec<(usize, PathBuf)> = Vec::new();
+    for (adapter_idx, adapter) in adapters.iter().enumerate() {
+        for path in adapter.list_session_paths()? {
+            paths.push((adapter_idx, path));
+        }
+    }
+
+    if paths.is_empty() {
+        catalog.entries.clear();
+        save_catalog(&catalog)?;
+        return Ok(Vec::new());
+    }
+
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let chunk_size = paths.len().div_ceil(worker_count).max(1);
+
+    let refreshed: Vec<(PathBuf, Option<SessionInfo>)> = std::thread::scope(|scope| {
+        let adapters = &adapters;
+        let catalog = &catalog;
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(adapter_idx, path)| {
+                            let session = refresh_one(adapters[*adapter_idx].as_ref(), path, catalog);
+                            (path.clone(), session)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+
+    let mut sessions = Vec::with_capacity(refreshed.len());
+    for (path, session) in refreshed {
+        let Some(session) = session else { continue };
+        if let Some((modified_secs, size)) = file_stat(&path) {
+            catalog.entries.insert(
+                path_key(&path),
+                CatalogEntry {
+                    modified_secs,
+                    size,
+                    session: session.clone(),
+                },
+            );
+        }
+        sessions.push(session);
+    }
+
+    // Drop rows for session files that have since been deleted or moved,
+    // so the catalog doesn't grow without bound.
+    let live_paths: HashSet<String> = paths.iter().map(|(_, p)| path_key(p)).collect();
+    catalog.entries.retain(|path, _| live_paths.contains(path));
+
+    save_catalog(&catalog)?;
+
+    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(sessions)
+}
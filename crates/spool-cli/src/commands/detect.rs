@@ -2,7 +2,9 @@
 
 use anyhow::Result;
 use serde::Serialize;
-use spool_format::{Entry, SecretDetector, SpoolFile, ToolOutput};
+use spool_format::{
+    Entry, PseudonymizationMode, RedactionConfig, SecretDetector, SpoolFile, TokenCounter, ToolOutput,
+};
 use std::path::Path;
 
 use super::agent::load_spool_or_log;
@@ -33,10 +35,51 @@ pub struct Detection {
     pub end: usize,
 }
 
+/// Build the detector used by the `detect`/`export` commands: the default
+/// config plus `high_entropy`, with any custom rules at `rules_path`
+/// merged in (see [`SecretDetector::from_config`]). A missing rules file
+/// is treated as "no custom rules"; a malformed one is an error.
+/// `pseudonymize` swaps flat `[REDACTED:category]` tags for stable
+/// per-secret `[REDACTED:category:token]` placeholders (see
+/// [`PseudonymizationMode`]).
+pub fn build_detector(
+    high_entropy: bool,
+    rules_path: Option<&Path>,
+    pseudonymize: bool,
+) -> Result<SecretDetector> {
+    let mut config = RedactionConfig {
+        detect_high_entropy: high_entropy,
+        pseudonymize: if pseudonymize {
+            PseudonymizationMode::RandomSalt
+        } else {
+            PseudonymizationMode::Off
+        },
+        ..RedactionConfig::default()
+    };
+    if let Some(path) = rules_path {
+        let profile = spool_format::RedactionProfile::load(path).map_err(anyhow::Error::msg)?;
+        config.custom_rules.extend(profile.rules);
+    }
+    Ok(SecretDetector::new(config))
+}
+
 /// Detect all secrets in a SpoolFile.
 /// Returns a list of detections with indices for selective redaction.
-pub fn detect_secrets(file: &SpoolFile) -> Vec<Detection> {
-    let detector = SecretDetector::with_defaults();
+/// `high_entropy` opts into [`RedactionConfig::detect_high_entropy`]'s
+/// generic, vendor-unaware scan for vendor-unknown tokens - off by
+/// default since it's a heuristic that can false-positive.
+pub fn detect_secrets(file: &SpoolFile, high_entropy: bool) -> Vec<Detection> {
+    let detector = SecretDetector::new(RedactionConfig {
+        detect_high_entropy: high_entropy,
+        ..RedactionConfig::default()
+    });
+    detect_secrets_with(file, &detector)
+}
+
+/// Detect all secrets in a SpoolFile using an already-built `detector` -
+/// the entry point for callers (like the CLI commands) that need custom
+/// rules merged in via [`build_detector`].
+pub fn detect_secrets_with(file: &SpoolFile, detector: &SecretDetector) -> Vec<Detection> {
     let mut detections = Vec::new();
     let mut detection_index = 0;
 
@@ -65,7 +108,7 @@ pub fn detect_secrets(file: &SpoolFile) -> Vec<Detection> {
                     entry_type: entry_type.to_string(),
                     category: format!("{:?}", secret.reason),
                     matched: secret.matched.clone(),
-                    replacement: secret.reason.replacement().to_string(),
+                    replacement: detector.replacement_for(&secret),
                     context_before: extract_context_before(text, secret.start, 40),
                     context_after: extract_context_after(text, secret.end, 40),
                     start: secret.start,
@@ -172,9 +215,10 @@ fn extract_context_after(text: &str, pos: usize, max_len: usize) -> String {
 }
 
 /// Run the detect command.
-pub fn run(source: &Path, json: bool) -> Result<()> {
+pub fn run(source: &Path, high_entropy: bool, rules: Option<&Path>, json: bool) -> Result<()> {
     let file = load_spool_or_log(source)?;
-    let detections = detect_secrets(&file);
+    let detector = build_detector(high_entropy, rules, false)?;
+    let detections = detect_secrets_with(&file, &detector);
 
     if json {
         println!("{}", serde_json::to_string_pretty(&detections)?);
@@ -196,6 +240,17 @@ pub fn run(source: &Path, json: bool) -> Result<()> {
             println!();
         }
         println!("Use 'spool export --redact --skip 0,1,2' to exclude specific detections.");
+
+        let mut preview = file.clone();
+        let tokens_before = TokenCounter::default().count_file(&preview).total;
+        apply_redactions(&mut preview, &detections, &[]);
+        let tokens_after = TokenCounter::default().count_file(&preview).total;
+        println!(
+            "Redacting would change token count: {} -> {} ({:+})",
+            tokens_before,
+            tokens_after,
+            tokens_after as i64 - tokens_before as i64
+        );
     }
 
     Ok(())
@@ -231,10 +286,15 @@ mod tests {
             entry_count: None,
             tools_used: None,
             files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
             first_prompt: None,
             schema_url: None,
             trimmed: None,
             ended: None,
+            content_hash: None,
             extra: HashMap::new(),
         };
         let mut file = SpoolFile::new(session);
@@ -252,7 +312,7 @@ mod tests {
     #[test]
     fn test_detect_secrets_finds_email_and_api_key() {
         let file = make_test_file();
-        let detections = detect_secrets(&file);
+        let detections = detect_secrets(&file, false);
         assert_eq!(detections.len(), 2);
         assert_eq!(detections[0].category, "Email");
         assert_eq!(detections[1].category, "ApiKey");
@@ -261,7 +321,7 @@ mod tests {
     #[test]
     fn test_apply_redactions_with_skip() {
         let mut file = make_test_file();
-        let detections = detect_secrets(&file);
+        let detections = detect_secrets(&file, false);
 
         // Skip the email (index 0), only redact API key
         apply_redactions(&mut file, &detections, &[0]);
@@ -277,7 +337,7 @@ mod tests {
     #[test]
     fn test_apply_redactions_all() {
         let mut file = make_test_file();
-        let detections = detect_secrets(&file);
+        let detections = detect_secrets(&file, false);
 
         apply_redactions(&mut file, &detections, &[]);
 
@@ -288,4 +348,15 @@ mod tests {
             panic!("Expected prompt entry");
         }
     }
+
+    #[test]
+    fn test_pseudonymized_detector_gives_stable_per_secret_placeholders() {
+        let file = make_test_file();
+        let detector = build_detector(false, None, true).unwrap();
+        let detections = detect_secrets_with(&file, &detector);
+
+        assert_ne!(detections[0].replacement, "[REDACTED:email]");
+        assert!(detections[0].replacement.starts_with("[REDACTED:email:"));
+        assert!(detector.pseudonym_map().unwrap().contains_key("test@example.com"));
+    }
 }
@@ -0,0 +1,312 @@
+//! Diff command - Compare two session recordings entry-by-entry.
+//!
+//! Unlike [`crate::diff`] (which diffs the two text blobs inside a single
+//! `Edit`/`Write` tool call for rendering), this treats each *entry* in a
+//! `.spool` file as one token and runs the same longest-common-subsequence
+//! idea over the two entry sequences, so a re-run of the same agent task
+//! can be compared call-by-call rather than line-by-line.
+
+use anyhow::{Context, Result};
+use spool_format::{Entry, SpoolFile, ToolOutput};
+use std::path::Path;
+
+/// Entries of unchanged context kept on each side of a hunk, matching the
+/// default context window of a real `diff -u`.
+const DEFAULT_CONTEXT: usize = 3;
+
+/// Whether a row in the aligned entry sequence is unchanged, only present
+/// in the old file, or only present in the new file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowKind {
+    Context,
+    Removed,
+    Added,
+}
+
+/// One row of the rendered diff: an entry present in the old sequence, the
+/// new sequence, or both, plus enough to group rows into hunks and render
+/// them.
+#[derive(Debug, Clone)]
+struct Row {
+    kind: RowKind,
+    old_index: Option<usize>,
+    new_index: Option<usize>,
+    /// Short uppercase tag for the entry's kind (e.g. `PROMPT`), used to
+    /// decide whether a removed/added pair is really one changed entry.
+    kind_label: &'static str,
+    /// One-line rendering of the entry, prefix not included.
+    text: String,
+}
+
+/// Normalize an entry's content into a single token for the LCS diff, so
+/// two equivalent entries (same kind, same text) compare equal regardless
+/// of their `id`/`ts`, which always differ between two separate captures.
+fn normalize_entry(entry: &Entry) -> String {
+    match entry {
+        Entry::Session(e) => format!("session\x1f{}\x1f{}", e.agent, e.version),
+        Entry::Prompt(e) => format!("prompt\x1f{}", e.content),
+        Entry::Thinking(e) => format!("thinking\x1f{}", e.content),
+        Entry::ToolCall(e) => format!("tool_call\x1f{}\x1f{}", e.tool, e.input),
+        Entry::ToolResult(e) => format!(
+            "tool_result\x1f{}",
+            e.output
+                .as_ref()
+                .map(|o| match o {
+                    ToolOutput::Text(t) => t.clone(),
+                    ToolOutput::Binary(b) => format!("<binary:{}>", b.media_type),
+                })
+                .or_else(|| e.error.clone())
+                .unwrap_or_default()
+        ),
+        Entry::Response(e) => format!("response\x1f{}", e.content),
+        Entry::Error(e) => format!("error\x1f{}", e.message),
+        Entry::SubagentStart(e) => format!("subagent_start\x1f{}", e.agent),
+        Entry::SubagentEnd(e) => format!("subagent_end\x1f{}", e.summary.clone().unwrap_or_default()),
+        Entry::Annotation(e) => format!("annotation\x1f{}", e.content),
+        Entry::RedactionMarker(e) => format!("redaction_marker\x1f{}", e.target_id),
+        Entry::Terminal(e) => format!("terminal\x1f{} frames", e.frames.len()),
+        Entry::Unknown => "unknown".to_string(),
+    }
+}
+
+/// Short uppercase tag for an entry's kind, mirroring the one the TUI
+/// finder overlay uses.
+fn kind_label(entry: &Entry) -> &'static str {
+    match entry {
+        Entry::Session(_) => "SESSION",
+        Entry::Prompt(_) => "PROMPT",
+        Entry::Thinking(_) => "THINKING",
+        Entry::ToolCall(_) => "TOOL CALL",
+        Entry::ToolResult(_) => "RESULT",
+        Entry::Response(_) => "RESPONSE",
+        Entry::Error(_) => "ERROR",
+        Entry::SubagentStart(_) => "SUBAGENT",
+        Entry::SubagentEnd(_) => "SUBAGENT",
+        Entry::Annotation(_) => "ANNOTATION",
+        Entry::RedactionMarker(_) => "REDACTED",
+        Entry::Terminal(_) => "TERMINAL",
+        Entry::Unknown => "UNKNOWN",
+    }
+}
+
+/// One-line, length-capped summary of an entry's content, for display next
+/// to its `kind_label`. Newlines are collapsed so one entry always renders
+/// as exactly one line.
+fn describe_entry(entry: &Entry) -> String {
+    const MAX_LEN: usize = 100;
+    let body = match entry {
+        Entry::Prompt(e) => e.content.clone(),
+        Entry::Thinking(e) => e.content.clone(),
+        Entry::ToolCall(e) => format!("{} {}", e.tool, e.input),
+        Entry::ToolResult(e) => e
+            .output
+            .as_ref()
+            .map(|o| match o {
+                ToolOutput::Text(t) => t.clone(),
+                ToolOutput::Binary(b) => format!("<binary:{}>", b.media_type),
+            })
+            .or_else(|| e.error.clone())
+            .unwrap_or_default(),
+        Entry::Response(e) => e.content.clone(),
+        Entry::Error(e) => e.message.clone(),
+        Entry::SubagentStart(e) => e.agent.clone(),
+        Entry::SubagentEnd(e) => e.summary.clone().unwrap_or_default(),
+        Entry::Annotation(e) => e.content.clone(),
+        _ => String::new(),
+    };
+    let flat: String = body.chars().map(|c| if c == '\n' || c == '\r' { ' ' } else { c }).collect();
+    let flat = flat.trim();
+    if flat.is_empty() {
+        kind_label(entry).to_string()
+    } else if flat.chars().count() > MAX_LEN {
+        let truncated: String = flat.chars().take(MAX_LEN).collect();
+        format!("{}: {}…", kind_label(entry), truncated)
+    } else {
+        format!("{}: {}", kind_label(entry), flat)
+    }
+}
+
+/// Classic LCS-table entry diff, the same approach as
+/// [`crate::diff::diff_lines`] but over normalized entry tokens instead of
+/// text lines, carrying each row's index back into its own sequence so
+/// hunk headers can report entry ranges.
+fn lcs_diff(old: &[Entry], new: &[Entry]) -> Vec<Row> {
+    let old_tokens: Vec<String> = old.iter().map(normalize_entry).collect();
+    let new_tokens: Vec<String> = new.iter().map(normalize_entry).collect();
+
+    let n = old.len();
+    let m = new.len();
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if old_tokens[i] == new_tokens[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut rows = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_tokens[i] == new_tokens[j] {
+            rows.push(Row {
+                kind: RowKind::Context,
+                old_index: Some(i),
+                new_index: Some(j),
+                kind_label: kind_label(&old[i]),
+                text: describe_entry(&old[i]),
+            });
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            rows.push(Row {
+                kind: RowKind::Removed,
+                old_index: Some(i),
+                new_index: None,
+                kind_label: kind_label(&old[i]),
+                text: describe_entry(&old[i]),
+            });
+            i += 1;
+        } else {
+            rows.push(Row {
+                kind: RowKind::Added,
+                old_index: None,
+                new_index: Some(j),
+                kind_label: kind_label(&new[j]),
+                text: describe_entry(&new[j]),
+            });
+            j += 1;
+        }
+    }
+    while i < n {
+        rows.push(Row {
+            kind: RowKind::Removed,
+            old_index: Some(i),
+            new_index: None,
+            kind_label: kind_label(&old[i]),
+            text: describe_entry(&old[i]),
+        });
+        i += 1;
+    }
+    while j < m {
+        rows.push(Row {
+            kind: RowKind::Added,
+            old_index: None,
+            new_index: Some(j),
+            kind_label: kind_label(&new[j]),
+            text: describe_entry(&new[j]),
+        });
+        j += 1;
+    }
+    rows
+}
+
+/// Group `rows` into hunks: each maximal run of non-`Context` rows, padded
+/// with up to `context` rows of unchanged entries on either side, with
+/// adjacent/overlapping windows merged into one hunk (same idea as a real
+/// unified diff's `-U<n>`).
+fn group_hunks(rows: &[Row], context: usize) -> Vec<&[Row]> {
+    let change_indices: Vec<usize> =
+        rows.iter().enumerate().filter(|(_, r)| r.kind != RowKind::Context).map(|(i, _)| i).collect();
+
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for &ci in &change_indices {
+        let start = ci.saturating_sub(context);
+        let end = (ci + context + 1).min(rows.len());
+        match windows.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => windows.push((start, end)),
+        }
+    }
+    windows.into_iter().map(|(s, e)| &rows[s..e]).collect()
+}
+
+/// `@@ -old_start,old_count +new_start,new_count @@` header for a hunk,
+/// 1-indexed to match unified diff convention.
+fn hunk_header(hunk: &[Row]) -> String {
+    let old_indices: Vec<usize> = hunk.iter().filter_map(|r| r.old_index).collect();
+    let new_indices: Vec<usize> = hunk.iter().filter_map(|r| r.new_index).collect();
+    let old_range = range_summary(&old_indices);
+    let new_range = range_summary(&new_indices);
+    format!("@@ -{} +{} @@", old_range, new_range)
+}
+
+fn range_summary(indices: &[usize]) -> String {
+    if indices.is_empty() {
+        return "0,0".to_string();
+    }
+    format!("{},{}", indices[0] + 1, indices.len())
+}
+
+fn render_row(row: &Row) -> String {
+    let prefix = match row.kind {
+        RowKind::Context => ' ',
+        RowKind::Removed => '-',
+        RowKind::Added => '+',
+    };
+    format!("{} {}", prefix, row.text)
+}
+
+/// A removed row immediately followed by an added row of the same entry
+/// kind reads as one changed entry (same role, different body) rather than
+/// an unrelated remove-then-add, matching how a human would read the pair.
+fn count_changed_pairs(rows: &[Row]) -> usize {
+    let mut changed = 0;
+    let mut i = 0;
+    while i < rows.len() {
+        if rows[i].kind == RowKind::Removed
+            && i + 1 < rows.len()
+            && rows[i + 1].kind == RowKind::Added
+            && rows[i].kind_label == rows[i + 1].kind_label
+        {
+            changed += 1;
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    changed
+}
+
+pub fn run(old_path: &Path, new_path: &Path, context: usize, stat: bool) -> Result<()> {
+    let old_file =
+        SpoolFile::from_path(old_path).with_context(|| format!("Failed to read: {:?}", old_path))?;
+    let new_file =
+        SpoolFile::from_path(new_path).with_context(|| format!("Failed to read: {:?}", new_path))?;
+
+    let rows = lcs_diff(&old_file.entries, &new_file.entries);
+
+    let removed = rows.iter().filter(|r| r.kind == RowKind::Removed).count();
+    let added = rows.iter().filter(|r| r.kind == RowKind::Added).count();
+    let changed = count_changed_pairs(&rows);
+
+    if stat {
+        println!(
+            "{} added, {} removed, {} changed",
+            added - changed,
+            removed - changed,
+            changed
+        );
+        return Ok(());
+    }
+
+    println!("--- {:?}", old_path);
+    println!("+++ {:?}", new_path);
+
+    let hunks = group_hunks(&rows, context);
+    if hunks.is_empty() {
+        println!("No differences");
+        return Ok(());
+    }
+
+    for hunk in hunks {
+        println!("{}", hunk_header(hunk));
+        for row in hunk {
+            println!("{}", render_row(row));
+        }
+    }
+
+    Ok(())
+}
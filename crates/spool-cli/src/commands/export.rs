@@ -1,122 +1,168 @@
 //! Export command - Convert and export sessions to .spool format.
 
 use anyhow::{Context, Result};
-use spool_adapters::{claude_code, codex, AgentType};
-use spool_format::{SecretDetector, SpoolFile};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-
-pub fn run(source: &Path, output: Option<&Path>, trim: Option<&str>, redact: bool) -> Result<()> {
-    println!("📤 Exporting session...");
-    println!("   Source: {:?}", source);
-
-    // Determine if source is already a .spool file or an agent log
-    let mut file = if source.extension().map(|e| e == "spool").unwrap_or(false) {
-        SpoolFile::from_path(source)?
-    } else {
-        let agent = detect_agent_from_log(source)?;
-        let session_info = spool_adapters::SessionInfo {
-            path: source.to_path_buf(),
-            agent,
-            created_at: None,
-            modified_at: None,
-            title: None,
-            project_dir: None,
-            message_count: None,
-        };
-        match agent {
-            AgentType::ClaudeCode => claude_code::convert(&session_info)?,
-            AgentType::Codex => codex::convert(&session_info)?,
-            _ => anyhow::bail!("Unsupported agent log: {:?}", source),
-        }
-    };
+use spool_format::{encrypt_bytes, encrypt_spool_file, TokenCounter};
+use std::collections::HashMap;
+
+use super::agent::{load_spool_or_log, passphrase_for_encrypt};
+use super::detect::{apply_redactions, build_detector, detect_secrets_with};
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    source: &std::path::Path,
+    output: Option<&std::path::Path>,
+    trim: Option<&str>,
+    redact: bool,
+    dry_run: bool,
+    skip: Option<&str>,
+    encrypt: bool,
+    high_entropy: bool,
+    rules: Option<&std::path::Path>,
+    pseudonymize: bool,
+    json: bool,
+) -> Result<()> {
+    if !json {
+        println!("📤 Exporting session...");
+        println!("   Source: {:?}", source);
+    }
+
+    let mut file = load_spool_or_log(source)?;
 
     // Apply trimming if specified
     if let Some(trim_range) = trim {
         let (start, end) = parse_trim_range(trim_range)?;
-        println!("   Trimming: {}ms - {}ms", start, end);
+        if !json {
+            println!("   Trimming: {}ms - {}ms", start, end);
+        }
         file.trim(start, end);
     }
 
+    // Determine output path
+    let output_path = output.map(|p| p.to_path_buf()).unwrap_or_else(|| {
+        let stem = source.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = if encrypt { "spool.enc" } else { "spool" };
+        source.with_file_name(format!("{}.{}", stem, ext))
+    });
+
     // Apply redaction if requested
-    if redact {
-        println!("   Applying redaction...");
-        let detector = SecretDetector::with_defaults();
-        let mut redaction_count = 0;
-
-        for entry in &mut file.entries {
-            // Redact content in various entry types
-            match entry {
-                spool_format::Entry::Prompt(p) => {
-                    let (redacted, secrets) = detector.redact(&p.content);
-                    redaction_count += secrets.len();
-                    p.content = redacted;
-                }
-                spool_format::Entry::Response(r) => {
-                    let (redacted, secrets) = detector.redact(&r.content);
-                    redaction_count += secrets.len();
-                    r.content = redacted;
-                }
-                spool_format::Entry::ToolResult(tr) => {
-                    if let Some(spool_format::ToolOutput::Text(ref mut text)) = tr.output {
-                        let (redacted, secrets) = detector.redact(text);
-                        redaction_count += secrets.len();
-                        *text = redacted;
-                    }
-                }
-                spool_format::Entry::Thinking(t) => {
-                    let (redacted, secrets) = detector.redact(&t.content);
-                    redaction_count += secrets.len();
-                    t.content = redacted;
+    if redact || dry_run {
+        let detector = build_detector(high_entropy, rules, pseudonymize)?;
+        let detections = detect_secrets_with(&file, &detector);
+        let skip_indices = parse_skip_indices(skip)?;
+
+        if dry_run {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&detections)?);
+            } else if detections.is_empty() {
+                println!("No secrets detected.");
+            } else {
+                println!("Would redact {} secret(s):", detections.len());
+                for d in &detections {
+                    let marker = if skip_indices.contains(&d.index) {
+                        "skipped"
+                    } else {
+                        "redact"
+                    };
+                    println!("  [{}] {} ({}) - {}", d.index, d.category, d.entry_type, marker);
                 }
-                _ => {}
+
+                let mut preview = file.clone();
+                let tokens_before = TokenCounter::default().count_file(&preview).total;
+                apply_redactions(&mut preview, &detections, &skip_indices);
+                let tokens_after = TokenCounter::default().count_file(&preview).total;
+                println!(
+                    "Would change token count: {} -> {} ({:+})",
+                    tokens_before,
+                    tokens_after,
+                    tokens_after as i64 - tokens_before as i64
+                );
             }
+            return Ok(());
         }
 
-        if redaction_count > 0 {
+        let redaction_count = detections
+            .iter()
+            .filter(|d| !skip_indices.contains(&d.index))
+            .count();
+
+        let tokens_before = TokenCounter::default().count_file(&file).total;
+        apply_redactions(&mut file, &detections, &skip_indices);
+        let tokens_after = TokenCounter::default().count_file(&file).total;
+
+        if !json && redaction_count > 0 {
             println!("   Redacted {} secret(s)", redaction_count);
+            println!(
+                "   Tokens: {} -> {} ({:+})",
+                tokens_before,
+                tokens_after,
+                tokens_after as i64 - tokens_before as i64
+            );
+        }
+
+        if pseudonymize {
+            if let Some(map) = detector.pseudonym_map() {
+                write_redaction_map(&map, &output_path)?;
+                if !json {
+                    println!(
+                        "   Wrote encrypted redaction map: {:?}",
+                        redaction_map_path(&output_path)
+                    );
+                }
+            }
         }
     }
 
-    // Determine output path
-    let output_path = output.map(|p| p.to_path_buf()).unwrap_or_else(|| {
-        let stem = source.file_stem().unwrap_or_default().to_string_lossy();
-        source.with_file_name(format!("{}.spool", stem))
-    });
+    if encrypt {
+        let passphrase = passphrase_for_encrypt()?;
+        let container = encrypt_spool_file(&file, &passphrase)?;
+        std::fs::write(&output_path, container)
+            .with_context(|| format!("Failed to write: {:?}", output_path))?;
+    } else {
+        file.write_to_path(&output_path)
+            .with_context(|| format!("Failed to write: {:?}", output_path))?;
+    }
+
+    if !json {
+        println!("✅ Exported to: {:?}", output_path);
+        println!("   Entries: {}", file.entries.len());
+    }
 
-    // Write output
-    file.write_to_path(&output_path)
-        .with_context(|| format!("Failed to write: {:?}", output_path))?;
+    Ok(())
+}
 
-    println!("✅ Exported to: {:?}", output_path);
-    println!("   Entries: {}", file.entries.len());
+/// Sidecar path for a pseudonymized export's redaction map: next to the
+/// `.spool` output, same stem, `.redaction-map.enc` extension.
+fn redaction_map_path(output_path: &std::path::Path) -> std::path::PathBuf {
+    let stem = output_path.file_stem().unwrap_or_default().to_string_lossy();
+    output_path.with_file_name(format!("{}.redaction-map.enc", stem))
+}
 
+/// Serialize `map` (original secret value -> placeholder) as JSON and seal
+/// it under a passphrase with the same [`encrypt_bytes`] primitive used for
+/// `--encrypt`, writing it next to `output_path` so an authorized holder of
+/// the passphrase can reverse a pseudonymized export later.
+fn write_redaction_map(map: &HashMap<String, String>, output_path: &std::path::Path) -> Result<()> {
+    let passphrase = passphrase_for_encrypt()?;
+    let json = serde_json::to_vec(map).context("Failed to serialize redaction map")?;
+    let sealed = encrypt_bytes(&json, &passphrase).context("Failed to encrypt redaction map")?;
+    let path = redaction_map_path(output_path);
+    std::fs::write(&path, sealed).with_context(|| format!("Failed to write: {:?}", path))?;
     Ok(())
 }
 
-fn detect_agent_from_log(path: &Path) -> Result<AgentType> {
-    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
-    let mut reader = BufReader::new(file);
-    let mut line = String::new();
-    loop {
-        line.clear();
-        if reader.read_line(&mut line)? == 0 {
-            break;
-        }
-        if line.trim().is_empty() {
-            continue;
-        }
-        let value: serde_json::Value = serde_json::from_str(&line)
-            .with_context(|| format!("Failed to parse JSON line in {:?}", path))?;
-        let kind = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        return Ok(match kind {
-            "session_meta" => AgentType::Codex,
-            "user" | "assistant" | "progress" | "summary" | "system" => AgentType::ClaudeCode,
-            _ => AgentType::Unknown,
-        });
+fn parse_skip_indices(skip: Option<&str>) -> Result<Vec<usize>> {
+    match skip {
+        None => Ok(Vec::new()),
+        Some(s) => s
+            .split(',')
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                part.parse::<usize>()
+                    .with_context(|| format!("Invalid skip index: {}", part))
+            })
+            .collect(),
     }
-    Ok(AgentType::Unknown)
 }
 
 fn parse_trim_range(range: &str) -> Result<(u64, u64)> {
@@ -0,0 +1,583 @@
+//! Lexical inverted-index search over converted sessions.
+//!
+//! `search` used to linearly `convert_session` every session on every
+//! query and stop at the first N lexical hits with no notion of
+//! relevance. This module builds a persistent inverted index — tokenized
+//! title/prompt/response text mapped to postings
+//! (`term -> Vec<(session_path, term_frequency)>`) plus per-document
+//! lengths — so a query can be scored by BM25 and returned ranked by
+//! relevance instead of discovery order.
+//!
+//! Persisted as a single JSON file under a `search-index/` directory in
+//! the data dir (mirroring [`super::semindex`]'s `dirs::cache_dir()`
+//! convention for its own `semantic_index.json`), keyed by each session's
+//! `modified_at` so re-indexing only reprocesses sessions that changed.
+//!
+//! Tokenization runs every term through a Porter stemmer and drops a small
+//! stopword list before it ever reaches the postings map, so indexing and
+//! querying agree on "refactor" and "refactoring" being the same term —
+//! the plain `contains` check `search` used before this module missed
+//! that entirely.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use spool_adapters::SessionInfo;
+use spool_format::Entry;
+
+use super::agent::{convert_session, find_all_sessions};
+
+/// One term's posting: the document it appears in, and how many times.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Posting {
+    session_path: PathBuf,
+    term_frequency: usize,
+}
+
+/// Denormalized snapshot of a [`SessionInfo`], stored so results can be
+/// reconstructed without re-running session discovery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedSession {
+    path: PathBuf,
+    agent: String,
+    title: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    modified_at: Option<DateTime<Utc>>,
+    project_dir: Option<PathBuf>,
+    message_count: Option<usize>,
+}
+
+impl IndexedSession {
+    fn from_session_info(session: &SessionInfo) -> Self {
+        Self {
+            path: session.path.clone(),
+            agent: session.agent.as_str().to_string(),
+            title: session.title.clone(),
+            created_at: session.created_at,
+            modified_at: session.modified_at,
+            project_dir: session.project_dir.clone(),
+            message_count: session.message_count,
+        }
+    }
+
+    fn into_session_info(self, agent: spool_adapters::AgentType) -> SessionInfo {
+        SessionInfo {
+            path: self.path,
+            agent,
+            created_at: self.created_at,
+            modified_at: self.modified_at,
+            title: self.title,
+            project_dir: self.project_dir,
+            message_count: self.message_count,
+        }
+    }
+}
+
+/// The persisted inverted index.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InvertedIndex {
+    sessions: HashMap<PathBuf, IndexedSession>,
+    doc_lengths: HashMap<PathBuf, usize>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+/// A session ranked by BM25 relevance to a query.
+pub struct RankedHit {
+    pub session: SessionInfo,
+    pub score: f32,
+}
+
+/// A small, indexing-agnostic stopword list: terms common enough to add
+/// noise to postings without ever narrowing a query.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "he",
+    "in", "is", "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "were", "will",
+    "with",
+];
+
+/// Split `text` into lowercased alphanumeric terms, dropping stopwords and
+/// the byte range each surviving term occupies in `text`.
+fn tokenize_with_offsets(text: &str) -> Vec<(String, usize, usize)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    let mut push_pending = |tokens: &mut Vec<(String, usize, usize)>, end: usize, start: &mut Option<usize>| {
+        if let Some(s) = start.take() {
+            let word = text[s..end].to_lowercase();
+            if !STOPWORDS.contains(&word.as_str()) {
+                tokens.push((stem(&word), s, end));
+            }
+        }
+    };
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            if start.is_none() {
+                start = Some(i);
+            }
+        } else {
+            push_pending(&mut tokens, i, &mut start);
+        }
+    }
+    push_pending(&mut tokens, text.len(), &mut start);
+
+    tokens
+}
+
+/// Reduce `word` to its stem via the Porter/Snowball algorithm, so plural
+/// and tense variants ("refactoring", "refactored") collapse onto the same
+/// postings entry as their base form ("refactor").
+fn stem(word: &str) -> String {
+    stemmer::Stemmer::new("english")
+        .map(|s| s.stem(word))
+        .unwrap_or_else(|_| word.to_string())
+}
+
+/// Split `text` into stemmed, stopword-filtered terms — the form stored in
+/// postings and looked up at query time. See [`tokenize_with_offsets`] when
+/// the original byte range of a surviving term is also needed.
+fn tokenize(text: &str) -> Vec<String> {
+    tokenize_with_offsets(text)
+        .into_iter()
+        .map(|(term, _, _)| term)
+        .collect()
+}
+
+/// Find the first term in `text` whose stem matches any stem of `query`,
+/// returning its *original* byte range so [`super::search::extract_snippet`]
+/// can quote the real surrounding text rather than the stemmed form.
+pub fn find_stemmed_match(text: &str, query: &str) -> Option<(usize, usize)> {
+    let query_stems: std::collections::HashSet<String> = tokenize(query).into_iter().collect();
+    if query_stems.is_empty() {
+        return None;
+    }
+    tokenize_with_offsets(text)
+        .into_iter()
+        .find(|(term, _, _)| query_stems.contains(term))
+        .map(|(_, start, end)| (start, end))
+}
+
+/// Directory the inverted index is persisted under, creating it if needed.
+fn index_dir() -> Result<PathBuf> {
+    let base = dirs::data_local_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from(".local/share"));
+    let dir = base.join("spool").join("search-index");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create search index directory: {:?}", dir))?;
+    }
+    Ok(dir)
+}
+
+fn index_path() -> Result<PathBuf> {
+    Ok(index_dir()?.join("index.json"))
+}
+
+/// Load the persisted index, defaulting to an empty one if it doesn't
+/// exist or fails to parse.
+fn load_index() -> InvertedIndex {
+    index_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &InvertedIndex) -> Result<()> {
+    let path = index_path()?;
+    let json = serde_json::to_string(index)?;
+    fs::write(&path, json).with_context(|| format!("Failed to write index: {:?}", path))
+}
+
+/// Whether `session` is already indexed at its current `modified_at`, so
+/// re-indexing can skip it.
+fn session_up_to_date(index: &InvertedIndex, session: &SessionInfo) -> bool {
+    index
+        .sessions
+        .get(&session.path)
+        .map(|indexed| indexed.modified_at == session.modified_at)
+        .unwrap_or(false)
+}
+
+/// Drop every posting for `path`, so a re-indexed session doesn't leave
+/// stale entries from its previous content behind.
+fn remove_session_postings(index: &mut InvertedIndex, path: &PathBuf) {
+    for postings in index.postings.values_mut() {
+        postings.retain(|p| &p.session_path != path);
+    }
+    index.postings.retain(|_, postings| !postings.is_empty());
+    index.doc_lengths.remove(path);
+}
+
+/// Build or incrementally update the inverted index over `sessions`.
+/// Sessions whose `modified_at` matches what's already indexed are
+/// skipped entirely. Returns the number of sessions that were
+/// (re)indexed.
+pub fn build_or_update_index(sessions: &[SessionInfo]) -> Result<usize> {
+    let mut index = load_index();
+    let mut reindexed = 0;
+
+    for session in sessions {
+        if session_up_to_date(&index, session) {
+            continue;
+        }
+
+        remove_session_postings(&mut index, &session.path);
+
+        let spool_file = match convert_session(session) {
+            Ok(file) => file,
+            Err(_) => continue,
+        };
+
+        let mut term_counts: HashMap<String, usize> = HashMap::new();
+        for term in tokenize(session.title.as_deref().unwrap_or_default()) {
+            *term_counts.entry(term).or_insert(0) += 1;
+        }
+        for entry in &spool_file.entries {
+            let text = match entry {
+                Entry::Prompt(p) => Some(p.content.as_str()),
+                Entry::Response(r) => Some(r.content.as_str()),
+                _ => None,
+            };
+            if let Some(text) = text {
+                for term in tokenize(text) {
+                    *term_counts.entry(term).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let doc_length: usize = term_counts.values().sum();
+        for (term, term_frequency) in term_counts {
+            index.postings.entry(term).or_default().push(Posting {
+                session_path: session.path.clone(),
+                term_frequency,
+            });
+        }
+        index.doc_lengths.insert(session.path.clone(), doc_length);
+        index
+            .sessions
+            .insert(session.path.clone(), IndexedSession::from_session_info(session));
+        reindexed += 1;
+    }
+
+    save_index(&index)?;
+    Ok(reindexed)
+}
+
+/// Term-frequency saturation point: how quickly additional occurrences of
+/// a term stop adding to a document's score.
+const BM25_K1: f32 = 1.2;
+
+/// Length-normalization strength: 0 disables it entirely, 1 fully
+/// normalizes by document length. `length_normalize` in [`search`] maps to
+/// either this or `0.0`.
+const BM25_B: f32 = 0.75;
+
+/// Score each indexed document against `query` by BM25 — for term `t`,
+/// `idf = ln((N - n_t + 0.5) / (n_t + 0.5) + 1)` (`N` the total indexed
+/// session count, `n_t` the number of documents containing `t`); a
+/// document's score is the sum over query terms of
+/// `idf_t * tf_{t,d} * (k1 + 1) / (tf_{t,d} + k1 * (1 - b + b * |d| / avgdl))`,
+/// with `b` zeroed out (no length normalization) unless `length_normalize`
+/// is set — and return the top `limit` sessions sorted by descending
+/// score.
+pub fn search(query: &str, limit: usize, length_normalize: bool) -> Result<Vec<RankedHit>> {
+    let index = load_index();
+    let total_docs = index.sessions.len();
+    if total_docs == 0 {
+        return Ok(Vec::new());
+    }
+
+    let b = if length_normalize { BM25_B } else { 0.0 };
+    let avg_doc_length: f32 = if index.doc_lengths.is_empty() {
+        1.0
+    } else {
+        let total: usize = index.doc_lengths.values().sum();
+        (total as f32 / index.doc_lengths.len() as f32).max(1.0)
+    };
+
+    let mut scores: HashMap<PathBuf, f32> = HashMap::new();
+    for term in tokenize(query) {
+        let Some(postings) = index.postings.get(&term) else {
+            continue;
+        };
+        let n = postings.len();
+        if n == 0 {
+            continue;
+        }
+        let idf = ((total_docs as f32 - n as f32 + 0.5) / (n as f32 + 0.5) + 1.0).ln();
+        for posting in postings {
+            let tf = posting.term_frequency as f32;
+            let doc_length =
+                *index.doc_lengths.get(&posting.session_path).unwrap_or(&1).max(&1) as f32;
+            let denom = tf + BM25_K1 * (1.0 - b + b * (doc_length / avg_doc_length));
+            let contribution = idf * (tf * (BM25_K1 + 1.0)) / denom;
+            *scores.entry(posting.session_path.clone()).or_insert(0.0) += contribution;
+        }
+    }
+
+    let mut ranked: Vec<(PathBuf, f32)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut hits = Vec::with_capacity(limit.min(ranked.len()));
+    for (path, score) in ranked.into_iter().take(limit) {
+        let Some(indexed) = index.sessions.get(&path) else {
+            continue;
+        };
+        let agent = match indexed.agent.as_str() {
+            "claude-code" => spool_adapters::AgentType::ClaudeCode,
+            "aichat" => spool_adapters::AgentType::Aichat,
+            "codex" => spool_adapters::AgentType::Codex,
+            "cursor" => spool_adapters::AgentType::Cursor,
+            "aider" => spool_adapters::AgentType::Aider,
+            "github-copilot" => spool_adapters::AgentType::GithubCopilot,
+            _ => spool_adapters::AgentType::Unknown,
+        };
+        hits.push(RankedHit {
+            session: indexed.clone().into_session_info(agent),
+            score,
+        });
+    }
+
+    Ok(hits)
+}
+
+/// (Re)build the index over every discovered session. Used by
+/// [`super::search::run`] before each query so the index stays current
+/// without the caller having to manage it explicitly.
+pub fn refresh() -> Result<usize> {
+    let sessions = find_all_sessions()?;
+    build_or_update_index(&sessions)
+}
+
+/// Char trigrams of `word` (itself, if shorter than 3 chars), used to
+/// cheaply narrow [`suggest_correction`]'s candidate set before the
+/// expensive Levenshtein check.
+fn trigrams(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < 3 {
+        return vec![word.to_string()];
+    }
+    chars.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// Bounded edit distance between `a` and `b`: standard Levenshtein DP.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let above = row[j];
+            let replace = prev_diag + cost;
+            let insert = row[j - 1] + 1;
+            let delete = above + 1;
+            row[j] = replace.min(insert).min(delete);
+            prev_diag = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// The nearest vocabulary term to `term` within edit distance 2, or `None`
+/// if `term` is already in the vocabulary or nothing is close enough.
+/// Narrows the search to terms sharing at least one trigram with `term`
+/// before running Levenshtein, so this stays cheap even over a large
+/// vocabulary.
+fn suggest_correction(term: &str, trigram_index: &HashMap<String, Vec<String>>) -> Option<String> {
+    const MAX_DISTANCE: usize = 2;
+
+    let mut candidates: std::collections::HashSet<&String> = std::collections::HashSet::new();
+    for gram in trigrams(term) {
+        if let Some(terms) = trigram_index.get(&gram) {
+            candidates.extend(terms);
+        }
+    }
+
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, levenshtein(term, candidate)))
+        .filter(|&(_, dist)| dist > 0 && dist <= MAX_DISTANCE)
+        .min_by_key(|&(_, dist)| dist)
+        .map(|(candidate, _)| candidate.clone())
+}
+
+/// Map every trigram of every indexed vocabulary term to the terms
+/// containing it, so [`suggest_correction`] can look up candidates in
+/// roughly constant time instead of scanning the whole vocabulary.
+fn build_trigram_index(vocab: impl Iterator<Item = String>) -> HashMap<String, Vec<String>> {
+    let mut trigram_index: HashMap<String, Vec<String>> = HashMap::new();
+    for term in vocab {
+        for gram in trigrams(&term) {
+            trigram_index.entry(gram).or_default().push(term.clone());
+        }
+    }
+    trigram_index
+}
+
+/// For a zero-result `query`, suggest a corrected spelling: replace each
+/// query token that has no postings at all with its nearest vocabulary
+/// term (within edit distance 2), leaving tokens that already match the
+/// index untouched. Returns `None` if every token already matches, or no
+/// token close enough to correct was found.
+pub fn suggest_query_correction(query: &str) -> Option<String> {
+    let index = load_index();
+    let trigram_index = build_trigram_index(index.postings.keys().cloned());
+
+    let mut corrected_any = false;
+    let corrected: Vec<String> = tokenize(query)
+        .into_iter()
+        .map(|term| {
+            if index.postings.contains_key(&term) {
+                return term;
+            }
+            match suggest_correction(&term, &trigram_index) {
+                Some(fixed) => {
+                    corrected_any = true;
+                    fixed
+                }
+                None => term,
+            }
+        })
+        .collect();
+
+    if corrected_any {
+        Some(corrected.join(" "))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_counts_single_edits() {
+        assert_eq!(levenshtein("refactor", "refactor"), 0);
+        assert_eq!(levenshtein("refactor", "refractor"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn suggest_correction_finds_a_nearby_vocabulary_term() {
+        let vocab = vec!["refactor".to_string(), "migration".to_string()];
+        let trigram_index = build_trigram_index(vocab.into_iter());
+        assert_eq!(
+            suggest_correction("refractor", &trigram_index),
+            Some("refactor".to_string())
+        );
+    }
+
+    #[test]
+    fn suggest_correction_returns_none_when_nothing_is_close() {
+        let vocab = vec!["refactor".to_string()];
+        let trigram_index = build_trigram_index(vocab.into_iter());
+        assert_eq!(suggest_correction("banana", &trigram_index), None);
+    }
+
+    #[test]
+    fn suggest_correction_returns_none_for_an_exact_vocabulary_match() {
+        let vocab = vec!["refactor".to_string()];
+        let trigram_index = build_trigram_index(vocab.into_iter());
+        assert_eq!(suggest_correction("refactor", &trigram_index), None);
+    }
+
+    #[test]
+    fn tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(
+            tokenize("Hello, World!"),
+            vec!["hello".to_string(), "world".to_string()]
+        );
+    }
+
+    #[test]
+    fn tokenize_skips_empty_tokens() {
+        assert_eq!(tokenize("  ,,  "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn tokenize_drops_stopwords() {
+        assert_eq!(
+            tokenize("this is a test of the index"),
+            vec!["test".to_string(), "index".to_string()]
+        );
+    }
+
+    #[test]
+    fn tokenize_stems_plural_and_tense_variants_to_the_same_term() {
+        assert_eq!(tokenize("refactoring"), tokenize("refactored"));
+    }
+
+    #[test]
+    fn find_stemmed_match_returns_original_byte_range_not_stemmed_text() {
+        let text = "we are refactoring the parser today";
+        let (start, end) = find_stemmed_match(text, "refactor").unwrap();
+        assert_eq!(&text[start..end], "refactoring");
+    }
+
+    #[test]
+    fn find_stemmed_match_returns_none_when_no_term_matches() {
+        assert!(find_stemmed_match("a quiet afternoon", "refactor").is_none());
+    }
+
+    #[test]
+    fn session_up_to_date_matches_on_path_and_mtime() {
+        let mtime = Utc::now();
+        let mut index = InvertedIndex::default();
+        let session = SessionInfo {
+            path: PathBuf::from("/a.jsonl"),
+            agent: spool_adapters::AgentType::ClaudeCode,
+            created_at: None,
+            modified_at: Some(mtime),
+            title: None,
+            project_dir: None,
+            message_count: None,
+        };
+        index
+            .sessions
+            .insert(session.path.clone(), IndexedSession::from_session_info(&session));
+
+        assert!(session_up_to_date(&index, &session));
+
+        let mut changed = session;
+        changed.modified_at = Some(mtime + chrono::Duration::seconds(1));
+        assert!(!session_up_to_date(&index, &changed));
+    }
+
+    #[test]
+    fn remove_session_postings_drops_only_that_sessions_entries() {
+        let mut index = InvertedIndex::default();
+        index.postings.insert(
+            "rust".to_string(),
+            vec![
+                Posting {
+                    session_path: PathBuf::from("/a.jsonl"),
+                    term_frequency: 3,
+                },
+                Posting {
+                    session_path: PathBuf::from("/b.jsonl"),
+                    term_frequency: 1,
+                },
+            ],
+        );
+        index.doc_lengths.insert(PathBuf::from("/a.jsonl"), 10);
+
+        remove_session_postings(&mut index, &PathBuf::from("/a.jsonl"));
+
+        let remaining = &index.postings["rust"];
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].session_path, PathBuf::from("/b.jsonl"));
+        assert!(!index.doc_lengths.contains_key(&PathBuf::from("/a.jsonl")));
+    }
+}
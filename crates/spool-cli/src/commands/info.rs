@@ -2,9 +2,110 @@
 
 use anyhow::Result;
 use serde::Serialize;
+use spool_format::{ModelTokenUsage, SubagentNode, TokenCountSummary, TokenCounter, TokenUsageSummary};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use super::agent::load_spool_or_log;
+use super::pricing::PriceTable;
+
+/// Per-model token usage plus its estimated cost, for JSON output.
+#[derive(Serialize)]
+struct ModelTokenUsageJson {
+    responses: usize,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_cost_cents: Option<f64>,
+}
+
+/// Aggregated token usage plus its estimated cost, for JSON output.
+#[derive(Serialize)]
+struct TokenUsageJson {
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+    by_model: BTreeMap<String, ModelTokenUsageJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_cost_cents: Option<f64>,
+}
+
+fn token_usage_json(usage: &TokenUsageSummary, prices: &PriceTable) -> TokenUsageJson {
+    let by_model: BTreeMap<String, ModelTokenUsageJson> = usage
+        .by_model
+        .iter()
+        .map(|(model, m): (&String, &ModelTokenUsage)| {
+            (
+                model.clone(),
+                ModelTokenUsageJson {
+                    responses: m.responses,
+                    input_tokens: m.input_tokens,
+                    output_tokens: m.output_tokens,
+                    cache_read_tokens: m.cache_read_tokens,
+                    cache_creation_tokens: m.cache_creation_tokens,
+                    estimated_cost_cents: prices.estimate_cents(model, m.input_tokens, m.output_tokens),
+                },
+            )
+        })
+        .collect();
+    let estimated_cost_cents = by_model
+        .values()
+        .map(|m| m.estimated_cost_cents)
+        .collect::<Option<Vec<_>>>()
+        .filter(|costs| !costs.is_empty())
+        .map(|costs| costs.into_iter().sum());
+    TokenUsageJson {
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cache_read_tokens: usage.cache_read_tokens,
+        cache_creation_tokens: usage.cache_creation_tokens,
+        by_model,
+        estimated_cost_cents,
+    }
+}
+
+/// Print a BPE token-count breakdown, one line per entry type, computed by
+/// actually tokenizing entry text rather than trusting recorded
+/// [`TokenUsageSummary`] metadata - useful for agents or log formats that
+/// never recorded `token_usage` at all.
+fn print_token_counts(counts: &TokenCountSummary) {
+    println!("\nTokens (BPE): {} total", counts.total);
+    for (entry_type, count) in &counts.by_entry_type {
+        println!("  {}: {}", entry_type, count);
+    }
+}
+
+/// Print the token usage summary as indented text, one line per model.
+fn print_token_usage(usage: &TokenUsageSummary, prices: &PriceTable) {
+    println!(
+        "\nTokens:       {} in / {} out (cache: {} read, {} created)",
+        usage.input_tokens, usage.output_tokens, usage.cache_read_tokens, usage.cache_creation_tokens
+    );
+    let mut total_cents = 0.0;
+    let mut any_cost_known = false;
+    for (model, m) in &usage.by_model {
+        let cost = prices.estimate_cents(model, m.input_tokens, m.output_tokens);
+        match cost {
+            Some(cents) => {
+                total_cents += cents;
+                any_cost_known = true;
+                println!(
+                    "  {}: {} in / {} out ({}x) - ${:.4}",
+                    model, m.input_tokens, m.output_tokens, m.responses, cents / 100.0
+                );
+            }
+            None => {
+                println!("  {}: {} in / {} out ({}x)", model, m.input_tokens, m.output_tokens, m.responses);
+            }
+        }
+    }
+    if any_cost_known {
+        println!("  Estimated cost: ${:.4}", total_cents / 100.0);
+    }
+}
 
 #[derive(Serialize)]
 struct SessionInfo {
@@ -26,15 +127,43 @@ struct SessionInfo {
     tags: Option<Vec<String>>,
     files_modified: Option<Vec<String>>,
     trimmed: bool,
+    subagents: Vec<SubagentNode>,
+    token_usage: TokenUsageJson,
+    token_counts: TokenCountSummary,
 }
 
-pub fn run(path: &Path, json: bool) -> Result<()> {
+/// Print a subagent call tree as an indented text outline.
+fn print_subagent_tree(nodes: &[SubagentNode], depth: usize) {
+    for node in nodes {
+        let indent = "  ".repeat(depth);
+        let status = node
+            .status
+            .as_ref()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "running".to_string());
+        let duration = node
+            .duration_ms
+            .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+            .unwrap_or_else(|| "?".to_string());
+        println!(
+            "{}- {} [{}, {}] ({}p/{}r/{}t/{}e)",
+            indent, node.agent, status, duration, node.prompts, node.responses, node.tool_calls, node.errors
+        );
+        print_subagent_tree(&node.children, depth + 1);
+    }
+}
+
+pub fn run(path: &Path, tokens: bool, json: bool) -> Result<()> {
     let file = load_spool_or_log(path)?;
 
     let duration_ms = file.duration_ms();
     let total_secs = duration_ms / 1000;
     let duration_display = format!("{}:{:02}", total_secs / 60, total_secs % 60);
     let tools = file.tools_used();
+    let subagents = file.subagent_tree();
+    let usage = file.token_usage_summary();
+    let prices = PriceTable::load();
+    let token_counts = TokenCounter::default().count_file(&file);
 
     if json {
         let info = SessionInfo {
@@ -56,6 +185,9 @@ pub fn run(path: &Path, json: bool) -> Result<()> {
             tags: file.session.tags.clone(),
             files_modified: file.session.files_modified.clone(),
             trimmed: file.session.trimmed.is_some(),
+            subagents,
+            token_usage: token_usage_json(&usage, &prices),
+            token_counts,
         };
         println!("{}", serde_json::to_string_pretty(&info)?);
     } else {
@@ -116,6 +248,19 @@ pub fn run(path: &Path, json: bool) -> Result<()> {
         if file.session.trimmed.is_some() {
             println!("\n[trimmed]");
         }
+
+        if !subagents.is_empty() {
+            println!("\nSubagents:");
+            print_subagent_tree(&subagents, 1);
+        }
+
+        if !usage.by_model.is_empty() {
+            print_token_usage(&usage, &prices);
+        }
+
+        if tokens {
+            print_token_counts(&token_counts);
+        }
     }
 
     Ok(())
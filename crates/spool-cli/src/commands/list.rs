@@ -3,7 +3,7 @@
 use anyhow::Result;
 use serde::Serialize;
 
-use super::agent::find_all_sessions;
+use super::cache::catalog;
 
 #[derive(Serialize)]
 struct SessionRow {
@@ -16,7 +16,8 @@ struct SessionRow {
 }
 
 pub fn run(agent_filter: Option<&str>, limit: Option<usize>, json: bool) -> Result<()> {
-    let sessions = find_all_sessions()?;
+    let mut sessions = catalog::scan()?;
+    sessions.retain(|s| s.message_count.map(|c| c > 0).unwrap_or(true));
 
     let filtered: Vec<_> = sessions
         .iter()
@@ -62,6 +63,7 @@ pub fn run(agent_filter: Option<&str>, limit: Option<usize>, json: bool) -> Resu
                 "codex" => "CX",
                 "cursor" => "CU",
                 "aider" => "AI",
+                "aichat" => "AC",
                 _ => "??",
             };
             let title = s.title.as_deref().unwrap_or("Untitled");
@@ -3,10 +3,21 @@
 pub mod agent;
 pub mod cache;
 pub mod detect;
+pub mod diff;
 pub mod export;
+pub mod index;
 pub mod info;
 pub mod list;
+#[cfg(feature = "mount")]
+pub mod mount;
+pub mod pricing;
+pub mod publish;
+pub mod record;
+pub mod repo;
 pub mod search;
+pub mod semindex;
+pub mod serve;
 pub mod skill;
 pub mod validate;
+pub mod verify;
 pub mod view;
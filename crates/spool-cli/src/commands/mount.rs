@@ -0,0 +1,305 @@
+//! Read-only FUSE mount of a `.spool` archive (requires the `mount`
+//! feature, backed by the `fuser` crate).
+//!
+//! Exposes a [`SpoolFile`] as a virtual filesystem so turns and tool
+//! output can be grepped, diffed, and opened with ordinary Unix tooling
+//! without an extraction step:
+//!
+//! ```text
+//! <mountpoint>/
+//!   session.json
+//!   prompts/0001.txt, 0002.txt, ...
+//!   responses/0001.md, ...
+//!   tools/0001-bash/input.json, tools/0001-bash/output.txt
+//!   annotations/0001.txt, ...
+//! ```
+//!
+//! The tree is built once from the already-loaded `SpoolFile`, so mounting
+//! a trimmed file reflects the trimmed view, and the mount is read-only:
+//! every write-side `Filesystem` method is left at its default (ENOSYS).
+
+use anyhow::{Context, Result};
+use fuser::{
+    FileAttr, FileType, Filesystem, MountOption, ReplyAttr, ReplyData, ReplyDirectory,
+    ReplyEntry, Request,
+};
+use spool_format::{Entry, SpoolFile};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long the kernel may cache attributes/entries before re-asking us -
+/// fine to set generously high since the tree never changes after mount.
+const TTL: Duration = Duration::from_secs(3600);
+const ROOT_INO: u64 = 1;
+
+enum NodeKind {
+    Dir { children: Vec<u64> },
+    File { content: Vec<u8> },
+}
+
+struct Node {
+    name: String,
+    parent: u64,
+    mtime: SystemTime,
+    kind: NodeKind,
+}
+
+/// In-memory filesystem tree built once from a [`SpoolFile`] and served
+/// read-only over FUSE.
+struct SpoolMount {
+    nodes: HashMap<u64, Node>,
+    next_ino: u64,
+}
+
+impl SpoolMount {
+    fn build(file: &SpoolFile) -> Self {
+        let mut mount = SpoolMount {
+            nodes: HashMap::new(),
+            next_ino: ROOT_INO,
+        };
+
+        let root = ROOT_INO;
+        mount.nodes.insert(
+            root,
+            Node {
+                name: String::new(),
+                parent: root,
+                mtime: ts_to_systemtime(file.session.ts),
+                kind: NodeKind::Dir { children: Vec::new() },
+            },
+        );
+
+        if let Ok(json) = serde_json::to_vec_pretty(&file.session) {
+            mount.add_file(root, "session.json", json, file.session.ts);
+        }
+
+        let prompts = mount.add_dir(root, "prompts", file.session.ts);
+        for (i, prompt) in file.prompts().iter().enumerate() {
+            mount.add_file(
+                prompts,
+                &format!("{:04}.txt", i + 1),
+                prompt.content.clone().into_bytes(),
+                prompt.ts,
+            );
+        }
+
+        let responses = mount.add_dir(root, "responses", file.session.ts);
+        for (i, response) in file.responses().iter().enumerate() {
+            mount.add_file(
+                responses,
+                &format!("{:04}.md", i + 1),
+                response.content.clone().into_bytes(),
+                response.ts,
+            );
+        }
+
+        let annotations = mount.add_dir(root, "annotations", file.session.ts);
+        for (i, annotation) in file.annotations().iter().enumerate() {
+            mount.add_file(
+                annotations,
+                &format!("{:04}.txt", i + 1),
+                annotation.content.clone().into_bytes(),
+                annotation.ts,
+            );
+        }
+
+        let tools = mount.add_dir(root, "tools", file.session.ts);
+        for (i, call) in file.tool_calls().iter().enumerate() {
+            let dir_name = format!("{:04}-{}", i + 1, sanitize(&call.tool));
+            let call_dir = mount.add_dir(tools, &dir_name, call.ts);
+
+            let input = serde_json::to_vec_pretty(&call.input).unwrap_or_default();
+            mount.add_file(call_dir, "input.json", input, call.ts);
+
+            let result = file.entries.iter().find_map(|e| match e {
+                Entry::ToolResult(r) if r.call_id == call.id => Some(r),
+                _ => None,
+            });
+            let (output, output_ts) = match result {
+                Some(r) => (
+                    r.output
+                        .as_ref()
+                        .map(|o| match o {
+                            spool_format::ToolOutput::Text(text) => text.clone(),
+                            spool_format::ToolOutput::Binary(b) => {
+                                format!("<binary: {} ({})>", b.media_type, b.content_type)
+                            }
+                        })
+                        .or_else(|| r.error.clone())
+                        .unwrap_or_default(),
+                    r.ts,
+                ),
+                None => (String::new(), call.ts),
+            };
+            mount.add_file(call_dir, "output.txt", output.into_bytes(), output_ts);
+        }
+
+        mount
+    }
+
+    fn add_dir(&mut self, parent: u64, name: &str, ts: u64) -> u64 {
+        self.next_ino += 1;
+        let ino = self.next_ino;
+        self.nodes.insert(
+            ino,
+            Node {
+                name: name.to_string(),
+                parent,
+                mtime: ts_to_systemtime(ts),
+                kind: NodeKind::Dir { children: Vec::new() },
+            },
+        );
+        if let Some(Node { kind: NodeKind::Dir { children }, .. }) = self.nodes.get_mut(&parent) {
+            children.push(ino);
+        }
+        ino
+    }
+
+    fn add_file(&mut self, parent: u64, name: &str, content: Vec<u8>, ts: u64) -> u64 {
+        self.next_ino += 1;
+        let ino = self.next_ino;
+        self.nodes.insert(
+            ino,
+            Node {
+                name: name.to_string(),
+                parent,
+                mtime: ts_to_systemtime(ts),
+                kind: NodeKind::File { content },
+            },
+        );
+        if let Some(Node { kind: NodeKind::Dir { children }, .. }) = self.nodes.get_mut(&parent) {
+            children.push(ino);
+        }
+        ino
+    }
+
+    fn attr(&self, ino: u64, req: &Request) -> Option<FileAttr> {
+        let node = self.nodes.get(&ino)?;
+        let (kind, size, perm) = match &node.kind {
+            NodeKind::Dir { .. } => (FileType::Directory, 0, 0o555),
+            NodeKind::File { content } => (FileType::RegularFile, content.len() as u64, 0o444),
+        };
+        Some(FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: node.mtime,
+            mtime: node.mtime,
+            ctime: node.mtime,
+            crtime: node.mtime,
+            kind,
+            perm,
+            nlink: 1,
+            uid: req.uid(),
+            gid: req.gid(),
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        })
+    }
+}
+
+fn ts_to_systemtime(ts_ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ts_ms)
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c == '/' || c == '\0' { '_' } else { c })
+        .collect()
+}
+
+impl Filesystem for SpoolMount {
+    fn lookup(&mut self, req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(Node { kind: NodeKind::Dir { children }, .. }) = self.nodes.get(&parent) else {
+            reply.error(libc::ENOTDIR);
+            return;
+        };
+        let found = children
+            .iter()
+            .find(|ino| self.nodes.get(ino).is_some_and(|n| n.name == name.to_string_lossy()));
+        match found.and_then(|ino| self.attr(*ino, req)) {
+            Some(attr) => reply.entry(&TTL, &attr, 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, req: &Request, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.attr(ino, req) {
+            Some(attr) => reply.attr(&TTL, &attr),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        match self.nodes.get(&ino) {
+            Some(Node { kind: NodeKind::File { content }, .. }) => {
+                let offset = offset.max(0) as usize;
+                let end = (offset + size as usize).min(content.len());
+                let slice = if offset < content.len() { &content[offset..end] } else { &[] };
+                reply.data(slice);
+            }
+            Some(_) => reply.error(libc::EISDIR),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn readdir(&mut self, _req: &Request, ino: u64, _fh: u64, offset: i64, mut reply: ReplyDirectory) {
+        let Some(Node { kind: NodeKind::Dir { children }, parent, .. }) = self.nodes.get(&ino) else {
+            reply.error(libc::ENOTDIR);
+            return;
+        };
+
+        let mut entries = vec![(ino, FileType::Directory, ".".to_string()), (*parent, FileType::Directory, "..".to_string())];
+        for child_ino in children {
+            if let Some(node) = self.nodes.get(child_ino) {
+                let kind = match node.kind {
+                    NodeKind::Dir { .. } => FileType::Directory,
+                    NodeKind::File { .. } => FileType::RegularFile,
+                };
+                entries.push((*child_ino, kind, node.name.clone()));
+            }
+        }
+
+        for (i, (entry_ino, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(entry_ino, (i + 1) as i64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+}
+
+/// Mount `path` (a `.spool` file) read-only at `mountpoint`, blocking until
+/// it's unmounted (Ctrl-C, or `umount`/`fusermount -u` from another shell).
+pub fn run(path: &Path, mountpoint: &Path) -> Result<()> {
+    let file = SpoolFile::from_path(path).with_context(|| format!("Failed to read: {:?}", path))?;
+    let mount = SpoolMount::build(&file);
+
+    println!(
+        "🔗 Mounted {:?} at {:?} (read-only, Ctrl-C or `umount {:?}` to exit)",
+        path, mountpoint, mountpoint
+    );
+
+    fuser::mount2(
+        mount,
+        mountpoint,
+        &[
+            MountOption::RO,
+            MountOption::FSName("spool".to_string()),
+        ],
+    )
+    .with_context(|| format!("Failed to mount {:?} at {:?}", path, mountpoint))
+}
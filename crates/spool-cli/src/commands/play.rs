@@ -14,15 +14,22 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
+    widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
-use spool_format::{Entry, SpoolFile, ToolOutput};
-use std::io;
+use regex::{Regex, RegexBuilder};
+use spool_format::{Entry, SessionEntry, SpoolFile, ToolOutput};
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 use super::agent::load_spool_or_log;
+use crate::ansi;
+use crate::tui::common::{centered_rect, format_tool_input};
+use crate::tui::fuzzy::fuzzy_match;
+
 /// Maximum gap (ms) before a Prompt entry (user think-time).
 const MAX_IDLE_GAP_MS: u64 = 2_000;
 
@@ -32,7 +39,22 @@ const MAX_THINKING_MS: u64 = 2_000;
 /// Available speed multipliers.
 const SPEEDS: &[f32] = &[0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
 
+/// Cap on how many lines of an entry's joined text the search scans - a
+/// single tool result can be thousands of lines, and re-scanning every
+/// entry on every keystroke needs to stay cheap.
+const MAX_SEARCH_LINES: usize = 100;
+
+/// How many `tick`-cadence ticks (~50ms each) an error flash stays on
+/// screen before clearing itself.
+const ERROR_FLASH_TICKS: u32 = 40;
+
+/// Top-N cap on the fuzzy jump overlay's results - a long session can have
+/// thousands of matching entries, and only the first screenful is ever
+/// shown anyway.
+const MAX_JUMP_RESULTS: usize = 20;
+
 /// A pre-computed playback timeline entry.
+#[derive(Clone, Copy)]
 struct TimelineEntry {
     /// Index into the original entries vec.
     entry_index: usize,
@@ -77,6 +99,116 @@ struct PlayApp {
     /// Status message for UI feedback.
     status_message: Option<String>,
 
+    // Search state
+    /// The current search query, captured live as the user types after
+    /// pressing `/`. `Some` for as long as a search is active (including
+    /// after `Enter` commits it, so `n`/`N` and the match highlight keep
+    /// working); `None` when there's no active search at all.
+    active_search: Option<String>,
+    /// Whether `/` is still capturing keystrokes into `active_search`
+    /// (distinct from `active_search.is_some()`, which stays true after
+    /// `Enter` commits the query).
+    searching: bool,
+    /// Timeline indices whose rendered text matches `active_search`,
+    /// case-insensitively, recomputed on every keystroke.
+    search_matches: Vec<usize>,
+    /// Byte ranges of every match within each matched entry's
+    /// `entry_search_text`, keyed by timeline index - lets
+    /// `render_entry_lines` highlight every occurrence, not just the
+    /// first, via the same compiled `search_regex`.
+    search_match_ranges: HashMap<usize, Vec<(usize, usize)>>,
+    /// The current query compiled as a regex (falling back to an
+    /// escaped-literal match on a compile error - see [`RegexSearch`]).
+    /// `None` when there's no active search.
+    search_regex: Option<RegexSearch>,
+    /// Index into `search_matches` of the match `n`/`N` last jumped to.
+    search_match_index: Option<usize>,
+    /// A timeline index `draw_entries` should scroll into view on its next
+    /// draw (set by `reveal_to_match`, cleared once acted on - the actual
+    /// line offset depends on how many lines each entry renders to, which
+    /// only `draw_entries` knows).
+    pending_scroll_to_timeline_index: Option<usize>,
+
+    // Focused entry state
+    /// Timeline index of the entry shown full-screen, untruncated, via
+    /// `draw_focused_entry` - `Enter` sets this to the playback head
+    /// (`visible_count - 1`), `Esc` clears it back to the scrolling
+    /// transcript.
+    focused_entry: Option<usize>,
+    /// Scroll offset within the focused entry's own view, independent of
+    /// `scroll_offset` (the transcript's), so returning to playback
+    /// doesn't disturb where the transcript was scrolled.
+    focused_scroll_offset: usize,
+
+    // Error notification state
+    /// Whether a newly-revealed error rings the terminal bell - toggled by
+    /// the user when fast-forwarding through a noisy session.
+    bell_enabled: bool,
+    /// Total `ToolResult`/`Error` entries revealed so far with an error set
+    /// - `tick`/`step_forward` compare against this each time
+    /// `visible_count` advances so the bell fires exactly once per error,
+    /// even when several become visible within one tick at high speed.
+    error_count: usize,
+    /// Transient highlighted message shown in the controls bar after a new
+    /// error is revealed, cleared once `error_flash_ticks` counts down to
+    /// zero (see `tick`).
+    error_flash: Option<String>,
+    /// Ticks remaining before `error_flash` clears itself.
+    error_flash_ticks: u32,
+
+    // Background loading state
+    /// `true` once `spool_file`/`timeline` have seen every entry the
+    /// background loader (see `spawn_loader_thread`) is going to send -
+    /// either `PlayEvent::LoadComplete` or `PlayEvent::LoadFailed` arrived.
+    /// Drives the "still loading" indicator in `progress_label`.
+    done_loading: bool,
+    /// `true` from `new_empty` until the first batch of real entries is
+    /// ingested. While `true`, `spool_file` holds a throwaway placeholder
+    /// session (just enough to satisfy `SpoolFile::new`'s constructor) that
+    /// `ingest_batch` discards and replaces with the real session entry the
+    /// loader sends as the first element of its first batch.
+    is_placeholder: bool,
+    /// Set on `PlayEvent::LoadFailed` or an empty session at
+    /// `PlayEvent::LoadComplete`; printed after the terminal is restored
+    /// since the TUI itself has nothing useful left to show.
+    load_error: Option<String>,
+
+    /// `true` when the marked trim region (`trim_start_ms`/`trim_end_ms`,
+    /// set by `mark_trim_start`/`mark_trim_end`) should also act as a
+    /// playback loop: reaching its end during playback jumps back to its
+    /// start instead of continuing on. Toggled with `r`; a no-op while
+    /// `trim_range()` is `None`.
+    loop_enabled: bool,
+
+    // Follow mode state
+    /// `true` when `run` was asked to tail a session still being recorded -
+    /// see [`spawn_follow_thread`]. Drives whether `ingest_follow_batch`
+    /// auto-advances playback and pins the view to the newest entry, and
+    /// the "LIVE" indicator in the title bar.
+    follow_mode: bool,
+    /// `true` once the user has scrolled up away from the bottom while
+    /// following - keeps `ingest_follow_batch` from yanking the view back
+    /// down while they're reading older output. Cleared by `jump_to_end`.
+    user_scrolled_up: bool,
+
+    // Fuzzy jump state
+    /// Live fuzzy-match overlay for jumping straight to a matching entry by
+    /// content, opened with `f` - `None` when the overlay isn't shown. Kept
+    /// separate from `active_search`/`searching` (the literal/regex
+    /// highlight search), which narrows the visible transcript instead of
+    /// jumping to one entry.
+    jump_search: Option<JumpSearch>,
+
+    // Selection state
+    /// Active text selection over `rendered_plain_lines`, entered with `v`
+    /// and extended with `h`/`j`/`k`/`l` - `None` outside selection mode.
+    selection: Option<Selection>,
+    /// Plain-text form of the last frame `draw_entries` rendered, one
+    /// entry per display line - kept in lockstep with the styled `Vec<Line>`
+    /// built there so [`PlayApp::yank_selection`] can slice the exact same
+    /// rows the user is looking at without re-deriving them from scratch.
+    rendered_plain_lines: Vec<String>,
+
     should_quit: bool,
 }
 
@@ -120,10 +252,141 @@ impl PlayApp {
             trim_start_ms: None,
             trim_end_ms: None,
             status_message: None,
+            active_search: None,
+            searching: false,
+            search_matches: Vec::new(),
+            search_match_ranges: HashMap::new(),
+            search_regex: None,
+            search_match_index: None,
+            pending_scroll_to_timeline_index: None,
+            focused_entry: None,
+            focused_scroll_offset: 0,
+            bell_enabled: true,
+            error_count: 0,
+            error_flash: None,
+            error_flash_ticks: 0,
+            done_loading: true,
+            is_placeholder: false,
+            load_error: None,
+            loop_enabled: false,
+            follow_mode: false,
+            user_scrolled_up: false,
+            jump_search: None,
+            selection: None,
+            rendered_plain_lines: Vec::new(),
             should_quit: false,
         }
     }
 
+    /// Construct a player with nothing loaded yet, for `run`'s background
+    /// loading path: an empty placeholder session so the terminal can come
+    /// up and the first frame can draw before `spawn_loader_thread` has
+    /// parsed anything. `ingest_batch` replaces the placeholder with the
+    /// real session entry once the first batch arrives.
+    fn new_empty(source_path: PathBuf, speed: f32) -> Self {
+        let placeholder = SessionEntry {
+            id: uuid::Uuid::nil(),
+            ts: 0,
+            version: "1.0".to_string(),
+            agent: "unknown".to_string(),
+            recorded_at: chrono::Utc::now(),
+            agent_version: None,
+            title: Some("Loading...".to_string()),
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: HashMap::new(),
+        };
+        let mut app = Self::new(SpoolFile::new(placeholder), source_path, speed);
+        app.done_loading = false;
+        app.is_placeholder = true;
+        app
+    }
+
+    /// Append a batch of freshly-parsed entries (see `spawn_loader_thread`)
+    /// and extend the compressed timeline from just the new entries, rather
+    /// than rebuilding it from scratch on every batch. The first batch ever
+    /// ingested replaces the placeholder session from `new_empty` with the
+    /// real one - `load_spool_or_log` guarantees every source's first entry
+    /// is `Entry::Session`, so it's always `batch[0]`.
+    fn ingest_batch(&mut self, mut batch: Vec<Entry>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let start = if self.is_placeholder {
+            self.is_placeholder = false;
+            let session = match batch.remove(0) {
+                Entry::Session(s) => s,
+                _ => unreachable!("loader always sends the Session entry first"),
+            };
+            self.session_title = session
+                .title
+                .clone()
+                .unwrap_or_else(|| "Untitled".to_string());
+            self.spool_file = SpoolFile::new(session);
+            self.timeline.clear();
+            self.playing = true;
+            self.last_tick = Instant::now();
+            0
+        } else {
+            self.spool_file.entries.len()
+        };
+
+        for entry in batch {
+            self.spool_file.add_entry(entry);
+        }
+
+        extend_timeline(&mut self.timeline, &self.spool_file.entries, start);
+        self.total_duration_ms = self.timeline.last().map(|t| t.playback_ms).unwrap_or(0);
+    }
+
+    /// `PlayEvent::LoadComplete`: no more batches are coming.
+    fn mark_load_complete(&mut self) {
+        self.done_loading = true;
+        if self.timeline.is_empty() {
+            self.load_error = Some("Session has no entries.".to_string());
+            self.should_quit = true;
+        }
+    }
+
+    /// `PlayEvent::LoadFailed`: the background parse itself errored out.
+    fn mark_load_failed(&mut self, err: String) {
+        self.done_loading = true;
+        self.load_error = Some(format!("Failed to load session: {}", err));
+        self.should_quit = true;
+    }
+
+    /// `PlayEvent::FileAppended`: `spawn_follow_thread` tailed new lines
+    /// flushed to a session still being recorded. Reuses `ingest_batch`'s
+    /// incremental timeline-append, then - since there's no "later" to
+    /// play towards in follow mode - jumps straight to the new entries and
+    /// pins the view to the bottom, unless the user has scrolled up to
+    /// review earlier output (`user_scrolled_up`).
+    fn ingest_follow_batch(&mut self, batch: Vec<Entry>) {
+        self.ingest_batch(batch);
+        if !self.follow_mode {
+            return;
+        }
+        self.visible_count = self.timeline.len();
+        self.playback_elapsed_ms = self.total_duration_ms;
+        if !self.user_scrolled_up {
+            self.auto_scroll();
+        }
+    }
+
     fn speed(&self) -> f32 {
         SPEEDS[self.speed_index]
     }
@@ -157,10 +420,14 @@ impl PlayApp {
         self.playing = false;
         if self.visible_count < self.timeline.len() {
             self.visible_count += 1;
-            if let Some(te) = self.timeline.get(self.visible_count.saturating_sub(1)) {
+            let revealed = self.timeline.get(self.visible_count - 1).copied();
+            if let Some(te) = revealed {
                 self.playback_elapsed_ms = te.playback_ms;
             }
             self.auto_scroll();
+            if let Some(te) = revealed {
+                self.flag_if_error(te.entry_index);
+            }
         }
     }
 
@@ -192,6 +459,7 @@ impl PlayApp {
         self.visible_count = self.timeline.len();
         self.playback_elapsed_ms = self.total_duration_ms;
         self.auto_scroll();
+        self.user_scrolled_up = false;
     }
 
     fn current_entry_timestamp(&self) -> Option<u64> {
@@ -278,6 +546,15 @@ impl PlayApp {
     }
 
     fn tick(&mut self) {
+        // Decay the error flash regardless of play/pause state, so pausing
+        // on a freshly-revealed error doesn't freeze it on screen forever.
+        if self.error_flash_ticks > 0 {
+            self.error_flash_ticks -= 1;
+            if self.error_flash_ticks == 0 {
+                self.error_flash = None;
+            }
+        }
+
         if !self.playing {
             return;
         }
@@ -293,13 +570,29 @@ impl PlayApp {
         while self.visible_count < self.timeline.len() {
             let te = &self.timeline[self.visible_count];
             if te.playback_ms <= self.playback_elapsed_ms {
+                let entry_index = te.entry_index;
                 self.visible_count += 1;
                 self.auto_scroll();
+                self.flag_if_error(entry_index);
             } else {
                 break;
             }
         }
 
+        // Loop region: jump back to the marked start once playback passes
+        // the marked end, instead of falling through to "stop at end"
+        // below.
+        if self.loop_enabled {
+            if let Some((start, end)) = self.trim_range() {
+                if self.current_entry_timestamp().is_some_and(|ts| ts >= end) {
+                    let target = self.playback_ms_for_original_ts(start).unwrap_or(0);
+                    self.seek_to(target);
+                    self.playing = true;
+                    return;
+                }
+            }
+        }
+
         // Stop at end
         if self.visible_count >= self.timeline.len() {
             self.playing = false;
@@ -307,6 +600,100 @@ impl PlayApp {
         }
     }
 
+    /// Translate an original (uncompressed) timestamp - the space
+    /// `trim_start_ms`/`trim_end_ms` live in, see `mark_trim_start` - into
+    /// the compressed `playback_ms` of the first timeline entry at or after
+    /// it. `None` if nothing in the timeline reaches that far.
+    fn playback_ms_for_original_ts(&self, ts: u64) -> Option<u64> {
+        self.timeline.iter().find_map(|te| {
+            let entry = self.spool_file.entries.get(te.entry_index)?;
+            if entry.timestamp()? >= ts {
+                Some(te.playback_ms)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Scrub directly to `target_ms` of compressed playback time: binary-
+    /// searches the timeline (whose `playback_ms` is non-decreasing) for
+    /// the entry bracket containing the target and reveals everything up
+    /// through it, the same "already revealed" semantics `tick` uses.
+    fn seek_to(&mut self, target_ms: u64) {
+        let idx = self.timeline.partition_point(|te| te.playback_ms <= target_ms);
+        self.visible_count = idx;
+        self.playback_elapsed_ms = target_ms.min(self.total_duration_ms);
+        self.auto_scroll();
+    }
+
+    /// Step `delta` entries forward (positive) or backward (negative)
+    /// while paused - the general transport primitive behind the named
+    /// `step_forward`/`step_backward` single-step bindings.
+    fn step(&mut self, delta: i64) {
+        match delta.cmp(&0) {
+            std::cmp::Ordering::Greater => {
+                for _ in 0..delta {
+                    self.step_forward();
+                }
+            }
+            std::cmp::Ordering::Less => {
+                for _ in 0..delta.unsigned_abs() {
+                    self.step_backward();
+                }
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+
+    /// `r`: toggle whether the marked trim region also loops during
+    /// playback (see `loop_enabled`).
+    fn toggle_loop_region(&mut self) {
+        if self.trim_range().is_none() {
+            self.status_message = Some("Mark a trim region with [ and ] before looping".to_string());
+            return;
+        }
+        self.loop_enabled = !self.loop_enabled;
+        self.status_message = Some(if self.loop_enabled {
+            "Loop region on".to_string()
+        } else {
+            "Loop region off".to_string()
+        });
+    }
+
+    /// Bump `error_count`, ring the bell, and start an error flash if the
+    /// just-revealed entry at `entry_index` is a `ToolResult`/`Error` with
+    /// an error set. Called from `tick` and `step_forward` every time
+    /// `visible_count` advances, so it fires exactly once per newly
+    /// revealed error even when several come into view in one tick.
+    fn flag_if_error(&mut self, entry_index: usize) {
+        let has_error = self
+            .spool_file
+            .entries
+            .get(entry_index)
+            .map(entry_has_error)
+            .unwrap_or(false);
+        if !has_error {
+            return;
+        }
+
+        self.error_count += 1;
+        if self.bell_enabled {
+            ring_bell();
+        }
+        self.error_flash = Some(format!("\u{26a0} error revealed ({} total)", self.error_count));
+        self.error_flash_ticks = ERROR_FLASH_TICKS;
+    }
+
+    /// Toggle whether a newly-revealed error rings the terminal bell.
+    fn toggle_bell(&mut self) {
+        self.bell_enabled = !self.bell_enabled;
+        self.status_message = Some(if self.bell_enabled {
+            "Bell on".to_string()
+        } else {
+            "Bell off".to_string()
+        });
+    }
+
     /// Ensure the scroll is positioned to show the latest entry.
     fn auto_scroll(&mut self) {
         // We'll let the draw function handle this based on content height.
@@ -316,6 +703,9 @@ impl PlayApp {
 
     fn scroll_up(&mut self, amount: usize) {
         self.scroll_offset = self.scroll_offset.saturating_sub(amount);
+        if self.follow_mode {
+            self.user_scrolled_up = true;
+        }
     }
 
     fn scroll_down(&mut self, amount: usize) {
@@ -337,176 +727,1002 @@ impl PlayApp {
     fn progress_label(&self) -> String {
         let current = format_duration_ms(self.playback_elapsed_ms);
         let total = format_duration_ms(self.total_duration_ms);
+        let loading_suffix = if self.done_loading {
+            ""
+        } else {
+            "  (loading...)"
+        };
         format!(
-            "{} / {}  [{}/{}]",
+            "{} / {}  [{}/{}]{}",
             current,
             total,
             self.visible_count,
-            self.timeline.len()
+            self.timeline.len(),
+            loading_suffix
         )
     }
-}
 
-/// Build a compressed timeline from entries.
-///
-/// Applies two compressions:
-/// 1. Idle gap compression: gaps before Prompt entries are capped at MAX_IDLE_GAP_MS
-/// 2. Thinking compression: gaps after Thinking entries are capped at MAX_THINKING_MS
-fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
-    if entries.is_empty() {
-        return Vec::new();
+    /// Enter search mode: pauses playback and starts capturing keystrokes
+    /// into `active_search`.
+    fn enter_search(&mut self) {
+        self.playing = false;
+        self.searching = true;
+        self.active_search = Some(String::new());
+        self.search_matches.clear();
+        self.search_match_ranges.clear();
+        self.search_regex = None;
+        self.search_match_index = None;
     }
 
-    let mut timeline = Vec::with_capacity(entries.len());
-    let mut compressed_time: u64 = 0;
-    let mut prev_original_ts: u64 = 0;
+    /// `Esc` while searching: leave search mode without quitting the
+    /// player, clearing the query and any matches.
+    fn exit_search(&mut self) {
+        self.searching = false;
+        self.active_search = None;
+        self.search_matches.clear();
+        self.search_match_ranges.clear();
+        self.search_regex = None;
+        self.search_match_index = None;
+    }
 
-    for (i, entry) in entries.iter().enumerate() {
-        let original_ts = entry.timestamp().unwrap_or(0);
-        let raw_gap = original_ts.saturating_sub(prev_original_ts);
+    /// `Enter` while searching: stop capturing keystrokes but keep the
+    /// query and matches active, so `n`/`N` and the match highlight in
+    /// `render_entry_lines` keep working.
+    fn commit_search(&mut self) {
+        self.searching = false;
+    }
 
-        let compressed_gap = if i == 0 {
-            0
-        } else {
-            let mut gap = raw_gap;
+    fn search_push_char(&mut self, c: char) {
+        if let Some(query) = self.active_search.as_mut() {
+            query.push(c);
+        }
+        self.update_search_matches();
+    }
 
-            // Idle gap compression: cap gaps before Prompt entries
-            if matches!(entry, Entry::Prompt(_)) && gap > MAX_IDLE_GAP_MS {
-                gap = MAX_IDLE_GAP_MS;
-            }
+    fn search_pop_char(&mut self) {
+        if let Some(query) = self.active_search.as_mut() {
+            query.pop();
+        }
+        self.update_search_matches();
+    }
 
-            // Thinking compression: cap gaps after Thinking entries
-            if i > 0 {
-                if let Some(prev_entry) = entries.get(i - 1) {
-                    if matches!(prev_entry, Entry::Thinking(_)) && gap > MAX_THINKING_MS {
-                        gap = MAX_THINKING_MS;
-                    }
+    /// Rescan every timeline entry's joined, capped text against the
+    /// current query - compiled as a regex, case-insensitively - and
+    /// record byte ranges for every entry with at least one match. An
+    /// empty query clears the matches entirely rather than matching
+    /// everything.
+    fn update_search_matches(&mut self) {
+        self.search_matches.clear();
+        self.search_match_ranges.clear();
+        self.search_match_index = None;
+        self.search_regex = None;
+
+        let query = match self.active_search.as_deref() {
+            Some(q) if !q.is_empty() => q,
+            _ => return,
+        };
+
+        let search = RegexSearch::compile(query);
+        self.status_message = search
+            .is_literal_fallback
+            .then(|| format!("Invalid regex, searching literally: {}", query));
+
+        for (ti, te) in self.timeline.iter().enumerate() {
+            if let Some(entry) = self.spool_file.entries.get(te.entry_index) {
+                let text = entry_search_text(entry);
+                let ranges: Vec<(usize, usize)> = search
+                    .regex
+                    .find_iter(&text)
+                    .map(|m| (m.start(), m.end()))
+                    .collect();
+                if !ranges.is_empty() {
+                    self.search_matches.push(ti);
+                    self.search_match_ranges.insert(ti, ranges);
                 }
             }
+        }
 
-            gap
-        };
-
-        compressed_time += compressed_gap;
+        self.search_regex = Some(search);
+    }
 
-        timeline.push(TimelineEntry {
-            entry_index: i,
-            playback_ms: compressed_time,
-        });
+    /// Jump `n` timeline indices forward to the next match, wrapping
+    /// around to the first match at the end.
+    fn jump_to_next_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let next = match self.search_match_index {
+            Some(i) => (i + 1) % self.search_matches.len(),
+            None => 0,
+        };
+        self.search_match_index = Some(next);
+        self.reveal_match(next);
+    }
 
-        prev_original_ts = original_ts;
+    /// Jump `N` timeline indices backward to the previous match, wrapping
+    /// around to the last match at the start.
+    fn jump_to_prev_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let prev = match self.search_match_index {
+            Some(0) | None => self.search_matches.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.search_match_index = Some(prev);
+        self.reveal_match(prev);
     }
 
-    timeline
-}
+    /// Reveal timeline entries up through the match at `search_matches[i]`
+    /// (if not already visible) and flag it for `draw_entries` to scroll
+    /// into view on the next draw.
+    fn reveal_match(&mut self, i: usize) {
+        let ti = self.search_matches[i];
+        self.playing = false;
+        if ti + 1 > self.visible_count {
+            self.visible_count = ti + 1;
+            if let Some(te) = self.timeline.get(ti) {
+                self.playback_elapsed_ms = te.playback_ms;
+            }
+        }
+        self.pending_scroll_to_timeline_index = Some(ti);
+    }
 
-fn format_duration_ms(ms: u64) -> String {
-    let total_secs = ms / 1000;
-    let minutes = total_secs / 60;
-    let seconds = total_secs % 60;
-    format!("{}:{:02}", minutes, seconds)
-}
+    /// `f`: open the fuzzy jump overlay, pausing playback. Starts with an
+    /// empty query, which `update_jump_results` treats as "show the first
+    /// `MAX_JUMP_RESULTS` entries in chronological order" so the overlay
+    /// isn't blank before the user types anything.
+    fn enter_jump_search(&mut self) {
+        self.playing = false;
+        self.jump_search = Some(JumpSearch {
+            query: String::new(),
+            results: Vec::new(),
+            selected: 0,
+        });
+        self.update_jump_results();
+    }
 
-pub fn run(path: &Path, speed: f32) -> Result<()> {
-    // Load session
-    let spool_file = load_spool_or_log(path)?;
+    /// `Esc` while the jump overlay is open: close it without jumping.
+    fn exit_jump_search(&mut self) {
+        self.jump_search = None;
+    }
 
-    if spool_file.entries.is_empty() {
-        println!("Session has no entries.");
-        return Ok(());
+    fn jump_search_push_char(&mut self, c: char) {
+        if let Some(jump) = self.jump_search.as_mut() {
+            jump.query.push(c);
+        }
+        self.update_jump_results();
     }
 
-    let mut app = PlayApp::new(spool_file, path.to_path_buf(), speed);
+    fn jump_search_pop_char(&mut self) {
+        if let Some(jump) = self.jump_search.as_mut() {
+            jump.query.pop();
+        }
+        self.update_jump_results();
+    }
 
-    // Set up terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = ratatui::prelude::CrosstermBackend::new(stdout);
-    let mut terminal = ratatui::Terminal::new(backend)?;
+    /// Move the overlay's selected result by `delta`, wrapping around.
+    fn jump_search_move(&mut self, delta: isize) {
+        let Some(jump) = self.jump_search.as_mut() else {
+            return;
+        };
+        if jump.results.is_empty() {
+            return;
+        }
+        let len = jump.results.len() as isize;
+        jump.selected = (jump.selected as isize + delta).rem_euclid(len) as usize;
+    }
 
-    // Panic hook to restore terminal
-    let original_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |panic_info| {
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
-        original_hook(panic_info);
-    }));
+    /// Re-run the fuzzy match (see [`crate::tui::fuzzy::fuzzy_match`])
+    /// against every timeline entry's [`entry_search_text`], ranking hits
+    /// by score (ties broken by chronological order) and keeping only the
+    /// top `MAX_JUMP_RESULTS`. An empty query matches everything with a
+    /// score of 0, so opening the overlay shows the session from the top
+    /// rather than nothing.
+    fn update_jump_results(&mut self) {
+        let Some(query) = self.jump_search.as_ref().map(|j| j.query.clone()) else {
+            return;
+        };
+        let query = query.trim();
 
-    // Start playing immediately
-    app.playing = true;
-    app.last_tick = Instant::now();
+        let mut matches: Vec<JumpResult> = self
+            .timeline
+            .iter()
+            .enumerate()
+            .filter_map(|(ti, te)| {
+                let entry = self.spool_file.entries.get(te.entry_index)?;
+                let text = entry_search_text(entry);
+                if text.is_empty() {
+                    return None;
+                }
+                let score = if query.is_empty() {
+                    0
+                } else {
+                    fuzzy_match(&text, query)?.score
+                };
+                Some(JumpResult {
+                    timeline_index: ti,
+                    score,
+                    label: jump_label(entry),
+                })
+            })
+            .collect();
+
+        if !query.is_empty() {
+            matches.sort_by(|a, b| {
+                b.score
+                    .cmp(&a.score)
+                    .then(a.timeline_index.cmp(&b.timeline_index))
+            });
+        }
+        matches.truncate(MAX_JUMP_RESULTS);
 
-    let result = run_loop(&mut terminal, &mut app);
+        if let Some(jump) = self.jump_search.as_mut() {
+            jump.results = matches;
+            jump.selected = 0;
+        }
+    }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    /// `Enter` while the jump overlay is open: reveal the timeline through
+    /// the selected result (same "fast-forward without replaying" logic as
+    /// [`PlayApp::reveal_match`]) and scroll it into view, then close the
+    /// overlay.
+    fn jump_to_selected(&mut self) {
+        let Some(jump) = self.jump_search.take() else {
+            return;
+        };
+        let Some(result) = jump.results.get(jump.selected) else {
+            return;
+        };
+        let ti = result.timeline_index;
+        if ti + 1 > self.visible_count {
+            self.visible_count = ti + 1;
+            if let Some(te) = self.timeline.get(ti) {
+                self.playback_elapsed_ms = te.playback_ms;
+            }
+        }
+        self.pending_scroll_to_timeline_index = Some(ti);
+    }
 
-    result
-}
+    /// `Enter`: expand the entry at the playback head into the full-screen
+    /// detail view. A no-op before anything has played yet.
+    fn enter_focus(&mut self) {
+        if self.visible_count == 0 {
+            return;
+        }
+        self.playing = false;
+        self.focused_entry = Some(self.visible_count - 1);
+        self.focused_scroll_offset = 0;
+    }
 
-fn run_loop(
-    terminal: &mut ratatui::Terminal<ratatui::prelude::CrosstermBackend<io::Stdout>>,
-    app: &mut PlayApp,
-) -> Result<()> {
-    loop {
-        app.tick();
+    /// `Esc` while focused: drop back to the scrolling transcript exactly
+    /// where playback left off.
+    fn exit_focus(&mut self) {
+        self.focused_entry = None;
+        self.focused_scroll_offset = 0;
+    }
 
-        terminal.draw(|f| draw(f, app))?;
+    fn focused_scroll_up(&mut self, amount: usize) {
+        self.focused_scroll_offset = self.focused_scroll_offset.saturating_sub(amount);
+    }
 
-        // Poll with short timeout for smooth playback
-        if event::poll(Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
+    fn focused_scroll_down(&mut self, amount: usize) {
+        self.focused_scroll_offset = self.focused_scroll_offset.saturating_add(amount);
+    }
 
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => {
-                        app.should_quit = true;
-                    }
-                    KeyCode::Char(' ') => app.toggle_play(),
-                    KeyCode::Right | KeyCode::Char('l') => app.step_forward(),
-                    KeyCode::Left | KeyCode::Char('h') => app.step_backward(),
-                    KeyCode::Char('+') | KeyCode::Char('=') => app.speed_up(),
-                    KeyCode::Char('-') | KeyCode::Char('_') => app.speed_down(),
-                    KeyCode::Home | KeyCode::Char('g') => app.jump_to_start(),
-                    KeyCode::End | KeyCode::Char('G') => app.jump_to_end(),
-                    KeyCode::PageUp | KeyCode::Char('k') => app.scroll_up(10),
-                    KeyCode::PageDown | KeyCode::Char('j') => app.scroll_down(10),
-                    KeyCode::Char('[') => app.mark_trim_start(),
-                    KeyCode::Char(']') => app.mark_trim_end(),
-                    KeyCode::Char('x') => app.export_trimmed(),
-                    _ => {}
-                }
-            }
+    /// `v`: start selecting transcript text at the current viewport's top
+    /// line. Pauses playback, same as entering search or focus mode, so the
+    /// rendered lines underneath the selection hold still.
+    fn enter_selection(&mut self) {
+        if self.rendered_plain_lines.is_empty() {
+            return;
         }
+        self.playing = false;
+        let start_row = self.scroll_offset.min(self.rendered_plain_lines.len() - 1);
+        self.selection = Some(Selection {
+            anchor: (start_row, 0),
+            cursor: (start_row, 0),
+        });
+    }
 
-        if app.should_quit {
-            break;
+    /// `Esc` while selecting: drop the selection without copying anything.
+    fn exit_selection(&mut self) {
+        self.selection = None;
+    }
+
+    /// Move the selection cursor by `d_row` lines and `d_col` columns over
+    /// `rendered_plain_lines`, clamping to the buffer's bounds and to the
+    /// target line's length. Also nudges `scroll_offset` so the cursor's
+    /// row stays a few lines below the top of the viewport.
+    fn selection_move(&mut self, d_row: isize, d_col: isize) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let (cur_row, cur_col) = selection.cursor;
+        let max_row = self.rendered_plain_lines.len().saturating_sub(1);
+        let new_row = (cur_row as isize + d_row).clamp(0, max_row as isize) as usize;
+        let line_len = self
+            .rendered_plain_lines
+            .get(new_row)
+            .map(|l| l.chars().count())
+            .unwrap_or(0);
+        let max_col = line_len.saturating_sub(1);
+        let new_col = (cur_col as isize + d_col).clamp(0, max_col as isize) as usize;
+
+        if let Some(selection) = self.selection.as_mut() {
+            selection.cursor = (new_row, new_col);
         }
+        self.scroll_offset = new_row.saturating_sub(3);
     }
 
-    Ok(())
+    /// `y`: reconstruct the plain text spanning the selected lines (full
+    /// lines in the middle, clipped to the anchor/cursor column on the
+    /// first and last) and copy it to the system clipboard, then drop the
+    /// selection.
+    fn yank_selection(&mut self) {
+        let Some(selection) = self.selection.take() else {
+            return;
+        };
+        let text = reconstruct_selection_text(&self.rendered_plain_lines, &selection);
+
+        self.status_message = Some(match copy_to_clipboard(&text) {
+            Ok(()) => format!("Copied {} chars to clipboard", text.chars().count()),
+            Err(err) => format!("Copy failed: {}", err),
+        });
+    }
 }
 
-/// Main draw function.
-fn draw(f: &mut Frame, app: &mut PlayApp) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // Title bar
-            Constraint::Min(1),    // Entry content
-            Constraint::Length(1), // Progress bar
-            Constraint::Length(1), // Controls
-        ])
-        .split(f.area());
+/// Join the lines `selection` covers into a single plain-text string - full
+/// lines in the middle, clipped to the anchor/cursor column on the first
+/// and last. Split out of [`PlayApp::yank_selection`] so the reconstruction
+/// logic can be tested without a real system clipboard.
+fn reconstruct_selection_text(lines: &[String], selection: &Selection) -> String {
+    let (start, end) = selection.ordered();
+    let mut text = String::new();
+    for row in start.0..=end.0 {
+        let Some(line) = lines.get(row) else {
+            continue;
+        };
+        let chars: Vec<char> = line.chars().collect();
+        let from = if row == start.0 { start.1.min(chars.len()) } else { 0 };
+        let to = if row == end.0 {
+            (end.1 + 1).min(chars.len())
+        } else {
+            chars.len()
+        };
+        if from < to {
+            text.extend(&chars[from..to]);
+        }
+        if row != end.0 {
+            text.push('\n');
+        }
+    }
+    text
+}
 
-    draw_title_bar(f, chunks[0], app);
-    draw_entries(f, chunks[1], app);
-    draw_progress_bar(f, chunks[2], app);
-    draw_controls(f, chunks[3], app);
+/// Anchor and cursor over `PlayApp::rendered_plain_lines`, both
+/// `(line, column)` pairs - extended by `h`/`j`/`k`/`l` while selecting and
+/// read back by `draw_entries` (to style the covered cells) and
+/// `PlayApp::yank_selection` (to reconstruct the plain text between them).
+struct Selection {
+    anchor: (usize, usize),
+    cursor: (usize, usize),
+}
+
+impl Selection {
+    /// Anchor and cursor in document order, regardless of which direction
+    /// the user has moved the cursor relative to where selection started.
+    fn ordered(&self) -> ((usize, usize), (usize, usize)) {
+        if self.anchor <= self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
+        }
+    }
+}
+
+/// Live state for the `f` fuzzy-jump overlay: a query string, the current
+/// ranked matches against the timeline (see `PlayApp::update_jump_results`),
+/// and which one is selected.
+struct JumpSearch {
+    query: String,
+    results: Vec<JumpResult>,
+    selected: usize,
+}
+
+/// One ranked hit in the fuzzy-jump overlay.
+struct JumpResult {
+    timeline_index: usize,
+    score: i32,
+    label: String,
+}
+
+/// Short one-line label for a timeline entry in the jump overlay - enough to
+/// tell entries apart at a glance, not a full preview.
+fn jump_label(entry: &Entry) -> String {
+    let (kind, text) = match entry {
+        Entry::Prompt(p) => ("PROMPT", p.content.clone()),
+        Entry::Thinking(t) => ("THINKING", t.content.clone()),
+        Entry::ToolCall(tc) => ("TOOL", format!("{} {}", tc.tool, format_tool_input(&tc.input, 80))),
+        Entry::ToolResult(tr) => {
+            let text = tr
+                .error
+                .clone()
+                .or_else(|| match &tr.output {
+                    Some(ToolOutput::Text(t)) => Some(t.clone()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            ("RESULT", text)
+        }
+        Entry::Response(r) => ("RESPONSE", r.content.clone()),
+        Entry::Error(e) => ("ERROR", e.message.clone()),
+        Entry::Annotation(a) => ("NOTE", a.content.clone()),
+        _ => ("ENTRY", String::new()),
+    };
+    let first_line = text.lines().next().unwrap_or("").trim();
+    format!("{kind}: {first_line}")
+}
+
+/// Copy `text` to the system clipboard. The standard library has no
+/// clipboard API and nothing else in this crate talks to one yet, so this
+/// is the one place `arboard` (the de-facto cross-platform clipboard crate)
+/// enters the dependency graph.
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard.set_text(text.to_string()).map_err(|e| e.to_string())
+}
+
+/// A compiled search pattern. The query is treated as a regex; an invalid
+/// pattern falls back to matching it as an escaped literal rather than
+/// refusing to search, with the fallback surfaced to the user via
+/// `status_message` (see [`PlayApp::update_search_matches`]).
+struct RegexSearch {
+    regex: Regex,
+    is_literal_fallback: bool,
+}
+
+impl RegexSearch {
+    fn compile(query: &str) -> Self {
+        match RegexBuilder::new(query).case_insensitive(true).build() {
+            Ok(regex) => RegexSearch {
+                regex,
+                is_literal_fallback: false,
+            },
+            Err(_) => {
+                let escaped = regex::escape(query);
+                let regex = RegexBuilder::new(&escaped)
+                    .case_insensitive(true)
+                    .build()
+                    .expect("an escaped literal always compiles");
+                RegexSearch {
+                    regex,
+                    is_literal_fallback: true,
+                }
+            }
+        }
+    }
+}
+
+/// Rendered text a search query matches against for one entry: prompt
+/// content, thinking text, tool name/input, and tool results/errors -
+/// whatever `render_entry_lines` would actually show for it. Since a
+/// single entry can wrap across many display lines, the query matches
+/// against this joined text rather than line-by-line, capped at
+/// `MAX_SEARCH_LINES` so a huge tool output doesn't make every keystroke
+/// re-scan it in full.
+fn entry_search_text(entry: &Entry) -> String {
+    let raw = match entry {
+        Entry::Prompt(p) => p.content.clone(),
+        Entry::Thinking(t) => t.content.clone(),
+        Entry::ToolCall(tc) => format!("{} {}", tc.tool, format_tool_input(&tc.input, 200)),
+        Entry::ToolResult(tr) => {
+            let mut text = tr.error.clone().unwrap_or_default();
+            if let Some(ToolOutput::Text(ref t)) = tr.output {
+                text.push(' ');
+                text.push_str(t);
+            }
+            text
+        }
+        Entry::Response(r) => r.content.clone(),
+        Entry::Error(e) => e.message.clone(),
+        Entry::Annotation(a) => a.content.clone(),
+        _ => String::new(),
+    };
+    raw.lines().take(MAX_SEARCH_LINES).collect::<Vec<_>>().join("\n")
+}
+
+/// Whether `entry` is a `ToolResult` with its `error` set, or an `Error`
+/// entry - the cases [`PlayApp::flag_if_error`] rings the bell for.
+fn entry_has_error(entry: &Entry) -> bool {
+    match entry {
+        Entry::ToolResult(tr) => tr.error.is_some(),
+        Entry::Error(_) => true,
+        _ => false,
+    }
+}
+
+/// Write a terminal bell directly to stdout, bypassing ratatui's buffer -
+/// this is an audible/visual OOB signal, not a drawn cell, so there's
+/// nothing for the draw loop to diff against.
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+/// Restyle the lines `draw_entries` built to reverse-video the cells
+/// `selection` covers. Selected lines are re-flattened to their plain text
+/// first - splitting an arbitrary column range out of each line's original
+/// multi-span styling (search highlights, colors) precisely isn't worth it
+/// for a highlight that's about to be reverse-videoed anyway.
+fn highlight_selection(lines: &mut [Line<'static>], selection: &Selection) {
+    let (start, end) = selection.ordered();
+    for (i, line) in lines.iter_mut().enumerate().take(end.0 + 1).skip(start.0) {
+        let plain: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        let chars: Vec<char> = plain.chars().collect();
+        let sel_start = if i == start.0 { start.1.min(chars.len()) } else { 0 };
+        let sel_end = if i == end.0 {
+            (end.1 + 1).min(chars.len())
+        } else {
+            chars.len()
+        };
+        if sel_start >= sel_end {
+            continue;
+        }
+
+        let mut spans = Vec::new();
+        if sel_start > 0 {
+            spans.push(Span::raw(chars[..sel_start].iter().collect::<String>()));
+        }
+        spans.push(Span::styled(
+            chars[sel_start..sel_end].iter().collect::<String>(),
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+        if sel_end < chars.len() {
+            spans.push(Span::raw(chars[sel_end..].iter().collect::<String>()));
+        }
+        *line = Line::from(spans);
+    }
+}
+
+/// Build a compressed timeline from entries.
+///
+/// Applies two compressions:
+/// 1. Idle gap compression: gaps before Prompt entries are capped at MAX_IDLE_GAP_MS
+/// 2. Thinking compression: gaps after Thinking entries are capped at MAX_THINKING_MS
+fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
+    let mut timeline = Vec::with_capacity(entries.len());
+    extend_timeline(&mut timeline, entries, 0);
+    timeline
+}
+
+/// Append timeline entries for `entries[start..]` onto an already-built
+/// `timeline`, continuing its compression state (elapsed compressed time,
+/// previous entry's original timestamp) from where it left off - this is
+/// what lets `PlayApp::ingest_batch` extend the timeline as each background
+/// load batch arrives instead of rebuilding it from scratch every time.
+/// `build_timeline` is just this called with an empty `timeline` and
+/// `start = 0`.
+fn extend_timeline(timeline: &mut Vec<TimelineEntry>, entries: &[Entry], start: usize) {
+    if start >= entries.len() {
+        return;
+    }
+
+    let mut compressed_time: u64 = timeline.last().map(|t| t.playback_ms).unwrap_or(0);
+    let mut prev_original_ts: u64 = if start == 0 {
+        0
+    } else {
+        entries[start - 1].timestamp().unwrap_or(0)
+    };
+
+    for (i, entry) in entries.iter().enumerate().skip(start) {
+        let original_ts = entry.timestamp().unwrap_or(0);
+        let raw_gap = original_ts.saturating_sub(prev_original_ts);
+
+        let compressed_gap = if i == 0 {
+            0
+        } else {
+            let mut gap = raw_gap;
+
+            // Idle gap compression: cap gaps before Prompt entries
+            if matches!(entry, Entry::Prompt(_)) && gap > MAX_IDLE_GAP_MS {
+                gap = MAX_IDLE_GAP_MS;
+            }
+
+            // Thinking compression: cap gaps after Thinking entries
+            if let Some(prev_entry) = entries.get(i - 1) {
+                if matches!(prev_entry, Entry::Thinking(_)) && gap > MAX_THINKING_MS {
+                    gap = MAX_THINKING_MS;
+                }
+            }
+
+            gap
+        };
+
+        compressed_time += compressed_gap;
+
+        timeline.push(TimelineEntry {
+            entry_index: i,
+            playback_ms: compressed_time,
+        });
+
+        prev_original_ts = original_ts;
+    }
+}
+
+fn format_duration_ms(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let minutes = total_secs / 60;
+    let seconds = total_secs % 60;
+    format!("{}:{:02}", minutes, seconds)
+}
+
+/// One event driving `run_loop`, delivered through a single channel by
+/// independent producers: [`spawn_input_thread`] (key presses, resizes),
+/// [`spawn_tick_thread`] (the redraw/playback timer), [`spawn_loader_thread`]
+/// (background session parsing), and - in follow mode -
+/// [`spawn_follow_thread`] (new lines tailed from a session still being
+/// recorded). Named `PlayEvent` rather than `Event` to avoid colliding with
+/// crossterm's `Event`, which `spawn_input_thread` reads from directly.
+enum PlayEvent {
+    Key(event::KeyEvent),
+    Resize(u16, u16),
+    Tick,
+    /// A batch of freshly-parsed entries from `spawn_loader_thread`.
+    EntriesLoaded(Vec<Entry>),
+    /// The loader has sent every entry it's going to send.
+    LoadComplete,
+    /// The background parse itself failed.
+    LoadFailed(String),
+    /// `spawn_follow_thread` tailed new complete lines appended to the
+    /// session file since the last poll, already parsed into entries.
+    FileAppended(Vec<Entry>),
+}
+
+/// How many entries `spawn_loader_thread` batches per `PlayEvent::EntriesLoaded`.
+const LOAD_BATCH_SIZE: usize = 200;
+
+/// How often `spawn_follow_thread` polls the session file for growth.
+const FOLLOW_POLL_INTERVAL_MS: u64 = 500;
+
+pub fn run(path: &Path, speed: f32, follow: bool) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+
+    let mut app = PlayApp::new_empty(path.to_path_buf(), speed);
+    app.follow_mode = follow;
+
+    // Set up terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = ratatui::prelude::CrosstermBackend::new(stdout);
+    let mut terminal = ratatui::Terminal::new(backend)?;
+
+    // Panic hook to restore terminal
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        original_hook(panic_info);
+    }));
+
+    spawn_input_thread(tx.clone());
+    spawn_tick_thread(tx.clone());
+    if follow {
+        // The loader reads the whole file as of this moment; the follow
+        // thread only needs to pick up bytes written after that point, so
+        // it starts counting from the file's current length rather than 0.
+        let start_offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        spawn_follow_thread(tx.clone(), path.to_path_buf(), start_offset);
+    }
+    spawn_loader_thread(tx, path.to_path_buf());
+
+    let result = run_loop(&mut terminal, &mut app, &rx);
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    result?;
+
+    // The TUI never got anything worth showing (empty session, or the
+    // parse itself failed) - report it now that the terminal is back.
+    if let Some(err) = app.load_error {
+        println!("{}", err);
+    }
+
+    Ok(())
+}
+
+/// Forward terminal key/resize events onto `tx` as they arrive. Exits
+/// quietly once the receiver is gone (the app is shutting down).
+fn spawn_input_thread(tx: mpsc::Sender<PlayEvent>) {
+    std::thread::spawn(move || loop {
+        match event::poll(Duration::from_millis(50)) {
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key)) => {
+                    if tx.send(PlayEvent::Key(key)).is_err() {
+                        return;
+                    }
+                }
+                Ok(Event::Resize(w, h)) => {
+                    if tx.send(PlayEvent::Resize(w, h)).is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => return,
+            },
+            Ok(false) => {}
+            Err(_) => return,
+        }
+    });
+}
+
+/// Drive redraws and playback advancement at a fixed cadence, independent
+/// of whether any other event arrived.
+fn spawn_tick_thread(tx: mpsc::Sender<PlayEvent>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(50));
+        if tx.send(PlayEvent::Tick).is_err() {
+            return;
+        }
+    });
+}
+
+/// Parse `path` off the main thread and stream its entries into the UI in
+/// batches, so a multi-hour session's terminal setup and first frame never
+/// block on a full parse.
+///
+/// `spool_format` has no incremental/streaming reader - `load_spool_or_log`
+/// parses the whole source in one pass - so this thread still pays that one
+/// parse cost up front rather than truly interleaving I/O with playback.
+/// What it buys is real: the parse runs off the main thread (raw mode and
+/// the first frame happen immediately, not after parsing completes), and
+/// the result reaches `PlayApp` in `LOAD_BATCH_SIZE`-entry chunks rather
+/// than one huge timeline rebuild, so the user can start scrubbing through
+/// the front of a huge session as soon as the first batch lands instead of
+/// waiting for every entry to arrive.
+fn spawn_loader_thread(tx: mpsc::Sender<PlayEvent>, path: PathBuf) {
+    std::thread::spawn(move || {
+        let spool_file = match load_spool_or_log(&path) {
+            Ok(f) => f,
+            Err(err) => {
+                let _ = tx.send(PlayEvent::LoadFailed(err.to_string()));
+                return;
+            }
+        };
+
+        for batch in spool_file.entries.chunks(LOAD_BATCH_SIZE) {
+            if tx.send(PlayEvent::EntriesLoaded(batch.to_vec())).is_err() {
+                return;
+            }
+        }
+
+        let _ = tx.send(PlayEvent::LoadComplete);
+    });
+}
+
+/// Tail `path` for lines appended after `start_offset` bytes, polling every
+/// `FOLLOW_POLL_INTERVAL_MS` for an agent still recording into it. Unlike
+/// `spawn_loader_thread`, which parses the whole file once via
+/// `load_spool_or_log`, this reads only the bytes written since the last
+/// poll and parses each complete new line with `spool_format::parse_line` -
+/// `spool_format` has no streaming reader, but a single JSONL line is cheap
+/// enough to parse one at a time as it lands. A trailing line with no
+/// terminating `\n` yet (the writer mid-flush) is left unconsumed so the
+/// next poll picks it up once it's complete, rather than risking a parse
+/// error on a half-written line.
+fn spawn_follow_thread(tx: mpsc::Sender<PlayEvent>, path: PathBuf, start_offset: u64) {
+    std::thread::spawn(move || {
+        let mut offset = start_offset;
+        loop {
+            std::thread::sleep(Duration::from_millis(FOLLOW_POLL_INTERVAL_MS));
+
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            if metadata.len() <= offset {
+                continue;
+            }
+
+            let Ok(mut file) = std::fs::File::open(&path) else {
+                continue;
+            };
+            if file.seek(io::SeekFrom::Start(offset)).is_err() {
+                continue;
+            }
+            let mut buf = String::new();
+            if file.read_to_string(&mut buf).is_err() {
+                continue;
+            }
+
+            let Some(last_newline) = buf.rfind('\n') else {
+                continue;
+            };
+            let complete = &buf[..=last_newline];
+            let entries: Vec<Entry> = complete
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .filter_map(|line| spool_format::parse_line(line).ok())
+                .collect();
+            offset += complete.len() as u64;
+
+            if !entries.is_empty() && tx.send(PlayEvent::FileAppended(entries)).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+fn run_loop(
+    terminal: &mut ratatui::Terminal<ratatui::prelude::CrosstermBackend<io::Stdout>>,
+    app: &mut PlayApp,
+    rx: &mpsc::Receiver<PlayEvent>,
+) -> Result<()> {
+    loop {
+        app.tick();
+
+        terminal.draw(|f| draw(f, app))?;
+
+        match rx.recv_timeout(Duration::from_millis(50)) {
+            Ok(PlayEvent::Key(key)) => {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                if app.searching {
+                    match key.code {
+                        KeyCode::Esc => app.exit_search(),
+                        KeyCode::Enter => app.commit_search(),
+                        KeyCode::Backspace => app.search_pop_char(),
+                        KeyCode::Char(c) => app.search_push_char(c),
+                        _ => {}
+                    }
+                } else if app.jump_search.is_some() {
+                    match key.code {
+                        KeyCode::Esc => app.exit_jump_search(),
+                        KeyCode::Enter => app.jump_to_selected(),
+                        KeyCode::Backspace => app.jump_search_pop_char(),
+                        KeyCode::Up => app.jump_search_move(-1),
+                        KeyCode::Down => app.jump_search_move(1),
+                        KeyCode::Char(c) => app.jump_search_push_char(c),
+                        _ => {}
+                    }
+                } else if app.selection.is_some() {
+                    match key.code {
+                        KeyCode::Esc => app.exit_selection(),
+                        KeyCode::Char('q') => app.should_quit = true,
+                        KeyCode::Char('h') | KeyCode::Left => app.selection_move(0, -1),
+                        KeyCode::Char('l') | KeyCode::Right => app.selection_move(0, 1),
+                        KeyCode::Char('j') | KeyCode::Down => app.selection_move(1, 0),
+                        KeyCode::Char('k') | KeyCode::Up => app.selection_move(-1, 0),
+                        KeyCode::Char('y') => app.yank_selection(),
+                        _ => {}
+                    }
+                } else if app.focused_entry.is_some() {
+                    match key.code {
+                        KeyCode::Esc => app.exit_focus(),
+                        KeyCode::Char('q') => app.should_quit = true,
+                        KeyCode::Up | KeyCode::Char('k') => app.focused_scroll_up(1),
+                        KeyCode::Down | KeyCode::Char('j') => app.focused_scroll_down(1),
+                        KeyCode::PageUp => app.focused_scroll_up(10),
+                        KeyCode::PageDown => app.focused_scroll_down(10),
+                        KeyCode::Home | KeyCode::Char('g') => app.focused_scroll_offset = 0,
+                        _ => {}
+                    }
+                } else {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => {
+                            app.should_quit = true;
+                        }
+                        KeyCode::Char(' ') => app.toggle_play(),
+                        KeyCode::Right | KeyCode::Char('l') => app.step_forward(),
+                        KeyCode::Left | KeyCode::Char('h') => app.step_backward(),
+                        KeyCode::Char('+') | KeyCode::Char('=') => app.speed_up(),
+                        KeyCode::Char('-') | KeyCode::Char('_') => app.speed_down(),
+                        KeyCode::Home | KeyCode::Char('g') => app.jump_to_start(),
+                        KeyCode::End | KeyCode::Char('G') => app.jump_to_end(),
+                        KeyCode::PageUp | KeyCode::Char('k') => app.scroll_up(10),
+                        KeyCode::PageDown | KeyCode::Char('j') => app.scroll_down(10),
+                        KeyCode::Char('[') => app.mark_trim_start(),
+                        KeyCode::Char(']') => app.mark_trim_end(),
+                        KeyCode::Char('x') => app.export_trimmed(),
+                        KeyCode::Char('/') => app.enter_search(),
+                        KeyCode::Char('n') => app.jump_to_next_match(),
+                        KeyCode::Char('N') => app.jump_to_prev_match(),
+                        KeyCode::Enter => app.enter_focus(),
+                        KeyCode::Char('b') => app.toggle_bell(),
+                        KeyCode::Char('v') => app.enter_selection(),
+                        KeyCode::Char('f') => app.enter_jump_search(),
+                        KeyCode::Char('r') => app.toggle_loop_region(),
+                        _ => {}
+                    }
+                }
+            }
+            Ok(PlayEvent::Resize(_, _)) => {
+                // ratatui measures the real terminal size on every draw;
+                // nothing to do but let the next frame pick it up.
+            }
+            Ok(PlayEvent::Tick) => {}
+            Ok(PlayEvent::EntriesLoaded(batch)) => app.ingest_batch(batch),
+            Ok(PlayEvent::LoadComplete) => app.mark_load_complete(),
+            Ok(PlayEvent::LoadFailed(err)) => app.mark_load_failed(err),
+            Ok(PlayEvent::FileAppended(batch)) => app.ingest_follow_batch(batch),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        if app.should_quit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Main draw function.
+fn draw(f: &mut Frame, app: &mut PlayApp) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Title bar
+            Constraint::Min(1),    // Entry content
+            Constraint::Length(1), // Progress bar
+            Constraint::Length(1), // Controls
+        ])
+        .split(f.area());
+
+    draw_title_bar(f, chunks[0], app);
+    if app.focused_entry.is_some() {
+        draw_focused_entry(f, chunks[1], app);
+    } else {
+        draw_entries(f, chunks[1], app);
+    }
+    draw_progress_bar(f, chunks[2], app);
+    draw_controls(f, chunks[3], app);
+
+    if app.jump_search.is_some() {
+        draw_jump_overlay(f, f.area(), app);
+    }
+}
+
+/// Fuzzy-jump overlay, modeled on `tui/library.rs`'s session search overlay:
+/// a centered box with the live query in the title and the ranked results
+/// as a selectable list below it.
+fn draw_jump_overlay(f: &mut Frame, area: Rect, app: &PlayApp) {
+    let Some(jump) = app.jump_search.as_ref() else {
+        return;
+    };
+
+    let popup = centered_rect(70, 60, area);
+    let title = format!(" Jump to: {} ", jump.query);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black).fg(Color::White));
+
+    if jump.results.is_empty() {
+        let paragraph = Paragraph::new("No matches.")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_widget(paragraph, popup);
+        return;
+    }
+
+    let items: Vec<ListItem> = jump
+        .results
+        .iter()
+        .map(|r| ListItem::new(Line::from(r.label.clone())))
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = ListState::default();
+    state.select(Some(jump.selected));
+    f.render_stateful_widget(list, popup, &mut state);
 }
 
 fn draw_title_bar(f: &mut Frame, area: Rect, app: &PlayApp) {
@@ -532,6 +1748,11 @@ fn draw_title_bar(f: &mut Frame, area: Rect, app: &PlayApp) {
         ),
         Span::raw("  "),
         Span::styled(speed_label, Style::default().fg(Color::Cyan)),
+        Span::raw(if app.follow_mode { "  " } else { "" }),
+        Span::styled(
+            if app.follow_mode { "LIVE" } else { "" },
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
     ]);
 
     f.render_widget(Paragraph::new(title), area);
@@ -542,7 +1763,9 @@ fn draw_entries(f: &mut Frame, area: Rect, app: &mut PlayApp) {
     let inner = block.inner(area);
 
     if app.visible_count == 0 {
-        let msg = if app.playing {
+        let msg = if !app.done_loading {
+            "Loading session..."
+        } else if app.playing {
             "Starting playback..."
         } else {
             "Press Space to start playback"
@@ -556,12 +1779,26 @@ fn draw_entries(f: &mut Frame, area: Rect, app: &mut PlayApp) {
 
     // Build lines for all visible entries
     let mut lines: Vec<Line> = Vec::new();
+    let mut match_scroll_target: Option<usize> = None;
 
     for ti in 0..app.visible_count {
+        if Some(ti) == app.pending_scroll_to_timeline_index {
+            match_scroll_target = Some(lines.len());
+        }
         let te = &app.timeline[ti];
         let entry = &app.spool_file.entries[te.entry_index];
 
-        render_entry_lines(entry, &mut lines, inner.width as usize);
+        render_entry_lines(
+            entry,
+            &mut lines,
+            inner.width as usize,
+            app.search_regex.as_ref().map(|s| &s.regex),
+        );
+    }
+
+    if let Some(target) = match_scroll_target {
+        app.scroll_offset = target;
+        app.pending_scroll_to_timeline_index = None;
     }
 
     // Clamp scroll offset
@@ -573,6 +1810,15 @@ fn draw_entries(f: &mut Frame, area: Rect, app: &mut PlayApp) {
         app.scroll_offset = max_scroll;
     }
 
+    app.rendered_plain_lines = lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>())
+        .collect();
+
+    if let Some(selection) = app.selection.as_ref() {
+        highlight_selection(&mut lines, selection);
+    }
+
     let paragraph = Paragraph::new(lines)
         .block(block)
         .wrap(Wrap { trim: false })
@@ -581,15 +1827,48 @@ fn draw_entries(f: &mut Frame, area: Rect, app: &mut PlayApp) {
     f.render_widget(paragraph, area);
 }
 
-fn draw_progress_bar(f: &mut Frame, area: Rect, app: &PlayApp) {
-    let ratio = app.progress_ratio();
-    let label = app.progress_label();
+/// Full-screen, untruncated view of `app.focused_entry`, with its own
+/// scroll offset (`focused_scroll_offset`) independent of the transcript's
+/// - see [`render_full_screen_lines`].
+fn draw_focused_entry(f: &mut Frame, area: Rect, app: &mut PlayApp) {
+    let block = Block::default().borders(Borders::NONE);
+    let inner = block.inner(area);
 
-    let gauge = Gauge::default()
-        .gauge_style(
-            Style::default()
-                .fg(Color::Cyan)
-                .bg(Color::DarkGray)
+    let Some(ti) = app.focused_entry else {
+        return;
+    };
+    let Some(te) = app.timeline.get(ti) else {
+        app.exit_focus();
+        return;
+    };
+    let entry = &app.spool_file.entries[te.entry_index];
+
+    let lines = render_full_screen_lines(entry, inner.width as usize);
+
+    let total_lines = lines.len();
+    let view_height = inner.height as usize;
+    let max_scroll = total_lines.saturating_sub(view_height);
+    if app.focused_scroll_offset > max_scroll {
+        app.focused_scroll_offset = max_scroll;
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.focused_scroll_offset as u16, 0));
+
+    f.render_widget(paragraph, area);
+}
+
+fn draw_progress_bar(f: &mut Frame, area: Rect, app: &PlayApp) {
+    let ratio = app.progress_ratio();
+    let label = app.progress_label();
+
+    let gauge = Gauge::default()
+        .gauge_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .bg(Color::DarkGray)
                 .add_modifier(Modifier::BOLD),
         )
         .ratio(ratio)
@@ -611,12 +1890,14 @@ fn draw_controls(f: &mut Frame, area: Rect, app: &PlayApp) {
         (Some(start), Some(end)) => {
             if start < end {
                 let (kept, duration) = app.trim_preview(start, end);
+                let loop_suffix = if app.loop_enabled { " [LOOP]" } else { "" };
                 format!(
-                    "Trim: {}-{} ({} entries, {})",
+                    "Trim: {}-{} ({} entries, {}){}",
                     format_duration_ms(start),
                     format_duration_ms(end),
                     kept,
-                    format_duration_ms(duration)
+                    format_duration_ms(duration),
+                    loop_suffix
                 )
             } else {
                 format!(
@@ -628,22 +1909,52 @@ fn draw_controls(f: &mut Frame, area: Rect, app: &PlayApp) {
         }
     };
 
-    let mut text = format!(
-        " {}  h/l:step  +/-:speed  j/k:scroll  g/G:start/end  [:start  ]:end  x:export  q:quit  {}",
-        play_key, trim_label
-    );
+    let mut text = if app.jump_search.is_some() {
+        " Esc:cancel  Up/Down:move  Enter:jump".to_string()
+    } else if app.selection.is_some() {
+        " Esc:cancel  h/j/k/l:move  y:yank  q:quit".to_string()
+    } else if app.focused_entry.is_some() {
+        " Esc:back  j/k:scroll  PgUp/PgDn:page  g:top  q:quit".to_string()
+    } else {
+        format!(
+            " {}  h/l:step  +/-:speed  j/k:scroll  g/G:start/end  [:start  ]:end  r:loop  x:export  /:search  f:jump  Enter:expand  b:bell  v:select  q:quit  {}",
+            play_key, trim_label
+        )
+    };
+    if app.searching {
+        text.push_str("  |  /");
+        text.push_str(app.active_search.as_deref().unwrap_or(""));
+    } else if let Some(ref query) = app.active_search {
+        let position = app
+            .search_match_index
+            .map(|i| format!("{}/{}", i + 1, app.search_matches.len()))
+            .unwrap_or_else(|| format!("0/{}", app.search_matches.len()));
+        text.push_str(&format!("  |  /{} [{}] n:next N:prev", query, position));
+    }
     if let Some(ref status) = app.status_message {
         text.push_str("  |  ");
         text.push_str(status);
     }
+    if let Some(ref flash) = app.error_flash {
+        text.push_str("  |  ");
+        text.push_str(flash);
+    }
 
-    let paragraph =
-        Paragraph::new(text).style(Style::default().fg(Color::DarkGray).bg(Color::Black));
+    let style = if app.error_flash.is_some() {
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray).bg(Color::Black)
+    };
+
+    let paragraph = Paragraph::new(text).style(style);
     f.render_widget(paragraph, area);
 }
 
 /// Render a single entry into styled lines.
-fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
+fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize, regex: Option<&Regex>) {
     match entry {
         Entry::Session(s) => {
             lines.push(Line::from(Span::styled(
@@ -683,28 +1994,27 @@ fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
             )));
             for line in p.content.lines() {
                 let truncated = truncate_str(line, width.saturating_sub(2));
-                lines.push(Line::from(Span::styled(
-                    format!("  {}", truncated),
-                    Style::default().fg(Color::Green),
-                )));
+                let mut spans = vec![Span::raw("  ")];
+                spans.extend(highlight_spans(truncated, Style::default().fg(Color::Green), regex));
+                lines.push(Line::from(spans));
             }
             lines.push(Line::from(""));
         }
         Entry::Thinking(t) => {
             let collapsed = t.content.replace('\n', " ");
             let preview = truncate_str(&collapsed, 80);
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "THINKING ",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::DIM),
-                ),
-                Span::styled(
-                    preview.to_string(),
-                    Style::default().add_modifier(Modifier::DIM),
-                ),
-            ]));
+            let mut spans = vec![Span::styled(
+                "THINKING ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::DIM),
+            )];
+            spans.extend(highlight_spans(
+                preview,
+                Style::default().add_modifier(Modifier::DIM),
+                regex,
+            ));
+            lines.push(Line::from(spans));
         }
         Entry::ToolCall(tc) => {
             let tool_display = if tc.tool == "Task" {
@@ -718,21 +2028,23 @@ fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
                 tc.tool.clone()
             };
 
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "TOOL ",
-                    Style::default()
-                        .fg(Color::Blue)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(tool_display, Style::default().fg(Color::Blue)),
-            ]));
+            let mut tool_line = vec![Span::styled(
+                "TOOL ",
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            tool_line.extend(highlight_spans(&tool_display, Style::default().fg(Color::Blue), regex));
+            lines.push(Line::from(tool_line));
 
             let input_preview = format_tool_input(&tc.input, width.saturating_sub(4));
-            lines.push(Line::from(Span::styled(
-                format!("  {}", input_preview),
+            let mut input_line = vec![Span::raw("  ")];
+            input_line.extend(highlight_spans(
+                &input_preview,
                 Style::default().fg(Color::DarkGray),
-            )));
+                regex,
+            ));
+            lines.push(Line::from(input_line));
         }
         Entry::ToolResult(tr) => {
             let status = if tr.error.is_some() {
@@ -747,28 +2059,46 @@ fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
 
             if let Some(ref err) = tr.error {
                 let truncated = truncate_str(err, width.saturating_sub(4));
-                lines.push(Line::from(Span::styled(
-                    format!("  {}", truncated),
-                    Style::default().fg(Color::Red),
-                )));
+                let mut spans = vec![Span::raw("  ")];
+                spans.extend(highlight_spans(truncated, Style::default().fg(Color::Red), regex));
+                lines.push(Line::from(spans));
             } else if let Some(ref output) = tr.output {
                 let text = match output {
                     ToolOutput::Text(t) => t.as_str(),
                     ToolOutput::Binary(_) => "<binary>",
                 };
-                for line in text.lines().take(5) {
-                    let truncated = truncate_str(line, width.saturating_sub(4));
-                    lines.push(Line::from(Span::styled(
-                        format!("  {}", truncated),
-                        Style::default().fg(Color::DarkGray),
-                    )));
-                }
-                let line_count = text.lines().count();
-                if line_count > 5 {
-                    lines.push(Line::from(Span::styled(
-                        format!("  ... ({} more lines)", line_count - 5),
-                        Style::default().fg(Color::DarkGray),
-                    )));
+                if text.contains('\x1b') {
+                    // Recorded shell/terminal output (cargo, git diff, ...)
+                    // carries its own SGR coloring - decode it instead of
+                    // dumping a flat gray block of escape-laden garbage.
+                    let ansi_lines = ansi::parse_ansi_lines(text);
+                    let total = ansi_lines.len();
+                    for ansi_line in ansi_lines.into_iter().take(5) {
+                        let (truncated, _) = ansi::truncate_spans(ansi_line, width.saturating_sub(4));
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(truncated.into_iter().map(|(style, s)| Span::styled(s, style)));
+                        lines.push(Line::from(spans));
+                    }
+                    if total > 5 {
+                        lines.push(Line::from(Span::styled(
+                            format!("  ... ({} more lines)", total - 5),
+                            Style::default().fg(Color::DarkGray),
+                        )));
+                    }
+                } else {
+                    for line in text.lines().take(5) {
+                        let truncated = truncate_str(line, width.saturating_sub(4));
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(highlight_spans(truncated, Style::default().fg(Color::DarkGray), regex));
+                        lines.push(Line::from(spans));
+                    }
+                    let line_count = text.lines().count();
+                    if line_count > 5 {
+                        lines.push(Line::from(Span::styled(
+                            format!("  ... ({} more lines)", line_count - 5),
+                            Style::default().fg(Color::DarkGray),
+                        )));
+                    }
                 }
             }
             lines.push(Line::from(""));
@@ -851,10 +2181,196 @@ fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
                 Style::default().fg(Color::Red).add_modifier(Modifier::DIM),
             )));
         }
+        Entry::Terminal(t) => {
+            lines.push(Line::from(Span::styled(
+                "TERMINAL",
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            let text = String::from_utf8_lossy(&t.decoded_bytes()).into_owned();
+            for line in text.lines().take(5) {
+                let truncated = truncate_str(line, width.saturating_sub(4));
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", truncated),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            let line_count = text.lines().count();
+            if line_count > 5 {
+                lines.push(Line::from(Span::styled(
+                    format!("  ... ({} more lines)", line_count - 5),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
         Entry::Unknown => {}
     }
 }
 
+/// Render a single entry's complete content for the full-screen detail
+/// view (`draw_focused_entry`), with none of `render_entry_lines`'
+/// truncation: thinking isn't collapsed to one line, tool input is
+/// pretty-printed JSON in full, and a `ToolResult`'s body/error isn't
+/// capped. Word wrapping is left to the `Paragraph`'s own `Wrap`, so lines
+/// here aren't pre-truncated to `width` either - it's only used by the
+/// less central entry kinds that fall back to `render_entry_lines`.
+fn render_full_screen_lines(entry: &Entry, width: usize) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = Vec::new();
+    match entry {
+        Entry::Prompt(p) => {
+            lines.push(Line::from(Span::styled(
+                "USER",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for line in p.content.lines() {
+                lines.push(Line::from(format!("  {}", line)));
+            }
+        }
+        Entry::Thinking(t) => {
+            lines.push(Line::from(Span::styled(
+                "THINKING",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for line in t.content.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().add_modifier(Modifier::DIM),
+                )));
+            }
+        }
+        Entry::ToolCall(tc) => {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "TOOL ",
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(tc.tool.clone(), Style::default().fg(Color::Blue)),
+            ]));
+            lines.push(Line::from(""));
+            let pretty =
+                serde_json::to_string_pretty(&tc.input).unwrap_or_else(|_| tc.input.to_string());
+            for line in pretty.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        Entry::ToolResult(tr) => {
+            let status = if tr.error.is_some() {
+                Span::styled("[ERROR]", Style::default().fg(Color::Red))
+            } else {
+                Span::styled("[OK]", Style::default().fg(Color::Green))
+            };
+            lines.push(Line::from(vec![
+                Span::styled("RESULT ", Style::default().fg(Color::Blue)),
+                status,
+            ]));
+            lines.push(Line::from(""));
+
+            if let Some(ref err) = tr.error {
+                for line in err.lines() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", line),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+            } else if let Some(ref output) = tr.output {
+                let text = match output {
+                    ToolOutput::Text(t) => t.as_str(),
+                    ToolOutput::Binary(_) => "<binary>",
+                };
+                if text.contains('\x1b') {
+                    for ansi_line in ansi::parse_ansi_lines(text) {
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(ansi_line.into_iter().map(|(style, s)| Span::styled(s, style)));
+                        lines.push(Line::from(spans));
+                    }
+                } else {
+                    for line in text.lines() {
+                        lines.push(Line::from(format!("  {}", line)));
+                    }
+                }
+            }
+        }
+        Entry::Response(r) => {
+            lines.push(Line::from(Span::styled(
+                "ASSISTANT",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for line in r.content.lines() {
+                lines.push(Line::from(format!("  {}", line)));
+            }
+            if let Some(ref model) = r.model {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("  [{}]", model),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        Entry::Error(e) => {
+            lines.push(Line::from(Span::styled(
+                format!("ERROR [{}]", e.code),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            for line in e.message.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+        }
+        other => render_entry_lines(other, &mut lines, width, None),
+    }
+    lines
+}
+
+/// Split `text` around the first case-insensitive occurrence of `query`,
+/// rendering every match with a reversed (black-on-yellow) highlight style
+/// and everything else with `base_style` - the search match highlight used
+/// by [`render_entry_lines`]. `regex` is re-run directly against this
+/// already-truncated display line (rather than reusing the byte ranges
+/// `PlayApp::search_match_ranges` computed against the capped joined
+/// entry text) since only a handful of lines are ever on screen at once -
+/// the cap in `entry_search_text` is what keeps the expensive per-entry,
+/// per-keystroke scan cheap; this is neither. Falls back to a single
+/// unstyled span when there's no active search or no match in this line.
+fn highlight_spans(text: &str, base_style: Style, regex: Option<&Regex>) -> Vec<Span<'static>> {
+    let Some(regex) = regex else {
+        return vec![Span::styled(text.to_string(), base_style)];
+    };
+
+    let highlight_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for m in regex.find_iter(text) {
+        if m.start() > last {
+            spans.push(Span::styled(text[last..m.start()].to_string(), base_style));
+        }
+        spans.push(Span::styled(text[m.start()..m.end()].to_string(), highlight_style));
+        last = m.end();
+    }
+
+    if spans.is_empty() {
+        return vec![Span::styled(text.to_string(), base_style)];
+    }
+    if last < text.len() {
+        spans.push(Span::styled(text[last..].to_string(), base_style));
+    }
+    spans
+}
+
 /// Truncate a string to fit within `max_len` bytes, respecting char boundaries.
 fn truncate_str(s: &str, max_len: usize) -> &str {
     if s.len() <= max_len {
@@ -942,10 +2458,15 @@ mod tests {
             entry_count: Some(5),
             tools_used: None,
             files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
             first_prompt: None,
             schema_url: None,
             trimmed: None,
             ended: None,
+            content_hash: None,
             extra: HashMap::new(),
         })
     }
@@ -974,6 +2495,25 @@ mod tests {
         })
     }
 
+    fn make_tool_result(ts: u64, error: Option<&str>) -> Entry {
+        Entry::ToolResult(spool_format::ToolResultEntry {
+            id: Uuid::new_v4(),
+            ts,
+            call_id: Uuid::new_v4(),
+            output: if error.is_none() {
+                Some(ToolOutput::Text("ok".to_string()))
+            } else {
+                None
+            },
+            error: error.map(|e| e.to_string()),
+            truncated: None,
+            original_bytes: None,
+            subagent_id: None,
+            redacted: None,
+            extra: HashMap::new(),
+        })
+    }
+
     fn make_response(ts: u64, content: &str) -> Entry {
         Entry::Response(ResponseEntry {
             id: Uuid::new_v4(),
@@ -1075,6 +2615,81 @@ mod tests {
         assert_eq!(duration, 1000);
     }
 
+    fn make_transport_test_file() -> SpoolFile {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "a"));
+        file.add_entry(make_response(2000, "b"));
+        file.add_entry(make_prompt(3000, "c"));
+        file
+    }
+
+    #[test]
+    fn test_seek_to_reveals_entries_up_to_bracket() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.seek_to(1500);
+        assert_eq!(app.visible_count, 2);
+        assert_eq!(app.playback_elapsed_ms, 1500);
+    }
+
+    #[test]
+    fn test_seek_to_clamps_past_end() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.seek_to(999_999);
+        assert_eq!(app.visible_count, app.timeline.len());
+        assert_eq!(app.playback_elapsed_ms, app.total_duration_ms);
+    }
+
+    #[test]
+    fn test_step_moves_multiple_entries_forward_and_back() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.step(2);
+        assert_eq!(app.visible_count, 2);
+        app.step(-1);
+        assert_eq!(app.visible_count, 1);
+    }
+
+    #[test]
+    fn test_toggle_loop_region_requires_trim_range() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.toggle_loop_region();
+        assert!(!app.loop_enabled);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Mark a trim region with [ and ] before looping")
+        );
+    }
+
+    #[test]
+    fn test_toggle_loop_region_flips_when_trim_range_set() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.trim_start_ms = Some(1000);
+        app.trim_end_ms = Some(2000);
+        app.toggle_loop_region();
+        assert!(app.loop_enabled);
+        app.toggle_loop_region();
+        assert!(!app.loop_enabled);
+    }
+
+    #[test]
+    fn test_tick_loops_back_to_region_start_when_loop_enabled() {
+        let mut app = PlayApp::new(make_transport_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.trim_start_ms = Some(1000);
+        app.trim_end_ms = Some(2000);
+        app.loop_enabled = true;
+        app.playing = true;
+        app.playback_elapsed_ms = 3000; // already past the loop region's end
+
+        app.tick();
+
+        assert!(app.playing);
+        assert_eq!(app.playback_elapsed_ms, 1000);
+        assert_eq!(app.visible_count, 2);
+    }
+
     #[test]
     fn test_current_entry_timestamp_skips_unknown() {
         let session = match make_session_entry() {
@@ -1104,4 +2719,715 @@ mod tests {
         let second = next_trimmed_path(&source);
         assert_eq!(second, dir.join("session.trimmed-1.spool"));
     }
+
+    fn make_search_test_file() -> SpoolFile {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "find the needle please"));
+        file.add_entry(make_response(2000, "no match here"));
+        file.add_entry(make_prompt(3000, "another Needle appears"));
+        file
+    }
+
+    #[test]
+    fn test_update_search_matches_is_case_insensitive() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("needle".to_string());
+        app.update_search_matches();
+        assert_eq!(app.search_matches, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_update_search_matches_empty_query_clears() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some(String::new());
+        app.update_search_matches();
+        assert!(app.search_matches.is_empty());
+    }
+
+    #[test]
+    fn test_jump_to_next_match_wraps_around() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("needle".to_string());
+        app.update_search_matches();
+
+        app.jump_to_next_match();
+        assert_eq!(app.search_match_index, Some(0));
+        app.jump_to_next_match();
+        assert_eq!(app.search_match_index, Some(1));
+        app.jump_to_next_match();
+        assert_eq!(app.search_match_index, Some(0));
+    }
+
+    #[test]
+    fn test_jump_to_prev_match_wraps_around() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("needle".to_string());
+        app.update_search_matches();
+
+        app.jump_to_prev_match();
+        assert_eq!(app.search_match_index, Some(1));
+        app.jump_to_prev_match();
+        assert_eq!(app.search_match_index, Some(0));
+    }
+
+    #[test]
+    fn test_reveal_match_advances_visible_count() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("needle".to_string());
+        app.update_search_matches();
+
+        app.jump_to_next_match();
+        app.jump_to_next_match();
+        // second match is the 4th timeline entry (session + 3 entries)
+        assert_eq!(app.visible_count, 4);
+        assert_eq!(app.pending_scroll_to_timeline_index, Some(3));
+    }
+
+    #[test]
+    fn test_exit_search_clears_query_and_matches() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_search();
+        app.search_push_char('n');
+        app.exit_search();
+        assert!(app.active_search.is_none());
+        assert!(app.search_matches.is_empty());
+        assert!(!app.searching);
+    }
+
+    #[test]
+    fn test_commit_search_keeps_query_active() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_search();
+        app.search_push_char('n');
+        app.search_push_char('e');
+        app.search_push_char('e');
+        app.search_push_char('d');
+        app.search_push_char('l');
+        app.search_push_char('e');
+        app.commit_search();
+        assert!(!app.searching);
+        assert_eq!(app.active_search.as_deref(), Some("needle"));
+        assert_eq!(app.search_matches, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_highlight_spans_splits_around_match() {
+        let regex = RegexSearch::compile("needle").regex;
+        let spans = highlight_spans("find the needle", Style::default(), Some(&regex));
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].content, "find the ");
+        assert_eq!(spans[1].content, "needle");
+    }
+
+    #[test]
+    fn test_highlight_spans_highlights_every_occurrence() {
+        let regex = RegexSearch::compile("needle").regex;
+        let spans = highlight_spans("needle one, needle two", Style::default(), Some(&regex));
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0].content, "needle");
+        assert_eq!(spans[1].content, " one, ");
+        assert_eq!(spans[2].content, "needle two");
+    }
+
+    #[test]
+    fn test_highlight_spans_no_regex_returns_whole_text() {
+        let spans = highlight_spans("plain text", Style::default(), None);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "plain text");
+    }
+
+    #[test]
+    fn test_regex_search_falls_back_to_literal_on_invalid_pattern() {
+        let search = RegexSearch::compile("[unclosed");
+        assert!(search.is_literal_fallback);
+        assert!(search.regex.is_match("a [unclosed bracket"));
+    }
+
+    #[test]
+    fn test_regex_search_supports_real_patterns() {
+        let search = RegexSearch::compile(r"error:\s+\d+");
+        assert!(!search.is_literal_fallback);
+        assert!(search.regex.is_match("saw error:   42 happen"));
+    }
+
+    #[test]
+    fn test_update_search_matches_supports_alternation() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("find|match".to_string());
+        app.update_search_matches();
+        assert_eq!(app.search_matches, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_update_search_matches_records_byte_ranges() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.active_search = Some("needle".to_string());
+        app.update_search_matches();
+        assert_eq!(app.search_match_ranges.get(&1), Some(&vec![(9, 15)]));
+    }
+
+    #[test]
+    fn test_enter_jump_search_pauses_and_populates_with_empty_query() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.playing = true;
+        app.enter_jump_search();
+        assert!(!app.playing);
+        let jump = app.jump_search.as_ref().unwrap();
+        assert_eq!(jump.query, "");
+        // session + 3 entries, all matched by an empty query
+        assert_eq!(jump.results.len(), 4);
+    }
+
+    #[test]
+    fn test_jump_search_push_char_filters_and_ranks_results() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_jump_search();
+        for c in "needle".chars() {
+            app.jump_search_push_char(c);
+        }
+        let jump = app.jump_search.as_ref().unwrap();
+        let indices: Vec<usize> = jump.results.iter().map(|r| r.timeline_index).collect();
+        assert_eq!(indices, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_jump_search_pop_char_widens_results_again() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_jump_search();
+        app.jump_search_push_char('x');
+        app.jump_search_push_char('x');
+        app.jump_search_pop_char();
+        app.jump_search_pop_char();
+        let jump = app.jump_search.as_ref().unwrap();
+        assert_eq!(jump.query, "");
+        assert_eq!(jump.results.len(), 4);
+    }
+
+    #[test]
+    fn test_jump_search_move_wraps_around() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_jump_search();
+        assert_eq!(app.jump_search.as_ref().unwrap().selected, 0);
+
+        app.jump_search_move(-1);
+        let last = app.jump_search.as_ref().unwrap().results.len() - 1;
+        assert_eq!(app.jump_search.as_ref().unwrap().selected, last);
+
+        app.jump_search_move(1);
+        assert_eq!(app.jump_search.as_ref().unwrap().selected, 0);
+    }
+
+    #[test]
+    fn test_jump_to_selected_reveals_and_closes_overlay() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_jump_search();
+        for c in "needle".chars() {
+            app.jump_search_push_char(c);
+        }
+        app.jump_search_move(1); // select the second ("another Needle") match
+        app.jump_to_selected();
+
+        assert!(app.jump_search.is_none());
+        assert_eq!(app.visible_count, 4);
+        assert_eq!(app.pending_scroll_to_timeline_index, Some(3));
+    }
+
+    #[test]
+    fn test_exit_jump_search_clears_state() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_jump_search();
+        app.exit_jump_search();
+        assert!(app.jump_search.is_none());
+    }
+
+    #[test]
+    fn test_jump_label_formats_prompt_and_tool_entries() {
+        let prompt = make_prompt(1000, "find the needle please");
+        assert_eq!(jump_label(&prompt), "PROMPT: find the needle please");
+    }
+
+    #[test]
+    fn test_new_empty_starts_not_done_loading() {
+        let app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        assert!(!app.done_loading);
+        assert!(app.is_placeholder);
+        assert_eq!(app.timeline.len(), 1); // placeholder session entry only
+    }
+
+    #[test]
+    fn test_ingest_batch_replaces_placeholder_session() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        app.ingest_batch(vec![
+            Entry::Session(session),
+            make_prompt(1000, "hello"),
+        ]);
+
+        assert!(!app.is_placeholder);
+        assert_eq!(app.session_title, "Test");
+        assert_eq!(app.spool_file.entries.len(), 2);
+        assert_eq!(app.timeline.len(), 2);
+        assert!(app.playing);
+    }
+
+    #[test]
+    fn test_ingest_batch_extends_timeline_incrementally() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.ingest_batch(vec![make_session_entry(), make_response(1000, "first")]);
+        app.ingest_batch(vec![make_prompt(31_000, "second")]); // 30s gap, capped before a Prompt
+
+        assert_eq!(app.timeline.len(), 3);
+        assert_eq!(app.timeline[1].playback_ms, 1000);
+        assert_eq!(app.timeline[2].playback_ms, 3000); // 1000 + MAX_IDLE_GAP_MS
+        assert_eq!(app.total_duration_ms, 3000);
+    }
+
+    #[test]
+    fn test_ingest_batch_ignores_empty_batch() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.ingest_batch(Vec::new());
+        assert!(app.is_placeholder);
+        assert_eq!(app.timeline.len(), 1);
+    }
+
+    #[test]
+    fn test_mark_load_complete_flags_empty_session() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.mark_load_complete();
+        assert!(app.done_loading);
+        assert!(app.should_quit);
+        assert_eq!(app.load_error.as_deref(), Some("Session has no entries."));
+    }
+
+    #[test]
+    fn test_mark_load_complete_keeps_playing_when_entries_arrived() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.ingest_batch(vec![make_session_entry(), make_prompt(0, "hello")]);
+        app.mark_load_complete();
+        assert!(app.done_loading);
+        assert!(!app.should_quit);
+        assert!(app.load_error.is_none());
+    }
+
+    #[test]
+    fn test_mark_load_failed_sets_error_and_quits() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.mark_load_failed("disk on fire".to_string());
+        assert!(app.done_loading);
+        assert!(app.should_quit);
+        assert!(app.load_error.unwrap().contains("disk on fire"));
+    }
+
+    #[test]
+    fn test_ingest_follow_batch_advances_visible_count_and_pins_to_bottom() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.follow_mode = true;
+        app.ingest_batch(vec![make_session_entry(), make_prompt(0, "hello")]);
+        app.mark_load_complete();
+
+        app.ingest_follow_batch(vec![make_response(1000, "more")]);
+
+        assert_eq!(app.spool_file.entries.len(), 3);
+        assert_eq!(app.visible_count, app.timeline.len());
+        assert_eq!(app.playback_elapsed_ms, app.total_duration_ms);
+        assert_eq!(app.scroll_offset, usize::MAX);
+    }
+
+    #[test]
+    fn test_ingest_follow_batch_respects_user_scrolled_up() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.follow_mode = true;
+        app.ingest_batch(vec![make_session_entry(), make_prompt(0, "hello")]);
+        app.mark_load_complete();
+        app.scroll_up(5);
+        assert!(app.user_scrolled_up);
+
+        app.ingest_follow_batch(vec![make_response(1000, "more")]);
+
+        // Visible count still advances so the new entry is revealed, but
+        // the scroll position is left alone while the user is reading back.
+        assert_eq!(app.visible_count, app.timeline.len());
+        assert_ne!(app.scroll_offset, usize::MAX);
+    }
+
+    #[test]
+    fn test_ingest_follow_batch_noop_outside_follow_mode() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.ingest_batch(vec![make_session_entry(), make_prompt(0, "hello")]);
+        app.mark_load_complete();
+        app.visible_count = 1;
+
+        app.ingest_follow_batch(vec![make_response(1000, "more")]);
+
+        assert_eq!(app.spool_file.entries.len(), 3);
+        assert_eq!(app.visible_count, 1);
+    }
+
+    #[test]
+    fn test_jump_to_end_clears_user_scrolled_up() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        app.follow_mode = true;
+        app.ingest_batch(vec![make_session_entry(), make_prompt(0, "hello")]);
+        app.scroll_up(5);
+        assert!(app.user_scrolled_up);
+
+        app.jump_to_end();
+        assert!(!app.user_scrolled_up);
+    }
+
+    #[test]
+    fn test_extend_timeline_matches_build_timeline() {
+        let entries = vec![
+            make_session_entry(),
+            make_response(1000, "first response"),
+            make_prompt(31_000, "second prompt"),
+        ];
+        let full = build_timeline(&entries);
+
+        let mut incremental = build_timeline(&entries[..2]);
+        extend_timeline(&mut incremental, &entries, 2);
+
+        assert_eq!(full.len(), incremental.len());
+        for (a, b) in full.iter().zip(incremental.iter()) {
+            assert_eq!(a.entry_index, b.entry_index);
+            assert_eq!(a.playback_ms, b.playback_ms);
+        }
+    }
+
+    #[test]
+    fn test_progress_label_shows_loading_suffix() {
+        let mut app = PlayApp::new_empty(PathBuf::from("session.spool"), 1.0);
+        assert!(app.progress_label().contains("(loading...)"));
+        app.done_loading = true;
+        assert!(!app.progress_label().contains("(loading...)"));
+    }
+
+    #[test]
+    fn test_enter_focus_targets_playback_head() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.visible_count = 2;
+        app.enter_focus();
+        assert_eq!(app.focused_entry, Some(1));
+        assert!(!app.playing);
+    }
+
+    #[test]
+    fn test_enter_focus_noop_before_playback_starts() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_focus();
+        assert_eq!(app.focused_entry, None);
+    }
+
+    #[test]
+    fn test_exit_focus_clears_state() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.visible_count = 2;
+        app.enter_focus();
+        app.focused_scroll_down(5);
+        app.exit_focus();
+        assert_eq!(app.focused_entry, None);
+        assert_eq!(app.focused_scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_focused_scroll_up_saturates_at_zero() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.focused_scroll_up(5);
+        assert_eq!(app.focused_scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_render_full_screen_lines_does_not_truncate_thinking() {
+        let long_thinking = "a".repeat(500);
+        let entry = make_thinking(0, &long_thinking);
+        let lines = render_full_screen_lines(&entry, 80);
+        let joined: String = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("");
+        assert!(joined.contains(&long_thinking));
+    }
+
+    #[test]
+    fn test_render_full_screen_lines_pretty_prints_tool_input() {
+        let entry = Entry::ToolCall(spool_format::ToolCallEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            tool: "Read".to_string(),
+            input: serde_json::json!({"path": "src/main.rs"}),
+            subagent_id: None,
+            extra: HashMap::new(),
+        });
+        let lines = render_full_screen_lines(&entry, 80);
+        let joined: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(joined.contains("\"path\""));
+        assert!(joined.contains("src/main.rs"));
+    }
+
+    #[test]
+    fn test_render_entry_lines_decodes_ansi_tool_output() {
+        let entry = make_tool_result(0, None);
+        let entry = match entry {
+            Entry::ToolResult(mut tr) => {
+                tr.output = Some(ToolOutput::Text("\x1b[31mred\x1b[0m plain".to_string()));
+                Entry::ToolResult(tr)
+            }
+            other => other,
+        };
+        let mut lines = Vec::new();
+        render_entry_lines(&entry, &mut lines, 80, None);
+        let joined: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(joined.contains("red plain"));
+        assert!(!joined.contains('\x1b'));
+
+        let red_span = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.as_ref() == "red")
+            .expect("red span present");
+        assert_eq!(red_span.style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_render_entry_lines_strips_non_sgr_escapes_from_tool_output() {
+        let entry = make_tool_result(0, None);
+        let entry = match entry {
+            Entry::ToolResult(mut tr) => {
+                tr.output = Some(ToolOutput::Text("a\x1b[2Ab".to_string()));
+                Entry::ToolResult(tr)
+            }
+            other => other,
+        };
+        let mut lines = Vec::new();
+        render_entry_lines(&entry, &mut lines, 80, None);
+        let joined: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(joined.contains("ab"));
+        assert!(!joined.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_render_full_screen_lines_decodes_ansi_tool_output() {
+        let entry = make_tool_result(0, None);
+        let entry = match entry {
+            Entry::ToolResult(mut tr) => {
+                tr.output = Some(ToolOutput::Text("\x1b[32mgreen\x1b[0m".to_string()));
+                Entry::ToolResult(tr)
+            }
+            other => other,
+        };
+        let lines = render_full_screen_lines(&entry, 80);
+        let green_span = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.as_ref() == "green")
+            .expect("green span present");
+        assert_eq!(green_span.style.fg, Some(Color::Green));
+    }
+
+    fn make_error_test_file() -> SpoolFile {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_tool_result(1000, None));
+        file.add_entry(make_tool_result(2000, Some("boom")));
+        file.add_entry(make_tool_result(3000, None));
+        file
+    }
+
+    #[test]
+    fn test_step_forward_flags_error_once() {
+        let mut app = PlayApp::new(make_error_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.step_forward(); // session
+        assert_eq!(app.error_count, 0);
+        app.step_forward(); // ok result
+        assert_eq!(app.error_count, 0);
+        app.step_forward(); // error result
+        assert_eq!(app.error_count, 1);
+        assert!(app.error_flash.is_some());
+        app.step_forward(); // ok result
+        assert_eq!(app.error_count, 1);
+    }
+
+    #[test]
+    fn test_tick_flags_every_error_revealed_in_one_jump() {
+        let mut app = PlayApp::new(make_error_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.playing = true;
+        app.playback_elapsed_ms = app.total_duration_ms;
+        app.tick();
+        assert_eq!(app.visible_count, app.timeline.len());
+        assert_eq!(app.error_count, 1);
+    }
+
+    #[test]
+    fn test_toggle_bell_flips_state_and_sets_status() {
+        let mut app = PlayApp::new(make_error_test_file(), PathBuf::from("session.spool"), 1.0);
+        assert!(app.bell_enabled);
+        app.toggle_bell();
+        assert!(!app.bell_enabled);
+        assert_eq!(app.status_message.as_deref(), Some("Bell off"));
+        app.toggle_bell();
+        assert!(app.bell_enabled);
+        assert_eq!(app.status_message.as_deref(), Some("Bell on"));
+    }
+
+    #[test]
+    fn test_error_flash_clears_after_ticks_elapse() {
+        let mut app = PlayApp::new(make_error_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.step_forward();
+        app.step_forward();
+        app.step_forward(); // reveals the error result
+        assert!(app.error_flash.is_some());
+
+        for _ in 0..ERROR_FLASH_TICKS {
+            app.tick();
+        }
+        assert!(app.error_flash.is_none());
+    }
+
+    #[test]
+    fn test_entry_has_error_distinguishes_ok_and_error_results() {
+        let ok = make_tool_result(0, None);
+        let err = make_tool_result(0, Some("nope"));
+        assert!(!entry_has_error(&ok));
+        assert!(entry_has_error(&err));
+    }
+
+    #[test]
+    fn test_enter_selection_anchors_at_scroll_offset() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.rendered_plain_lines = vec!["one".to_string(), "two".to_string(), "three".to_string()];
+        app.scroll_offset = 1;
+        app.enter_selection();
+        let selection = app.selection.as_ref().unwrap();
+        assert_eq!(selection.anchor, (1, 0));
+        assert_eq!(selection.cursor, (1, 0));
+        assert!(!app.playing);
+    }
+
+    #[test]
+    fn test_enter_selection_noop_with_nothing_rendered() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.enter_selection();
+        assert!(app.selection.is_none());
+    }
+
+    #[test]
+    fn test_exit_selection_clears_state() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.rendered_plain_lines = vec!["one".to_string()];
+        app.enter_selection();
+        app.exit_selection();
+        assert!(app.selection.is_none());
+    }
+
+    #[test]
+    fn test_selection_move_clamps_to_buffer_bounds() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.rendered_plain_lines = vec!["abc".to_string(), "de".to_string()];
+        app.enter_selection();
+
+        app.selection_move(-5, 0);
+        assert_eq!(app.selection.as_ref().unwrap().cursor, (0, 0));
+
+        app.selection_move(5, 0);
+        assert_eq!(app.selection.as_ref().unwrap().cursor.0, 1);
+
+        app.selection_move(0, 50);
+        assert_eq!(app.selection.as_ref().unwrap().cursor, (1, 1));
+    }
+
+    #[test]
+    fn test_selection_move_follows_row_into_shorter_line() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.rendered_plain_lines = vec!["abcdef".to_string(), "x".to_string()];
+        app.enter_selection();
+        app.selection_move(0, 4);
+        assert_eq!(app.selection.as_ref().unwrap().cursor, (0, 4));
+
+        app.selection_move(1, 0);
+        assert_eq!(app.selection.as_ref().unwrap().cursor, (1, 0));
+    }
+
+    #[test]
+    fn test_reconstruct_selection_text_single_line_range() {
+        let lines = vec!["hello world".to_string()];
+        let selection = Selection {
+            anchor: (0, 0),
+            cursor: (0, 4),
+        };
+        assert_eq!(reconstruct_selection_text(&lines, &selection), "hello");
+    }
+
+    #[test]
+    fn test_reconstruct_selection_text_spans_multiple_lines() {
+        let lines = vec!["abcdef".to_string(), "ghijkl".to_string(), "mnopqr".to_string()];
+        let selection = Selection {
+            anchor: (0, 3),
+            cursor: (2, 1),
+        };
+        assert_eq!(reconstruct_selection_text(&lines, &selection), "def\nghijkl\nmn");
+    }
+
+    #[test]
+    fn test_reconstruct_selection_text_normalizes_reversed_anchor_cursor() {
+        let lines = vec!["abcdef".to_string(), "ghijkl".to_string()];
+        let selection = Selection {
+            anchor: (1, 2),
+            cursor: (0, 1),
+        };
+        assert_eq!(reconstruct_selection_text(&lines, &selection), "bcdef\nghi");
+    }
+
+    #[test]
+    fn test_yank_selection_clears_selection_and_sets_status() {
+        let mut app = PlayApp::new(make_search_test_file(), PathBuf::from("session.spool"), 1.0);
+        app.rendered_plain_lines = vec!["hello".to_string()];
+        app.enter_selection();
+        app.selection_move(0, 4);
+        app.yank_selection();
+        assert!(app.selection.is_none());
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_highlight_selection_reverses_covered_span() {
+        let mut lines = vec![Line::from("hello world".to_string())];
+        let selection = Selection {
+            anchor: (0, 0),
+            cursor: (0, 4),
+        };
+        highlight_selection(&mut lines, &selection);
+        let plain: String = lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(plain, "hello world");
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+    }
 }
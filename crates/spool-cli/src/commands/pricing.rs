@@ -0,0 +1,55 @@
+//! Optional per-model price table for estimating the cost of a recorded
+//! session from its aggregated token usage.
+//!
+//! There's no API to fetch current model pricing, so the table is
+//! user-supplied: a JSON file at `<config dir>/spool/prices.json` (or
+//! wherever `SPOOL_PRICE_TABLE` points), mapping model name to cents per
+//! 1,000 input/output tokens. Missing or malformed config means no prices
+//! are known, not an error - cost estimates are simply omitted.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Env var overriding the price table's location, e.g. for sharing one
+/// price file across a team instead of relying on each user's config dir.
+const PRICE_TABLE_ENV_VAR: &str = "SPOOL_PRICE_TABLE";
+
+/// Price for a single model, in cents per 1,000 tokens.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ModelPrice {
+    pub input_cents_per_1k: f64,
+    pub output_cents_per_1k: f64,
+}
+
+/// A loaded price table, keyed by model name.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PriceTable(HashMap<String, ModelPrice>);
+
+impl PriceTable {
+    /// Load the price table from `SPOOL_PRICE_TABLE` if set, otherwise
+    /// from `<config dir>/spool/prices.json`. Returns an empty table (no
+    /// prices known for any model) if neither exists or parses.
+    pub fn load() -> Self {
+        let path = match std::env::var(PRICE_TABLE_ENV_VAR) {
+            Ok(path) => Some(std::path::PathBuf::from(path)),
+            Err(_) => dirs::config_dir().map(|dir| dir.join("spool").join("prices.json")),
+        };
+        let Some(path) = path else {
+            return Self::default();
+        };
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&json).unwrap_or_default()
+    }
+
+    /// Estimate the cost of `input_tokens` + `output_tokens` for `model`,
+    /// in cents. Returns `None` if no price is known for that model.
+    pub fn estimate_cents(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+        let price = self.0.get(model)?;
+        Some(
+            price.input_cents_per_1k * (input_tokens as f64 / 1000.0)
+                + price.output_cents_per_1k * (output_tokens as f64 / 1000.0),
+        )
+    }
+}
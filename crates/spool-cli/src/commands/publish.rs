@@ -1,19 +1,136 @@
 //! Publish command - Upload a session to unspool.dev.
+//!
+//! Publishing is gated on redaction: a session with secrets detected by
+//! [`SecretDetector`] is refused unless `--redact` is passed (which redacts
+//! it in memory before upload, the same way `spool export --redact` does)
+//! or `--force` (which uploads as-is, for callers who already redacted
+//! out-of-band). This mirrors `export`'s `--redact`/`--dry-run` flags
+//! rather than inventing a new flag vocabulary for the same problem.
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use spool_format::SecretDetector;
 use std::path::Path;
+use std::time::Duration;
 
-pub fn run(path: &Path, public: bool) -> Result<()> {
-    println!("📤 Publish command");
+use super::agent::load_spool_or_log;
+use super::detect::detect_secrets;
+
+/// Env var overriding the unspool.dev API base, e.g. for pointing at a
+/// staging instance.
+const API_BASE_ENV_VAR: &str = "SPOOL_PUBLISH_URL";
+const DEFAULT_API_BASE: &str = "https://unspool.dev";
+
+/// Env var holding the unspool.dev API token used to authenticate the
+/// upload. There is no interactive login flow yet; users generate a token
+/// on unspool.dev and export it into their shell.
+const API_TOKEN_ENV_VAR: &str = "SPOOL_API_TOKEN";
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn run(path: &Path, public: bool, redact: bool, force: bool) -> Result<()> {
+    println!("Publishing session...");
     println!("   Path: {:?}", path);
-    println!("   Public: {}", public);
-    println!();
-    println!("🌐 unspool.dev publishing coming in Phase 2!");
-    println!();
-    println!("For now, you can:");
-    println!("  1. Export your session: spool export <path> --redact");
-    println!("  2. Share the .spool file directly");
-    println!("  3. Host the viewer yourself (coming soon)");
+
+    let mut file = load_spool_or_log(path)?;
+
+    let detections = detect_secrets(&file);
+    if !detections.is_empty() {
+        if redact {
+            let detector = SecretDetector::with_defaults();
+            let mut redaction_count = 0;
+            for entry in &mut file.entries {
+                redaction_count += redact_entry(entry, &detector);
+            }
+            println!("   Redacted {} secret(s) before upload", redaction_count);
+        } else if !force {
+            bail!(
+                "Refusing to publish: {} secret(s) detected (run `spool detect {:?}` to review). \
+                 Pass --redact to redact them first, or --force to publish anyway.",
+                detections.len(),
+                path
+            );
+        } else {
+            println!(
+                "   Warning: publishing with {} unredacted secret(s) (--force)",
+                detections.len()
+            );
+        }
+    }
+
+    let body = {
+        let mut bytes = Vec::new();
+        file.write_to(&mut bytes)?;
+        bytes
+    };
+
+    let api_base =
+        std::env::var(API_BASE_ENV_VAR).unwrap_or_else(|_| DEFAULT_API_BASE.to_string());
+    let token = std::env::var(API_TOKEN_ENV_VAR).with_context(|| {
+        format!(
+            "{} is not set. Generate an upload token on unspool.dev and export it as {}.",
+            API_TOKEN_ENV_VAR, API_TOKEN_ENV_VAR
+        )
+    })?;
+
+    let url = format!("{}/api/sessions?public={}", api_base, public);
+    println!("   Uploading to: {}", api_base);
+
+    let response = ureq::post(&url)
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("Content-Type", "application/x-ndjson")
+        .timeout(UPLOAD_TIMEOUT)
+        .send_bytes(&body)
+        .context("Failed to upload session to unspool.dev")?;
+
+    let location: PublishResponse = response
+        .into_json()
+        .context("Failed to parse unspool.dev response")?;
+
+    println!("Published: {}", location.url);
+    if public {
+        println!("   Visibility: public");
+    } else {
+        println!("   Visibility: private (only you can view this link)");
+    }
 
     Ok(())
 }
+
+/// Redact one entry's text fields in place, returning how many secrets were
+/// replaced. Mirrors `export::run`'s per-entry-type redaction match.
+fn redact_entry(entry: &mut spool_format::Entry, detector: &SecretDetector) -> usize {
+    use spool_format::{Entry, ToolOutput};
+
+    match entry {
+        Entry::Prompt(p) => {
+            let (redacted, secrets) = detector.redact(&p.content);
+            p.content = redacted;
+            secrets.len()
+        }
+        Entry::Response(r) => {
+            let (redacted, secrets) = detector.redact(&r.content);
+            r.content = redacted;
+            secrets.len()
+        }
+        Entry::ToolResult(tr) => {
+            if let Some(ToolOutput::Text(ref mut text)) = tr.output {
+                let (redacted, secrets) = detector.redact(text);
+                *text = redacted;
+                secrets.len()
+            } else {
+                0
+            }
+        }
+        Entry::Thinking(t) => {
+            let (redacted, secrets) = detector.redact(&t.content);
+            t.content = redacted;
+            secrets.len()
+        }
+        _ => 0,
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct PublishResponse {
+    url: String,
+}
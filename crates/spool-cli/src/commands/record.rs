@@ -0,0 +1,89 @@
+//! Record command - Forward a locally-recorded session to a remote
+//! `spool serve` listener as it's written.
+//!
+//! `spool` itself has no in-process capture of a running agent - sessions
+//! are written by each agent's own logging and read back via
+//! `spool_adapters`. What this command does is bridge that growing log
+//! file to a remote listener: it tails `path` the same way `spool play`'s
+//! follow mode does, parsing each newly-appended line with
+//! `spool_format::parse_line` and forwarding it over a
+//! `spool_net::NetClient` connection as it arrives.
+
+use anyhow::{bail, Context, Result};
+use spool_format::SpoolFile;
+use spool_net::NetClient;
+use std::io::{Read, Seek};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often to poll `path` for newly-appended lines, matching `spool
+/// play`'s follow mode.
+const POLL_INTERVAL_MS: u64 = 500;
+
+pub fn run(path: &Path, remote: Option<&str>, agent: Option<&str>, session_id: Option<Uuid>) -> Result<()> {
+    let Some(addr) = remote else {
+        bail!("`spool record` currently only supports remote forwarding - pass --remote <addr>");
+    };
+
+    let file = SpoolFile::from_path(path).with_context(|| format!("Failed to read: {:?}", path))?;
+    let agent = agent.unwrap_or(&file.session.agent).to_string();
+    let session_id = session_id.unwrap_or(file.session.id);
+
+    let mut client =
+        NetClient::connect(addr, &agent, session_id).with_context(|| format!("Failed to connect to {addr}"))?;
+    println!("📡 Forwarding {:?} to {} (session {})", path, addr, session_id);
+    if client.resume_from > 0 {
+        println!("   Server already has {} entries - resuming from there", client.resume_from);
+    }
+
+    for entry in file.entries.iter().skip(client.resume_from) {
+        client.send_entry(entry)?;
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let cancelled = Arc::clone(&cancelled);
+        let _ = ctrlc::set_handler(move || cancelled.store(true, Ordering::SeqCst));
+    }
+
+    let mut offset = std::fs::metadata(path)?.len();
+    while !cancelled.load(Ordering::SeqCst) {
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        if metadata.len() <= offset {
+            continue;
+        }
+
+        let Ok(mut f) = std::fs::File::open(path) else {
+            continue;
+        };
+        if f.seek(std::io::SeekFrom::Start(offset)).is_err() {
+            continue;
+        }
+        let mut buf = String::new();
+        if f.read_to_string(&mut buf).is_err() {
+            continue;
+        }
+
+        let Some(last_newline) = buf.rfind('\n') else {
+            continue;
+        };
+        let complete = &buf[..=last_newline];
+        for line in complete.lines().filter(|l| !l.trim().is_empty()) {
+            if let Ok(entry) = spool_format::parse_line(line) {
+                client.send_entry(&entry)?;
+            }
+        }
+        offset += complete.len() as u64;
+    }
+
+    client.close()?;
+    println!("👋 Stopped forwarding");
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! Repo command - An explicit, user-managed archive of `.spool` sessions,
+//! backed by the same content-defined chunking used by [`crate::commands::cache`].
+//!
+//! Unlike the cache (an internal, auto-managed store under `~/.cache/spool/`
+//! that exists purely to skip re-parsing an unchanged agent log), the repo
+//! is something a user deliberately adds sessions to and restores from -
+//! `spool repo init`, `add <file>`, `list`, `restore <id>`. Sessions are
+//! split into blake3-addressed chunks under `chunks/`, deduplicating any
+//! payload (file contents, diffs, near-identical tool output) that recurs
+//! across archived sessions, with a `sessions/<id>.manifest` recording the
+//! ordered chunk hashes plus the session's own metadata. `restore`
+//! recomputes every chunk's hash before trusting it, since - unlike the
+//! cache - a repo is meant to be copied around and live longer than one
+//! machine's trust in its own disk.
+
+use crate::chunking::{read_chunks_verified, write_atomic, write_chunks};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use spool_format::SpoolFile;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Default repo location, distinct from the cache's `~/.cache/spool/`.
+fn default_repo_root() -> Result<PathBuf> {
+    let base = dirs::data_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    Ok(base.join("spool").join("repo"))
+}
+
+fn repo_root(repo: Option<&Path>) -> Result<PathBuf> {
+    match repo {
+        Some(path) => Ok(path.to_path_buf()),
+        None => default_repo_root(),
+    }
+}
+
+fn sessions_dir(root: &Path) -> PathBuf {
+    root.join("sessions")
+}
+
+fn chunks_dir(root: &Path) -> PathBuf {
+    root.join("chunks")
+}
+
+/// Per-session manifest: the archived session's own metadata plus the
+/// ordered chunk hashes that reassemble into its serialized bytes.
+#[derive(Serialize, Deserialize)]
+struct RepoManifest {
+    session_id: Uuid,
+    agent: String,
+    recorded_at: DateTime<Utc>,
+    title: Option<String>,
+    entry_count: usize,
+    /// When this session was added to the repo (distinct from
+    /// `recorded_at`, the session's own start time).
+    added_at: DateTime<Utc>,
+    chunks: Vec<String>,
+}
+
+/// Create the repo's directory structure.
+pub fn init(repo: Option<&Path>) -> Result<()> {
+    let root = repo_root(repo)?;
+    fs::create_dir_all(sessions_dir(&root))
+        .with_context(|| format!("Failed to create {:?}", sessions_dir(&root)))?;
+    fs::create_dir_all(chunks_dir(&root))
+        .with_context(|| format!("Failed to create {:?}", chunks_dir(&root)))?;
+    println!("📁 Initialized repo at {:?}", root);
+    Ok(())
+}
+
+/// Chunk, hash, and archive a `.spool` file's bytes, recording a manifest
+/// keyed by the session's own UUID.
+pub fn add(file: &Path, repo: Option<&Path>) -> Result<()> {
+    let root = repo_root(repo)?;
+    let chunks_path = chunks_dir(&root);
+    let sessions_path = sessions_dir(&root);
+    if !chunks_path.exists() || !sessions_path.exists() {
+        bail!("Repo at {:?} isn't initialized - run `spool repo init` first", root);
+    }
+
+    let spool = SpoolFile::from_path(file).with_context(|| format!("Failed to read: {:?}", file))?;
+
+    let mut bytes = Vec::new();
+    spool
+        .write_to(&mut bytes)
+        .with_context(|| "Failed to serialize session for archiving")?;
+
+    let stats = write_chunks(&chunks_path, &bytes)?;
+    let manifest = RepoManifest {
+        session_id: spool.session.id,
+        agent: spool.session.agent.clone(),
+        recorded_at: spool.session.recorded_at,
+        title: spool.session.title.clone(),
+        entry_count: spool.entries.len(),
+        added_at: Utc::now(),
+        chunks: stats.hashes,
+    };
+
+    let manifest_path = sessions_path.join(format!("{}.manifest", manifest.session_id));
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    write_atomic(&manifest_path, manifest_json.as_bytes())
+        .with_context(|| format!("Failed to write manifest: {:?}", manifest_path))?;
+
+    println!(
+        "📦 Archived session {} ({} entries, {} chunks, {} new)",
+        manifest.session_id,
+        manifest.entry_count,
+        manifest.chunks.len(),
+        stats.new_chunks
+    );
+    Ok(())
+}
+
+/// List every session archived in the repo.
+pub fn list(repo: Option<&Path>, json: bool) -> Result<()> {
+    let root = repo_root(repo)?;
+    let sessions_path = sessions_dir(&root);
+    if !sessions_path.exists() {
+        bail!("Repo at {:?} isn't initialized - run `spool repo init` first", root);
+    }
+
+    let mut manifests = Vec::new();
+    for entry in fs::read_dir(&sessions_path)
+        .with_context(|| format!("Failed to read {:?}", sessions_path))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map(|e| e == "manifest").unwrap_or(false) {
+            let json_str = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {:?}", path))?;
+            let manifest: RepoManifest = serde_json::from_str(&json_str)
+                .with_context(|| format!("Failed to parse {:?}", path))?;
+            manifests.push(manifest);
+        }
+    }
+    manifests.sort_by(|a, b| b.added_at.cmp(&a.added_at));
+
+    if json {
+        let rows: Vec<_> = manifests
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "session_id": m.session_id,
+                    "agent": m.agent,
+                    "recorded_at": m.recorded_at,
+                    "title": m.title,
+                    "entry_count": m.entry_count,
+                    "added_at": m.added_at,
+                    "chunk_count": m.chunks.len(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if manifests.is_empty() {
+        println!("No sessions archived in {:?}", root);
+        return Ok(());
+    }
+    for m in &manifests {
+        let title = m.title.as_deref().unwrap_or("(untitled)");
+        println!(
+            "{}  {:<12}  {:>6} entries  {}",
+            m.session_id, m.agent, m.entry_count, title
+        );
+    }
+    Ok(())
+}
+
+/// Reassemble a session from its manifest, verifying every chunk's hash,
+/// and write it out to `output` (defaulting to `<id>.spool` in the current
+/// directory).
+pub fn restore(id: &str, repo: Option<&Path>, output: Option<&Path>) -> Result<()> {
+    let root = repo_root(repo)?;
+    let chunks_path = chunks_dir(&root);
+    let sessions_path = sessions_dir(&root);
+    let manifest_path = sessions_path.join(format!("{}.manifest", id));
+    if !manifest_path.exists() {
+        bail!("No archived session {} in {:?}", id, root);
+    }
+
+    let manifest_json = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {:?}", manifest_path))?;
+    let manifest: RepoManifest = serde_json::from_str(&manifest_json)
+        .with_context(|| format!("Failed to parse {:?}", manifest_path))?;
+
+    let bytes = read_chunks_verified(&chunks_path, &manifest.chunks)
+        .with_context(|| format!("Failed to reassemble session {}", manifest.session_id))?;
+
+    // Round-trip through SpoolFile to confirm the reassembled bytes still
+    // parse, not just that every chunk's hash matched.
+    let spool = SpoolFile::from_reader(std::io::Cursor::new(&bytes[..]))
+        .with_context(|| "Reassembled session failed to parse")?;
+
+    let output_path = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.spool", manifest.session_id)));
+    fs::write(&output_path, &bytes)
+        .with_context(|| format!("Failed to write {:?}", output_path))?;
+
+    println!(
+        "✅ Restored {} ({} entries) to {:?}",
+        manifest.session_id,
+        spool.entries.len(),
+        output_path
+    );
+    Ok(())
+}
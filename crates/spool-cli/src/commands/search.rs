@@ -1,9 +1,68 @@
 //! Search command - Search sessions by title, project, or content (non-interactive).
 
-use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use regex::Regex;
 use serde::Serialize;
 
 use super::agent::{convert_session, find_all_sessions};
+use super::index;
+use super::semindex;
+use crate::tui::fuzzy::fuzzy_match;
+
+/// Restricts results to sessions whose `project_dir` lives under one of a
+/// set of roots, within a depth range — following distant's multi-path
+/// `SearchQuery` so `--path ~/work --max-depth 2` searches only transcripts
+/// from projects up to two directories under `~/work`, not every session
+/// on the machine.
+struct PathScope {
+    roots: Vec<PathBuf>,
+    min_depth: usize,
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+}
+
+impl PathScope {
+    fn new(roots: &[PathBuf], min_depth: usize, max_depth: Option<usize>, follow_symlinks: bool) -> Self {
+        Self {
+            roots: roots.to_vec(),
+            min_depth,
+            max_depth,
+            follow_symlinks,
+        }
+    }
+
+    /// Whether `project_dir` falls within this scope. An empty `roots` list
+    /// scopes nothing, so every session passes.
+    fn allows(&self, project_dir: Option<&Path>) -> bool {
+        if self.roots.is_empty() {
+            return true;
+        }
+        let Some(dir) = project_dir else {
+            return false;
+        };
+
+        self.roots.iter().any(|root| {
+            let (dir, root) = if self.follow_symlinks {
+                match (dir.canonicalize(), root.canonicalize()) {
+                    (Ok(d), Ok(r)) => (d, r),
+                    _ => return false,
+                }
+            } else {
+                (dir.to_path_buf(), root.clone())
+            };
+
+            let Ok(relative) = dir.strip_prefix(&root) else {
+                return false;
+            };
+            let depth = relative.components().count();
+            depth >= self.min_depth && self.max_depth.map(|max| depth <= max).unwrap_or(true)
+        })
+    }
+}
 
 #[derive(Serialize)]
 struct SearchResult {
@@ -13,40 +72,367 @@ struct SearchResult {
     modified: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     matched_content: Option<String>,
+    /// Relevance score: BM25 against the persistent index in the
+    /// default (lexical, ranked) mode, a constant `1.0` in `--regex` mode
+    /// (a regex match is binary — there's no index to rank against), or
+    /// the fuzzy alignment score in `--fuzzy` mode.
+    score: f32,
+    /// Char indices in `title` that matched the query, set only in
+    /// `--fuzzy` mode, so the CLI can highlight them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matched_positions: Option<Vec<usize>>,
+}
+
+/// Compiled once from `query`, then applied to every candidate entry body.
+/// `--regex` swaps the default lowercase-substring test for a `regex`-crate
+/// pattern, so word boundaries, alternation, and case-insensitive flags
+/// (`(?i)...`) work the way `rg` users expect.
+enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn compile(query: &str, regex: bool) -> Result<Self> {
+        if regex {
+            let re = Regex::new(query).context("invalid regex pattern")?;
+            Ok(Matcher::Regex(re))
+        } else {
+            Ok(Matcher::Substring(query.to_lowercase()))
+        }
+    }
+
+    /// The byte range of this matcher's first match in `text`, if any.
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        match self {
+            Matcher::Substring(needle) => {
+                let lower = text.to_lowercase();
+                let start = lower.find(needle.as_str())?;
+                Some((start, start + needle.len()))
+            }
+            Matcher::Regex(re) => re.find(text).map(|m| (m.start(), m.end())),
+        }
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     query: &str,
     agent_filter: Option<&str>,
+    paths: &[PathBuf],
+    min_depth: usize,
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    limit: Option<usize>,
+    regex: bool,
+    fuzzy: bool,
+    semantic: bool,
+    normalize: bool,
+    did_you_mean: bool,
+    json: bool,
+) -> Result<()> {
+    let scope = PathScope::new(paths, min_depth, max_depth, follow_symlinks);
+
+    // --fuzzy, --regex and --semantic are mutually exclusive query styles,
+    // none of which goes through the lexical index: a fuzzy pattern isn't a
+    // set of index terms, neither is a regex, and a semantic query ranks by
+    // embedding similarity rather than term postings.
+    if fuzzy {
+        return run_fuzzy(query, agent_filter, &scope, limit, json);
+    }
+    if regex {
+        return run_regex(query, agent_filter, &scope, limit, json);
+    }
+    if semantic {
+        return run_semantic(query, agent_filter, &scope, limit, json);
+    }
+
+    let max = limit.unwrap_or(20);
+    index::refresh()?;
+    let results = ranked_results(query, max, normalize, agent_filter, &scope)?;
+
+    if results.is_empty() {
+        // A typo in a content/title query silently returns nothing today;
+        // suggest the nearest indexed term and, with --did-you-mean,
+        // re-run the search against it instead of making the user retype.
+        if let Some(suggestion) = index::suggest_query_correction(query) {
+            println!("No sessions matching \"{}\" — did you mean \"{}\"?", query, suggestion);
+            if did_you_mean {
+                let corrected = ranked_results(&suggestion, max, normalize, agent_filter, &scope)?;
+                return print_results(&corrected, &suggestion, false, json);
+            }
+            return Ok(());
+        }
+    }
+
+    print_results(&results, query, false, json)
+}
+
+/// Run the BM25 ranked query and convert each hit into a [`SearchResult`],
+/// applying the agent/path filters and re-converting only the sessions
+/// actually returned to pull a content snippet.
+fn ranked_results(
+    query: &str,
+    max: usize,
+    normalize: bool,
+    agent_filter: Option<&str>,
+    scope: &PathScope,
+) -> Result<Vec<SearchResult>> {
+    let hits = index::search(query, max, normalize)?;
+
+    let mut results: Vec<SearchResult> = Vec::new();
+    for hit in hits {
+        if let Some(filter) = agent_filter {
+            if hit.session.agent.as_str() != filter {
+                continue;
+            }
+        }
+        if !scope.allows(hit.session.project_dir.as_deref()) {
+            continue;
+        }
+
+        // Only the results actually shown get re-converted for a snippet,
+        // not every indexed session — the index itself already did the
+        // heavy lifting of finding which sessions are relevant. Match via
+        // stems, same as the index's postings, so a query for "refactor"
+        // still quotes a body that only says "refactoring".
+        let matched_content = convert_session(&hit.session).ok().and_then(|spool_file| {
+            spool_file.entries.iter().find_map(|entry| {
+                let text = match entry {
+                    spool_format::Entry::Prompt(p) => Some(p.content.as_str()),
+                    spool_format::Entry::Response(r) => Some(r.content.as_str()),
+                    _ => None,
+                }?;
+                let range = index::find_stemmed_match(text, query)?;
+                Some(extract_snippet(text, range))
+            })
+        });
+
+        results.push(SearchResult {
+            path: hit.session.path.to_string_lossy().to_string(),
+            agent: hit.session.agent.as_str().to_string(),
+            title: hit
+                .session
+                .title
+                .clone()
+                .unwrap_or_else(|| "Untitled".to_string()),
+            modified: hit
+                .session
+                .modified_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            matched_content,
+            score: hit.score,
+            matched_positions: None,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Fuzzy-match `query` as a subsequence of each session's title or project
+/// directory, using the same char-bag-prefiltered, gap-penalized scorer
+/// [`crate::tui::fuzzy`] uses for the Library session filter — so `clde
+/// refctr` on the CLI ranks the same way it would while browsing.
+fn run_fuzzy(
+    query: &str,
+    agent_filter: Option<&str>,
+    scope: &PathScope,
+    limit: Option<usize>,
+    json: bool,
+) -> Result<()> {
+    let sessions = find_all_sessions()?;
+    let max = limit.unwrap_or(20);
+
+    let mut results: Vec<SearchResult> = Vec::new();
+    for session in &sessions {
+        if let Some(filter) = agent_filter {
+            if session.agent.as_str() != filter {
+                continue;
+            }
+        }
+        if !scope.allows(session.project_dir.as_deref()) {
+            continue;
+        }
+
+        let title = session.title.clone().unwrap_or_else(|| "Untitled".to_string());
+        let title_match = fuzzy_match(&title, query);
+        let project_match = session
+            .project_dir
+            .as_ref()
+            .and_then(|p| fuzzy_match(&p.to_string_lossy(), query));
+
+        let (score, positions) = match (title_match, project_match) {
+            (Some(t), Some(p)) if p.score > t.score => (p.score, None),
+            (Some(t), _) => (t.score, Some(t.positions)),
+            (None, Some(p)) => (p.score, None),
+            (None, None) => continue,
+        };
+
+        results.push(SearchResult {
+            path: session.path.to_string_lossy().to_string(),
+            agent: session.agent.as_str().to_string(),
+            title,
+            modified: session
+                .modified_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            matched_content: None,
+            score: score as f32,
+            matched_positions: positions,
+        });
+    }
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    results.truncate(max);
+
+    print_results(&results, query, false, json)
+}
+
+/// Rank by embedding similarity via [`semindex`] instead of term postings,
+/// so a query matches by meaning rather than shared words. Falls back to
+/// the ordinary lexical ranked search if no embedding backend is
+/// configured (`SPOOL_EMBEDDING_ENDPOINT` unset) or if the configured
+/// backend errors, rather than returning nothing.
+fn run_semantic(
+    query: &str,
+    agent_filter: Option<&str>,
+    scope: &PathScope,
     limit: Option<usize>,
     json: bool,
 ) -> Result<()> {
+    let max = limit.unwrap_or(20);
+
+    if !semindex::http_backend_configured() {
+        if !json {
+            println!(
+                "No embedding backend configured (set SPOOL_EMBEDDING_ENDPOINT); falling back to lexical search."
+            );
+        }
+        index::refresh()?;
+        let results = ranked_results(query, max, false, agent_filter, scope)?;
+        return print_results(&results, query, false, json);
+    }
+
     let sessions = find_all_sessions()?;
-    let query_lower = query.to_lowercase();
+    let provider = semindex::select_provider();
+    let hits = semindex::build_or_update_index(&sessions, provider.as_ref())
+        .and_then(|_| semindex::search(query, max, provider.as_ref()));
+
+    let hits = match hits {
+        Ok(hits) => hits,
+        Err(e) => {
+            if !json {
+                println!("Embedding backend failed ({}); falling back to lexical search.", e);
+            }
+            index::refresh()?;
+            let results = ranked_results(query, max, false, agent_filter, scope)?;
+            return print_results(&results, query, false, json);
+        }
+    };
+
+    let mut results: Vec<SearchResult> = Vec::new();
+    for (session, entry_id, score) in hits {
+        if let Some(filter) = agent_filter {
+            if session.agent.as_str() != filter {
+                continue;
+            }
+        }
+        if !scope.allows(session.project_dir.as_deref()) {
+            continue;
+        }
+
+        // The index only stores vectors, not raw text, so there's no match
+        // byte range to center a snippet on like the lexical paths have —
+        // just quote the start of whichever entry the hit pointed at.
+        let matched_content = convert_session(&session).ok().and_then(|spool_file| {
+            spool_file
+                .entries
+                .iter()
+                .find(|entry| entry.id() == Some(&entry_id))
+                .and_then(|entry| match entry {
+                    spool_format::Entry::Prompt(p) => Some(p.content.as_str()),
+                    spool_format::Entry::Response(r) => Some(r.content.as_str()),
+                    _ => None,
+                })
+                .map(|text| {
+                    let snippet: String = text.chars().take(160).collect();
+                    format!("{}...", snippet.trim())
+                })
+        });
+
+        results.push(SearchResult {
+            path: session.path.to_string_lossy().to_string(),
+            agent: session.agent.as_str().to_string(),
+            title: session.title.clone().unwrap_or_else(|| "Untitled".to_string()),
+            modified: session
+                .modified_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            matched_content,
+            score,
+            matched_positions: None,
+        });
+    }
+
+    print_results(&results, query, false, json)
+}
+
+/// The original linear substring/regex scan: `convert_session`s every
+/// discovered session and tests each prompt/response body directly,
+/// used only for `--regex` since a regex pattern can't be tokenized into
+/// the lexical index's term postings.
+fn run_regex(
+    query: &str,
+    agent_filter: Option<&str>,
+    scope: &PathScope,
+    limit: Option<usize>,
+    json: bool,
+) -> Result<()> {
+    let sessions = find_all_sessions()?;
+    let matcher = Matcher::compile(query, true)?;
+
+    // Like distant's Search/CancelSearch: a long scan over hundreds of
+    // sessions should be abortable without killing the whole process.
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let cancelled = Arc::clone(&cancelled);
+        let _ = ctrlc::set_handler(move || {
+            cancelled.store(true, Ordering::SeqCst);
+        });
+    }
 
     let mut results: Vec<SearchResult> = Vec::new();
     let max = limit.unwrap_or(20);
+    let mut was_cancelled = false;
 
     for session in &sessions {
         if results.len() >= max {
             break;
         }
+        if cancelled.load(Ordering::SeqCst) {
+            was_cancelled = true;
+            break;
+        }
 
         if let Some(filter) = agent_filter {
             if session.agent.as_str() != filter {
                 continue;
             }
         }
+        if !scope.allows(session.project_dir.as_deref()) {
+            continue;
+        }
 
         // Check title match
         let title = session.title.as_deref().unwrap_or("Untitled");
-        let title_matches = title.to_lowercase().contains(&query_lower);
+        let title_matches = matcher.find(title).is_some();
 
         // Check project dir match
         let project_matches = session
             .project_dir
             .as_ref()
-            .map(|p| p.to_string_lossy().to_lowercase().contains(&query_lower))
+            .map(|p| matcher.find(&p.to_string_lossy()).is_some())
             .unwrap_or(false);
 
         // Check content match (search prompts and responses)
@@ -60,9 +446,8 @@ pub fn run(
                         _ => None,
                     };
                     if let Some(text) = text {
-                        if text.to_lowercase().contains(&query_lower) {
-                            // Extract a snippet around the match using char indices for UTF-8 safety
-                            matched_content = extract_snippet(text, &query_lower);
+                        if let Some(range) = matcher.find(text) {
+                            matched_content = Some(extract_snippet(text, range));
                             break;
                         }
                     }
@@ -84,44 +469,81 @@ pub fn run(
                 .map(|d| d.to_rfc3339())
                 .unwrap_or_default(),
             matched_content,
+            score: 1.0,
+            matched_positions: None,
         });
     }
 
+    print_results(&results, query, was_cancelled, json)
+}
+
+/// Print `results` as JSON or as the human-readable badge/title/path/
+/// snippet listing, shared by both the ranked (`run`) and regex
+/// (`run_regex`) paths so their output stays identical apart from scoring.
+fn print_results(results: &[SearchResult], query: &str, was_cancelled: bool, json: bool) -> Result<()> {
     if json {
         println!("{}", serde_json::to_string_pretty(&results)?);
-    } else {
-        if results.is_empty() {
-            println!("No sessions matching \"{}\".", query);
-            return Ok(());
-        }
+        return Ok(());
+    }
 
-        for r in &results {
-            let badge = match r.agent.as_str() {
-                "claude-code" => "CC",
-                "codex" => "CX",
-                "cursor" => "CU",
-                "aider" => "AI",
-                _ => "??",
-            };
-            println!("[{}] {}", badge, r.title);
-            println!("     {}", r.path);
-            if let Some(ref snippet) = r.matched_content {
-                println!("     match: {}", snippet);
-            }
-            println!();
+    if results.is_empty() {
+        println!("No sessions matching \"{}\".", query);
+        return Ok(());
+    }
+
+    for r in results {
+        let badge = match r.agent.as_str() {
+            "claude-code" => "CC",
+            "codex" => "CX",
+            "cursor" => "CU",
+            "aider" => "AI",
+            "aichat" => "AC",
+            _ => "??",
+        };
+        println!("[{}] {}", badge, highlight_matches(&r.title, r.matched_positions.as_deref()));
+        println!("     {}", r.path);
+        if let Some(ref snippet) = r.matched_content {
+            println!("     match: {}", snippet);
         }
+        println!();
+    }
 
-        println!("{} result(s).", results.len());
+    println!("{} result(s).", results.len());
+    if was_cancelled {
+        println!("(search cancelled)");
     }
 
     Ok(())
 }
 
-/// Extract a snippet around the query match, using char indices for UTF-8 safety.
-fn extract_snippet(text: &str, query_lower: &str) -> Option<String> {
-    let text_lower = text.to_lowercase();
-    let match_start = text_lower.find(query_lower)?;
+/// Bold the chars of `title` at `positions` (the `--fuzzy` match indices),
+/// honoring `NO_COLOR` the same way [`crate::diff::diff_to_ansi`] does.
+fn highlight_matches(title: &str, positions: Option<&[usize]>) -> String {
+    let Some(positions) = positions else {
+        return title.to_string();
+    };
+    if std::env::var_os("NO_COLOR").is_some() {
+        return title.to_string();
+    }
 
+    let positions: std::collections::HashSet<usize> = positions.iter().copied().collect();
+    let mut out = String::new();
+    for (i, c) in title.chars().enumerate() {
+        if positions.contains(&i) {
+            out.push_str("\x1b[1m");
+            out.push(c);
+            out.push_str("\x1b[0m");
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Extract a snippet of `text` around the byte range `[start, end)` a
+/// matcher already found, widening to char/word boundaries for UTF-8
+/// safety and readability.
+fn extract_snippet(text: &str, (match_start, match_end): (usize, usize)) -> String {
     // Build a mapping of char indices to byte positions
     let char_indices: Vec<(usize, usize)> = text
         .char_indices()
@@ -129,15 +551,19 @@ fn extract_snippet(text: &str, query_lower: &str) -> Option<String> {
         .map(|(char_idx, (byte_idx, _))| (char_idx, byte_idx))
         .collect();
 
-    // Find char index of match start
-    let match_char_idx = char_indices
+    // Find char index of match start/end
+    let match_start_char_idx = char_indices
         .iter()
         .position(|(_, byte_idx)| *byte_idx >= match_start)
         .unwrap_or(0);
+    let match_end_char_idx = char_indices
+        .iter()
+        .position(|(_, byte_idx)| *byte_idx >= match_end)
+        .unwrap_or(char_indices.len());
 
     // Calculate snippet boundaries in char space (40 chars context)
-    let start_char = match_char_idx.saturating_sub(40);
-    let end_char = (match_char_idx + query_lower.chars().count() + 40).min(char_indices.len());
+    let start_char = match_start_char_idx.saturating_sub(40);
+    let end_char = (match_end_char_idx + 40).min(char_indices.len());
 
     // Convert back to byte positions
     let start_byte = char_indices.get(start_char).map(|(_, b)| *b).unwrap_or(0);
@@ -157,5 +583,72 @@ fn extract_snippet(text: &str, query_lower: &str) -> Option<String> {
         .unwrap_or(end_byte);
 
     let snippet = text[start_byte..end_byte].replace('\n', " ");
-    Some(format!("...{}...", snippet))
+    format!("...{}...", snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substring_matcher_is_case_insensitive() {
+        let matcher = Matcher::compile("ERROR", false).unwrap();
+        assert_eq!(matcher.find("an error occurred"), Some((3, 8)));
+    }
+
+    #[test]
+    fn path_scope_with_no_roots_allows_everything() {
+        let scope = PathScope::new(&[], 0, None, false);
+        assert!(scope.allows(None));
+        assert!(scope.allows(Some(Path::new("/anywhere"))));
+    }
+
+    #[test]
+    fn path_scope_rejects_sessions_outside_every_root() {
+        let scope = PathScope::new(&[PathBuf::from("/work")], 0, None, false);
+        assert!(!scope.allows(Some(Path::new("/home/me/personal"))));
+        assert!(!scope.allows(None));
+    }
+
+    #[test]
+    fn path_scope_enforces_min_and_max_depth() {
+        let scope = PathScope::new(&[PathBuf::from("/work")], 1, Some(1), false);
+        assert!(!scope.allows(Some(Path::new("/work"))));
+        assert!(scope.allows(Some(Path::new("/work/proj-a"))));
+        assert!(!scope.allows(Some(Path::new("/work/proj-a/nested"))));
+    }
+
+    #[test]
+    fn regex_matcher_supports_alternation_and_word_boundaries() {
+        let matcher = Matcher::compile(r"\bfoo\b|\bbar\b", true).unwrap();
+        assert!(matcher.find("a foo here").is_some());
+        assert!(matcher.find("football").is_none());
+    }
+
+    #[test]
+    fn regex_matcher_rejects_invalid_pattern() {
+        assert!(Matcher::compile("(unclosed", true).is_err());
+    }
+
+    #[test]
+    fn extract_snippet_uses_given_match_range_not_a_re_find() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        // "fox" is at byte range (16, 19)
+        let snippet = extract_snippet(text, (16, 19));
+        assert!(snippet.contains("fox"));
+    }
+
+    #[test]
+    fn highlight_matches_bolds_only_given_positions() {
+        std::env::remove_var("NO_COLOR");
+        let highlighted = highlight_matches("claude", Some(&[0, 2]));
+        assert!(highlighted.contains("\x1b[1mc\x1b[0m"));
+        assert!(highlighted.contains("\x1b[1ma\x1b[0m"));
+        assert!(!highlighted.contains("\x1b[1ml\x1b[0m"));
+    }
+
+    #[test]
+    fn highlight_matches_is_plain_without_positions() {
+        assert_eq!(highlight_matches("claude", None), "claude");
+    }
 }
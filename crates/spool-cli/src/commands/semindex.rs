@@ -0,0 +1,609 @@
+//! Semantic search index over converted sessions.
+//!
+//! `search` (the plain-text command) only matches literal substrings, so a
+//! session can't be found by paraphrase or topic. This module builds a
+//! persistent index of embedding vectors over each session's
+//! prompt/response/thinking text and exposes [`search`] for ranked,
+//! similarity-based lookup. The embedding backend is a trait object
+//! ([`EmbeddingProvider`]) so [`LocalHashEmbedder`] (the dependency-free
+//! default) can later be swapped for a local model or remote API without
+//! touching this module's indexing or search logic.
+//!
+//! The index is persisted as a single JSON file under the cache directory
+//! (mirroring [`super::cache`]'s `dirs::cache_dir()` convention), keyed by
+//! each session's `modified_at` so re-indexing only reprocesses sessions
+//! that actually changed ([`session_up_to_date`]) — `find_all_sessions`
+//! already applies each adapter's own ignore rules when crawling, so this
+//! module doesn't need to re-implement directory walking. Each session is
+//! chunked per entry ([`chunk_text`]) rather than embedded as a whole, and
+//! every stored [`IndexChunk`] keeps the originating `EntryId` so a search
+//! hit can point at the exact message instead of just the session.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use spool_format::{Entry, EntryId};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use spool_adapters::SessionInfo;
+
+use super::agent::{convert_session, find_all_sessions};
+
+/// Maximum chunk size, in characters, when splitting entry text for indexing.
+const MAX_CHUNK_CHARS: usize = 800;
+
+/// Produces embedding vectors for a chunk of text.
+///
+/// Implementations must return vectors of consistent `dimensions()` length
+/// so vectors can be meaningfully compared across calls.
+pub trait EmbeddingProvider {
+    /// Embed a chunk of text into a fixed-length vector.
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// The length of vectors this provider produces.
+    fn dimensions(&self) -> usize;
+}
+
+/// Dependency-free default embedding provider.
+///
+/// Hashes each lowercased word into one of `dims` buckets with FNV-1a and
+/// accumulates a signed bag-of-words vector, then L2-normalizes it so
+/// cosine similarity between two vectors reduces to a plain dot product.
+/// This is a crude bag-of-words model, not a trained embedding — it's
+/// meant to make semantic search usable out of the box without a model
+/// download or API key; swap in a real [`EmbeddingProvider`] for better
+/// recall.
+pub struct LocalHashEmbedder {
+    dims: usize,
+}
+
+impl LocalHashEmbedder {
+    /// Create a hash embedder producing vectors of length `dims`.
+    pub fn new(dims: usize) -> Self {
+        Self { dims }
+    }
+}
+
+impl Default for LocalHashEmbedder {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl EmbeddingProvider for LocalHashEmbedder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vector = vec![0f32; self.dims];
+        for word in text.split_whitespace() {
+            let word = word.to_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            let hash = fnv1a(word.as_bytes());
+            let bucket = (hash % self.dims as u64) as usize;
+            // Use a second bit of the hash to pick a sign, so unrelated
+            // words don't all push the vector in the same direction.
+            let sign = if hash & 1 == 0 { 1.0 } else { -1.0 };
+            vector[bucket] += sign;
+        }
+        normalize(&mut vector);
+        Ok(vector)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+}
+
+/// Env var pointing at an HTTP embedding endpoint, for callers who want
+/// real embeddings instead of [`LocalHashEmbedder`]'s bag-of-words
+/// approximation. Same naming convention as [`super::publish`]'s
+/// `SPOOL_PUBLISH_URL`.
+const EMBEDDING_ENDPOINT_ENV_VAR: &str = "SPOOL_EMBEDDING_ENDPOINT";
+
+/// Vector length assumed for [`HttpEmbeddingProvider`] responses until the
+/// first real response is seen - only used to pre-size buffers, since the
+/// actual dimensionality comes from the response itself.
+const HTTP_EMBEDDER_DEFAULT_DIMS: usize = 1536;
+
+/// Embeds text by POSTing `{"input": text}` to a configured HTTP endpoint
+/// and reading back `{"embedding": [f32...]}` - the shape used by
+/// OpenAI-compatible embedding APIs, so this works against a local model
+/// server or a hosted provider without caring which.
+pub struct HttpEmbeddingProvider {
+    endpoint: String,
+    dims: std::cell::Cell<usize>,
+}
+
+impl HttpEmbeddingProvider {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            dims: std::cell::Cell::new(HTTP_EMBEDDER_DEFAULT_DIMS),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingProvider for HttpEmbeddingProvider {
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let response: EmbeddingResponse = ureq::post(&self.endpoint)
+            .send_json(EmbeddingRequest { input: text })
+            .context("embedding request failed")?
+            .into_json()
+            .context("invalid embedding response")?;
+        self.dims.set(response.embedding.len());
+        Ok(response.embedding)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims.get()
+    }
+}
+
+/// Select the embedding provider to use: an [`HttpEmbeddingProvider`] if
+/// `SPOOL_EMBEDDING_ENDPOINT` is set, otherwise the dependency-free
+/// [`LocalHashEmbedder`]. Callers that need to distinguish "falls back
+/// because nothing is configured" from "a configured backend failed" can
+/// check the env var themselves; this just picks the best available
+/// provider.
+pub fn select_provider() -> Box<dyn EmbeddingProvider> {
+    match std::env::var(EMBEDDING_ENDPOINT_ENV_VAR) {
+        Ok(endpoint) => Box::new(HttpEmbeddingProvider::new(endpoint)),
+        Err(_) => Box::new(LocalHashEmbedder::default()),
+    }
+}
+
+/// Whether a real embedding backend is configured, as opposed to falling
+/// back on [`LocalHashEmbedder`]'s bag-of-words approximation. Used by
+/// `search --semantic` to decide whether embedding search is worth running
+/// at all, or whether it should defer to lexical search instead.
+pub fn http_backend_configured() -> bool {
+    std::env::var_os(EMBEDDING_ENDPOINT_ENV_VAR).is_some()
+}
+
+/// FNV-1a hash, used to bucket words without pulling in a hashing crate.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Scale `vector` to unit length in place. A zero vector is left as-is.
+fn normalize(vector: &mut [f32]) {
+    let magnitude = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if magnitude > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= magnitude;
+        }
+    }
+}
+
+/// Cosine similarity between two vectors of equal length.
+///
+/// Both vectors produced by [`LocalHashEmbedder`] are already unit length,
+/// so this is just a dot product, but other providers may not normalize —
+/// dividing by the magnitudes keeps the result correct either way.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let mag_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let mag_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if mag_a == 0.0 || mag_b == 0.0 {
+        0.0
+    } else {
+        dot / (mag_a * mag_b)
+    }
+}
+
+/// Split `text` into whitespace-respecting chunks of at most `max_chars`
+/// characters, preferring to break at the last whitespace before the
+/// limit so words aren't split mid-way. Empty/whitespace-only chunks are
+/// skipped.
+fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let mut end = (start + max_chars).min(chars.len());
+        if end < chars.len() {
+            if let Some(break_at) = chars[start..end].iter().rposition(|c| c.is_whitespace()) {
+                if break_at > 0 {
+                    end = start + break_at;
+                }
+            }
+        }
+
+        let chunk: String = chars[start..end].iter().collect();
+        if !chunk.trim().is_empty() {
+            chunks.push(chunk.trim().to_string());
+        }
+
+        start = if end > start { end } else { end + 1 };
+    }
+
+    chunks
+}
+
+/// Denormalized snapshot of a [`SessionInfo`], stored so search results can
+/// be reconstructed without re-running session discovery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedSession {
+    path: PathBuf,
+    agent: String,
+    title: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    modified_at: Option<DateTime<Utc>>,
+    project_dir: Option<PathBuf>,
+    message_count: Option<usize>,
+}
+
+impl IndexedSession {
+    fn from_session_info(session: &SessionInfo) -> Self {
+        Self {
+            path: session.path.clone(),
+            agent: session.agent.as_str().to_string(),
+            title: session.title.clone(),
+            created_at: session.created_at,
+            modified_at: session.modified_at,
+            project_dir: session.project_dir.clone(),
+            message_count: session.message_count,
+        }
+    }
+
+    fn into_session_info(self, agent: spool_adapters::AgentType) -> SessionInfo {
+        SessionInfo {
+            path: self.path,
+            agent,
+            created_at: self.created_at,
+            modified_at: self.modified_at,
+            title: self.title,
+            project_dir: self.project_dir,
+            message_count: self.message_count,
+        }
+    }
+}
+
+/// One embedded chunk of an entry's text, tagged with the entry it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexChunk {
+    session_path: PathBuf,
+    entry_id: EntryId,
+    vector: Vec<f32>,
+}
+
+/// The persisted index: embedded chunks plus the session snapshots needed
+/// to turn a chunk hit back into a displayable result.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SemanticIndex {
+    dims: usize,
+    sessions: HashMap<PathBuf, IndexedSession>,
+    chunks: Vec<IndexChunk>,
+}
+
+/// Path to the persisted semantic index, creating its parent directory if
+/// necessary.
+fn index_path() -> Result<PathBuf> {
+    let base = dirs::cache_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from(".cache"));
+    let dir = base.join("spool");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create cache directory: {:?}", dir))?;
+    }
+    Ok(dir.join("semantic_index.json"))
+}
+
+/// Load the persisted index, defaulting to an empty one if it doesn't
+/// exist or fails to parse.
+fn load_index() -> SemanticIndex {
+    index_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &SemanticIndex) -> Result<()> {
+    let path = index_path()?;
+    let json = serde_json::to_string(index)?;
+    fs::write(&path, json).with_context(|| format!("Failed to write index: {:?}", path))
+}
+
+/// Text worth indexing from a single entry, if any.
+fn entry_text(entry: &Entry) -> Option<&str> {
+    match entry {
+        Entry::Prompt(p) => Some(&p.content),
+        Entry::Response(r) => Some(&r.content),
+        Entry::Thinking(t) => Some(&t.content),
+        _ => None,
+    }
+}
+
+/// Whether `session` is already indexed at its current `modified_at`, so
+/// re-indexing can skip it (the crawler's path+mtime dedup check).
+fn session_up_to_date(index: &SemanticIndex, session: &SessionInfo) -> bool {
+    index
+        .sessions
+        .get(&session.path)
+        .map(|indexed| indexed.modified_at == session.modified_at)
+        .unwrap_or(false)
+}
+
+/// Build or incrementally update the semantic index over `sessions`.
+///
+/// Sessions whose `modified_at` matches what's already indexed are
+/// skipped entirely. If `provider`'s dimensionality doesn't match the
+/// stored index, the whole index is discarded first — vectors from
+/// different providers aren't comparable. Returns the number of sessions
+/// that were (re)indexed.
+pub fn build_or_update_index(
+    sessions: &[SessionInfo],
+    provider: &dyn EmbeddingProvider,
+) -> Result<usize> {
+    let mut index = load_index();
+    if index.dims != provider.dimensions() {
+        index = SemanticIndex {
+            dims: provider.dimensions(),
+            ..Default::default()
+        };
+    }
+
+    let mut reindexed = 0;
+
+    for session in sessions {
+        if session_up_to_date(&index, session) {
+            continue;
+        }
+
+        index.chunks.retain(|c| c.session_path != session.path);
+
+        let spool_file = match convert_session(session) {
+            Ok(file) => file,
+            Err(_) => continue,
+        };
+
+        for entry in &spool_file.entries {
+            let Some(text) = entry_text(entry) else {
+                continue;
+            };
+            let Some(entry_id) = entry.id() else {
+                continue;
+            };
+
+            for chunk in chunk_text(text, MAX_CHUNK_CHARS) {
+                let vector = provider.embed(&chunk)?;
+                index.chunks.push(IndexChunk {
+                    session_path: session.path.clone(),
+                    entry_id: *entry_id,
+                    vector,
+                });
+            }
+        }
+
+        index
+            .sessions
+            .insert(session.path.clone(), IndexedSession::from_session_info(session));
+        reindexed += 1;
+    }
+
+    save_index(&index)?;
+    Ok(reindexed)
+}
+
+/// Search the persisted index for the chunks most similar to `query`,
+/// returning up to `top_k` `(SessionInfo, EntryId, score)` hits sorted by
+/// descending similarity.
+pub fn search(
+    query: &str,
+    top_k: usize,
+    provider: &dyn EmbeddingProvider,
+) -> Result<Vec<(SessionInfo, EntryId, f32)>> {
+    let index = load_index();
+    let query_vector = provider.embed(query)?;
+
+    let mut scored: Vec<(f32, &IndexChunk)> = index
+        .chunks
+        .iter()
+        .map(|chunk| (cosine_similarity(&query_vector, &chunk.vector), chunk))
+        .collect();
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut results = Vec::with_capacity(top_k);
+    for (score, chunk) in scored.into_iter().take(top_k) {
+        let Some(indexed) = index.sessions.get(&chunk.session_path) else {
+            continue;
+        };
+        let agent = match indexed.agent.as_str() {
+            "claude-code" => spool_adapters::AgentType::ClaudeCode,
+            "aichat" => spool_adapters::AgentType::Aichat,
+            "codex" => spool_adapters::AgentType::Codex,
+            "cursor" => spool_adapters::AgentType::Cursor,
+            "aider" => spool_adapters::AgentType::Aider,
+            "github-copilot" => spool_adapters::AgentType::GithubCopilot,
+            _ => spool_adapters::AgentType::Unknown,
+        };
+        results.push((
+            indexed.clone().into_session_info(agent),
+            chunk.entry_id,
+            score,
+        ));
+    }
+
+    Ok(results)
+}
+
+/// CLI entry point: (re)build the index over every discovered session,
+/// then run `query` against it (unless `query` is empty, in which case
+/// this just updates the index and reports how many sessions changed).
+pub fn run(query: &str, limit: usize, json: bool) -> Result<()> {
+    let sessions = find_all_sessions()?;
+    let provider = select_provider();
+
+    let reindexed = build_or_update_index(&sessions, provider.as_ref())?;
+
+    if query.trim().is_empty() {
+        println!("Indexed {} session(s) ({} updated).", sessions.len(), reindexed);
+        return Ok(());
+    }
+
+    let hits = search(query, limit, provider.as_ref())?;
+
+    if json {
+        #[derive(Serialize)]
+        struct Hit {
+            path: String,
+            agent: String,
+            title: String,
+            entry_id: String,
+            score: f32,
+        }
+        let out: Vec<Hit> = hits
+            .iter()
+            .map(|(session, entry_id, score)| Hit {
+                path: session.path.to_string_lossy().to_string(),
+                agent: session.agent.as_str().to_string(),
+                title: session.title.clone().unwrap_or_else(|| "Untitled".to_string()),
+                entry_id: entry_id.to_string(),
+                score: *score,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No semantic matches for \"{}\".", query);
+        return Ok(());
+    }
+
+    for (session, entry_id, score) in &hits {
+        let badge = match session.agent.as_str() {
+            "claude-code" => "CC",
+            "codex" => "CX",
+            "cursor" => "CU",
+            "aider" => "AI",
+            "aichat" => "AC",
+            _ => "??",
+        };
+        println!(
+            "[{}] {:.3}  {}",
+            badge,
+            score,
+            session.title.as_deref().unwrap_or("Untitled")
+        );
+        println!("     {}  (entry {})", session.path.display(), entry_id);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_embedder_produces_unit_vectors() {
+        let embedder = LocalHashEmbedder::new(32);
+        let vector = embedder.embed("the quick brown fox jumps").unwrap();
+        assert_eq!(vector.len(), 32);
+        let magnitude: f32 = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((magnitude - 1.0).abs() < 1e-4 || magnitude == 0.0);
+    }
+
+    #[test]
+    fn test_cosine_similarity_identical_is_one() {
+        let embedder = LocalHashEmbedder::new(32);
+        let vector = embedder.embed("spool sessions are great").unwrap();
+        let score = cosine_similarity(&vector, &vector);
+        assert!((score - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cosine_similarity_unrelated_text_scores_lower() {
+        let embedder = LocalHashEmbedder::new(256);
+        let a = embedder.embed("debugging a rust borrow checker error").unwrap();
+        let b = embedder.embed("debugging a rust borrow checker issue").unwrap();
+        let c = embedder.embed("baking sourdough bread this weekend").unwrap();
+        assert!(cosine_similarity(&a, &b) > cosine_similarity(&a, &c));
+    }
+
+    #[test]
+    fn test_chunk_text_splits_on_whitespace_boundary() {
+        let text = "one two three four five six seven eight";
+        let chunks = chunk_text(text, 10);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 10);
+            assert_eq!(chunk, chunk.trim());
+        }
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let rejoined: Vec<&str> = chunks.iter().flat_map(|c| c.split_whitespace()).collect();
+        assert_eq!(rejoined, words);
+    }
+
+    #[test]
+    fn test_chunk_text_skips_empty_input() {
+        assert!(chunk_text("   ", 10).is_empty());
+        assert!(chunk_text("", 10).is_empty());
+    }
+
+    fn test_session(path: &str, modified_at: Option<DateTime<Utc>>) -> SessionInfo {
+        SessionInfo {
+            path: PathBuf::from(path),
+            agent: spool_adapters::AgentType::ClaudeCode,
+            created_at: None,
+            modified_at,
+            title: None,
+            project_dir: None,
+            message_count: None,
+        }
+    }
+
+    #[test]
+    fn test_session_up_to_date_matches_on_path_and_mtime() {
+        let mtime = Utc::now();
+        let mut index = SemanticIndex::default();
+        let indexed_session = test_session("/a.jsonl", Some(mtime));
+        index.sessions.insert(
+            indexed_session.path.clone(),
+            IndexedSession::from_session_info(&indexed_session),
+        );
+
+        assert!(session_up_to_date(&index, &test_session("/a.jsonl", Some(mtime))));
+    }
+
+    #[test]
+    fn test_session_up_to_date_false_when_modified() {
+        let mut index = SemanticIndex::default();
+        let original = test_session("/a.jsonl", Some(Utc::now()));
+        index
+            .sessions
+            .insert(original.path.clone(), IndexedSession::from_session_info(&original));
+
+        let later = test_session("/a.jsonl", Some(Utc::now() + chrono::Duration::seconds(1)));
+        assert!(!session_up_to_date(&index, &later));
+    }
+
+    #[test]
+    fn test_session_up_to_date_false_when_unseen() {
+        let index = SemanticIndex::default();
+        assert!(!session_up_to_date(&index, &test_session("/new.jsonl", Some(Utc::now()))));
+    }
+}
@@ -0,0 +1,14 @@
+//! Serve command - Accept live remote recordings over `spool-net`.
+
+use anyhow::{Context, Result};
+use spool_net::SessionServer;
+use std::path::Path;
+
+/// Bind `addr` and append every connecting agent's entries to
+/// `<dir>/<session_id>.spool` in real time, blocking until killed
+/// (Ctrl-C).
+pub fn run(addr: &str, dir: &Path) -> Result<()> {
+    println!("📡 Listening on {addr}, writing sessions into {:?}", dir);
+    let server = SessionServer::new(dir);
+    server.serve(addr).with_context(|| format!("Failed to serve on {addr}"))
+}
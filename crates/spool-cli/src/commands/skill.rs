@@ -1,4 +1,10 @@
-//! Skill management commands - install/uninstall Claude Code skills.
+//! Skill management commands - install/uninstall agent command helpers.
+//!
+//! Each adapter in [`spool_adapters::registry`] advertises its own
+//! [`spool_adapters::AdapterRegistration::skill_install_dir`] (`None` if it
+//! doesn't support one), so installing/uninstalling/locating the skill file
+//! fans out to every agent that has one rather than hardcoding Claude
+//! Code's `~/.claude/commands`.
 
 use anyhow::{Context, Result};
 use std::fs;
@@ -7,58 +13,66 @@ use std::path::PathBuf;
 /// The embedded skill content (compiled into the binary).
 const SPOOL_SKILL: &str = include_str!("../../../../skills/spool.md");
 
-/// Get the Claude Code commands directory.
-fn claude_commands_dir() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    Ok(home.join(".claude").join("commands"))
+/// Target directories of every agent that supports a skill/command helper.
+fn skill_install_dirs() -> Vec<PathBuf> {
+    spool_adapters::registry()
+        .iter()
+        .filter_map(|a| a.skill_install_dir())
+        .collect()
 }
 
-/// Install the spool skill to Claude Code.
+/// Install the spool skill to every agent that supports one.
 pub fn install() -> Result<()> {
-    let commands_dir = claude_commands_dir()?;
-
-    // Create directory if it doesn't exist
-    if !commands_dir.exists() {
-        fs::create_dir_all(&commands_dir)
-            .with_context(|| format!("Failed to create {:?}", commands_dir))?;
-        println!("Created {:?}", commands_dir);
+    let dirs = skill_install_dirs();
+    if dirs.is_empty() {
+        println!("No agent on this system supports a command/skill helper.");
+        return Ok(());
     }
 
-    let skill_path = commands_dir.join("spool.md");
+    for commands_dir in dirs {
+        if !commands_dir.exists() {
+            fs::create_dir_all(&commands_dir)
+                .with_context(|| format!("Failed to create {:?}", commands_dir))?;
+            println!("Created {:?}", commands_dir);
+        }
+
+        let skill_path = commands_dir.join("spool.md");
 
-    // Check if already installed
-    if skill_path.exists() {
-        let existing = fs::read_to_string(&skill_path)?;
-        if existing == SPOOL_SKILL {
-            println!("Skill already installed and up to date: {:?}", skill_path);
-            return Ok(());
+        if skill_path.exists() {
+            let existing = fs::read_to_string(&skill_path)?;
+            if existing == SPOOL_SKILL {
+                println!("Skill already installed and up to date: {:?}", skill_path);
+                continue;
+            }
+            println!("Updating existing skill...");
         }
-        println!("Updating existing skill...");
-    }
 
-    fs::write(&skill_path, SPOOL_SKILL)
-        .with_context(|| format!("Failed to write {:?}", skill_path))?;
+        fs::write(&skill_path, SPOOL_SKILL)
+            .with_context(|| format!("Failed to write {:?}", skill_path))?;
+
+        println!("Installed spool skill to {:?}", skill_path);
+    }
 
-    println!("Installed spool skill to {:?}", skill_path);
-    println!("\nClaude Code can now use /spool to get help with spool commands.");
-    println!("Try asking: \"How do I export a session with redaction?\"");
+    println!("\nAsk your agent for help with spool, e.g. \"How do I export a session with redaction?\"");
 
     Ok(())
 }
 
-/// Uninstall the spool skill from Claude Code.
+/// Uninstall the spool skill from every agent that supports one.
 pub fn uninstall() -> Result<()> {
-    let commands_dir = claude_commands_dir()?;
-    let skill_path = commands_dir.join("spool.md");
+    for commands_dir in skill_install_dirs() {
+        let skill_path = commands_dir.join("spool.md");
 
-    if !skill_path.exists() {
-        println!("Skill not installed: {:?}", skill_path);
-        return Ok(());
-    }
+        if !skill_path.exists() {
+            println!("Skill not installed: {:?}", skill_path);
+            continue;
+        }
 
-    fs::remove_file(&skill_path).with_context(|| format!("Failed to remove {:?}", skill_path))?;
+        fs::remove_file(&skill_path)
+            .with_context(|| format!("Failed to remove {:?}", skill_path))?;
 
-    println!("Uninstalled spool skill from {:?}", skill_path);
+        println!("Uninstalled spool skill from {:?}", skill_path);
+    }
 
     Ok(())
 }
@@ -69,17 +83,22 @@ pub fn show() -> Result<()> {
     Ok(())
 }
 
-/// Show where the skill would be installed.
+/// Show where the skill would be installed for each supporting agent.
 pub fn path() -> Result<()> {
-    let commands_dir = claude_commands_dir()?;
-    let skill_path = commands_dir.join("spool.md");
-
-    println!("{}", skill_path.display());
+    let dirs = skill_install_dirs();
+    if dirs.is_empty() {
+        println!("No agent on this system supports a command/skill helper.");
+        return Ok(());
+    }
 
-    if skill_path.exists() {
-        println!("(installed)");
-    } else {
-        println!("(not installed)");
+    for commands_dir in dirs {
+        let skill_path = commands_dir.join("spool.md");
+        print!("{}", skill_path.display());
+        if skill_path.exists() {
+            println!(" (installed)");
+        } else {
+            println!(" (not installed)");
+        }
     }
 
     Ok(())
@@ -1,43 +1,158 @@
 //! Validate command - Check a .spool file for errors.
 
 use anyhow::{Context, Result};
-use spool_format::{validate_default, SpoolFile};
+use serde::Serialize;
+use spool_format::{validate_default, Diagnostic, EntryId, LineParseError, SourceSpan, SpoolFile};
 use std::path::Path;
 
-pub fn run(path: &Path) -> Result<()> {
-    println!("🔍 Validating: {:?}\n", path);
+/// Format a diagnostic's source location for display, e.g. " (line 12)".
+fn format_location(location: Option<SourceSpan>) -> String {
+    match location {
+        Some(span) => format!(" (line {})", span.line),
+        None => String::new(),
+    }
+}
+
+/// Render a pinpointed parse failure as `error at line L, column C` followed
+/// by the offending source line and a caret underline, e.g.:
+///
+/// ```text
+/// error at line 7, column 12
+///     {"id": "oops", "content": }
+///            ^
+/// ```
+fn format_parse_error(err: &LineParseError) -> String {
+    let caret = " ".repeat(err.col.saturating_sub(1)) + &"^".repeat(err.span_len.max(1));
+    format!(
+        "error at line {}, column {}: {}\n    {}\n    {}",
+        err.line, err.col, err.message, err.source_line, caret
+    )
+}
+
+/// A single diagnostic, shaped for machine consumption: a stable `code`
+/// (the rule name) plus a pointer back to the offending entry.
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry_id: Option<EntryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<&Diagnostic> for JsonDiagnostic {
+    fn from(d: &Diagnostic) -> Self {
+        JsonDiagnostic {
+            code: d.rule,
+            message: d.message.clone(),
+            entry_index: d.entry_index,
+            entry_id: d.entry_id,
+            line: d.location.map(|span| span.line),
+        }
+    }
+}
+
+/// A pinpointed parse failure, shaped for machine consumption.
+#[derive(Serialize)]
+struct JsonParseError {
+    line: usize,
+    col: usize,
+    span_len: usize,
+    message: String,
+    source_line: String,
+}
+
+impl From<&LineParseError> for JsonParseError {
+    fn from(e: &LineParseError) -> Self {
+        JsonParseError {
+            line: e.line,
+            col: e.col,
+            span_len: e.span_len,
+            message: e.message.clone(),
+            source_line: e.source_line.clone(),
+        }
+    }
+}
 
+#[derive(Serialize)]
+struct JsonReport {
+    valid: bool,
+    errors: Vec<JsonDiagnostic>,
+    warnings: Vec<JsonDiagnostic>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    parse_errors: Vec<JsonParseError>,
+}
+
+pub fn run(path: &Path, json: bool) -> Result<()> {
     let file = SpoolFile::from_path(path).with_context(|| format!("Failed to read: {:?}", path))?;
 
     let result = validate_default(&file);
 
-    if result.is_valid() && result.warnings.is_empty() {
+    let errors: Vec<_> = result.errors().collect();
+    let warnings: Vec<_> = result.warnings().collect();
+    let is_valid = result.is_valid() && file.parse_errors.is_empty();
+    let total_errors = errors.len() + file.parse_errors.len();
+
+    if json {
+        let report = JsonReport {
+            valid: is_valid,
+            errors: errors.iter().map(|d| JsonDiagnostic::from(*d)).collect(),
+            warnings: warnings.iter().map(|d| JsonDiagnostic::from(*d)).collect(),
+            parse_errors: file.parse_errors.iter().map(JsonParseError::from).collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return if is_valid {
+            Ok(())
+        } else {
+            anyhow::bail!("Validation failed with {} error(s)", total_errors);
+        };
+    }
+
+    println!("🔍 Validating: {:?}\n", path);
+
+    if !file.parse_errors.is_empty() {
+        println!("❌ Parse errors ({}):", file.parse_errors.len());
+        for err in &file.parse_errors {
+            println!("{}", format_parse_error(err));
+        }
+        println!();
+    }
+
+    if result.diagnostics.is_empty() && file.parse_errors.is_empty() {
         println!("✅ File is valid!");
         println!("   Version: {}", file.session.version);
         println!("   Entries: {}", file.entries.len());
         return Ok(());
     }
 
-    if !result.errors.is_empty() {
-        println!("❌ Errors ({}):", result.errors.len());
-        for error in &result.errors {
-            println!("   • {}", error);
+    if !errors.is_empty() {
+        println!("❌ Errors ({}):", errors.len());
+        for error in &errors {
+            println!("   • [{}]{} {}", error.rule, format_location(error.location), error.message);
         }
         println!();
     }
 
-    if !result.warnings.is_empty() {
-        println!("⚠️  Warnings ({}):", result.warnings.len());
-        for warning in &result.warnings {
-            println!("   • {}", warning);
+    if !warnings.is_empty() {
+        println!("⚠️  Warnings ({}):", warnings.len());
+        for warning in &warnings {
+            println!(
+                "   • [{}]{} {}",
+                warning.rule,
+                format_location(warning.location),
+                warning.message
+            );
         }
         println!();
     }
 
-    if result.is_valid() {
+    if is_valid {
         println!("✅ File is valid (with warnings)");
         Ok(())
     } else {
-        anyhow::bail!("Validation failed with {} error(s)", result.errors.len());
+        anyhow::bail!("Validation failed with {} error(s)", total_errors);
     }
 }
@@ -0,0 +1,181 @@
+//! Verify command - scan discovered sessions for corrupt or malformed logs.
+//!
+//! `SpoolFile::from_reader` already accumulates `unparsed_lines` and can
+//! fail with `MissingSessionEntry`, but nothing surfaced either to users
+//! before this. `run` loads every discovered session (in parallel) and
+//! reports anything wrong with it, exiting non-zero if any session is
+//! broken so this can be wired into a pre-commit hook or CI check.
+
+use anyhow::Result;
+use serde::Serialize;
+use spool_adapters::SessionInfo;
+use spool_format::Entry;
+
+use super::agent::load_spool_or_log;
+use super::cache::catalog;
+
+/// One problem found in a session, e.g. a line that failed to parse.
+#[derive(Serialize)]
+struct VerifyIssue {
+    kind: String,
+    detail: String,
+}
+
+/// Per-session verification result, in the spirit of `list`'s `SessionRow`.
+#[derive(Serialize)]
+struct SessionStatus {
+    path: String,
+    agent: String,
+    ok: bool,
+    issues: Vec<VerifyIssue>,
+}
+
+pub fn run(agent_filter: Option<&str>, json: bool) -> Result<()> {
+    let sessions: Vec<SessionInfo> = catalog::scan()?
+        .into_iter()
+        .filter(|s| agent_filter.map(|f| s.agent.as_str() == f).unwrap_or(true))
+        .collect();
+
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let chunk_size = sessions.len().div_ceil(worker_count.max(1)).max(1);
+
+    let statuses: Vec<SessionStatus> = std::thread::scope(|scope| {
+        let handles: Vec<_> = sessions
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(verify_one).collect::<Vec<_>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+
+    let broken_count = statuses.iter().filter(|s| !s.ok).count();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+    } else {
+        for status in statuses.iter().filter(|s| !s.ok) {
+            println!("❌ {} ({})", status.path, status.agent);
+            for issue in &status.issues {
+                println!("   • [{}] {}", issue.kind, issue.detail);
+            }
+        }
+        if broken_count == 0 {
+            println!("✅ {} session(s) checked, none broken.", statuses.len());
+        } else {
+            println!(
+                "\n{} session(s) checked, {} broken.",
+                statuses.len(),
+                broken_count
+            );
+        }
+    }
+
+    if broken_count == 0 {
+        Ok(())
+    } else {
+        anyhow::bail!("{} session(s) failed verification", broken_count);
+    }
+}
+
+fn verify_one(session: &SessionInfo) -> SessionStatus {
+    let path = session.path.to_string_lossy().to_string();
+    let agent = session.agent.as_str().to_string();
+
+    let file = match load_spool_or_log(&session.path) {
+        Ok(file) => file,
+        Err(e) => {
+            let message = e.to_string();
+            let kind = if message.contains("File must start with a session entry") {
+                "missing_session_entry"
+            } else {
+                "load_error"
+            };
+            return SessionStatus {
+                path,
+                agent,
+                ok: false,
+                issues: vec![VerifyIssue {
+                    kind: kind.to_string(),
+                    detail: message,
+                }],
+            };
+        }
+    };
+
+    let mut issues = Vec::new();
+
+    if !matches!(file.entries.first(), Some(Entry::Session(_))) {
+        issues.push(VerifyIssue {
+            kind: "missing_session_entry".to_string(),
+            detail: "first entry is not a session entry".to_string(),
+        });
+    }
+
+    let last_line = file
+        .entry_spans
+        .iter()
+        .flatten()
+        .map(|span| span.line)
+        .chain(file.unparsed_lines.iter().map(|(line, _)| *line))
+        .max()
+        .unwrap_or(0);
+
+    for (line, content) in &file.unparsed_lines {
+        if *line == last_line && content.len() < 2 {
+            issues.push(VerifyIssue {
+                kind: "truncated_trailing_json".to_string(),
+                detail: format!("line {} looks like a truncated write", line),
+            });
+        } else {
+            issues.push(VerifyIssue {
+                kind: "unparsed_line".to_string(),
+                detail: format!("line {} failed to parse", line),
+            });
+        }
+    }
+
+    let mut last_ts: Option<u64> = None;
+    for entry in &file.entries {
+        if let Some(ts) = entry.timestamp() {
+            if let Some(last) = last_ts {
+                if ts < last {
+                    issues.push(VerifyIssue {
+                        kind: "non_monotonic_timestamp".to_string(),
+                        detail: format!("timestamp {} follows later timestamp {}", ts, last),
+                    });
+                }
+            }
+            last_ts = Some(ts);
+        }
+    }
+
+    if let Some(trimmed) = file.session.trimmed.as_ref() {
+        let (start, end) = trimmed.kept_range;
+        let out_of_range = file
+            .entries
+            .iter()
+            .skip(1)
+            .any(|e| e.timestamp().is_some_and(|ts| ts < start || ts > end));
+        if out_of_range {
+            issues.push(VerifyIssue {
+                kind: "inconsistent_trim".to_string(),
+                detail: format!(
+                    "entries fall outside kept_range ({}, {})",
+                    start, end
+                ),
+            });
+        }
+    }
+
+    SessionStatus {
+        path,
+        agent,
+        ok: issues.is_empty(),
+        issues,
+    }
+}
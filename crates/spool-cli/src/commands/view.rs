@@ -1,10 +1,14 @@
 //! View command - Display a session file.
 
 use anyhow::Result;
-use spool_format::Entry;
+use spool_format::{Entry, EntryId};
+use std::collections::HashMap;
 use std::path::Path;
 
 use super::agent::load_spool_or_log;
+use crate::ansi;
+use crate::diff;
+use crate::highlight;
 
 /// Truncate a string to at most `max_bytes` bytes at a char boundary.
 fn truncate_str(s: &str, max_bytes: usize) -> &str {
@@ -30,14 +34,72 @@ pub fn run(path: &Path) -> Result<()> {
     println!("   Entries: {}", file.entries.len());
     println!();
 
+    let ctx = EntryContext::from_entries(&file.entries);
     for entry in &file.entries {
-        print_entry(entry);
+        print_entry_ctx(entry, &ctx);
     }
 
     Ok(())
 }
 
+/// Per-session context gathered upfront so entries that need more than
+/// themselves to render (a `ToolResult` inferring its syntax-highlighting
+/// language from the `ToolCall` it answers) don't require widening
+/// [`print_entry`]'s signature.
+#[derive(Default)]
+pub struct EntryContext {
+    tool_languages: HashMap<EntryId, String>,
+}
+
+impl EntryContext {
+    /// Scan `entries` once, recording each `ToolCall`'s inferred language
+    /// (see [`highlight::infer_tool_language`]) keyed by its id, so a later
+    /// `ToolResult` (which references it via `call_id`) can look it up.
+    pub fn from_entries(entries: &[Entry]) -> Self {
+        let mut tool_languages = HashMap::new();
+        for entry in entries {
+            if let Entry::ToolCall(tc) = entry {
+                if let Some(lang) = highlight::infer_tool_language(&tc.tool, &tc.input) {
+                    tool_languages.insert(tc.id, lang);
+                }
+            }
+        }
+        EntryContext { tool_languages }
+    }
+}
+
+/// Syntax-highlight a response's fenced code blocks in place, leaving the
+/// surrounding prose untouched. Language comes from each block's own fence
+/// info string or guessed language (see [`spool_format::extract_code_blocks`]).
+fn highlight_response_content(content: &str) -> String {
+    let blocks = spool_format::extract_code_blocks(content);
+    if blocks.is_empty() {
+        return content.to_string();
+    }
+
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for block in &blocks {
+        out.push_str(&content[last..block.byte_range.0]);
+        let code = &content[block.byte_range.0..block.byte_range.1];
+        out.push_str(&highlight::highlight_to_ansi(code, block.language.as_deref()));
+        last = block.byte_range.1;
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// Print a single entry with no cross-entry context. Tool results print
+/// without syntax highlighting (there's no originating `ToolCall` to
+/// infer a language from); use [`print_entry_ctx`] with an
+/// [`EntryContext`] built from the whole session to get that.
 pub fn print_entry(entry: &Entry) {
+    print_entry_ctx(entry, &EntryContext::default());
+}
+
+/// Print a single entry, using `ctx` to look up syntax-highlighting hints
+/// gathered from the rest of the session.
+pub fn print_entry_ctx(entry: &Entry, ctx: &EntryContext) {
     match entry {
         Entry::Session(_) => {
             // Already printed above
@@ -73,10 +135,19 @@ pub fn print_entry(entry: &Entry) {
                 tc.tool.clone()
             };
             println!("┌─ TOOL: {} ─────────────────────────────", tool_display);
-            println!(
-                "│ Input: {}",
-                serde_json::to_string(&tc.input).unwrap_or_default()
-            );
+            if let Some(rows) = diff::diff_for_tool_call(&tc.tool, &tc.input) {
+                if let Some(path) = tc.input.get("file_path").and_then(|v| v.as_str()) {
+                    println!("│ {}", path);
+                }
+                for line in diff::diff_to_ansi(&rows).lines() {
+                    println!("│ {}", line);
+                }
+            } else {
+                println!(
+                    "│ Input: {}",
+                    serde_json::to_string(&tc.input).unwrap_or_default()
+                );
+            }
             println!("└──────────────────────────────────────────────");
         }
         Entry::ToolResult(tr) => {
@@ -87,12 +158,28 @@ pub fn print_entry(entry: &Entry) {
                     spool_format::ToolOutput::Text(t) => t.clone(),
                     spool_format::ToolOutput::Binary(_) => "[binary content]".to_string(),
                 };
-                let preview = if text.len() > 200 {
-                    format!("{}...", truncate_str(&text, 200))
+                let rendered = if text.contains('\x1b') {
+                    // Raw terminal output (cargo/git/test colors): parse
+                    // and re-emit the SGR codes instead of dumping the
+                    // escape bytes, truncating by visible character count
+                    // so a cut never lands inside a stripped escape.
+                    let (spans, truncated) =
+                        ansi::truncate_spans(ansi::parse_ansi_spans(&text), 200);
+                    let mut rendered = ansi::spans_to_ansi(&spans);
+                    if truncated {
+                        rendered.push_str("...");
+                    }
+                    rendered
                 } else {
-                    text
+                    let preview = if text.len() > 200 {
+                        format!("{}...", truncate_str(&text, 200))
+                    } else {
+                        text
+                    };
+                    let lang = ctx.tool_languages.get(&tr.call_id).map(|s| s.as_str());
+                    highlight::highlight_to_ansi(&preview, lang)
                 };
-                for line in preview.lines().take(5) {
+                for line in rendered.lines().take(5) {
                     println!("│ {}", line);
                 }
             }
@@ -104,7 +191,7 @@ pub fn print_entry(entry: &Entry) {
         }
         Entry::Response(r) => {
             println!("┌─ RESPONSE ───────────────────────────────────");
-            for line in r.content.lines().take(10) {
+            for line in highlight_response_content(&r.content).lines().take(10) {
                 println!("│ {}", line);
             }
             if r.content.lines().count() > 10 {
@@ -129,6 +216,20 @@ pub fn print_entry(entry: &Entry) {
         Entry::RedactionMarker(r) => {
             println!("   🔒 Redacted: {:?}", r.reason);
         }
+        Entry::Terminal(t) => {
+            println!("┌─ TERMINAL ───────────────────────────────────");
+            let text = String::from_utf8_lossy(&t.decoded_bytes()).into_owned();
+            let (spans, truncated) = ansi::truncate_spans(ansi::parse_ansi_spans(&text), 200);
+            let mut rendered = ansi::spans_to_ansi(&spans);
+            if truncated {
+                rendered.push_str("...");
+            }
+            for line in rendered.lines().take(5) {
+                println!("│ {}", line);
+            }
+            println!("└──────────────────────────────────────────────");
+            println!();
+        }
         Entry::SubagentStart(s) => {
             println!("┌─ SUBAGENT: {} ─────────────────────────", s.agent);
             if let Some(ref ctx) = s.context {
@@ -151,7 +252,9 @@ pub fn print_entry(entry: &Entry) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use spool_format::{PromptEntry, ResponseEntry, ThinkingEntry, ToolOutput, ToolResultEntry};
+    use spool_format::{
+        PromptEntry, ResponseEntry, ThinkingEntry, ToolCallEntry, ToolOutput, ToolResultEntry,
+    };
     use std::collections::HashMap;
     use uuid::Uuid;
 
@@ -322,4 +425,147 @@ mod tests {
         });
         print_entry(&entry);
     }
+
+    // ── EntryContext / highlighting ────────────────────────────────────
+
+    #[test]
+    fn entry_context_maps_tool_result_to_its_call_language() {
+        let call_id = Uuid::new_v4();
+        let entries = vec![
+            Entry::ToolCall(ToolCallEntry {
+                id: call_id,
+                ts: 0,
+                tool: "Read".to_string(),
+                input: serde_json::json!({"file_path": "src/main.rs"}),
+                subagent_id: None,
+                extra: HashMap::new(),
+            }),
+            Entry::ToolResult(ToolResultEntry {
+                id: make_id(),
+                ts: 0,
+                call_id,
+                output: Some(ToolOutput::Text("fn main() {}".to_string())),
+                error: None,
+                truncated: None,
+                original_bytes: None,
+                subagent_id: None,
+                redacted: None,
+                extra: HashMap::new(),
+            }),
+        ];
+        let ctx = EntryContext::from_entries(&entries);
+        assert_eq!(ctx.tool_languages.get(&call_id).map(|s| s.as_str()), Some("rs"));
+    }
+
+    #[test]
+    fn entry_context_ignores_tools_without_a_file_path() {
+        let entries = vec![Entry::ToolCall(ToolCallEntry {
+            id: make_id(),
+            ts: 0,
+            tool: "Bash".to_string(),
+            input: serde_json::json!({"command": "ls"}),
+            subagent_id: None,
+            extra: HashMap::new(),
+        })];
+        let ctx = EntryContext::from_entries(&entries);
+        assert!(ctx.tool_languages.is_empty());
+    }
+
+    #[test]
+    fn print_entry_ctx_with_tool_call_context_does_not_panic() {
+        let call_id = Uuid::new_v4();
+        let entries = vec![
+            Entry::ToolCall(ToolCallEntry {
+                id: call_id,
+                ts: 0,
+                tool: "Read".to_string(),
+                input: serde_json::json!({"file_path": "src/main.rs"}),
+                subagent_id: None,
+                extra: HashMap::new(),
+            }),
+            Entry::ToolResult(ToolResultEntry {
+                id: make_id(),
+                ts: 0,
+                call_id,
+                output: Some(ToolOutput::Text("fn main() {}".to_string())),
+                error: None,
+                truncated: None,
+                original_bytes: None,
+                subagent_id: None,
+                redacted: None,
+                extra: HashMap::new(),
+            }),
+        ];
+        let ctx = EntryContext::from_entries(&entries);
+        for entry in &entries {
+            print_entry_ctx(entry, &ctx);
+        }
+    }
+
+    #[test]
+    fn print_entry_tool_result_with_ansi_codes_does_not_panic() {
+        let text = "\x1b[31mFAILED\x1b[0m: 3 tests\n\x1b[32mok\x1b[0m: 42 tests".to_string();
+        let entry = Entry::ToolResult(ToolResultEntry {
+            id: make_id(),
+            ts: 0,
+            call_id: make_id(),
+            output: Some(ToolOutput::Text(text)),
+            error: None,
+            truncated: None,
+            original_bytes: None,
+            subagent_id: None,
+            redacted: None,
+            extra: HashMap::new(),
+        });
+        print_entry(&entry);
+    }
+
+    #[test]
+    fn print_entry_tool_call_edit_renders_diff_not_raw_json() {
+        let entry = Entry::ToolCall(ToolCallEntry {
+            id: make_id(),
+            ts: 0,
+            tool: "Edit".to_string(),
+            input: serde_json::json!({
+                "file_path": "src/lib.rs",
+                "old_string": "fn a() {}",
+                "new_string": "fn a() { todo!() }"
+            }),
+            subagent_id: None,
+            extra: HashMap::new(),
+        });
+        // Should not panic, and should take the diff branch (exercised via
+        // diff::diff_for_tool_call returning Some for "Edit" elsewhere).
+        print_entry(&entry);
+    }
+
+    #[test]
+    fn print_entry_tool_call_write_does_not_panic() {
+        let entry = Entry::ToolCall(ToolCallEntry {
+            id: make_id(),
+            ts: 0,
+            tool: "Write".to_string(),
+            input: serde_json::json!({"file_path": "src/new.rs", "content": "fn new() {}"}),
+            subagent_id: None,
+            extra: HashMap::new(),
+        });
+        print_entry(&entry);
+    }
+
+    #[test]
+    fn print_entry_response_with_fenced_code_does_not_panic() {
+        let content = "Here's the fix:\n```rust\nfn a() {}\n```\nDone.";
+        let entry = Entry::Response(ResponseEntry {
+            id: make_id(),
+            ts: 0,
+            content: content.to_string(),
+            truncated: None,
+            original_bytes: None,
+            model: None,
+            token_usage: None,
+            subagent_id: None,
+            extra: HashMap::new(),
+        });
+        print_entry(&entry);
+    }
 }
@@ -0,0 +1,357 @@
+//! Line-oriented unified diff rendering for Edit/MultiEdit/Write tool
+//! calls.
+//!
+//! [`diff_lines`] computes an LCS-based line diff between an old and new
+//! text, then collapses long unchanged runs into `@@`-style context
+//! hunks — a few lines of surrounding context, like a real unified diff —
+//! via [`collapse_context`]. [`diff_for_tool_call`] pulls the right
+//! fields out of a tool call's `input` for the tools that carry a diff,
+//! so `print_entry` (ANSI) and the TUI preview (ratatui spans) can share
+//! one renderer instead of each re-deriving it from the raw JSON.
+
+use ratatui::style::{Color, Style};
+
+/// Lines of context kept on each side of a collapsed unchanged run.
+const CONTEXT_LINES: usize = 3;
+
+/// Whether a [`DiffLine`] was removed, added, or unchanged context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// One row of a rendered diff: a real line, a run of unchanged lines
+/// collapsed into a `@@ N unchanged lines @@` marker, or (for
+/// `MultiEdit`) a separator between one edit's diff and the next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffRow {
+    Line(DiffLine),
+    Collapsed(usize),
+    Separator(String),
+}
+
+/// Compute a line-oriented unified diff between `old` and `new`: the
+/// longest-common-subsequence of their lines decides what's unchanged,
+/// then [`collapse_context`] folds long unchanged runs down to a few
+/// lines of context plus a count of what was hidden.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffRow> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    collapse_context(lcs_diff(&old_lines, &new_lines))
+}
+
+/// Classic LCS-table line diff: `dp[i][j]` is the LCS length of
+/// `old[i..]` and `new[j..]`, then a reverse walk through the table picks
+/// unchanged/removed/added lines in one pass.
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let n = old.len();
+    let m = new.len();
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if old[i] == new[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            lines.push(DiffLine {
+                kind: DiffLineKind::Context,
+                text: old[i].to_string(),
+            });
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            lines.push(DiffLine {
+                kind: DiffLineKind::Removed,
+                text: old[i].to_string(),
+            });
+            i += 1;
+        } else {
+            lines.push(DiffLine {
+                kind: DiffLineKind::Added,
+                text: new[j].to_string(),
+            });
+            j += 1;
+        }
+    }
+    while i < n {
+        lines.push(DiffLine {
+            kind: DiffLineKind::Removed,
+            text: old[i].to_string(),
+        });
+        i += 1;
+    }
+    while j < m {
+        lines.push(DiffLine {
+            kind: DiffLineKind::Added,
+            text: new[j].to_string(),
+        });
+        j += 1;
+    }
+    lines
+}
+
+/// Fold any run of unchanged (`Context`) lines longer than
+/// `2 * CONTEXT_LINES` down to `CONTEXT_LINES` lines of leading and
+/// trailing context plus a `DiffRow::Collapsed` count of what's hidden
+/// between them. A run at the very start or end of the diff only keeps
+/// the context that borders a change, matching how a real unified diff
+/// never shows context past the last hunk.
+fn collapse_context(lines: Vec<DiffLine>) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].kind != DiffLineKind::Context {
+            rows.push(DiffRow::Line(lines[i].clone()));
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < lines.len() && lines[i].kind == DiffLineKind::Context {
+            i += 1;
+        }
+        let run = &lines[start..i];
+
+        if run.len() <= CONTEXT_LINES * 2 {
+            rows.extend(run.iter().cloned().map(DiffRow::Line));
+            continue;
+        }
+
+        let lead = if start == 0 { 0 } else { CONTEXT_LINES };
+        let trail = if i == lines.len() { 0 } else { CONTEXT_LINES };
+
+        rows.extend(run[..lead].iter().cloned().map(DiffRow::Line));
+        let hidden = run.len() - lead - trail;
+        if hidden > 0 {
+            rows.push(DiffRow::Collapsed(hidden));
+        }
+        rows.extend(run[run.len() - trail..].iter().cloned().map(DiffRow::Line));
+    }
+    rows
+}
+
+/// Pull a unified diff's (old, new) text out of a tool call's `input`,
+/// for the tools that carry one: `Edit`'s `old_string`/`new_string`,
+/// `MultiEdit`'s array of the same (rendered back-to-back, separated by
+/// a `DiffRow::Separator`), and `Write`'s `content` (diffed against
+/// nothing, since the prior file content isn't recorded — the whole
+/// thing renders as added). Any other tool has no diff to show.
+pub fn diff_for_tool_call(tool: &str, input: &serde_json::Value) -> Option<Vec<DiffRow>> {
+    match tool {
+        "Edit" => {
+            let old = input.get("old_string").and_then(|v| v.as_str())?;
+            let new = input.get("new_string").and_then(|v| v.as_str()).unwrap_or("");
+            Some(diff_lines(old, new))
+        }
+        "MultiEdit" => {
+            let edits = input.get("edits").and_then(|v| v.as_array())?;
+            let mut rows = Vec::new();
+            for (idx, edit) in edits.iter().enumerate() {
+                let old = edit.get("old_string").and_then(|v| v.as_str()).unwrap_or("");
+                let new = edit.get("new_string").and_then(|v| v.as_str()).unwrap_or("");
+                if idx > 0 {
+                    rows.push(DiffRow::Separator(format!("--- edit {} ---", idx + 1)));
+                }
+                rows.extend(diff_lines(old, new));
+            }
+            Some(rows)
+        }
+        "Write" => {
+            let content = input.get("content").and_then(|v| v.as_str())?;
+            Some(diff_lines("", content))
+        }
+        _ => None,
+    }
+}
+
+fn collapsed_label(n: usize) -> String {
+    format!("@@ {} unchanged line{} @@", n, if n == 1 { "" } else { "s" })
+}
+
+/// Render `rows` as a `spool view` ANSI string: `-` lines in red, `+`
+/// lines in green, ` ` context lines plain, collapsed-context markers and
+/// edit separators in dim gray. Honors `NO_COLOR` by falling back to the
+/// bare `-`/`+`/` ` prefixes with no escape codes.
+pub fn diff_to_ansi(rows: &[DiffRow]) -> String {
+    let color = std::env::var_os("NO_COLOR").is_none();
+    let mut out = String::new();
+    for row in rows {
+        match row {
+            DiffRow::Line(line) => {
+                let (prefix, code) = match line.kind {
+                    DiffLineKind::Context => (" ", None),
+                    DiffLineKind::Removed => ("-", Some("\x1b[31m")),
+                    DiffLineKind::Added => ("+", Some("\x1b[32m")),
+                };
+                match (color, code) {
+                    (true, Some(code)) => {
+                        out.push_str(code);
+                        out.push_str(prefix);
+                        out.push_str(&line.text);
+                        out.push_str("\x1b[0m");
+                    }
+                    _ => {
+                        out.push_str(prefix);
+                        out.push_str(&line.text);
+                    }
+                }
+            }
+            DiffRow::Collapsed(n) => {
+                if color {
+                    out.push_str("\x1b[90m");
+                }
+                out.push_str(&collapsed_label(*n));
+                if color {
+                    out.push_str("\x1b[0m");
+                }
+            }
+            DiffRow::Separator(label) => {
+                if color {
+                    out.push_str("\x1b[90m");
+                }
+                out.push_str(label);
+                if color {
+                    out.push_str("\x1b[0m");
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out.pop();
+    out
+}
+
+/// Render `rows` as one `(Style, String)` per row, for the ratatui
+/// preview. Each row already reads as a full display line (prefix
+/// included), so callers can wrap each in its own single-segment line.
+pub fn diff_to_spans(rows: &[DiffRow]) -> Vec<(Style, String)> {
+    rows.iter()
+        .map(|row| match row {
+            DiffRow::Line(line) => {
+                let (prefix, style) = match line.kind {
+                    DiffLineKind::Context => (" ", Style::default()),
+                    DiffLineKind::Removed => ("-", Style::default().fg(Color::Red)),
+                    DiffLineKind::Added => ("+", Style::default().fg(Color::Green)),
+                };
+                (style, format!("{}{}", prefix, line.text))
+            }
+            DiffRow::Collapsed(n) => (Style::default().fg(Color::DarkGray), collapsed_label(*n)),
+            DiffRow::Separator(label) => (Style::default().fg(Color::DarkGray), label.clone()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_lines_marks_unchanged_removed_and_added() {
+        let rows = diff_lines("a\nb\nc", "a\nx\nc");
+        assert_eq!(
+            rows,
+            vec![
+                DiffRow::Line(DiffLine {
+                    kind: DiffLineKind::Context,
+                    text: "a".to_string()
+                }),
+                DiffRow::Line(DiffLine {
+                    kind: DiffLineKind::Removed,
+                    text: "b".to_string()
+                }),
+                DiffRow::Line(DiffLine {
+                    kind: DiffLineKind::Added,
+                    text: "x".to_string()
+                }),
+                DiffRow::Line(DiffLine {
+                    kind: DiffLineKind::Context,
+                    text: "c".to_string()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_lines_collapses_long_unchanged_runs() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\nCHANGED\n9";
+        let new = "1\n2\n3\n4\n5\n6\n7\n8\nchanged\n9";
+        let rows = diff_lines(old, new);
+        assert!(rows.iter().any(|r| matches!(r, DiffRow::Collapsed(_))));
+        // 8 leading context lines collapse to 3 kept + 1 marker, not all 8 shown.
+        let context_lines = rows
+            .iter()
+            .filter(|r| matches!(r, DiffRow::Line(l) if l.kind == DiffLineKind::Context))
+            .count();
+        assert_eq!(context_lines, CONTEXT_LINES);
+    }
+
+    #[test]
+    fn diff_lines_keeps_short_unchanged_runs_uncollapsed() {
+        let rows = diff_lines("a\nb\nCHANGED", "a\nb\nchanged");
+        assert!(!rows.iter().any(|r| matches!(r, DiffRow::Collapsed(_))));
+    }
+
+    #[test]
+    fn diff_for_tool_call_reads_edit_old_and_new_string() {
+        let input = json!({"file_path": "a.rs", "old_string": "a", "new_string": "b"});
+        let rows = diff_for_tool_call("Edit", &input).unwrap();
+        assert!(rows
+            .iter()
+            .any(|r| matches!(r, DiffRow::Line(l) if l.kind == DiffLineKind::Removed && l.text == "a")));
+        assert!(rows
+            .iter()
+            .any(|r| matches!(r, DiffRow::Line(l) if l.kind == DiffLineKind::Added && l.text == "b")));
+    }
+
+    #[test]
+    fn diff_for_tool_call_joins_multi_edit_with_separators() {
+        let input = json!({
+            "file_path": "a.rs",
+            "edits": [
+                {"old_string": "a", "new_string": "b"},
+                {"old_string": "c", "new_string": "d"},
+            ]
+        });
+        let rows = diff_for_tool_call("MultiEdit", &input).unwrap();
+        assert!(rows.iter().any(|r| matches!(r, DiffRow::Separator(_))));
+    }
+
+    #[test]
+    fn diff_for_tool_call_renders_write_as_all_added() {
+        let input = json!({"file_path": "a.rs", "content": "fn a() {}\nfn b() {}"});
+        let rows = diff_for_tool_call("Write", &input).unwrap();
+        assert!(rows
+            .iter()
+            .all(|r| matches!(r, DiffRow::Line(l) if l.kind == DiffLineKind::Added)));
+    }
+
+    #[test]
+    fn diff_for_tool_call_returns_none_for_other_tools() {
+        assert!(diff_for_tool_call("Bash", &json!({"command": "ls"})).is_none());
+    }
+
+    #[test]
+    fn diff_to_ansi_colors_removed_and_added_lines() {
+        let rows = diff_lines("a", "b");
+        let out = diff_to_ansi(&rows);
+        assert!(out.contains("\x1b[31m-a"));
+        assert!(out.contains("\x1b[32m+b"));
+    }
+}
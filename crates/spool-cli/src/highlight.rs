@@ -0,0 +1,273 @@
+//! Syntax highlighting for code and file content shown by `spool view` and
+//! the TUI preview.
+//!
+//! A single [`SyntaxSet`]/[`ThemeSet`] pair is built once (behind a
+//! `OnceLock`, since loading either is too expensive to repeat per entry)
+//! and shared by both renderers: [`highlight_to_ansi`] for the terminal
+//! `spool view` output and [`highlight_to_spans`] for the ratatui preview.
+//! Language is inferred from the originating tool call's file extension
+//! (see [`infer_tool_language`]) or, for response text, from a fenced code
+//! block's own info string — callers with neither just pass `None` and get
+//! the text back unstyled.
+
+use ratatui::style::{Color, Modifier, Style};
+use std::path::Path;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Style as SynStyle, Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::as_24_bit_terminal_escaped;
+
+/// Env var overriding the bundled default theme, e.g. `base16-eighties.dark`.
+const THEME_ENV_VAR: &str = "SPOOL_THEME";
+const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+/// Env var disabling syntax highlighting entirely (falling back to plain,
+/// unstyled text everywhere this module is used) - independent of
+/// `NO_COLOR`, which only affects [`highlight_to_ansi`]'s terminal output.
+const DISABLE_ENV_VAR: &str = "SPOOL_NO_SYNTAX";
+
+fn highlighting_enabled() -> bool {
+    std::env::var_os(DISABLE_ENV_VAR).is_none()
+}
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static SET: OnceLock<ThemeSet> = OnceLock::new();
+    let set = SET.get_or_init(ThemeSet::load_defaults);
+    let name = std::env::var(THEME_ENV_VAR).unwrap_or_else(|_| DEFAULT_THEME.to_string());
+    set.themes
+        .get(&name)
+        .unwrap_or_else(|| &set.themes[DEFAULT_THEME])
+}
+
+fn find_syntax(lang: Option<&str>) -> Option<&'static SyntaxReference> {
+    let lang = lang?;
+    let set = syntax_set();
+    set.find_syntax_by_token(lang)
+        .or_else(|| set.find_syntax_by_extension(lang))
+}
+
+/// Infer a highlighting language hint (a file extension, resolved against
+/// syntect's bundled syntaxes by [`find_syntax`]) from a tool call's name
+/// and input. Only the file-oriented tools carry a `file_path` worth
+/// inferring from; anything else returns `None`.
+pub fn infer_tool_language(tool: &str, input: &serde_json::Value) -> Option<String> {
+    if !matches!(tool, "Read" | "Edit" | "MultiEdit" | "Write") {
+        return None;
+    }
+    let path = input.get("file_path").and_then(|v| v.as_str())?;
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_string())
+}
+
+/// Whether ANSI color output should be suppressed, per the `NO_COLOR`
+/// convention (<https://no-color.org>).
+fn color_disabled() -> bool {
+    std::env::var_os("NO_COLOR").is_some()
+}
+
+/// Highlight `text` as `lang` (a syntect syntax token or file extension),
+/// returning ANSI-colored output for the `spool view` terminal renderer.
+/// Falls back to `text` unchanged when no syntax matches, `lang` is
+/// `None`, or `NO_COLOR` is set.
+pub fn highlight_to_ansi(text: &str, lang: Option<&str>) -> String {
+    if color_disabled() || !highlighting_enabled() {
+        return text.to_string();
+    }
+    let Some(syntax) = find_syntax(lang) else {
+        return text.to_string();
+    };
+
+    let set = syntax_set();
+    let mut highlighter = HighlightLines::new(syntax, theme());
+    let mut out = String::new();
+    for line in text.lines() {
+        match highlighter.highlight_line(line, set) {
+            Ok(ranges) => {
+                out.push_str(&as_24_bit_terminal_escaped(&ranges[..], false));
+                out.push_str("\x1b[0m\n");
+            }
+            Err(_) => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out.pop(); // drop the trailing newline; callers split on `.lines()`
+    out
+}
+
+/// Highlight `text` as `lang`, returning one `Vec<(Style, String)>` of
+/// styled segments per line, for the ratatui preview. Same language
+/// resolution and fallback behavior as [`highlight_to_ansi`] (minus the
+/// `NO_COLOR` check, which is a piped-terminal-output concern, not a TUI
+/// one).
+pub fn highlight_to_spans(text: &str, lang: Option<&str>) -> Vec<Vec<(Style, String)>> {
+    let syntax = highlighting_enabled().then(|| find_syntax(lang)).flatten();
+    let Some(syntax) = syntax else {
+        return text
+            .lines()
+            .map(|line| vec![(Style::default(), line.to_string())])
+            .collect();
+    };
+
+    let set = syntax_set();
+    let mut highlighter = HighlightLines::new(syntax, theme());
+    text.lines()
+        .map(|line| match highlighter.highlight_line(line, set) {
+            Ok(ranges) => ranges
+                .into_iter()
+                .map(|(style, s)| (syn_style_to_ratatui(style), s.to_string()))
+                .collect(),
+            Err(_) => vec![(Style::default(), line.to_string())],
+        })
+        .collect()
+}
+
+/// Highlight a response's fenced code blocks for the ratatui preview,
+/// leaving prose lines as unstyled segments. Unlike [`highlight_to_spans`]
+/// (which highlights its whole input as one language), this splits
+/// `content` on [`spool_format::extract_code_blocks`] and highlights each
+/// block with its own language, so a response mixing prose and multiple
+/// fenced languages renders correctly line-for-line.
+pub fn highlight_response_lines(content: &str) -> Vec<Vec<(Style, String)>> {
+    let blocks = spool_format::extract_code_blocks(content);
+    if blocks.is_empty() {
+        return content
+            .lines()
+            .map(|line| vec![(Style::default(), line.to_string())])
+            .collect();
+    }
+
+    let mut result = Vec::new();
+    let mut pos = 0;
+    for block in &blocks {
+        if block.byte_range.0 > pos {
+            for line in content[pos..block.byte_range.0].lines() {
+                result.push(vec![(Style::default(), line.to_string())]);
+            }
+        }
+        let code = &content[block.byte_range.0..block.byte_range.1];
+        result.extend(highlight_to_spans(code, block.language.as_deref()));
+        pos = block.byte_range.1;
+    }
+    if pos < content.len() {
+        for line in content[pos..].lines() {
+            result.push(vec![(Style::default(), line.to_string())]);
+        }
+    }
+    result
+}
+
+fn syn_style_to_ratatui(style: SynStyle) -> Style {
+    let mut s = Style::default().fg(Color::Rgb(
+        style.foreground.r,
+        style.foreground.g,
+        style.foreground.b,
+    ));
+    if style.font_style.contains(FontStyle::BOLD) {
+        s = s.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        s = s.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        s = s.add_modifier(Modifier::UNDERLINED);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn infer_tool_language_reads_read_file_path_extension() {
+        let input = json!({"file_path": "/tmp/example.rs"});
+        assert_eq!(infer_tool_language("Read", &input).as_deref(), Some("rs"));
+    }
+
+    #[test]
+    fn infer_tool_language_reads_edit_and_write_too() {
+        let input = json!({"file_path": "notes.py"});
+        assert_eq!(infer_tool_language("Edit", &input).as_deref(), Some("py"));
+        assert_eq!(infer_tool_language("Write", &input).as_deref(), Some("py"));
+        assert_eq!(
+            infer_tool_language("MultiEdit", &input).as_deref(),
+            Some("py")
+        );
+    }
+
+    #[test]
+    fn infer_tool_language_ignores_other_tools() {
+        let input = json!({"file_path": "notes.py"});
+        assert_eq!(infer_tool_language("Bash", &input), None);
+    }
+
+    #[test]
+    fn infer_tool_language_none_without_file_path() {
+        assert_eq!(infer_tool_language("Read", &json!({})), None);
+    }
+
+    #[test]
+    fn highlight_to_ansi_passes_through_unknown_language() {
+        let text = "hello\nworld";
+        assert_eq!(highlight_to_ansi(text, Some("not-a-real-language")), text);
+    }
+
+    #[test]
+    fn highlight_to_ansi_passes_through_when_no_lang_hint() {
+        let text = "plain text, no hint";
+        assert_eq!(highlight_to_ansi(text, None), text);
+    }
+
+    #[test]
+    fn highlight_to_spans_falls_back_to_unstyled_lines() {
+        let spans = highlight_to_spans("a\nb", None);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0], vec![(Style::default(), "a".to_string())]);
+    }
+
+    #[test]
+    fn highlight_response_lines_passes_through_plain_prose() {
+        let spans = highlight_response_lines("just prose\nno code here");
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            spans[0],
+            vec![(Style::default(), "just prose".to_string())]
+        );
+    }
+
+    #[test]
+    fn highlight_response_lines_keeps_prose_unstyled_around_a_block() {
+        let content = "before\n```rust\nfn a() {}\n```\nafter";
+        let spans = highlight_response_lines(content);
+        // "before", the fence line, the code line, the closing fence, "after"
+        assert_eq!(spans.len(), 5);
+        assert_eq!(spans[0], vec![(Style::default(), "before".to_string())]);
+        assert_eq!(spans[4], vec![(Style::default(), "after".to_string())]);
+    }
+
+    #[test]
+    fn highlight_to_ansi_colors_known_language() {
+        let out = highlight_to_ansi("fn main() {}", Some("rs"));
+        assert_ne!(out, "fn main() {}");
+        assert!(out.contains("\x1b["));
+    }
+
+    #[test]
+    fn highlight_to_spans_falls_back_when_disabled_via_env() {
+        std::env::set_var(DISABLE_ENV_VAR, "1");
+        let spans = highlight_to_spans("fn main() {}", Some("rs"));
+        std::env::remove_var(DISABLE_ENV_VAR);
+        assert_eq!(spans, vec![vec![(Style::default(), "fn main() {}".to_string())]]);
+    }
+}
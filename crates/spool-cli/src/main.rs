@@ -8,8 +8,13 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+mod ansi;
+mod chunking;
 mod commands;
+mod diff;
+mod highlight;
 mod tui;
+mod vt;
 
 #[derive(Parser)]
 #[command(name = "spool")]
@@ -44,6 +49,11 @@ enum Commands {
         /// Path to the session (agent log or .spool file)
         path: PathBuf,
 
+        /// Print a BPE token-count breakdown by entry type in text mode
+        /// (always included in --json output regardless of this flag)
+        #[arg(long)]
+        tokens: bool,
+
         /// Output as JSON (for machine consumption)
         #[arg(long)]
         json: bool,
@@ -72,10 +82,68 @@ enum Commands {
         #[arg(short, long)]
         agent: Option<String>,
 
+        /// Restrict results to sessions whose project lives under this root
+        /// (repeatable)
+        #[arg(long = "path")]
+        paths: Vec<PathBuf>,
+
+        /// Minimum directory depth under a `--path` root a project may be at
+        #[arg(long, default_value = "0")]
+        min_depth: usize,
+
+        /// Maximum directory depth under a `--path` root a project may be at
+        #[arg(long)]
+        max_depth: Option<usize>,
+
+        /// Resolve symlinks before checking `--path` roots
+        #[arg(long)]
+        follow_symlinks: bool,
+
         /// Maximum number of results
         #[arg(short = 'n', long, default_value = "20")]
         limit: Option<usize>,
 
+        /// Treat the query as a regex pattern instead of a literal substring
+        #[arg(long)]
+        regex: bool,
+
+        /// Fuzzy-match the query as a subsequence of the title/project
+        /// directory instead of an exact or regex substring
+        #[arg(long)]
+        fuzzy: bool,
+
+        /// Rank by embedding similarity instead of shared terms, so a
+        /// paraphrase can match without sharing any words with the query.
+        /// Uses SPOOL_EMBEDDING_ENDPOINT if set, falling back to lexical
+        /// search otherwise (see `spool semsearch` for the standalone form).
+        #[arg(long)]
+        semantic: bool,
+
+        /// Divide each result's BM25 score by its session length, so long
+        /// sessions don't rank higher purely on volume
+        #[arg(long)]
+        normalize: bool,
+
+        /// On a zero-result query, re-run the search against the suggested
+        /// spelling correction instead of just printing it
+        #[arg(long = "did-you-mean")]
+        did_you_mean: bool,
+
+        /// Output as JSON (for machine consumption)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Semantic search over indexed sessions by meaning, not just substring
+    Semsearch {
+        /// Search query (leave empty to just rebuild the index)
+        #[arg(default_value = "")]
+        query: String,
+
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value = "10")]
+        limit: usize,
+
         /// Output as JSON (for machine consumption)
         #[arg(long)]
         json: bool,
@@ -106,6 +174,32 @@ enum Commands {
         #[arg(long)]
         skip: Option<String>,
 
+        /// Encrypt the output as a `.spool.enc` container. Passphrase comes
+        /// from SPOOL_PASSPHRASE, or an interactive prompt if unset.
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Also flag high-entropy tokens that don't match any known
+        /// vendor format (see `detect_high_entropy` in spool-format). Off
+        /// by default since it's a heuristic that can false-positive.
+        #[arg(long)]
+        high_entropy: bool,
+
+        /// Path to a custom redaction rules file (TOML, same format as
+        /// `<config dir>/spool/redaction.toml`). Merged onto the built-in
+        /// detectors so teams can catch internal token formats.
+        #[arg(long)]
+        rules: Option<PathBuf>,
+
+        /// Replace each distinct secret with a stable per-secret placeholder
+        /// (`[REDACTED:email:ab12cd34]`) instead of collapsing every match
+        /// in a category to the same flat tag, and write an encrypted
+        /// "redaction map" sidecar next to the output so the owner can
+        /// reverse it later. Passphrase comes from SPOOL_PASSPHRASE, or an
+        /// interactive prompt if unset. Requires --redact.
+        #[arg(long, requires = "redact")]
+        pseudonymize: bool,
+
         /// Output as JSON (for machine consumption)
         #[arg(long)]
         json: bool,
@@ -116,6 +210,18 @@ enum Commands {
         /// Path to the session (agent log or .spool file)
         path: PathBuf,
 
+        /// Also flag high-entropy tokens that don't match any known
+        /// vendor format. Off by default since it's a heuristic that can
+        /// false-positive.
+        #[arg(long)]
+        high_entropy: bool,
+
+        /// Path to a custom redaction rules file (TOML, same format as
+        /// `<config dir>/spool/redaction.toml`). Merged onto the built-in
+        /// detectors so teams can catch internal token formats.
+        #[arg(long)]
+        rules: Option<PathBuf>,
+
         /// Output as JSON (for machine consumption)
         #[arg(long)]
         json: bool,
@@ -125,6 +231,153 @@ enum Commands {
     Validate {
         /// Path to the .spool file
         path: PathBuf,
+
+        /// Output a machine-readable JSON report
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare two .spool recordings entry-by-entry
+    Diff {
+        /// Path to the earlier .spool file
+        old: PathBuf,
+
+        /// Path to the later .spool file
+        new: PathBuf,
+
+        /// Entries of unchanged context to show around each change
+        #[arg(long, default_value = "3")]
+        context: usize,
+
+        /// Print only counts of added/removed/changed entries
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Accept live remote recordings over `spool-net`, appending each
+    /// session to a .spool file as entries arrive
+    Serve {
+        /// Address to bind, e.g. `127.0.0.1:7777`
+        #[arg(default_value = "127.0.0.1:7777")]
+        addr: String,
+
+        /// Directory to write `<session_id>.spool` files into
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+    },
+
+    /// Forward a locally-recorded session to a `spool serve` listener as
+    /// it's written
+    Record {
+        /// Path to the session (agent log or .spool file) being recorded
+        path: PathBuf,
+
+        /// Address of a `spool serve` listener to forward entries to
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Agent identifier to report in the handshake (defaults to the
+        /// session's own `agent` field)
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Session UUID to hand off to the server - reuse the same one
+        /// across reconnects to resume rather than duplicate
+        #[arg(long)]
+        session_id: Option<uuid::Uuid>,
+    },
+
+    /// Archive .spool sessions into a deduplicated, content-addressed
+    /// repository, and restore them later
+    Repo {
+        #[command(subcommand)]
+        action: RepoAction,
+    },
+
+    /// Mount a .spool file read-only as a browsable filesystem (requires
+    /// the `mount` feature)
+    #[cfg(feature = "mount")]
+    Mount {
+        /// Path to the .spool file
+        path: PathBuf,
+
+        /// Directory to mount onto
+        mountpoint: PathBuf,
+    },
+
+    /// Scan discovered sessions for corrupt or malformed logs
+    Verify {
+        /// Filter by agent type (e.g., claude-code, codex)
+        #[arg(short, long)]
+        agent: Option<String>,
+
+        /// Output as JSON (for machine consumption)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Upload a session to unspool.dev
+    Publish {
+        /// Path to the session (agent log or .spool file)
+        path: PathBuf,
+
+        /// Make the published session publicly viewable (default: private, link-only)
+        #[arg(long)]
+        public: bool,
+
+        /// Redact secrets before uploading
+        #[arg(long)]
+        redact: bool,
+
+        /// Upload even if secrets are detected and --redact wasn't passed
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RepoAction {
+    /// Create the repo's directory structure
+    Init {
+        /// Repo location (default: platform data dir / spool/repo)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+
+    /// Archive a .spool file into the repo
+    Add {
+        /// Path to the .spool file to archive
+        file: PathBuf,
+
+        /// Repo location (default: platform data dir / spool/repo)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+
+    /// List archived sessions
+    List {
+        /// Repo location (default: platform data dir / spool/repo)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output as JSON (for machine consumption)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Reassemble and write out an archived session, verifying every
+    /// chunk's hash
+    Restore {
+        /// Session id (the session's own UUID) to restore
+        id: String,
+
+        /// Repo location (default: platform data dir / spool/repo)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+
+        /// Output path (default: `<id>.spool` in the current directory)
+        #[arg(long)]
+        output: Option<PathBuf>,
     },
 }
 
@@ -135,7 +388,7 @@ fn main() -> Result<()> {
         Some(Commands::List { agent, limit, json }) => {
             commands::list::run(agent.as_deref(), limit, json)
         }
-        Some(Commands::Info { path, json }) => commands::info::run(&path, json),
+        Some(Commands::Info { path, tokens, json }) => commands::info::run(&path, tokens, json),
         Some(Commands::View {
             path,
             json,
@@ -144,9 +397,35 @@ fn main() -> Result<()> {
         Some(Commands::Search {
             query,
             agent,
+            paths,
+            min_depth,
+            max_depth,
+            follow_symlinks,
+            limit,
+            regex,
+            fuzzy,
+            semantic,
+            normalize,
+            did_you_mean,
+            json,
+        }) => commands::search::run(
+            &query,
+            agent.as_deref(),
+            &paths,
+            min_depth,
+            max_depth,
+            follow_symlinks,
             limit,
+            regex,
+            fuzzy,
+            semantic,
+            normalize,
+            did_you_mean,
             json,
-        }) => commands::search::run(&query, agent.as_deref(), limit, json),
+        ),
+        Some(Commands::Semsearch { query, limit, json }) => {
+            commands::semindex::run(&query, limit, json)
+        }
         Some(Commands::Export {
             source,
             output,
@@ -154,6 +433,10 @@ fn main() -> Result<()> {
             redact,
             dry_run,
             skip,
+            encrypt,
+            high_entropy,
+            rules,
+            pseudonymize,
             json,
         }) => commands::export::run(
             &source,
@@ -162,10 +445,38 @@ fn main() -> Result<()> {
             redact,
             dry_run,
             skip.as_deref(),
+            encrypt,
+            high_entropy,
+            rules.as_deref(),
+            pseudonymize,
             json,
         ),
-        Some(Commands::Detect { path, json }) => commands::detect::run(&path, json),
-        Some(Commands::Validate { path }) => commands::validate::run(&path),
+        Some(Commands::Detect { path, high_entropy, rules, json }) => {
+            commands::detect::run(&path, high_entropy, rules.as_deref(), json)
+        }
+        Some(Commands::Validate { path, json }) => commands::validate::run(&path, json),
+        Some(Commands::Diff { old, new, context, stat }) => commands::diff::run(&old, &new, context, stat),
+        Some(Commands::Serve { addr, dir }) => commands::serve::run(&addr, &dir),
+        Some(Commands::Record { path, remote, agent, session_id }) => {
+            commands::record::run(&path, remote.as_deref(), agent.as_deref(), session_id)
+        }
+        Some(Commands::Repo { action }) => match action {
+            RepoAction::Init { repo } => commands::repo::init(repo.as_deref()),
+            RepoAction::Add { file, repo } => commands::repo::add(&file, repo.as_deref()),
+            RepoAction::List { repo, json } => commands::repo::list(repo.as_deref(), json),
+            RepoAction::Restore { id, repo, output } => {
+                commands::repo::restore(&id, repo.as_deref(), output.as_deref())
+            }
+        },
+        #[cfg(feature = "mount")]
+        Some(Commands::Mount { path, mountpoint }) => commands::mount::run(&path, &mountpoint),
+        Some(Commands::Verify { agent, json }) => commands::verify::run(agent.as_deref(), json),
+        Some(Commands::Publish {
+            path,
+            public,
+            redact,
+            force,
+        }) => commands::publish::run(&path, public, redact, force),
         None => {
             // spool <path> → open directly in Editor (TUI)
             // spool        → open Library (TUI)
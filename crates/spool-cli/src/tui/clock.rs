@@ -0,0 +1,113 @@
+//! Injectable wall-clock abstraction for playback timing (see
+//! [`super::editor::EditorState::tick`]), so tests can drive playback
+//! deterministically instead of sleeping on real time.
+
+use std::time::{Duration, Instant};
+
+/// A source of "now" for playback timing, in milliseconds since some fixed
+/// starting point. The real implementation ([`SystemClock`]) wraps
+/// [`Instant`]; tests substitute [`MockClock`] to advance time explicitly.
+pub trait Clocks {
+    /// Milliseconds elapsed since the clock was constructed.
+    fn now_ms(&self) -> u64;
+
+    /// Block the calling thread until `deadline_ms` (as measured by
+    /// [`Self::now_ms`]) has passed. A no-op if `deadline_ms` is already in
+    /// the past.
+    fn sleep_until(&self, deadline_ms: u64);
+}
+
+/// Real wall-clock time, anchored to the moment it's constructed so
+/// [`Clocks::now_ms`] returns milliseconds since then rather than since the
+/// Unix epoch - playback only ever needs relative elapsed time.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clocks for SystemClock {
+    fn now_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+
+    fn sleep_until(&self, deadline_ms: u64) {
+        let now = self.now_ms();
+        if deadline_ms > now {
+            std::thread::sleep(Duration::from_millis(deadline_ms - now));
+        }
+    }
+}
+
+/// Deterministic clock for tests: [`Clocks::now_ms`] returns whatever was
+/// last set via [`MockClock::advance`], and [`Clocks::sleep_until`] just
+/// fast-forwards to the deadline instead of actually waiting.
+pub struct MockClock {
+    now_ms: std::cell::Cell<u64>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        MockClock {
+            now_ms: std::cell::Cell::new(0),
+        }
+    }
+
+    /// Advance the mock clock by `ms` milliseconds.
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.set(self.now_ms.get() + ms);
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clocks for MockClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.get()
+    }
+
+    fn sleep_until(&self, deadline_ms: u64) {
+        if deadline_ms > self.now_ms.get() {
+            self.now_ms.set(deadline_ms);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advances_on_demand() {
+        let clock = MockClock::new();
+        assert_eq!(clock.now_ms(), 0);
+        clock.advance(500);
+        assert_eq!(clock.now_ms(), 500);
+    }
+
+    #[test]
+    fn test_mock_clock_sleep_until_fast_forwards() {
+        let clock = MockClock::new();
+        clock.sleep_until(1_000);
+        assert_eq!(clock.now_ms(), 1_000);
+        // A deadline already in the past doesn't move time backwards.
+        clock.sleep_until(500);
+        assert_eq!(clock.now_ms(), 1_000);
+    }
+}
@@ -5,19 +5,41 @@ use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
-use spool_format::{AnnotationStyle, Entry, ToolOutput};
+use spool_format::{AnnotationStyle, Entry, EntryId, ToolOutput};
+use std::collections::HashMap;
 
-/// Truncate a string to fit within `max_len` bytes, respecting char boundaries.
+use crate::diff;
+use crate::highlight;
+use crate::vt;
+
+use super::markdown;
+
+/// Truncate a string to fit within `max_len` *terminal columns*, not bytes:
+/// East-Asian Wide/Fullwidth clusters count as 2 columns, combining/
+/// zero-width marks count as 0, everything else as 1. Truncation never
+/// splits a grapheme cluster, so a cluster that alone would overflow the
+/// remaining budget is dropped whole rather than half-printed. No ellipsis
+/// is added - see [`truncate_str_by_display_width`] for that variant.
 pub fn truncate_str(s: &str, max_len: usize) -> &str {
-    if s.len() <= max_len {
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+
+    if s.width() <= max_len {
         return s;
     }
     if max_len == 0 {
         return "";
     }
-    let mut end = max_len;
-    while end > 0 && !s.is_char_boundary(end) {
-        end -= 1;
+
+    let mut used = 0usize;
+    let mut end = 0usize;
+    for grapheme in s.graphemes(true) {
+        let w = grapheme.width();
+        if used + w > max_len {
+            break;
+        }
+        used += w;
+        end += grapheme.len();
     }
     &s[..end]
 }
@@ -44,6 +66,128 @@ pub fn format_tool_input(input: &serde_json::Value, max_len: usize) -> String {
     }
 }
 
+/// How a `ToolResult`'s output should be rendered, chosen once per entry
+/// from its output shape and shared by both [`render_entry_lines`] (the
+/// Editor's full view) and the Library preview, so "what does this look
+/// like" doesn't diverge between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolResultView {
+    /// Plain (optionally syntax- or ANSI-highlighted) text lines - the
+    /// default for anything that isn't one of the shapes below.
+    Lines,
+    /// The text parses as a JSON array of objects sharing the same keys;
+    /// rendered as a bordered table via [`json_table_rows`].
+    Table,
+    /// Binary output; rendered as a hex dump via [`hex_dump_lines`].
+    Hex,
+}
+
+/// Pick the autoview mode for a tool result's output (see [`ToolResultView`]).
+pub fn tool_result_view(output: &ToolOutput) -> ToolResultView {
+    match output {
+        ToolOutput::Binary(_) => ToolResultView::Hex,
+        ToolOutput::Text(t) if json_table_rows(t, usize::MAX).is_some() => ToolResultView::Table,
+        ToolOutput::Text(_) => ToolResultView::Lines,
+    }
+}
+
+/// Render `bytes` as a classic hex dump, 16 bytes per row: an 8-digit
+/// offset, space-separated hex byte pairs, and an ASCII gutter with
+/// non-printable bytes shown as `.`. Capped at `max_rows` rows so a large
+/// payload doesn't blow up the preview/editor layout.
+pub fn hex_dump_lines(bytes: &[u8], max_rows: usize) -> Vec<(Style, String)> {
+    bytes
+        .chunks(16)
+        .take(max_rows)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let offset = row * 16;
+            let hex: String = chunk.iter().map(|b| format!("{:02x} ", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if (0x20..=0x7e).contains(&b) { b as char } else { '.' })
+                .collect();
+            (
+                Style::default(),
+                format!("{:08x}  {:<48}|{}|", offset, hex, ascii),
+            )
+        })
+        .collect()
+}
+
+/// Try rendering `text` as a bordered table: a JSON array of objects all
+/// sharing the same set of keys becomes columns (in the first object's key
+/// order), with cell values truncated to keep rows narrow. Returns `None`
+/// when `text` isn't that shape, so callers fall back to the plain line
+/// preview. `max_rows` caps the number of *data* rows rendered (the
+/// consistency check still covers the whole array, so a table with one
+/// inconsistent row far down still correctly falls back to `None`).
+pub fn json_table_rows(text: &str, max_rows: usize) -> Option<Vec<(Style, String)>> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let array = value.as_array()?;
+    if array.is_empty() {
+        return None;
+    }
+    let first = array[0].as_object()?;
+    let keys: Vec<String> = first.keys().cloned().collect();
+    let key_set: std::collections::HashSet<&String> = keys.iter().collect();
+
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(array.len());
+    for item in array {
+        let obj = item.as_object()?;
+        if obj.keys().collect::<std::collections::HashSet<_>>() != key_set {
+            return None;
+        }
+        rows.push(
+            keys.iter()
+                .map(|k| truncate_str_with_ellipsis(&json_cell(&obj[k]), 24))
+                .collect(),
+        );
+    }
+
+    let widths: Vec<usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain(std::iter::once(k.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = |left: &str, mid: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        format!("{}{}{}", left, segments.join(mid), right)
+    };
+    let data_row = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!(" {:<width$} ", c, width = w))
+            .collect();
+        format!("│{}│", padded.join("│"))
+    };
+
+    let mut out = vec![
+        (Style::default().fg(Color::DarkGray), border("┌", "┬", "┐")),
+        (Style::default().add_modifier(Modifier::BOLD), data_row(&keys)),
+        (Style::default().fg(Color::DarkGray), border("├", "┼", "┤")),
+    ];
+    out.extend(rows.iter().take(max_rows).map(|r| (Style::default(), data_row(r))));
+    out.push((Style::default().fg(Color::DarkGray), border("└", "┴", "┘")));
+    Some(out)
+}
+
+fn json_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
 /// Format a duration in milliseconds as `m:ss`.
 pub fn format_duration_ms(ms: u64) -> String {
     let total_secs = ms / 1000;
@@ -52,8 +196,360 @@ pub fn format_duration_ms(ms: u64) -> String {
     format!("{}:{:02}", minutes, seconds)
 }
 
-/// Render a single entry into styled lines for the Editor view.
-pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
+/// Per-session context gathered upfront so a `ToolResult` can infer its
+/// syntax-highlighting language from the `ToolCall` it answers (see
+/// `commands::view::EntryContext`, which exists for the same reason) without
+/// widening [`render_entry_lines`] into a multi-entry function.
+#[derive(Default)]
+pub struct HighlightContext {
+    tool_languages: HashMap<EntryId, String>,
+    /// `/`-style search match positions (char offsets into the matched
+    /// entry's [`entry_search_text`]), keyed by entry id, set by
+    /// [`HighlightContext::with_search_matches`] so a live Editor search
+    /// can show *why* an entry matched.
+    search_matches: HashMap<EntryId, Vec<usize>>,
+    /// Subagent nesting depth of each entry, keyed by id, computed once by
+    /// [`HighlightContext::from_entries`] by counting `SubagentStart`/
+    /// `SubagentEnd` markers. Exposed via [`HighlightContext::depth_of`] so a
+    /// future collapsed/expanded subagent block view can reuse it.
+    depths: HashMap<EntryId, usize>,
+    /// Whether `ToolResult` output should be replayed through
+    /// [`crate::vt::render_vt`] instead of shown as highlighted plain text,
+    /// set by [`HighlightContext::with_vt_mode`]. Falls back to the literal
+    /// text view whenever a `/`-search is highlighting the same entry, since
+    /// search match positions are offsets into the raw text, not the VT
+    /// grid's reflowed cells.
+    vt_mode: bool,
+    /// Real wall-clock elapsed time (from the original, uncompressed
+    /// timestamps) and whether the step failed, keyed by the id of the
+    /// `ToolResult`/`Response` entry that closes a `ToolCall`/`Prompt` pair.
+    /// Computed once by [`HighlightContext::from_entries`]; shown when
+    /// [`HighlightContext::with_duration_overlay`] is set.
+    durations: HashMap<EntryId, (u64, bool)>,
+    show_duration: bool,
+    /// Whether `Response`/`Thinking` content is rendered through
+    /// [`markdown::render_markdown_lines`] (headings, lists, inline
+    /// code/emphasis) instead of [`highlight::highlight_response_lines`]'s
+    /// plain-prose-plus-code-blocks view, set by
+    /// [`HighlightContext::with_markdown_mode`].
+    markdown_mode: bool,
+}
+
+/// A step runs "slow" (colored like a failure even on success) once it
+/// takes at least this long in real wall-clock time.
+const SLOW_STEP_MS: u64 = 3_000;
+
+impl HighlightContext {
+    /// Scan `entries` once, recording each `ToolCall`'s inferred language
+    /// (see [`highlight::infer_tool_language`]) keyed by its id, so a later
+    /// `ToolResult` (which references it via `call_id`) can look it up, each
+    /// entry's subagent nesting depth (see [`HighlightContext::depths`]),
+    /// and each `ToolCall`→`ToolResult`/`Prompt`→`Response` pair's real
+    /// elapsed time (see [`HighlightContext::durations`]).
+    pub fn from_entries(entries: &[Entry]) -> Self {
+        let mut tool_languages = HashMap::new();
+        let mut depths = HashMap::new();
+        let mut depth = 0usize;
+        let mut pending_tool_calls: HashMap<EntryId, u64> = HashMap::new();
+        let mut pending_prompt: Option<u64> = None;
+        let mut durations: HashMap<EntryId, (u64, bool)> = HashMap::new();
+
+        for entry in entries {
+            match entry {
+                Entry::ToolCall(tc) => {
+                    if let Some(lang) = highlight::infer_tool_language(&tc.tool, &tc.input) {
+                        tool_languages.insert(tc.id, lang);
+                    }
+                    pending_tool_calls.insert(tc.id, tc.ts);
+                }
+                Entry::ToolResult(tr) => {
+                    if let Some(start_ts) = pending_tool_calls.remove(&tr.call_id) {
+                        let elapsed = tr.ts.saturating_sub(start_ts);
+                        durations.insert(tr.id, (elapsed, tr.error.is_some()));
+                    }
+                }
+                Entry::Prompt(p) => pending_prompt = Some(p.ts),
+                Entry::Response(r) => {
+                    if let Some(start_ts) = pending_prompt.take() {
+                        let elapsed = r.ts.saturating_sub(start_ts);
+                        durations.insert(r.id, (elapsed, false));
+                    }
+                }
+                _ => {}
+            }
+
+            match entry {
+                Entry::SubagentStart(_) => {
+                    if let Some(id) = entry.id() {
+                        depths.insert(*id, depth);
+                    }
+                    depth += 1;
+                }
+                Entry::SubagentEnd(_) => {
+                    depth = depth.saturating_sub(1);
+                    if let Some(id) = entry.id() {
+                        depths.insert(*id, depth);
+                    }
+                }
+                _ => {
+                    if let Some(id) = entry.id() {
+                        depths.insert(*id, depth);
+                    }
+                }
+            }
+        }
+        HighlightContext {
+            tool_languages,
+            search_matches: HashMap::new(),
+            depths,
+            vt_mode: false,
+            durations,
+            show_duration: false,
+            markdown_mode: false,
+        }
+    }
+
+    /// Attach the Editor's current `/`-style search matches so
+    /// [`render_entry_lines`] highlights the matched characters.
+    pub fn with_search_matches(mut self, search_matches: HashMap<EntryId, Vec<usize>>) -> Self {
+        self.search_matches = search_matches;
+        self
+    }
+
+    /// Toggle VT-emulated replay of `ToolResult` output (see
+    /// [`HighlightContext::vt_mode`]).
+    pub fn with_vt_mode(mut self, vt_mode: bool) -> Self {
+        self.vt_mode = vt_mode;
+        self
+    }
+
+    /// Toggle the real-duration/exit-status overlay (see
+    /// [`HighlightContext::durations`]).
+    pub fn with_duration_overlay(mut self, show_duration: bool) -> Self {
+        self.show_duration = show_duration;
+        self
+    }
+
+    /// Toggle Markdown-aware rendering of `Response`/`Thinking` content
+    /// (see [`HighlightContext::markdown_mode`]).
+    pub fn with_markdown_mode(mut self, markdown_mode: bool) -> Self {
+        self.markdown_mode = markdown_mode;
+        self
+    }
+
+    /// The subagent nesting depth recorded for `entry`, or 0 if it has no id
+    /// or wasn't seen by [`HighlightContext::from_entries`].
+    pub fn depth_of(&self, entry: &Entry) -> usize {
+        entry
+            .id()
+            .and_then(|id| self.depths.get(id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The duration badge span to append to `entry`'s header line, if the
+    /// overlay is on and a real elapsed time was recorded for it.
+    fn duration_span(&self, entry: &Entry) -> Option<Span<'static>> {
+        if !self.show_duration {
+            return None;
+        }
+        let (elapsed_ms, is_failure) = *entry.id().and_then(|id| self.durations.get(id))?;
+        let color = if is_failure || elapsed_ms >= SLOW_STEP_MS {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        Some(Span::styled(
+            format!("  [{}]", format_real_duration_ms(elapsed_ms)),
+            Style::default().fg(color),
+        ))
+    }
+}
+
+/// Format a real elapsed duration for the duration overlay: sub-second as
+/// whole milliseconds, otherwise seconds with one decimal place.
+fn format_real_duration_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+/// Rotating palette of dim guide-bar colors, one per nesting level (wrapping
+/// around for subagents nested deeper than the palette).
+const GUIDE_COLORS: [Color; 4] = [Color::Cyan, Color::Magenta, Color::Yellow, Color::Blue];
+
+/// Build the `│ ` guide-bar prefix for `depth` levels of subagent nesting,
+/// one dim, color-rotated span per level.
+fn depth_guide_prefix(depth: usize) -> Vec<Span<'static>> {
+    (0..depth)
+        .map(|level| {
+            Span::styled(
+                "\u{2502} ",
+                Style::default()
+                    .fg(GUIDE_COLORS[level % GUIDE_COLORS.len()])
+                    .add_modifier(Modifier::DIM),
+            )
+        })
+        .collect()
+}
+
+/// The text a `/`-style search matches `entry` against: its prompt,
+/// response, or thinking content, its tool-call input JSON, or its
+/// tool-result text output. Mirrors the field choice of
+/// `library::extract_searchable_text`, but keeps one entry's text on its
+/// own (rather than concatenating a whole session) so match positions can
+/// be reported relative to that entry alone.
+pub fn entry_search_text(entry: &Entry) -> Option<String> {
+    match entry {
+        Entry::Prompt(p) => Some(p.content.clone()),
+        Entry::Response(r) => Some(r.content.clone()),
+        Entry::Thinking(t) => Some(t.content.clone()),
+        Entry::ToolCall(tc) => Some(tc.input.to_string()),
+        Entry::ToolResult(tr) => match &tr.output {
+            Some(ToolOutput::Text(t)) => Some(t.clone()),
+            _ => None,
+        },
+        Entry::Terminal(t) => Some(String::from_utf8_lossy(&t.decoded_bytes()).into_owned()),
+        _ => None,
+    }
+}
+
+/// Context text immediately before a match position, cut at the nearest
+/// preceding line break (or `max_len` bytes back, whichever comes first).
+/// Shared by the Editor's in-session finder and the library-wide search
+/// ([`super::library_search`]) so a hit's snippet looks the same regardless
+/// of which one found it.
+pub fn extract_context_before(text: &str, pos: usize, max_len: usize) -> String {
+    let start = pos.saturating_sub(max_len);
+    let slice = &text[start..pos];
+    if let Some(nl) = slice.rfind('\n') {
+        slice[nl + 1..].to_string()
+    } else {
+        slice.to_string()
+    }
+}
+
+/// Context text immediately after a match position, cut at the nearest
+/// following line break (or `max_len` bytes forward, whichever comes first).
+/// See [`extract_context_before`].
+pub fn extract_context_after(text: &str, pos: usize, max_len: usize) -> String {
+    let end = (pos + max_len).min(text.len());
+    let slice = &text[pos..end];
+    if let Some(nl) = slice.find('\n') {
+        slice[..nl].to_string()
+    } else {
+        slice.to_string()
+    }
+}
+
+/// Styling applied to the characters of an entry's content that matched a
+/// `/`-style search.
+fn search_highlight_style() -> Style {
+    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+}
+
+/// Cumulative char offset of the start of each line in `content`, so whole-
+/// entry match positions (as returned by [`entry_search_text`] fed through
+/// `fuzzy::fuzzy_match`) can be mapped back to a position within one
+/// rendered line.
+fn line_start_offsets(content: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+    for line in content.lines() {
+        offsets.push(offset);
+        offset += line.chars().count() + 1;
+    }
+    offsets
+}
+
+/// The subset of `positions` that fall within `line_start..line_start +
+/// line_len`, rebased to be relative to `line_start`.
+fn positions_in_line(positions: &[usize], line_start: usize, line_len: usize) -> Vec<usize> {
+    positions
+        .iter()
+        .filter(|&&p| p >= line_start && p < line_start + line_len)
+        .map(|&p| p - line_start)
+        .collect()
+}
+
+/// Split `text` into spans styled `base`, except the chars at
+/// `relative_positions` (0-based char offsets into `text`), which get
+/// `highlight` instead.
+fn highlight_chars(
+    text: &str,
+    relative_positions: &[usize],
+    base: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    if relative_positions.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+    let marks: std::collections::HashSet<usize> = relative_positions.iter().copied().collect();
+    let mut spans = Vec::new();
+    let mut chunk = String::new();
+    let mut chunk_is_match = false;
+    for (i, c) in text.chars().enumerate() {
+        let is_match = marks.contains(&i);
+        if i > 0 && is_match != chunk_is_match {
+            spans.push(Span::styled(
+                std::mem::take(&mut chunk),
+                if chunk_is_match { highlight } else { base },
+            ));
+        }
+        chunk.push(c);
+        chunk_is_match = is_match;
+    }
+    if !chunk.is_empty() {
+        spans.push(Span::styled(
+            chunk,
+            if chunk_is_match { highlight } else { base },
+        ));
+    }
+    spans
+}
+
+/// Truncate a line of styled segments (as produced by
+/// `highlight::highlight_to_spans`/`highlight::highlight_response_lines`) to
+/// at most `max_len` bytes total, preserving each segment's style up to the
+/// cut point.
+fn truncate_styled_line(spans: Vec<(Style, String)>, max_len: usize) -> Vec<(Style, String)> {
+    let mut out = Vec::new();
+    let mut remaining = max_len;
+    for (style, text) in spans {
+        if remaining == 0 {
+            break;
+        }
+        let truncated = truncate_str(&text, remaining);
+        remaining -= truncated.len();
+        out.push((style, truncated.to_string()));
+    }
+    out
+}
+
+/// Render a single entry into styled lines for the Editor view. `ctx` (built
+/// once per session via [`HighlightContext::from_entries`]) supplies the
+/// syntax-highlighting language hint for any `ToolResult` shown, its `/`-
+/// search match positions, and its subagent nesting depth. Every line pushed
+/// for this entry (including wrapped continuation lines and the `... (N more
+/// lines)` markers) is prefixed with one colored `│ ` guide bar per nesting
+/// level, so a nested `Task` subagent's output reads as a contained block.
+///
+/// `terminal_frame` only matters for `Entry::Terminal`: it caps how many of
+/// the entry's frames (inclusive, 0-indexed) are decoded and shown, so
+/// playback can reveal a capture frame-by-frame instead of all at once.
+/// `None` means "every frame" - the player's other callers (a fully-played
+/// entry, or non-Terminal entries) don't need partial reveal.
+pub fn render_entry_lines(
+    entry: &Entry,
+    ctx: &HighlightContext,
+    lines: &mut Vec<Line>,
+    width: usize,
+    terminal_frame: Option<usize>,
+) {
+    let depth = ctx.depth_of(entry);
+    let start = lines.len();
     match entry {
         Entry::Session(s) => {
             lines.push(Line::from(Span::styled(
@@ -91,30 +587,61 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             )));
-            for line in p.content.lines() {
+            let positions = ctx.search_matches.get(&p.id);
+            let line_starts = line_start_offsets(&p.content);
+            for (i, line) in p.content.lines().enumerate() {
                 let truncated = truncate_str(line, width.saturating_sub(2));
-                lines.push(Line::from(Span::styled(
-                    format!("  {}", truncated),
-                    Style::default().fg(Color::Green),
-                )));
+                let base = Style::default().fg(Color::Green);
+                let spans = match positions {
+                    Some(pos) => {
+                        let rel =
+                            positions_in_line(pos, line_starts[i], truncated.chars().count());
+                        highlight_chars(truncated, &rel, base, search_highlight_style())
+                    }
+                    None => vec![Span::styled(truncated.to_string(), base)],
+                };
+                let mut out = vec![Span::raw("  ")];
+                out.extend(spans);
+                lines.push(Line::from(out));
             }
             lines.push(Line::from(""));
         }
         Entry::Thinking(t) => {
-            let collapsed = t.content.replace('\n', " ");
-            let preview = truncate_str(&collapsed, 80);
-            lines.push(Line::from(vec![
-                Span::styled(
-                    "THINKING ",
+            if ctx.markdown_mode {
+                lines.push(Line::from(Span::styled(
+                    "THINKING",
                     Style::default()
                         .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
                         .add_modifier(Modifier::DIM),
-                ),
-                Span::styled(
-                    preview.to_string(),
-                    Style::default().add_modifier(Modifier::DIM),
-                ),
-            ]));
+                )));
+                for styled_line in markdown::render_markdown_lines(&t.content) {
+                    let truncated = truncate_styled_line(styled_line, width.saturating_sub(2));
+                    let mut spans = vec![Span::raw("  ")];
+                    spans.extend(
+                        truncated
+                            .into_iter()
+                            .map(|(style, s)| Span::styled(s, style.add_modifier(Modifier::DIM))),
+                    );
+                    lines.push(Line::from(spans));
+                }
+                lines.push(Line::from(""));
+            } else {
+                let collapsed = t.content.replace('\n', " ");
+                let preview = truncate_str(&collapsed, 80);
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        "THINKING ",
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::DIM),
+                    ),
+                    Span::styled(
+                        preview.to_string(),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ),
+                ]));
+            }
         }
         Entry::ToolCall(tc) => {
             let tool_display = if tc.tool == "Task" {
@@ -138,11 +665,26 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
                 Span::styled(tool_display, Style::default().fg(Color::Blue)),
             ]));
 
-            let input_preview = format_tool_input(&tc.input, width.saturating_sub(4));
-            lines.push(Line::from(Span::styled(
-                format!("  {}", input_preview),
-                Style::default().fg(Color::DarkGray),
-            )));
+            if let Some(rows) = diff::diff_for_tool_call(&tc.tool, &tc.input) {
+                let spans = diff::diff_to_spans(&rows);
+                let total = spans.len();
+                for (style, text) in spans.into_iter().take(5) {
+                    let truncated = truncate_str(&text, width.saturating_sub(4));
+                    lines.push(Line::from(Span::styled(format!("  {}", truncated), style)));
+                }
+                if total > 5 {
+                    lines.push(Line::from(Span::styled(
+                        format!("  ... ({} more lines)", total - 5),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            } else {
+                let input_preview = format_tool_input(&tc.input, width.saturating_sub(4));
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", input_preview),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
         }
         Entry::ToolResult(tr) => {
             let status = if tr.error.is_some() {
@@ -150,10 +692,12 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
             } else {
                 Span::styled("[OK]", Style::default().fg(Color::Green))
             };
-            lines.push(Line::from(vec![
+            let mut header = vec![
                 Span::styled("  RESULT ", Style::default().fg(Color::Blue)),
                 status,
-            ]));
+            ];
+            header.extend(ctx.duration_span(entry));
+            lines.push(Line::from(header));
 
             if let Some(ref err) = tr.error {
                 let truncated = truncate_str(err, width.saturating_sub(4));
@@ -162,37 +706,145 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
                     Style::default().fg(Color::Red),
                 )));
             } else if let Some(ref output) = tr.output {
-                let text = match output {
-                    ToolOutput::Text(t) => t.as_str(),
-                    ToolOutput::Binary(_) => "<binary>",
+                let autoview_handled = match tool_result_view(output) {
+                    ToolResultView::Hex => {
+                        let bytes = match output {
+                            ToolOutput::Binary(bin) => bin.decoded_bytes().ok(),
+                            ToolOutput::Text(_) => None,
+                        };
+                        match bytes {
+                            Some(bytes) => {
+                                for (style, text) in hex_dump_lines(&bytes, 5) {
+                                    lines.push(Line::from(Span::styled(format!("  {}", text), style)));
+                                }
+                            }
+                            None => lines.push(Line::from(Span::styled(
+                                "  <binary>",
+                                Style::default().fg(Color::DarkGray),
+                            ))),
+                        }
+                        true
+                    }
+                    ToolResultView::Table => match output {
+                        ToolOutput::Text(t) => match json_table_rows(t, 5) {
+                            Some(rows) => {
+                                for (style, text) in rows {
+                                    lines.push(Line::from(Span::styled(format!("  {}", text), style)));
+                                }
+                                true
+                            }
+                            None => false,
+                        },
+                        ToolOutput::Binary(_) => false,
+                    },
+                    ToolResultView::Lines => false,
                 };
-                for line in text.lines().take(5) {
-                    let truncated = truncate_str(line, width.saturating_sub(4));
-                    lines.push(Line::from(Span::styled(
-                        format!("  {}", truncated),
-                        Style::default().fg(Color::DarkGray),
-                    )));
-                }
-                let line_count = text.lines().count();
-                if line_count > 5 {
-                    lines.push(Line::from(Span::styled(
-                        format!("  ... ({} more lines)", line_count - 5),
-                        Style::default().fg(Color::DarkGray),
-                    )));
+
+                if !autoview_handled {
+                    let text = match output {
+                        ToolOutput::Text(t) => t.as_str(),
+                        ToolOutput::Binary(_) => "<binary>",
+                    };
+                    let lang = ctx.tool_languages.get(&tr.call_id).map(|s| s.as_str());
+                    let positions = ctx.search_matches.get(&tr.id);
+                    if ctx.vt_mode && positions.is_none() {
+                        let vt_lines = vt::render_vt(text, width.saturating_sub(4).max(1));
+                        let total = vt_lines.len();
+                        for line in vt_lines.into_iter().take(5) {
+                            let truncated = truncate_styled_line(line, width.saturating_sub(4));
+                            let mut out = vec![Span::raw("  ")];
+                            out.extend(truncated.into_iter().map(|(style, s)| Span::styled(s, style)));
+                            lines.push(Line::from(out));
+                        }
+                        if total > 5 {
+                            lines.push(Line::from(Span::styled(
+                                format!("  ... ({} more lines)", total - 5),
+                                Style::default().fg(Color::DarkGray),
+                            )));
+                        }
+                    } else {
+                        let line_starts = line_start_offsets(text);
+                        for (i, line) in text.lines().take(5).enumerate() {
+                            let truncated = truncate_str(line, width.saturating_sub(4));
+                            let spans = match positions {
+                                Some(pos) => {
+                                    let rel = positions_in_line(
+                                        pos,
+                                        line_starts[i],
+                                        truncated.chars().count(),
+                                    );
+                                    highlight_chars(
+                                        truncated,
+                                        &rel,
+                                        Style::default(),
+                                        search_highlight_style(),
+                                    )
+                                }
+                                None => highlight::highlight_to_spans(truncated, lang)
+                                    .into_iter()
+                                    .next()
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|(style, s)| Span::styled(s, style))
+                                    .collect(),
+                            };
+                            let mut out = vec![Span::raw("  ")];
+                            out.extend(spans);
+                            lines.push(Line::from(out));
+                        }
+                        let line_count = text.lines().count();
+                        if line_count > 5 {
+                            lines.push(Line::from(Span::styled(
+                                format!("  ... ({} more lines)", line_count - 5),
+                                Style::default().fg(Color::DarkGray),
+                            )));
+                        }
+                    }
                 }
             }
             lines.push(Line::from(""));
         }
         Entry::Response(r) => {
-            lines.push(Line::from(Span::styled(
+            let mut header = vec![Span::styled(
                 "ASSISTANT",
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
-            )));
-            for line in r.content.lines() {
-                let truncated = truncate_str(line, width.saturating_sub(2));
-                lines.push(Line::from(format!("  {}", truncated)));
+            )];
+            header.extend(ctx.duration_span(entry));
+            lines.push(Line::from(header));
+            let positions = ctx.search_matches.get(&r.id);
+            match positions {
+                Some(pos) => {
+                    let line_starts = line_start_offsets(&r.content);
+                    for (i, line) in r.content.lines().enumerate() {
+                        let truncated = truncate_str(line, width.saturating_sub(2));
+                        let rel =
+                            positions_in_line(pos, line_starts[i], truncated.chars().count());
+                        let spans = highlight_chars(
+                            truncated,
+                            &rel,
+                            Style::default(),
+                            search_highlight_style(),
+                        );
+                        let mut out = vec![Span::raw("  ")];
+                        out.extend(spans);
+                        lines.push(Line::from(out));
+                    }
+                }
+                None => {
+                    let styled_lines = if ctx.markdown_mode {
+                        markdown::render_markdown_lines(&r.content)
+                    } else {
+                        highlight::highlight_response_lines(&r.content)
+                    };
+                    for styled_line in styled_lines {
+                        let truncated = truncate_styled_line(styled_line, width.saturating_sub(2));
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(truncated.into_iter().map(|(style, s)| Span::styled(s, style)));
+                        lines.push(Line::from(spans));
+                    }
+                }
             }
             if let Some(ref model) = r.model {
                 lines.push(Line::from(Span::styled(
@@ -255,6 +907,49 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
             lines.push(Line::from(format!("  {}", truncated)));
             lines.push(Line::from(""));
         }
+        Entry::Terminal(t) => {
+            let mut header = vec![Span::styled(
+                "  TERMINAL ",
+                Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            )];
+            header.extend(ctx.duration_span(entry));
+            lines.push(Line::from(header));
+
+            let text = String::from_utf8_lossy(&t.decoded_bytes_upto(terminal_frame)).into_owned();
+
+            if ctx.vt_mode {
+                let vt_lines = vt::render_vt(&text, width.saturating_sub(4).max(1));
+                let total = vt_lines.len();
+                for line in vt_lines.into_iter().take(5) {
+                    let truncated = truncate_styled_line(line, width.saturating_sub(4));
+                    let mut out = vec![Span::raw("  ")];
+                    out.extend(truncated.into_iter().map(|(style, s)| Span::styled(s, style)));
+                    lines.push(Line::from(out));
+                }
+                if total > 5 {
+                    lines.push(Line::from(Span::styled(
+                        format!("  ... ({} more lines)", total - 5),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            } else {
+                for line in text.lines().take(5) {
+                    let truncated = truncate_str(line, width.saturating_sub(4));
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", truncated),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                let line_count = text.lines().count();
+                if line_count > 5 {
+                    lines.push(Line::from(Span::styled(
+                        format!("  ... ({} more lines)", line_count - 5),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+            lines.push(Line::from(""));
+        }
         Entry::RedactionMarker(_) => {
             lines.push(Line::from(Span::styled(
                 "[REDACTED]",
@@ -263,6 +958,15 @@ pub fn render_entry_lines(entry: &Entry, lines: &mut Vec<Line>, width: usize) {
         }
         Entry::Unknown => {}
     }
+
+    if depth > 0 {
+        let prefix = depth_guide_prefix(depth);
+        for line in &mut lines[start..] {
+            let mut spans = prefix.clone();
+            spans.extend(std::mem::take(&mut line.spans));
+            line.spans = spans;
+        }
+    }
 }
 
 /// Create a centered popup rectangle.
@@ -299,13 +1003,14 @@ pub fn annotation_style_from_key(ch: char) -> Option<AnnotationStyle> {
 }
 
 /// Human-readable label for an annotation style.
-pub fn annotation_style_label(style: &AnnotationStyle) -> &'static str {
+pub fn annotation_style_label(style: &AnnotationStyle) -> std::borrow::Cow<'static, str> {
     match style {
-        AnnotationStyle::Highlight => "highlight",
-        AnnotationStyle::Comment => "comment",
-        AnnotationStyle::Pin => "pin",
-        AnnotationStyle::Warning => "warning",
-        AnnotationStyle::Success => "success",
+        AnnotationStyle::Highlight => "highlight".into(),
+        AnnotationStyle::Comment => "comment".into(),
+        AnnotationStyle::Pin => "pin".into(),
+        AnnotationStyle::Warning => "warning".into(),
+        AnnotationStyle::Success => "success".into(),
+        AnnotationStyle::Other(raw) => raw.clone().into(),
     }
 }
 
@@ -436,6 +1141,84 @@ pub fn truncate_str_with_ellipsis(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Truncate `s` to fit within `width` terminal columns, counting display
+/// width rather than `char`s: East-Asian Wide/Fullwidth clusters count as 2
+/// columns, combining/zero-width marks count as 0, everything else as 1.
+/// Truncation never splits a grapheme cluster. If `s` already fits, it's
+/// returned unchanged with no ellipsis; otherwise clusters are accumulated
+/// up to `width - 3` columns (reserving room for `"..."`), dropping a
+/// cluster entirely rather than half-printing it if it alone would overflow
+/// the remaining budget.
+pub fn truncate_str_by_display_width(s: &str, width: usize) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+
+    if s.width() <= width {
+        return s.to_string();
+    }
+    if width < 3 {
+        return String::new();
+    }
+
+    let budget = width - 3;
+    let mut result = String::new();
+    let mut used = 0usize;
+    for grapheme in s.graphemes(true) {
+        let w = grapheme.width();
+        if used + w > budget {
+            break;
+        }
+        result.push_str(grapheme);
+        used += w;
+    }
+    result.push_str("...");
+    result
+}
+
+/// Lazily yields lines from a file, reader, or in-memory string, truncating
+/// each one (via [`truncate_str_with_ellipsis`]) as it's produced, so
+/// spooling a multi-gigabyte log never holds more than one line in memory.
+pub struct TruncateLines<R> {
+    lines: std::io::Lines<R>,
+    width: usize,
+}
+
+impl TruncateLines<std::io::BufReader<std::fs::File>> {
+    /// Open `path` and truncate each line to `width` columns as it's read.
+    pub fn from_path(path: impl AsRef<std::path::Path>, width: usize) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(Self::from_reader(std::io::BufReader::new(file), width))
+    }
+}
+
+impl TruncateLines<std::io::Cursor<Vec<u8>>> {
+    /// Truncate the lines of an already-materialized string. Useful for
+    /// tests and for callers that already have the content in memory.
+    pub fn from_str(s: &str, width: usize) -> Self {
+        Self::from_reader(std::io::Cursor::new(s.as_bytes().to_vec()), width)
+    }
+}
+
+impl<R: std::io::BufRead> TruncateLines<R> {
+    /// Wrap any buffered reader (e.g. `io::stdin().lock()`).
+    pub fn from_reader(reader: R, width: usize) -> Self {
+        Self {
+            lines: reader.lines(),
+            width,
+        }
+    }
+}
+
+impl<R: std::io::BufRead> Iterator for TruncateLines<R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines
+            .next()
+            .map(|line| line.map(|l| truncate_str_with_ellipsis(&l, self.width)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -456,28 +1239,43 @@ mod tests {
     }
 
     #[test]
-    fn truncate_str_multibyte_arrow_at_boundary() {
-        assert_eq!(truncate_str("a\u{2192}b", 4), "a\u{2192}");
+    fn truncate_str_narrow_multibyte_char_counts_as_one_column() {
+        // '→' is a single column regardless of its 3-byte UTF-8 encoding.
+        assert_eq!(truncate_str("a\u{2192}b", 2), "a\u{2192}");
+        assert_eq!(truncate_str("a\u{2192}b", 1), "a");
     }
 
     #[test]
-    fn truncate_str_multibyte_arrow_mid_char() {
-        assert_eq!(truncate_str("a\u{2192}b", 3), "a");
+    fn truncate_str_wide_emoji_counts_as_two_columns() {
+        // The lock emoji is East-Asian Wide (2 columns); a budget of 2 fits
+        // it exactly, a budget of 1 can't fit it at all.
+        assert_eq!(truncate_str("a\u{1f512}b", 3), "a\u{1f512}");
+        assert_eq!(truncate_str("a\u{1f512}b", 1), "a");
     }
 
     #[test]
-    fn truncate_str_emoji_4byte() {
-        assert_eq!(truncate_str("a\u{1f512}b", 5), "a\u{1f512}");
-        assert_eq!(truncate_str("a\u{1f512}b", 3), "a");
+    fn truncate_str_counts_cjk_as_two_columns() {
+        assert_eq!(
+            truncate_str("\u{4f60}\u{597d}\u{4e16}\u{754c}", 5),
+            "\u{4f60}\u{597d}"
+        );
+        assert_eq!(truncate_str("\u{4f60}\u{597d}\u{4e16}\u{754c}", 4), "\u{4f60}\u{597d}");
     }
 
     #[test]
-    fn truncate_str_all_multibyte() {
-        assert_eq!(
-            truncate_str("\u{2192}\u{2192}\u{2192}", 6),
-            "\u{2192}\u{2192}"
-        );
-        assert_eq!(truncate_str("\u{2192}\u{2192}\u{2192}", 4), "\u{2192}");
+    fn truncate_str_drops_overflowing_wide_cluster_whole() {
+        // 'a' (1 column) fits a budget of 2, but the following 2-column
+        // character can't fit in the remaining 1 column, so it's dropped
+        // entirely rather than half-printed.
+        assert_eq!(truncate_str("a\u{4f60}\u{4f60}", 2), "a");
+    }
+
+    #[test]
+    fn truncate_str_preserves_combining_marks() {
+        // "e" + combining acute accent is one grapheme cluster with a
+        // display width of 1; it must not be split mid-cluster.
+        let s = "e\u{0301}bcdef";
+        assert_eq!(truncate_str(s, 1), "e\u{0301}");
     }
 
     #[test]
@@ -511,6 +1309,101 @@ mod tests {
         assert_eq!(truncate_str_with_ellipsis("hello", 0), "");
     }
 
+    #[test]
+    fn truncate_str_by_display_width_fits_unchanged() {
+        assert_eq!(truncate_str_by_display_width("hello", 10), "hello");
+        assert_eq!(truncate_str_by_display_width("hello", 5), "hello");
+    }
+
+    #[test]
+    fn truncate_str_by_display_width_ascii() {
+        assert_eq!(
+            truncate_str_by_display_width("hello world", 8),
+            "hello..."
+        );
+    }
+
+    #[test]
+    fn truncate_str_by_display_width_cjk_counts_as_two_columns() {
+        // Each CJK character is 2 columns wide, so "\u{4f60}\u{597d}\u{4e16}\u{754c}"
+        // (8 columns) must be cut down to fit a 5-column budget (2 columns
+        // for one character, leaving 3 for "...").
+        assert_eq!(
+            truncate_str_by_display_width("\u{4f60}\u{597d}\u{4e16}\u{754c}", 5),
+            "\u{4f60}..."
+        );
+    }
+
+    #[test]
+    fn truncate_str_by_display_width_drops_overflowing_wide_cluster_whole() {
+        // Budget of 4 columns leaves 1 column after reserving 3 for the
+        // ellipsis; "a" (1 column) fits, but the following 2-column-wide
+        // character can't fit in the remaining 0 columns, so it's dropped
+        // entirely rather than half-printed.
+        assert_eq!(
+            truncate_str_by_display_width("a\u{4f60}\u{4f60}", 4),
+            "a..."
+        );
+    }
+
+    #[test]
+    fn truncate_str_by_display_width_preserves_combining_marks() {
+        // "e" + combining acute accent is one grapheme cluster with a
+        // display width of 1; it must not be split mid-cluster.
+        let s = "e\u{0301}bcdef";
+        let truncated = truncate_str_by_display_width(s, 4);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.starts_with("e\u{0301}"));
+    }
+
+    #[test]
+    fn truncate_str_by_display_width_tiny_width() {
+        assert_eq!(truncate_str_by_display_width("hello", 2), "");
+    }
+
+    #[test]
+    fn truncate_lines_from_str_truncates_each_line() {
+        let input = "short\nthis line is much too long to fit\nok";
+        let lines: Vec<String> = TruncateLines::from_str(input, 10)
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(lines, vec!["short", "this li...", "ok"]);
+    }
+
+    #[test]
+    fn truncate_lines_from_str_empty_input() {
+        let lines: Vec<String> = TruncateLines::from_str("", 10)
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn truncate_lines_from_reader_multibyte() {
+        let input = "abc\u{2192}def\nhello";
+        let reader = std::io::Cursor::new(input.as_bytes().to_vec());
+        let lines: Vec<String> = TruncateLines::from_reader(reader, 7)
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(lines, vec!["abc...", "hello"]);
+    }
+
+    #[test]
+    fn truncate_lines_from_path_reads_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "spool_truncate_lines_test_{:?}.txt",
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "line one\nline two is longer\n").unwrap();
+        let lines: Vec<String> = TruncateLines::from_path(&path, 8)
+            .unwrap()
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(lines, vec!["line one", "line ..."]);
+    }
+
     #[test]
     fn test_format_duration_ms() {
         assert_eq!(format_duration_ms(0), "0:00");
@@ -519,4 +1412,69 @@ mod tests {
         assert_eq!(format_duration_ms(90_000), "1:30");
         assert_eq!(format_duration_ms(3_661_000), "61:01");
     }
+
+    #[test]
+    fn tool_result_view_picks_hex_for_binary() {
+        let output = ToolOutput::Binary(spool_format::BinaryContent::from_bytes(
+            "application/octet-stream",
+            &[0u8, 1, 2],
+        ));
+        assert_eq!(tool_result_view(&output), ToolResultView::Hex);
+    }
+
+    #[test]
+    fn tool_result_view_picks_table_for_consistent_json_array() {
+        let output = ToolOutput::Text(r#"[{"a":1,"b":"x"},{"a":2,"b":"y"}]"#.to_string());
+        assert_eq!(tool_result_view(&output), ToolResultView::Table);
+    }
+
+    #[test]
+    fn tool_result_view_falls_back_to_lines_for_plain_text() {
+        let output = ToolOutput::Text("just some plain output\nmore lines".to_string());
+        assert_eq!(tool_result_view(&output), ToolResultView::Lines);
+    }
+
+    #[test]
+    fn tool_result_view_falls_back_to_lines_for_inconsistent_keys() {
+        let output = ToolOutput::Text(r#"[{"a":1},{"b":2}]"#.to_string());
+        assert_eq!(tool_result_view(&output), ToolResultView::Lines);
+    }
+
+    #[test]
+    fn hex_dump_lines_formats_offset_hex_and_ascii_gutter() {
+        let bytes = b"Hi\x00\x01";
+        let rows = hex_dump_lines(bytes, 10);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].1.starts_with("00000000  "));
+        assert!(rows[0].1.contains("48 69 00 01"));
+        assert!(rows[0].1.ends_with("|Hi..|"));
+    }
+
+    #[test]
+    fn hex_dump_lines_caps_at_max_rows() {
+        let bytes = vec![0u8; 64];
+        assert_eq!(hex_dump_lines(&bytes, 2).len(), 2);
+    }
+
+    #[test]
+    fn json_table_rows_renders_header_and_data_rows() {
+        let rows = json_table_rows(r#"[{"name":"a","count":1},{"name":"b","count":2}]"#, 10).unwrap();
+        // top border, header, separator, 2 data rows, bottom border
+        assert_eq!(rows.len(), 6);
+        assert!(rows[1].1.contains("name"));
+        assert!(rows[1].1.contains("count"));
+        assert!(rows[3].1.contains("a"));
+    }
+
+    #[test]
+    fn json_table_rows_caps_data_rows_at_max_rows() {
+        let rows = json_table_rows(r#"[{"a":1},{"a":2},{"a":3}]"#, 1).unwrap();
+        // top border, header, separator, 1 data row, bottom border
+        assert_eq!(rows.len(), 5);
+    }
+
+    #[test]
+    fn json_table_rows_none_for_non_array() {
+        assert!(json_table_rows(r#"{"a":1}"#, 10).is_none());
+    }
 }
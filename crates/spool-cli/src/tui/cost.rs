@@ -0,0 +1,234 @@
+//! Token-usage and cost analytics derived from a session's timeline.
+//!
+//! `ResponseEntry` carries `model` and `token_usage`, which `build_timeline`
+//! otherwise ignores. [`cost_series`] turns it into a cumulative
+//! tokens-in/tokens-out/cost series aligned to `playback_ms` (for a
+//! cost-over-time overlay), and [`cost_summary`] reduces that into totals
+//! plus the priciest turns (for
+//! [`super::editor::EditorState::cost_summary`]).
+
+use spool_format::Entry;
+
+/// Price per million tokens for one model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+/// Built-in prices for models this codebase is commonly run against.
+/// Entirely best-effort: a `model` string missing from the table costs
+/// nothing rather than the analytics inventing a number.
+pub const DEFAULT_PRICES: &[(&str, ModelPrice)] = &[
+    (
+        "claude-opus-4",
+        ModelPrice {
+            input_per_million: 15.0,
+            output_per_million: 75.0,
+        },
+    ),
+    (
+        "claude-sonnet-4",
+        ModelPrice {
+            input_per_million: 3.0,
+            output_per_million: 15.0,
+        },
+    ),
+    (
+        "claude-haiku-4",
+        ModelPrice {
+            input_per_million: 0.8,
+            output_per_million: 4.0,
+        },
+    ),
+    (
+        "gpt-4o",
+        ModelPrice {
+            input_per_million: 2.5,
+            output_per_million: 10.0,
+        },
+    ),
+];
+
+fn price_for(prices: &[(&str, ModelPrice)], model: &str) -> Option<ModelPrice> {
+    prices
+        .iter()
+        .find(|(name, _)| *name == model)
+        .map(|(_, p)| *p)
+}
+
+/// Dollar cost of one turn's token usage, or 0 if `model` isn't in `prices`.
+pub(crate) fn turn_cost(
+    prices: &[(&str, ModelPrice)],
+    model: &str,
+    input_tokens: u64,
+    output_tokens: u64,
+) -> f64 {
+    let Some(price) = price_for(prices, model) else {
+        return 0.0;
+    };
+    (input_tokens as f64 / 1_000_000.0) * price.input_per_million
+        + (output_tokens as f64 / 1_000_000.0) * price.output_per_million
+}
+
+/// One point in the cumulative cost-over-time series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostPoint {
+    pub playback_ms: u64,
+    pub cumulative_input_tokens: u64,
+    pub cumulative_output_tokens: u64,
+    pub cumulative_cost: f64,
+}
+
+/// One response turn's token usage and cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnCost {
+    pub entry_index: usize,
+    pub playback_ms: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+}
+
+/// Totals and the priciest turns across a timeline.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CostSummary {
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    pub total_cost: f64,
+    /// Turns with token usage, sorted by cost descending.
+    pub most_expensive: Vec<TurnCost>,
+}
+
+/// Walk `entries` (each paired with its entry index and timeline
+/// `playback_ms`), producing the cumulative tokens-in/tokens-out/cost
+/// series. Only `Response` entries carrying `token_usage` contribute;
+/// everything else just carries the running totals forward.
+pub fn cost_series(entries: &[(usize, u64, &Entry)], prices: &[(&str, ModelPrice)]) -> Vec<CostPoint> {
+    let mut cumulative_input = 0u64;
+    let mut cumulative_output = 0u64;
+    let mut cumulative_cost = 0.0;
+    let mut series = Vec::with_capacity(entries.len());
+
+    for (_, playback_ms, entry) in entries {
+        if let Entry::Response(r) = entry {
+            if let Some(usage) = &r.token_usage {
+                cumulative_input += usage.input_tokens;
+                cumulative_output += usage.output_tokens;
+                let model = r.model.as_deref().unwrap_or("");
+                cumulative_cost +=
+                    turn_cost(prices, model, usage.input_tokens, usage.output_tokens);
+            }
+        }
+        series.push(CostPoint {
+            playback_ms: *playback_ms,
+            cumulative_input_tokens: cumulative_input,
+            cumulative_output_tokens: cumulative_output,
+            cumulative_cost,
+        });
+    }
+
+    series
+}
+
+/// Reduce `entries` into overall totals plus the `top_n` priciest turns.
+pub fn cost_summary(
+    entries: &[(usize, u64, &Entry)],
+    prices: &[(&str, ModelPrice)],
+    top_n: usize,
+) -> CostSummary {
+    let mut turns = Vec::new();
+    let mut total_input = 0u64;
+    let mut total_output = 0u64;
+    let mut total_cost = 0.0;
+
+    for (entry_index, playback_ms, entry) in entries {
+        if let Entry::Response(r) = entry {
+            if let Some(usage) = &r.token_usage {
+                let model = r.model.as_deref().unwrap_or("");
+                let cost = turn_cost(prices, model, usage.input_tokens, usage.output_tokens);
+                total_input += usage.input_tokens;
+                total_output += usage.output_tokens;
+                total_cost += cost;
+                turns.push(TurnCost {
+                    entry_index: *entry_index,
+                    playback_ms: *playback_ms,
+                    input_tokens: usage.input_tokens,
+                    output_tokens: usage.output_tokens,
+                    cost,
+                });
+            }
+        }
+    }
+
+    turns.sort_by(|a, b| b.cost.partial_cmp(&a.cost).unwrap_or(std::cmp::Ordering::Equal));
+    turns.truncate(top_n);
+
+    CostSummary {
+        total_input_tokens: total_input,
+        total_output_tokens: total_output,
+        total_cost,
+        most_expensive: turns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spool_format::{ResponseEntry, TokenUsage};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn response(model: &str, input_tokens: u64, output_tokens: u64) -> Entry {
+        Entry::Response(ResponseEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            content: "hi".to_string(),
+            truncated: None,
+            original_bytes: None,
+            model: Some(model.to_string()),
+            token_usage: Some(TokenUsage {
+                input_tokens,
+                output_tokens,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            }),
+            subagent_id: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn cost_series_accumulates_across_responses() {
+        let a = response("claude-sonnet-4", 1_000_000, 0);
+        let b = response("claude-sonnet-4", 0, 1_000_000);
+        let entries = vec![(0, 0u64, &a), (1, 1000u64, &b)];
+
+        let series = cost_series(&entries, DEFAULT_PRICES);
+        assert_eq!(series[0].cumulative_cost, 3.0);
+        assert_eq!(series[1].cumulative_cost, 18.0);
+        assert_eq!(series[1].cumulative_input_tokens, 1_000_000);
+        assert_eq!(series[1].cumulative_output_tokens, 1_000_000);
+    }
+
+    #[test]
+    fn unknown_model_costs_nothing() {
+        let a = response("some-future-model", 1_000_000, 1_000_000);
+        let entries = vec![(0, 0u64, &a)];
+        let series = cost_series(&entries, DEFAULT_PRICES);
+        assert_eq!(series[0].cumulative_cost, 0.0);
+    }
+
+    #[test]
+    fn cost_summary_sorts_most_expensive_first() {
+        let cheap = response("claude-haiku-4", 1_000_000, 0);
+        let pricey = response("claude-opus-4", 1_000_000, 0);
+        let entries = vec![(0, 0u64, &cheap), (1, 1000u64, &pricey)];
+
+        let summary = cost_summary(&entries, DEFAULT_PRICES, 1);
+        assert_eq!(summary.most_expensive.len(), 1);
+        assert_eq!(summary.most_expensive[0].entry_index, 1);
+        assert_eq!(summary.total_input_tokens, 2_000_000);
+        assert_eq!(summary.total_cost, 18.0);
+    }
+}
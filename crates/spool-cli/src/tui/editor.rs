@@ -1,6 +1,7 @@
 //! Editor view - Session replay with playback, trimming, annotations, and info overlay.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use regex::Regex;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -9,17 +10,49 @@ use ratatui::{
     Frame,
 };
 use spool_format::{
-    AnnotationEntry, AnnotationStyle, Entry, SecretDetector, SpoolFile, ToolOutput,
+    AnnotationEntry, AnnotationStyle, CustomRule, Entry, RedactionConfig, RedactionProfile, SecretCategory,
+    SecretDetector, SpoolFile, ToolOutput,
 };
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
 use uuid::Uuid;
 
+use super::clock::{Clocks, SystemClock};
+use super::cost::{cost_summary, CostSummary, DEFAULT_PRICES};
+
 use super::common::{
-    annotation_style_from_key, annotation_style_label, centered_rect, format_duration_ms,
-    render_entry_lines, render_info_lines, truncate_str,
+    annotation_style_from_key, annotation_style_label, centered_rect, entry_search_text,
+    extract_context_after, extract_context_before, format_duration_ms, render_entry_lines,
+    render_info_lines, truncate_str, HighlightContext,
 };
+use super::fuzzy::fuzzy_match;
+use super::keymap::{EditorCommand, Keymaps, RedactionCommand};
+use crate::vt;
+
+/// A [`render_entry_lines`] result cached per `(entry_index, terminal_frame)`
+/// so replaying a session doesn't re-run syntax highlighting on every
+/// playback tick - only whichever entries actually changed since the last
+/// frame. The `terminal_frame` half of the key keeps an `Entry::Terminal`'s
+/// successive frames from colliding, since they all share one `entry_index`.
+/// Also keyed by the render inputs that can make a stale entry wrong: the
+/// terminal width it was wrapped for, whether VT replay mode was on, and
+/// whether Markdown rendering was on.
+struct CachedRender {
+    width: usize,
+    vt_mode: bool,
+    show_duration: bool,
+    markdown_mode: bool,
+    lines: Vec<Line<'static>>,
+}
+
+/// What [`EditorState::trim_preview`] reports about trimming to a given
+/// range: how much stays, and the token/cost footprint of what's cut.
+struct TrimPreview {
+    kept: usize,
+    duration_ms: u64,
+    tokens_removed: u64,
+    cost_removed: f64,
+}
 
 /// Maximum gap (ms) before a Prompt entry (user think-time).
 const MAX_IDLE_GAP_MS: u64 = 2_000;
@@ -46,6 +79,125 @@ struct TimelineEntry {
     entry_index: usize,
     /// Compressed playback time (ms) at which this entry appears.
     playback_ms: u64,
+    /// True when this entry's captured output entered the terminal
+    /// alternate screen (a full-screen program like vim/htop/a pager) and
+    /// never emitted the matching exit sequence - see [`ends_in_alt_screen`].
+    fullscreen: bool,
+    /// For an `Entry::Terminal`, the index of the last frame that should be
+    /// visible once this timeline slot is reached - one slot is pushed per
+    /// frame, so several consecutive `TimelineEntry`s can share the same
+    /// `entry_index` while this field counts up through the capture.
+    /// `None` for every other entry kind.
+    terminal_frame: Option<usize>,
+}
+
+/// Binary-searchable index over [`TimelineEntry`] positions, letting
+/// [`EditorState::seek`] jump straight to an arbitrary playback position
+/// instead of replaying ticks one at a time from the start - so scrubbing a
+/// multi-hour session stays instant regardless of its length.
+struct SeekIndex {
+    /// `(playback_ms, timeline_position)`, sorted by `playback_ms` since
+    /// [`build_timeline`] already produces compressed times in
+    /// non-decreasing order. One entry per timeline slot, so a multi-frame
+    /// `Entry::Terminal`'s frames each get their own seek point.
+    points: Vec<(u64, usize)>,
+}
+
+impl SeekIndex {
+    fn build(timeline: &[TimelineEntry]) -> Self {
+        SeekIndex {
+            points: timeline
+                .iter()
+                .enumerate()
+                .map(|(i, te)| (te.playback_ms, i))
+                .collect(),
+        }
+    }
+
+    /// The timeline position to resume playback from for `playback_ms`: the
+    /// last point at or before it, or the first point if `playback_ms`
+    /// precedes everything. `None` only for an empty timeline.
+    fn seek_to(&self, playback_ms: u64) -> Option<usize> {
+        match self.points.binary_search_by_key(&playback_ms, |&(ts, _)| ts) {
+            Ok(i) => Some(self.points[i].1),
+            Err(0) => self.points.first().map(|&(_, pos)| pos),
+            Err(i) => Some(self.points[i - 1].1),
+        }
+    }
+}
+
+/// Sequences a captured tool output uses to enter/exit the terminal
+/// alternate screen buffer - xterm's `\x1b[?1049h`/`\x1b[?1049l`, and the
+/// older `\x1b[?47h`/`\x1b[?47l` variant some pagers still emit.
+const ALT_SCREEN_ENTER: [&str; 2] = ["\x1b[?1049h", "\x1b[?47h"];
+const ALT_SCREEN_EXIT: [&str; 2] = ["\x1b[?1049l", "\x1b[?47l"];
+
+/// True if `text` is still inside the alternate screen at the end of the
+/// capture - it entered, but no matching exit sequence came after that,
+/// meaning the recorded program (vim, htop, a pager) was still
+/// full-screen when its result was captured.
+fn ends_in_alt_screen(text: &str) -> bool {
+    let last_enter = ALT_SCREEN_ENTER.iter().filter_map(|s| text.rfind(s)).max();
+    let last_exit = ALT_SCREEN_EXIT.iter().filter_map(|s| text.rfind(s)).max();
+    match (last_enter, last_exit) {
+        (Some(enter), Some(exit)) => enter > exit,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Human-readable label for a detected secret's category, shown in the
+/// redaction review/preview modals - a custom rule renders the name the
+/// user gave it in their config rather than the `Custom { .. }` Debug dump.
+fn category_label(reason: &SecretCategory) -> String {
+    match reason {
+        SecretCategory::Custom { name, .. } => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// One regex match against an entry's [`entry_search_text`], found by the
+/// live `/`-style search.
+struct SearchMatch {
+    /// Index into `spool_file.entries` (and, 1:1, into `timeline`).
+    entry_index: usize,
+    /// Byte offset range of the match within that entry's searchable text.
+    start: usize,
+    end: usize,
+}
+
+/// Live `/`-style search state: matches re-ranked on every keystroke while
+/// `editing`. Enter confirms the query (`editing` goes false) and hands
+/// control back to normal mode, where `n`/`N` step through `matches`
+/// without reopening the query buffer; `/` again or Esc clears it.
+struct SearchState {
+    query: String,
+    matches: Vec<SearchMatch>,
+    cursor: usize,
+    editing: bool,
+}
+
+/// One ranked result in the fuzzy-finder overlay (`Ctrl-f`).
+struct FinderMatch {
+    /// Index into `spool_file.entries` (and, 1:1, into `timeline`).
+    entry_index: usize,
+    score: i32,
+    /// Short snippets around the first matched position, for the picker
+    /// row - built with the same helpers the redaction review's candidate
+    /// snippets use.
+    context_before: String,
+    context_after: String,
+}
+
+/// Fuzzy-finder overlay state (`Ctrl-f`): a picker over every entry's
+/// searchable text, re-ranked via [`fuzzy_match`] on each keystroke, with
+/// Up/Down moving `selected` and Enter revealing the chosen entry. Unlike
+/// [`SearchState`] (which jumps live to the best match as you type), this
+/// shows the full ranked list so you can pick among several candidates.
+struct FinderState {
+    query: String,
+    matches: Vec<FinderMatch>,
+    selected: usize,
 }
 
 #[derive(Clone, Copy)]
@@ -54,6 +206,25 @@ enum AnnotationStage {
     Style,
 }
 
+/// One undoable edit (`u`) / redoable edit (`Ctrl-r`). Holds enough of a
+/// snapshot to restore the prior state exactly in either direction - so
+/// the same variant pushed onto `undo_stack` (when applying an edit) is
+/// also what gets pushed onto `redo_stack` (when undoing it), and vice
+/// versa. Only annotation insertion and trim-mark changes mutate the live
+/// `EditorState`/`SpoolFile` in place; redaction review clones the file
+/// before redacting and exports to a separate path, so it never needs an
+/// undo entry here.
+enum EditOp {
+    /// An [`Entry::Annotation`] was inserted at `index` into
+    /// `spool_file.entries`.
+    Annotate { index: usize, entry: Entry },
+    /// Trim marks changed; `start`/`end` are the values to restore.
+    SetTrim {
+        start: Option<u64>,
+        end: Option<u64>,
+    },
+}
+
 struct AnnotationDraft {
     target_index: usize,
     target_id: spool_format::EntryId,
@@ -112,6 +283,11 @@ pub struct EditorState {
     session_title: String,
     /// Pre-computed playback timeline with compressed timestamps.
     timeline: Vec<TimelineEntry>,
+    /// Gap-compression tuning used to build `timeline` (see
+    /// [`CompressionConfig`]). Re-applied by [`EditorState::rebuild_timeline`].
+    compression_config: CompressionConfig,
+    /// Binary-searchable index over `timeline`, for [`EditorState::seek`].
+    seek_index: SeekIndex,
     /// Total compressed duration.
     total_duration_ms: u64,
 
@@ -120,7 +296,10 @@ pub struct EditorState {
     playing: bool,
     speed_index: usize,
     playback_elapsed_ms: u64,
-    last_tick: Instant,
+    /// Source of "now" driving [`EditorState::tick`] - real time outside
+    /// tests, a [`crate::tui::clock::MockClock`] inside them.
+    clock: Box<dyn Clocks>,
+    last_tick_ms: u64,
 
     // Display
     scroll_offset: usize,
@@ -130,6 +309,10 @@ pub struct EditorState {
     trim_end_ms: Option<u64>,
     status_message: Option<String>,
 
+    // Undo/redo history (see [`EditOp`])
+    undo_stack: Vec<EditOp>,
+    redo_stack: Vec<EditOp>,
+
     // Annotation state
     annotation_draft: Option<AnnotationDraft>,
 
@@ -139,19 +322,70 @@ pub struct EditorState {
     // Redaction review modal
     redaction_review: Option<RedactionReviewState>,
 
+    // Live entry search (`/`)
+    search: Option<SearchState>,
+
+    /// Whether `ToolResult` output is replayed through the VT grid emulator
+    /// (see [`crate::vt::render_vt`]) instead of shown as literal text.
+    /// Toggled with `t`.
+    vt_mode: bool,
+
+    /// Whether each visible entry is annotated with its real wall-clock
+    /// duration and exit status (see [`crate::tui::common::HighlightContext::with_duration_overlay`]).
+    /// Toggled with `d`.
+    show_duration: bool,
+
+    /// Whether `Response`/`Thinking` content is rendered Markdown-aware
+    /// (see [`crate::tui::common::HighlightContext::with_markdown_mode`])
+    /// instead of as plain prose. Toggled with `m`.
+    markdown_mode: bool,
+
+    /// Per-entry-index render cache (see [`CachedRender`]). Cleared whenever
+    /// [`EditorState::rebuild_timeline`] runs, since an annotation insert
+    /// shifts every later entry's index. Bypassed entirely while a `/`-
+    /// search is active, since search highlighting is query-dependent.
+    render_cache: HashMap<(usize, Option<usize>), CachedRender>,
+
+    /// Fuzzy-finder overlay state (`Ctrl-f`), see [`FinderState`].
+    finder: Option<FinderState>,
+
+    /// Entry indices of fullscreen segments (see [`TimelineEntry::fullscreen`])
+    /// the user has already stepped past with Enter. Once dismissed, a
+    /// segment renders inline in the scrolling entry list like any other
+    /// `ToolResult`, instead of re-claiming the dedicated full-frame view.
+    fullscreen_dismissed: std::collections::HashSet<usize>,
+
     /// Whether we came from a Library (true) or were opened directly (false).
     pub has_library: bool,
+
+    /// Key bindings for normal mode and the redaction review modal, loaded
+    /// once at construction (see [`Keymaps::load`]).
+    keymaps: Keymaps,
 }
 
 impl EditorState {
     pub fn new(spool_file: SpoolFile, source_path: PathBuf, speed: f32) -> Self {
+        Self::with_clock(spool_file, source_path, speed, Box::new(SystemClock::new()))
+    }
+
+    /// Like [`Self::new`], but with an explicit [`Clocks`] implementation -
+    /// used by tests to drive playback deterministically via
+    /// [`crate::tui::clock::MockClock`] instead of real time.
+    pub(crate) fn with_clock(
+        spool_file: SpoolFile,
+        source_path: PathBuf,
+        speed: f32,
+        clock: Box<dyn Clocks>,
+    ) -> Self {
         let session_title = spool_file
             .session
             .title
             .clone()
             .unwrap_or_else(|| "Untitled".to_string());
 
-        let timeline = build_timeline(&spool_file.entries);
+        let compression_config = CompressionConfig::default();
+        let timeline = build_timeline(&spool_file.entries, &compression_config);
+        let seek_index = SeekIndex::build(&timeline);
         let total_duration_ms = timeline.last().map(|t| t.playback_ms).unwrap_or(0);
 
         let speed_index = SPEEDS
@@ -166,32 +400,46 @@ impl EditorState {
             .map(|(i, _)| i)
             .unwrap_or(2);
 
+        let last_tick_ms = clock.now_ms();
         EditorState {
             source_path,
             spool_file,
             session_title,
             timeline,
+            compression_config,
+            seek_index,
             total_duration_ms,
             visible_count: 0,
             playing: false,
             speed_index,
             playback_elapsed_ms: 0,
-            last_tick: Instant::now(),
+            clock,
+            last_tick_ms,
             scroll_offset: 0,
             trim_start_ms: None,
             trim_end_ms: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             status_message: None,
             annotation_draft: None,
             show_info: false,
             redaction_review: None,
+            search: None,
+            vt_mode: false,
+            show_duration: false,
+            markdown_mode: false,
+            render_cache: HashMap::new(),
+            finder: None,
+            fullscreen_dismissed: std::collections::HashSet::new(),
             has_library: false,
+            keymaps: Keymaps::load(),
         }
     }
 
     /// Start playback immediately.
     pub fn start_playing(&mut self) {
         self.playing = true;
-        self.last_tick = Instant::now();
+        self.last_tick_ms = self.clock.now_ms();
     }
 
     fn speed(&self) -> f32 {
@@ -213,7 +461,7 @@ impl EditorState {
     fn toggle_play(&mut self) {
         self.playing = !self.playing;
         if self.playing {
-            self.last_tick = Instant::now();
+            self.last_tick_ms = self.clock.now_ms();
             if self.visible_count >= self.timeline.len() {
                 self.visible_count = 0;
                 self.playback_elapsed_ms = 0;
@@ -262,6 +510,351 @@ impl EditorState {
         self.auto_scroll();
     }
 
+    /// Reveal `entry_index` (which may be ahead of the current playback
+    /// position, like [`Self::jump_to_end`]) and scroll it into view. Also
+    /// used by `tui::run_app_loop` to open the Editor positioned at a
+    /// library-search hit rather than at the start of playback.
+    pub(crate) fn reveal_entry(&mut self, entry_index: usize) {
+        self.playing = false;
+        // A terminal entry expands into several timeline slots sharing the
+        // same `entry_index`, so its position can no longer be assumed to
+        // equal `entry_index` - find the first slot that actually holds it.
+        let position = self
+            .timeline
+            .iter()
+            .position(|te| te.entry_index == entry_index)
+            .unwrap_or(entry_index);
+        self.visible_count = (position + 1).min(self.timeline.len());
+        if let Some(te) = self.timeline.get(position) {
+            self.playback_elapsed_ms = te.playback_ms;
+        }
+        self.auto_scroll();
+    }
+
+    /// The entry index of the fullscreen segment currently occupying the
+    /// dedicated replay view, if any - the most recently revealed timeline
+    /// entry, when it's a fullscreen segment not yet stepped past.
+    fn active_fullscreen_entry_index(&self) -> Option<usize> {
+        if self.visible_count == 0 {
+            return None;
+        }
+        let te = &self.timeline[self.visible_count - 1];
+        if te.fullscreen && !self.fullscreen_dismissed.contains(&te.entry_index) {
+            Some(te.entry_index)
+        } else {
+            None
+        }
+    }
+
+    fn dismiss_fullscreen(&mut self) {
+        if let Some(idx) = self.active_fullscreen_entry_index() {
+            self.fullscreen_dismissed.insert(idx);
+            self.status_message = Some("Fullscreen segment dismissed".to_string());
+        }
+    }
+
+    fn toggle_vt_mode(&mut self) {
+        self.vt_mode = !self.vt_mode;
+        self.status_message = Some(if self.vt_mode {
+            "Terminal replay mode on".to_string()
+        } else {
+            "Terminal replay mode off".to_string()
+        });
+    }
+
+    fn toggle_duration_overlay(&mut self) {
+        self.show_duration = !self.show_duration;
+        self.status_message = Some(if self.show_duration {
+            "Duration overlay on".to_string()
+        } else {
+            "Duration overlay off".to_string()
+        });
+    }
+
+    fn toggle_markdown_mode(&mut self) {
+        self.markdown_mode = !self.markdown_mode;
+        self.status_message = Some(if self.markdown_mode {
+            "Markdown rendering on".to_string()
+        } else {
+            "Markdown rendering off".to_string()
+        });
+    }
+
+    fn start_search(&mut self) {
+        self.search = Some(SearchState {
+            query: String::new(),
+            matches: Vec::new(),
+            cursor: 0,
+            editing: true,
+        });
+        self.status_message = Some("Search: type to filter, Enter: confirm, Esc: cancel".to_string());
+    }
+
+    fn cancel_search(&mut self) {
+        self.search = None;
+        self.status_message = Some("Search cancelled".to_string());
+    }
+
+    /// Re-run the regex against every searchable entry and jump to the
+    /// first match, so each keystroke narrows the view live. Case-
+    /// insensitive by default; a query containing an uppercase letter
+    /// forces case-sensitive matching (smart case, as in `rg`/`vim`). An
+    /// invalid pattern clears the matches and reports the error through
+    /// `status_message` instead of panicking.
+    fn refresh_search_matches(&mut self) {
+        let Some(query) = self.search.as_ref().map(|s| s.query.clone()) else {
+            return;
+        };
+
+        if query.is_empty() {
+            if let Some(search) = self.search.as_mut() {
+                search.matches.clear();
+                search.cursor = 0;
+            }
+            return;
+        }
+
+        let case_sensitive = query.chars().any(|c| c.is_uppercase());
+        let pattern = if case_sensitive {
+            query.clone()
+        } else {
+            format!("(?i){}", query)
+        };
+
+        let re = match Regex::new(&pattern) {
+            Ok(re) => re,
+            Err(err) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.matches.clear();
+                    search.cursor = 0;
+                }
+                self.status_message = Some(format!("Invalid pattern: {}", err));
+                return;
+            }
+        };
+
+        let mut matches: Vec<SearchMatch> = Vec::new();
+        for (entry_index, entry) in self.spool_file.entries.iter().enumerate() {
+            let Some(text) = entry_search_text(entry) else {
+                continue;
+            };
+            for m in re.find_iter(&text) {
+                matches.push(SearchMatch {
+                    entry_index,
+                    start: m.start(),
+                    end: m.end(),
+                });
+            }
+        }
+
+        let count = matches.len();
+        let entry_index = matches.first().map(|m| m.entry_index);
+
+        if let Some(search) = self.search.as_mut() {
+            search.matches = matches;
+            search.cursor = 0;
+        }
+
+        self.status_message = Some(format!("Search \"{}\": {} match(es)", query, count));
+        if let Some(entry_index) = entry_index {
+            self.reveal_entry(entry_index);
+        }
+    }
+
+    /// Jump to the next match, wrapping back to the first after the last.
+    fn jump_to_next_match(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+        search.cursor = (search.cursor + 1) % search.matches.len();
+        self.reveal_current_match();
+    }
+
+    /// Jump to the previous match, wrapping to the last after the first.
+    fn jump_to_prev_match(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+        search.cursor = search
+            .cursor
+            .checked_sub(1)
+            .unwrap_or(search.matches.len() - 1);
+        self.reveal_current_match();
+    }
+
+    /// Reveal the entry at the search cursor and show a "match N/M" counter.
+    fn reveal_current_match(&mut self) {
+        let Some(search) = self.search.as_ref() else {
+            return;
+        };
+        let Some(m) = search.matches.get(search.cursor) else {
+            return;
+        };
+        let entry_index = m.entry_index;
+        self.status_message = Some(format!(
+            "Search \"{}\": match {}/{}",
+            search.query,
+            search.cursor + 1,
+            search.matches.len()
+        ));
+        self.reveal_entry(entry_index);
+    }
+
+    /// The current search's matches, keyed by entry id, as the char offsets
+    /// each byte-range match covers - [`HighlightContext::with_search_matches`]
+    /// (and the `highlight_chars` it feeds) marks highlights per char, so a
+    /// multi-byte-wide regex match expands to every char index it spans.
+    fn search_highlight_map(&self) -> HashMap<spool_format::EntryId, Vec<usize>> {
+        let Some(search) = self.search.as_ref() else {
+            return HashMap::new();
+        };
+        let mut map: HashMap<spool_format::EntryId, Vec<usize>> = HashMap::new();
+        for m in &search.matches {
+            let Some(entry) = self.spool_file.entries.get(m.entry_index) else {
+                continue;
+            };
+            let Some(id) = entry.id() else {
+                continue;
+            };
+            let Some(text) = entry_search_text(entry) else {
+                continue;
+            };
+            let positions = text
+                .char_indices()
+                .enumerate()
+                .filter(|(_, (byte_offset, _))| *byte_offset >= m.start && *byte_offset < m.end)
+                .map(|(char_index, _)| char_index);
+            map.entry(*id).or_default().extend(positions);
+        }
+        map
+    }
+
+    fn handle_search_key(&mut self, key: KeyCode) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Esc => self.cancel_search(),
+            KeyCode::Enter => {
+                search.editing = false;
+                self.reveal_current_match();
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.refresh_search_matches();
+            }
+            KeyCode::Char(ch) => {
+                search.query.push(ch);
+                self.refresh_search_matches();
+            }
+            _ => {}
+        }
+    }
+
+    fn start_finder(&mut self) {
+        self.finder = Some(FinderState {
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        });
+        self.refresh_finder_matches();
+        self.status_message = Some("Find: type to filter, Up/Down: select, Enter: jump, Esc: cancel".to_string());
+    }
+
+    fn cancel_finder(&mut self) {
+        self.finder = None;
+        self.status_message = Some("Find cancelled".to_string());
+    }
+
+    /// Re-score every searchable entry against the finder's query and
+    /// re-rank. An empty query matches everything in timeline order (score
+    /// 0, no snippet position); ties break by earlier `entry_index`.
+    fn refresh_finder_matches(&mut self) {
+        let Some(finder) = self.finder.as_mut() else {
+            return;
+        };
+
+        let mut matches: Vec<FinderMatch> = Vec::new();
+        for (entry_index, entry) in self.spool_file.entries.iter().enumerate() {
+            let Some(text) = entry_search_text(entry) else {
+                continue;
+            };
+            let Some(m) = fuzzy_match(&text, &finder.query) else {
+                continue;
+            };
+            let byte_pos = m
+                .positions
+                .first()
+                .and_then(|&ci| text.char_indices().nth(ci).map(|(b, _)| b))
+                .unwrap_or(0);
+            matches.push(FinderMatch {
+                entry_index,
+                score: m.score,
+                context_before: extract_context_before(&text, byte_pos, 30),
+                context_after: extract_context_after(&text, byte_pos, 30),
+            });
+        }
+        matches.sort_by(|a, b| b.score.cmp(&a.score).then(a.entry_index.cmp(&b.entry_index)));
+
+        finder.matches = matches;
+        finder.selected = 0;
+    }
+
+    fn finder_select_up(&mut self) {
+        if let Some(finder) = self.finder.as_mut() {
+            finder.selected = finder.selected.saturating_sub(1);
+        }
+    }
+
+    fn finder_select_down(&mut self) {
+        if let Some(finder) = self.finder.as_mut() {
+            if finder.selected + 1 < finder.matches.len() {
+                finder.selected += 1;
+            }
+        }
+    }
+
+    /// Reveal the selected match's entry and close the finder overlay.
+    fn confirm_finder(&mut self) {
+        let entry_index = self
+            .finder
+            .as_ref()
+            .and_then(|f| f.matches.get(f.selected))
+            .map(|m| m.entry_index);
+        self.finder = None;
+        if let Some(entry_index) = entry_index {
+            self.reveal_entry(entry_index);
+        }
+    }
+
+    fn handle_finder_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => self.cancel_finder(),
+            KeyCode::Enter => self.confirm_finder(),
+            KeyCode::Up => self.finder_select_up(),
+            KeyCode::Down => self.finder_select_down(),
+            KeyCode::Backspace => {
+                if let Some(finder) = self.finder.as_mut() {
+                    finder.query.pop();
+                }
+                self.refresh_finder_matches();
+            }
+            KeyCode::Char(ch) => {
+                if let Some(finder) = self.finder.as_mut() {
+                    finder.query.push(ch);
+                }
+                self.refresh_finder_matches();
+            }
+            _ => {}
+        }
+    }
+
     fn current_entry_timestamp(&self) -> Option<u64> {
         if self.visible_count == 0 {
             return None;
@@ -382,9 +975,12 @@ impl EditorState {
         };
 
         let insert_at = (target_index + 1).min(self.spool_file.entries.len());
-        self.spool_file
-            .entries
-            .insert(insert_at, Entry::Annotation(annotation));
+        let entry = Entry::Annotation(annotation);
+        self.spool_file.entries.insert(insert_at, entry.clone());
+        self.push_undo(EditOp::Annotate {
+            index: insert_at,
+            entry,
+        });
         self.update_session_entry_count();
         self.rebuild_timeline();
     }
@@ -398,18 +994,27 @@ impl EditorState {
     }
 
     fn rebuild_timeline(&mut self) {
-        self.timeline = build_timeline(&self.spool_file.entries);
+        self.timeline = build_timeline(&self.spool_file.entries, &self.compression_config);
+        self.seek_index = SeekIndex::build(&self.timeline);
         self.total_duration_ms = self.timeline.last().map(|t| t.playback_ms).unwrap_or(0);
         self.visible_count = self
             .timeline
             .iter()
             .take_while(|t| t.playback_ms <= self.playback_elapsed_ms)
             .count();
+        // An annotation insert shifts every later entry's index, so a
+        // cached render keyed by the old index would now describe the
+        // wrong entry.
+        self.render_cache.clear();
     }
 
     fn mark_trim_start(&mut self) {
         match self.current_entry_timestamp() {
             Some(ts) => {
+                self.push_undo(EditOp::SetTrim {
+                    start: self.trim_start_ms,
+                    end: self.trim_end_ms,
+                });
                 self.trim_start_ms = Some(ts);
                 self.status_message = Some(format!("Trim start set to {}", format_duration_ms(ts)));
             }
@@ -422,6 +1027,10 @@ impl EditorState {
     fn mark_trim_end(&mut self) {
         match self.current_entry_timestamp() {
             Some(ts) => {
+                self.push_undo(EditOp::SetTrim {
+                    start: self.trim_start_ms,
+                    end: self.trim_end_ms,
+                });
                 self.trim_end_ms = Some(ts);
                 self.status_message = Some(format!("Trim end set to {}", format_duration_ms(ts)));
             }
@@ -431,6 +1040,76 @@ impl EditorState {
         }
     }
 
+    /// Record `op` as the most recent edit and drop any redo history, since
+    /// a fresh edit invalidates whatever was previously undone.
+    fn push_undo(&mut self, op: EditOp) {
+        self.undo_stack.push(op);
+        self.redo_stack.clear();
+    }
+
+    /// Undo the most recent edit (trim mark or annotation insert),
+    /// rebuilding the timeline so playback offsets stay consistent.
+    fn undo(&mut self) {
+        let Some(op) = self.undo_stack.pop() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return;
+        };
+        match op {
+            EditOp::Annotate { index, entry } => {
+                if index < self.spool_file.entries.len() {
+                    self.spool_file.entries.remove(index);
+                }
+                self.update_session_entry_count();
+                self.rebuild_timeline();
+                self.redo_stack.push(EditOp::Annotate { index, entry });
+                self.status_message = Some("Undid annotation".to_string());
+            }
+            EditOp::SetTrim { start, end } => {
+                let current = EditOp::SetTrim {
+                    start: self.trim_start_ms,
+                    end: self.trim_end_ms,
+                };
+                self.trim_start_ms = start;
+                self.trim_end_ms = end;
+                self.redo_stack.push(current);
+                self.status_message = Some("Undid trim mark".to_string());
+            }
+        }
+    }
+
+    /// Redo the most recently undone edit.
+    fn redo(&mut self) {
+        let Some(op) = self.redo_stack.pop() else {
+            self.status_message = Some("Nothing to redo".to_string());
+            return;
+        };
+        match op {
+            EditOp::Annotate { index, entry } => {
+                let insert_at = index.min(self.spool_file.entries.len());
+                self.spool_file
+                    .entries
+                    .insert(insert_at, entry.clone());
+                self.update_session_entry_count();
+                self.rebuild_timeline();
+                self.undo_stack.push(EditOp::Annotate {
+                    index: insert_at,
+                    entry,
+                });
+                self.status_message = Some("Redid annotation".to_string());
+            }
+            EditOp::SetTrim { start, end } => {
+                let current = EditOp::SetTrim {
+                    start: self.trim_start_ms,
+                    end: self.trim_end_ms,
+                };
+                self.trim_start_ms = start;
+                self.trim_end_ms = end;
+                self.undo_stack.push(current);
+                self.status_message = Some("Redid trim mark".to_string());
+            }
+        }
+    }
+
     fn trim_range(&self) -> Option<(u64, u64)> {
         match (self.trim_start_ms, self.trim_end_ms) {
             (Some(start), Some(end)) if start < end => Some((start, end)),
@@ -438,16 +1117,52 @@ impl EditorState {
         }
     }
 
-    fn trim_preview(&self, start: u64, end: u64) -> (usize, u64) {
+    /// What trimming to `[start, end]` would keep, and - separately - the
+    /// tokens/cost the entries *outside* that range would take with them,
+    /// so the user can see the budget impact before committing via
+    /// [`Self::export_trimmed`].
+    fn trim_preview(&self, start: u64, end: u64) -> TrimPreview {
         let mut kept = 1; // session entry always kept
+        let mut tokens_removed = 0u64;
+        let mut cost_removed = 0.0;
         for entry in self.spool_file.entries.iter().skip(1) {
-            if let Some(ts) = entry.timestamp() {
-                if ts >= start && ts <= end {
-                    kept += 1;
+            let Some(ts) = entry.timestamp() else {
+                continue;
+            };
+            if ts >= start && ts <= end {
+                kept += 1;
+            } else if let Entry::Response(r) = entry {
+                if let Some(usage) = &r.token_usage {
+                    tokens_removed += usage.input_tokens + usage.output_tokens;
+                    let model = r.model.as_deref().unwrap_or("");
+                    cost_removed += super::cost::turn_cost(
+                        DEFAULT_PRICES,
+                        model,
+                        usage.input_tokens,
+                        usage.output_tokens,
+                    );
                 }
             }
         }
-        (kept, end.saturating_sub(start))
+        TrimPreview {
+            kept,
+            duration_ms: end.saturating_sub(start),
+            tokens_removed,
+            cost_removed,
+        }
+    }
+
+    /// Cumulative token-usage/cost analytics over the full timeline: totals
+    /// plus the priciest turns, derived from `Response` entries'
+    /// `token_usage`/`model` fields via the built-in [`DEFAULT_PRICES`]
+    /// table.
+    pub fn cost_summary(&self) -> CostSummary {
+        let scored: Vec<(usize, u64, &Entry)> = self
+            .timeline
+            .iter()
+            .map(|te| (te.entry_index, te.playback_ms, &self.spool_file.entries[te.entry_index]))
+            .collect();
+        cost_summary(&scored, DEFAULT_PRICES, 5)
     }
 
     fn export_trimmed(&mut self) {
@@ -489,7 +1204,14 @@ impl EditorState {
             }
         };
 
-        let candidates = self.detect_secrets_in_range(start, end);
+        let (candidates, rule_errors) = self.detect_secrets_in_range(start, end);
+
+        if !rule_errors.is_empty() {
+            self.status_message = Some(format!(
+                "Skipped invalid custom redaction rule(s): {}",
+                rule_errors.join("; ")
+            ));
+        }
 
         if candidates.is_empty() {
             // No secrets found, export directly
@@ -502,13 +1224,22 @@ impl EditorState {
                 stage: RedactionStage::Review,
                 preview_scroll: 0,
             });
-            self.status_message = Some("Review detected secrets".to_string());
+            if rule_errors.is_empty() {
+                self.status_message = Some("Review detected secrets".to_string());
+            }
         }
     }
 
-    /// Detect secrets in entries within the given timestamp range.
-    fn detect_secrets_in_range(&self, start: u64, end: u64) -> Vec<RedactionCandidate> {
-        let detector = SecretDetector::with_defaults();
+    /// Detect secrets in entries within the given timestamp range, using the
+    /// built-in detectors plus any user-defined rules from
+    /// [`load_custom_redaction_rules`]. Returns the candidates alongside any
+    /// custom rules that failed to compile, for the caller to surface.
+    fn detect_secrets_in_range(&self, start: u64, end: u64) -> (Vec<RedactionCandidate>, Vec<String>) {
+        let detector = SecretDetector::new(RedactionConfig {
+            custom_rules: load_custom_redaction_rules(),
+            ..RedactionConfig::default()
+        });
+        let rule_errors = detector.rule_errors().to_vec();
         let mut candidates = Vec::new();
 
         for (idx, entry) in self.spool_file.entries.iter().enumerate() {
@@ -561,76 +1292,75 @@ impl EditorState {
             }
         }
 
-        candidates
+        (candidates, rule_errors)
     }
 
     /// Handle key input when redaction review modal is open.
-    fn handle_redaction_key(&mut self, key: KeyCode) {
+    /// Handle a key in the redaction review modal by looking it up in
+    /// `keymaps.redaction` rather than matching raw `KeyCode`s - the same
+    /// physical key can mean different things per stage (e.g. `Confirm`
+    /// advances Review to Preview, but triggers the export from Preview).
+    fn handle_redaction_key(&mut self, key: KeyEvent) {
+        let Some(cmd) = self.keymaps.redaction.lookup(&key) else {
+            return;
+        };
         let state = match self.redaction_review.as_mut() {
             Some(s) => s,
             None => return,
         };
 
-        match state.stage {
-            RedactionStage::Review => match key {
-                KeyCode::Esc => {
-                    self.redaction_review = None;
-                    self.status_message = Some("Export cancelled".to_string());
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.selected_index + 1 < state.candidates.len() {
-                        state.selected_index += 1;
-                    }
-                }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.selected_index > 0 {
-                        state.selected_index -= 1;
-                    }
-                }
-                KeyCode::Char(' ') => {
-                    // Toggle confirmation
-                    if let Some(candidate) = state.candidates.get_mut(state.selected_index) {
-                        candidate.confirmed = !candidate.confirmed;
-                    }
-                }
-                KeyCode::Char('a') => {
-                    // Accept all
-                    for candidate in &mut state.candidates {
-                        candidate.confirmed = true;
-                    }
-                }
-                KeyCode::Char('d') => {
-                    // Dismiss all
-                    for candidate in &mut state.candidates {
-                        candidate.confirmed = false;
-                    }
-                }
-                KeyCode::Enter => {
-                    // Proceed to preview
-                    state.stage = RedactionStage::Preview;
-                    state.preview_scroll = 0;
+        match (state.stage, cmd) {
+            (RedactionStage::Review, RedactionCommand::Cancel) => {
+                self.redaction_review = None;
+                self.status_message = Some("Export cancelled".to_string());
+            }
+            (RedactionStage::Review, RedactionCommand::Down) => {
+                if state.selected_index + 1 < state.candidates.len() {
+                    state.selected_index += 1;
                 }
-                _ => {}
-            },
-            RedactionStage::Preview => match key {
-                KeyCode::Esc => {
-                    // Back to review
-                    state.stage = RedactionStage::Review;
+            }
+            (RedactionStage::Review, RedactionCommand::Up) => {
+                if state.selected_index > 0 {
+                    state.selected_index -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    state.preview_scroll = state.preview_scroll.saturating_add(1);
+            }
+            (RedactionStage::Review, RedactionCommand::ToggleConfirm) => {
+                if let Some(candidate) = state.candidates.get_mut(state.selected_index) {
+                    candidate.confirmed = !candidate.confirmed;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    state.preview_scroll = state.preview_scroll.saturating_sub(1);
+            }
+            (RedactionStage::Review, RedactionCommand::AcceptAll) => {
+                for candidate in &mut state.candidates {
+                    candidate.confirmed = true;
                 }
-                KeyCode::Enter => {
-                    // Execute export with redactions
-                    let candidates = state.candidates.clone();
-                    self.redaction_review = None;
-                    self.export_with_redactions(&candidates);
+            }
+            (RedactionStage::Review, RedactionCommand::DismissAll) => {
+                for candidate in &mut state.candidates {
+                    candidate.confirmed = false;
                 }
-                _ => {}
-            },
+            }
+            (RedactionStage::Review, RedactionCommand::Confirm) => {
+                // Proceed to preview
+                state.stage = RedactionStage::Preview;
+                state.preview_scroll = 0;
+            }
+            (RedactionStage::Preview, RedactionCommand::Cancel) => {
+                // Back to review
+                state.stage = RedactionStage::Review;
+            }
+            (RedactionStage::Preview, RedactionCommand::Down) => {
+                state.preview_scroll = state.preview_scroll.saturating_add(1);
+            }
+            (RedactionStage::Preview, RedactionCommand::Up) => {
+                state.preview_scroll = state.preview_scroll.saturating_sub(1);
+            }
+            (RedactionStage::Preview, RedactionCommand::Confirm) => {
+                // Execute export with redactions
+                let candidates = state.candidates.clone();
+                self.redaction_review = None;
+                self.export_with_redactions(&candidates);
+            }
+            _ => {}
         }
     }
 
@@ -678,11 +1408,11 @@ impl EditorState {
             return;
         }
 
-        let now = Instant::now();
-        let real_elapsed = now.duration_since(self.last_tick);
-        self.last_tick = now;
+        let now_ms = self.clock.now_ms();
+        let real_elapsed_ms = now_ms.saturating_sub(self.last_tick_ms);
+        self.last_tick_ms = now_ms;
 
-        let advance_ms = (real_elapsed.as_millis() as f64 * self.speed() as f64) as u64;
+        let advance_ms = (real_elapsed_ms as f64 * self.speed() as f64) as u64;
         self.playback_elapsed_ms = self.playback_elapsed_ms.saturating_add(advance_ms);
 
         while self.visible_count < self.timeline.len() {
@@ -690,6 +1420,13 @@ impl EditorState {
             if te.playback_ms <= self.playback_elapsed_ms {
                 self.visible_count += 1;
                 self.auto_scroll();
+                // Pause on a fresh fullscreen segment so its dedicated view
+                // stays up until the user steps past it, instead of playback
+                // racing straight through the entries behind it.
+                if self.active_fullscreen_entry_index().is_some() {
+                    self.playing = false;
+                    return;
+                }
             } else {
                 break;
             }
@@ -701,6 +1438,19 @@ impl EditorState {
         }
     }
 
+    /// Scrub directly to `playback_ms` via [`SeekIndex::seek_to`] - O(log n)
+    /// instead of ticking forward one entry at a time, so scrubbing a
+    /// multi-hour session is instant.
+    pub fn seek(&mut self, playback_ms: u64) {
+        self.playing = false;
+        let Some(position) = self.seek_index.seek_to(playback_ms) else {
+            return;
+        };
+        self.visible_count = (position + 1).min(self.timeline.len());
+        self.playback_elapsed_ms = self.timeline[position].playback_ms;
+        self.auto_scroll();
+    }
+
     fn auto_scroll(&mut self) {
         self.scroll_offset = usize::MAX;
     }
@@ -745,7 +1495,7 @@ impl EditorState {
 
         // Redaction review modal takes precedence
         if self.redaction_review.is_some() {
-            self.handle_redaction_key(key.code);
+            self.handle_redaction_key(key);
             return EditorAction::None;
         }
 
@@ -754,37 +1504,74 @@ impl EditorState {
             return EditorAction::None;
         }
 
+        // A fullscreen segment occupies the whole entry pane; only accept
+        // the key that steps past it back to the normal scrolling layout.
+        if self.active_fullscreen_entry_index().is_some() {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Char(' ')) {
+                self.dismiss_fullscreen();
+            }
+            return EditorAction::None;
+        }
+
+        if self.finder.is_some() {
+            self.handle_finder_key(key.code);
+            return EditorAction::None;
+        }
+
+        if self.search.as_ref().is_some_and(|s| s.editing) {
+            self.handle_search_key(key.code);
+            return EditorAction::None;
+        }
+
         // Info overlay: any key dismisses it
         if self.show_info {
             self.show_info = false;
             return EditorAction::None;
         }
 
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
+        // Search overlay active but not editing: Esc cancels it outright,
+        // taking precedence over the normal keymap's `Back` binding (which
+        // Esc is also bound to by default).
+        if key.code == KeyCode::Esc && self.search.is_some() {
+            self.cancel_search();
+            return EditorAction::None;
+        }
+
+        let Some(cmd) = self.keymaps.normal.lookup(&key) else {
+            return EditorAction::None;
+        };
+
+        match cmd {
+            EditorCommand::Back => {
                 if self.has_library {
                     return EditorAction::Back;
                 } else {
                     return EditorAction::Quit;
                 }
             }
-            KeyCode::Char(' ') => self.toggle_play(),
-            KeyCode::Right | KeyCode::Char('l') => self.step_forward(),
-            KeyCode::Left | KeyCode::Char('h') => self.step_backward(),
-            KeyCode::Char('+') | KeyCode::Char('=') => self.speed_up(),
-            KeyCode::Char('-') | KeyCode::Char('_') => self.speed_down(),
-            KeyCode::Home | KeyCode::Char('g') => self.jump_to_start(),
-            KeyCode::End | KeyCode::Char('G') => self.jump_to_end(),
-            KeyCode::PageUp | KeyCode::Char('k') => self.scroll_up(10),
-            KeyCode::PageDown | KeyCode::Char('j') => self.scroll_down(10),
-            KeyCode::Char('[') => self.mark_trim_start(),
-            KeyCode::Char(']') => self.mark_trim_end(),
-            KeyCode::Char('x') => self.start_redaction_review(),
-            KeyCode::Char('a') => self.start_annotation(),
-            KeyCode::Char('i') => {
-                self.show_info = true;
-            }
-            _ => {}
+            EditorCommand::PlayPause => self.toggle_play(),
+            EditorCommand::StepForward => self.step_forward(),
+            EditorCommand::StepBack => self.step_backward(),
+            EditorCommand::SpeedUp => self.speed_up(),
+            EditorCommand::SpeedDown => self.speed_down(),
+            EditorCommand::JumpStart => self.jump_to_start(),
+            EditorCommand::JumpEnd => self.jump_to_end(),
+            EditorCommand::ScrollUp => self.scroll_up(10),
+            EditorCommand::ScrollDown => self.scroll_down(10),
+            EditorCommand::MarkTrimStart => self.mark_trim_start(),
+            EditorCommand::MarkTrimEnd => self.mark_trim_end(),
+            EditorCommand::StartRedactionReview => self.start_redaction_review(),
+            EditorCommand::StartAnnotation => self.start_annotation(),
+            EditorCommand::StartSearch => self.start_search(),
+            EditorCommand::JumpNextMatch => self.jump_to_next_match(),
+            EditorCommand::JumpPrevMatch => self.jump_to_prev_match(),
+            EditorCommand::StartFinder => self.start_finder(),
+            EditorCommand::ToggleVtMode => self.toggle_vt_mode(),
+            EditorCommand::ToggleDurationOverlay => self.toggle_duration_overlay(),
+            EditorCommand::ToggleMarkdownMode => self.toggle_markdown_mode(),
+            EditorCommand::Undo => self.undo(),
+            EditorCommand::Redo => self.redo(),
+            EditorCommand::ToggleInfo => self.show_info = true,
         }
 
         EditorAction::None
@@ -818,6 +1605,10 @@ impl EditorState {
         if self.show_info {
             self.draw_info_overlay(f);
         }
+
+        if self.finder.is_some() {
+            self.draw_finder_overlay(f);
+        }
     }
 
     fn draw_title_bar(&self, f: &mut Frame, area: Rect) {
@@ -865,12 +1656,73 @@ impl EditorState {
             return;
         }
 
+        if let Some(entry_index) = self.active_fullscreen_entry_index() {
+            self.draw_fullscreen_segment(f, area, entry_index);
+            return;
+        }
+
         let mut lines: Vec<Line> = Vec::new();
+        if self.show_duration {
+            lines.push(duration_summary_line(
+                &self.spool_file.entries,
+                self.total_duration_ms,
+            ));
+            lines.push(Line::from(""));
+        }
+
+        let search_matches = self.search_highlight_map();
+        let highlighting_active_search = !search_matches.is_empty();
+        let highlight_ctx = HighlightContext::from_entries(&self.spool_file.entries)
+            .with_search_matches(search_matches)
+            .with_vt_mode(self.vt_mode)
+            .with_duration_overlay(self.show_duration)
+            .with_markdown_mode(self.markdown_mode);
+        let width = inner.width as usize;
 
         for ti in 0..self.visible_count {
             let te = &self.timeline[ti];
-            let entry = &self.spool_file.entries[te.entry_index];
-            render_entry_lines(entry, &mut lines, inner.width as usize);
+            let entry_index = te.entry_index;
+
+            // A multi-frame `Entry::Terminal` pushes one timeline slot per
+            // frame; only render at the last frame currently visible so
+            // earlier frames of the same entry don't each produce their own
+            // duplicate block.
+            if ti + 1 < self.visible_count && self.timeline[ti + 1].entry_index == entry_index {
+                continue;
+            }
+
+            let cache_key = (entry_index, te.terminal_frame);
+
+            if !highlighting_active_search {
+                if let Some(cached) = self.render_cache.get(&cache_key) {
+                    if cached.width == width
+                        && cached.vt_mode == self.vt_mode
+                        && cached.show_duration == self.show_duration
+                        && cached.markdown_mode == self.markdown_mode
+                    {
+                        lines.extend(cached.lines.iter().cloned());
+                        continue;
+                    }
+                }
+            }
+
+            let entry = &self.spool_file.entries[entry_index];
+            let mut entry_lines = Vec::new();
+            render_entry_lines(entry, &highlight_ctx, &mut entry_lines, width, te.terminal_frame);
+
+            if !highlighting_active_search {
+                self.render_cache.insert(
+                    cache_key,
+                    CachedRender {
+                        width,
+                        vt_mode: self.vt_mode,
+                        show_duration: self.show_duration,
+                        markdown_mode: self.markdown_mode,
+                        lines: entry_lines.clone(),
+                    },
+                );
+            }
+            lines.extend(entry_lines);
         }
 
         let total_lines = lines.len();
@@ -889,6 +1741,98 @@ impl EditorState {
         f.render_widget(paragraph, area);
     }
 
+    /// Dedicated full-frame replay of a fullscreen segment (see
+    /// [`TimelineEntry::fullscreen`]), rendered through the VT grid at the
+    /// pane's full size instead of appended to the scrolling entry list -
+    /// dumping raw alt-screen output inline would destroy the layout.
+    fn draw_fullscreen_segment(&self, f: &mut Frame, area: Rect, entry_index: usize) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(area);
+
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                " ALTERNATE SCREEN - Enter: step past ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))),
+            chunks[0],
+        );
+
+        let text = match self.spool_file.entries.get(entry_index) {
+            Some(Entry::ToolResult(tr)) => match &tr.output {
+                Some(ToolOutput::Text(t)) => t.as_str(),
+                _ => "",
+            },
+            _ => "",
+        };
+
+        let width = chunks[1].width as usize;
+        let height = chunks[1].height as usize;
+        let lines: Vec<Line> = vt::render_vt(text, width.max(1))
+            .into_iter()
+            .take(height)
+            .map(|spans| {
+                Line::from(
+                    spans
+                        .into_iter()
+                        .map(|(style, s)| Span::styled(s, style))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+    }
+
+    /// The fuzzy-finder overlay: a ranked, scrollable list of matches with
+    /// context snippets, the selected row highlighted (see [`FinderState`]).
+    fn draw_finder_overlay(&self, f: &mut Frame) {
+        let Some(ref finder) = self.finder else {
+            return;
+        };
+
+        let area = centered_rect(80, 70, f.area());
+        let title = format!(" Find ({} matches) - {}_ ", finder.matches.len(), finder.query);
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+
+        let inner = block.inner(area);
+        let visible_rows = inner.height as usize;
+        let scroll = finder
+            .selected
+            .saturating_sub(visible_rows.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (row, m) in finder.matches.iter().enumerate().skip(scroll).take(visible_rows) {
+            let entry = &self.spool_file.entries[m.entry_index];
+            let kind = entry_kind_label(entry);
+            let snippet = format!("{}…{}", m.context_before, m.context_after);
+            let text = format!("[{}] {}", kind, snippet);
+            let style = if row == finder.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(Span::styled(text, style)));
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No matches",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+        f.render_widget(paragraph, area);
+    }
+
     fn draw_progress_bar(&self, f: &mut Frame, area: Rect) {
         let ratio = self.progress_ratio();
         let label = self.progress_label();
@@ -907,10 +1851,12 @@ impl EditorState {
     }
 
     fn draw_controls(&self, f: &mut Frame, area: Rect) {
+        let key = |cmd: EditorCommand| self.keymaps.normal.label_for(cmd);
+
         let play_key = if self.playing {
-            "Space:pause"
+            format!("{}:pause", key(EditorCommand::PlayPause))
         } else {
-            "Space:play"
+            format!("{}:play", key(EditorCommand::PlayPause))
         };
         let trim_label = match (self.trim_start_ms, self.trim_end_ms) {
             (None, None) => "Trim: [unset]".to_string(),
@@ -918,14 +1864,21 @@ impl EditorState {
             (None, Some(end)) => format!("Trim end: {}", format_duration_ms(end)),
             (Some(start), Some(end)) => {
                 if start < end {
-                    let (kept, duration) = self.trim_preview(start, end);
-                    format!(
+                    let preview = self.trim_preview(start, end);
+                    let mut label = format!(
                         "Trim: {}-{} ({} entries, {})",
                         format_duration_ms(start),
                         format_duration_ms(end),
-                        kept,
-                        format_duration_ms(duration)
-                    )
+                        preview.kept,
+                        format_duration_ms(preview.duration_ms)
+                    );
+                    if preview.tokens_removed > 0 {
+                        label.push_str(&format!(
+                            ", removes {} tokens (${:.2})",
+                            preview.tokens_removed, preview.cost_removed
+                        ));
+                    }
+                    label
                 } else {
                     format!(
                         "Trim: {}-{} (invalid)",
@@ -936,11 +1889,45 @@ impl EditorState {
             }
         };
 
-        let back_key = if self.has_library { "q:back" } else { "q:quit" };
+        let back_key = if self.has_library {
+            format!("{}:back", key(EditorCommand::Back))
+        } else {
+            format!("{}:quit", key(EditorCommand::Back))
+        };
+        let undo_label = format!(
+            "{}:undo({})/{}:redo({})",
+            key(EditorCommand::Undo),
+            self.undo_stack.len(),
+            key(EditorCommand::Redo),
+            self.redo_stack.len()
+        );
 
         let mut text = format!(
-            " {}  h/l:step  +/-:speed  j/k:scroll  g/G:start/end  [/]:trim  x:export  a:annotate  i:info  {}  {}",
-            play_key, back_key, trim_label
+            " {}  {}/{}:step  {}/{}:speed  {}/{}:scroll  {}/{}:start/end  {}/{}:trim  {}:search  {}/{}:next/prev match  {}:find  {}:term  {}:duration  {}:markdown  {}  {}:export  {}:annotate  {}:info  {}  {}",
+            play_key,
+            key(EditorCommand::StepBack),
+            key(EditorCommand::StepForward),
+            key(EditorCommand::SpeedUp),
+            key(EditorCommand::SpeedDown),
+            key(EditorCommand::ScrollUp),
+            key(EditorCommand::ScrollDown),
+            key(EditorCommand::JumpStart),
+            key(EditorCommand::JumpEnd),
+            key(EditorCommand::MarkTrimStart),
+            key(EditorCommand::MarkTrimEnd),
+            key(EditorCommand::StartSearch),
+            key(EditorCommand::JumpNextMatch),
+            key(EditorCommand::JumpPrevMatch),
+            key(EditorCommand::StartFinder),
+            key(EditorCommand::ToggleVtMode),
+            key(EditorCommand::ToggleDurationOverlay),
+            key(EditorCommand::ToggleMarkdownMode),
+            undo_label,
+            key(EditorCommand::StartRedactionReview),
+            key(EditorCommand::StartAnnotation),
+            key(EditorCommand::ToggleInfo),
+            back_key,
+            trim_label
         );
         if let Some(ref status) = self.status_message {
             text.push_str("  |  ");
@@ -977,7 +1964,7 @@ impl EditorState {
                     .style
                     .as_ref()
                     .map(annotation_style_label)
-                    .unwrap_or("none");
+                    .unwrap_or(std::borrow::Cow::Borrowed("none"));
                 vec![
                     Line::from("Select style: 1/2/3/4/5 or h/c/p/w/s"),
                     Line::from("Enter to save, Esc to cancel"),
@@ -1066,7 +2053,7 @@ impl EditorState {
             let status = if candidate.confirmed { "[x]" } else { "[ ]" };
 
             // Format: > [x] email: test@example.com  (context...match...context)
-            let category = format!("{:?}", candidate.detection.reason);
+            let category = category_label(&candidate.detection.reason);
             let matched = truncate_str(&candidate.detection.matched, 30);
             let ctx_before = truncate_str(&candidate.context_before, 20);
             let ctx_after = truncate_str(&candidate.context_after, 20);
@@ -1171,7 +2158,7 @@ impl EditorState {
         // Preview: show what will be redacted
         let mut lines: Vec<Line> = Vec::new();
         for candidate in &confirmed {
-            let category = format!("{:?}", candidate.detection.reason);
+            let category = category_label(&candidate.detection.reason);
             let replacement = candidate.detection.reason.replacement();
 
             lines.push(Line::from(vec![
@@ -1203,8 +2190,73 @@ impl EditorState {
     }
 }
 
+/// Build the duration-overlay summary line comparing the session's real
+/// wall-clock span (first entry's timestamp to last) against the
+/// idle-compressed playback duration used to drive the timeline.
+fn duration_summary_line(entries: &[Entry], compressed_ms: u64) -> Line<'static> {
+    let timestamps: Vec<u64> = entries.iter().filter_map(|e| e.timestamp()).collect();
+    let real_ms = match (timestamps.first(), timestamps.last()) {
+        (Some(&first), Some(&last)) => last.saturating_sub(first),
+        _ => 0,
+    };
+    Line::from(Span::styled(
+        format!(
+            "  Real time: {}  |  Playback (compressed): {}",
+            format_duration_ms(real_ms),
+            format_duration_ms(compressed_ms)
+        ),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+/// Tunable parameters for [`build_timeline`]'s gap compression. Below a
+/// threshold, real time passes through unchanged; above it, the excess is
+/// compressed via `threshold + scale * ln(1 + excess / scale)` - monotonic
+/// (a longer real wait always reads as at least as long) but with
+/// diminishing growth, so a 30-minute gap still reads as longer than a
+/// 30-second one instead of both collapsing to the same hard clamp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressionConfig {
+    /// Below this, the real gap before a `Prompt` (user think-time) passes
+    /// through unchanged.
+    pub idle_linear_threshold_ms: u64,
+    /// Divisor controlling how slowly the log curve grows past the idle
+    /// threshold - larger means slower growth (more compression).
+    pub idle_scale_ms: f64,
+    /// Below this, the real gap after a `Thinking` entry passes through
+    /// unchanged.
+    pub thinking_linear_threshold_ms: u64,
+    /// Divisor controlling how slowly the log curve grows past the
+    /// thinking threshold.
+    pub thinking_scale_ms: f64,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            idle_linear_threshold_ms: MAX_IDLE_GAP_MS,
+            idle_scale_ms: MAX_IDLE_GAP_MS as f64,
+            thinking_linear_threshold_ms: MAX_THINKING_MS,
+            thinking_scale_ms: MAX_THINKING_MS as f64,
+        }
+    }
+}
+
+/// Compress `gap` against one threshold/scale pair: unchanged below the
+/// threshold, logarithmic above it. Monotonic in `gap`, so callers applying
+/// this to successive (non-decreasing) real gaps get a non-decreasing
+/// cumulative playback time.
+fn compress_gap(gap: u64, linear_threshold_ms: u64, scale_ms: f64) -> u64 {
+    if gap <= linear_threshold_ms || scale_ms <= 0.0 {
+        return gap;
+    }
+    let excess = (gap - linear_threshold_ms) as f64;
+    let compressed_excess = scale_ms * (1.0 + excess / scale_ms).ln();
+    linear_threshold_ms + compressed_excess.round() as u64
+}
+
 /// Build a compressed timeline from entries.
-fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
+fn build_timeline(entries: &[Entry], config: &CompressionConfig) -> Vec<TimelineEntry> {
     if entries.is_empty() {
         return Vec::new();
     }
@@ -1222,15 +2274,17 @@ fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
         } else {
             let mut gap = raw_gap;
 
-            if matches!(entry, Entry::Prompt(_)) && gap > MAX_IDLE_GAP_MS {
-                gap = MAX_IDLE_GAP_MS;
+            if matches!(entry, Entry::Prompt(_)) {
+                gap = compress_gap(gap, config.idle_linear_threshold_ms, config.idle_scale_ms);
             }
 
-            if i > 0 {
-                if let Some(prev_entry) = entries.get(i - 1) {
-                    if matches!(prev_entry, Entry::Thinking(_)) && gap > MAX_THINKING_MS {
-                        gap = MAX_THINKING_MS;
-                    }
+            if let Some(prev_entry) = entries.get(i - 1) {
+                if matches!(prev_entry, Entry::Thinking(_)) {
+                    gap = compress_gap(
+                        gap,
+                        config.thinking_linear_threshold_ms,
+                        config.thinking_scale_ms,
+                    );
                 }
             }
 
@@ -1239,10 +2293,21 @@ fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
 
         compressed_time += compressed_gap;
 
-        timeline.push(TimelineEntry {
-            entry_index: i,
-            playback_ms: compressed_time,
-        });
+        let fullscreen = matches!(
+            entry,
+            Entry::ToolResult(tr) if matches!(&tr.output, Some(ToolOutput::Text(t)) if ends_in_alt_screen(t))
+        );
+
+        if let Entry::Terminal(t) = entry {
+            push_terminal_frames(&mut timeline, i, compressed_time, &t.frames, config);
+        } else {
+            timeline.push(TimelineEntry {
+                entry_index: i,
+                playback_ms: compressed_time,
+                fullscreen,
+                terminal_frame: None,
+            });
+        }
 
         prev_original_ts = original_ts;
     }
@@ -1250,6 +2315,50 @@ fn build_timeline(entries: &[Entry]) -> Vec<TimelineEntry> {
     timeline
 }
 
+/// Expand one `Entry::Terminal`'s frames into a `TimelineEntry` per frame,
+/// so playback replays the capture frame-by-frame at its original relative
+/// pacing instead of dumping it all at once. A long quiet span between two
+/// frames is compressed the same way `build_timeline` compresses the gap
+/// before a `Prompt`, so one slow command doesn't stall the whole replay.
+fn push_terminal_frames(
+    timeline: &mut Vec<TimelineEntry>,
+    entry_index: usize,
+    entry_playback_ms: u64,
+    frames: &[spool_format::TerminalFrame],
+    config: &CompressionConfig,
+) {
+    if frames.is_empty() {
+        timeline.push(TimelineEntry {
+            entry_index,
+            playback_ms: entry_playback_ms,
+            fullscreen: false,
+            terminal_frame: None,
+        });
+        return;
+    }
+
+    let mut compressed_offset: u64 = 0;
+    let mut prev_offset_ms: u64 = 0;
+    for (frame_idx, frame) in frames.iter().enumerate() {
+        let raw_gap = frame.offset_ms.saturating_sub(prev_offset_ms);
+        let gap = if frame_idx > 0 {
+            compress_gap(raw_gap, config.idle_linear_threshold_ms, config.idle_scale_ms)
+        } else {
+            raw_gap
+        };
+        compressed_offset += gap;
+
+        timeline.push(TimelineEntry {
+            entry_index,
+            playback_ms: entry_playback_ms + compressed_offset,
+            fullscreen: false,
+            terminal_frame: Some(frame_idx),
+        });
+
+        prev_offset_ms = frame.offset_ms;
+    }
+}
+
 fn next_trimmed_path(source: &Path) -> PathBuf {
     let parent = source.parent().unwrap_or_else(|| Path::new("."));
     let stem = source
@@ -1273,28 +2382,40 @@ fn next_trimmed_path(source: &Path) -> PathBuf {
     base
 }
 
-/// Extract context text before a match position.
-fn extract_context_before(text: &str, pos: usize, max_len: usize) -> String {
-    let start = pos.saturating_sub(max_len);
-    let slice = &text[start..pos];
-    // Find a good start boundary (word/line break if possible)
-    if let Some(nl) = slice.rfind('\n') {
-        slice[nl + 1..].to_string()
-    } else {
-        slice.to_string()
+/// Short uppercase tag for an entry's kind, used by the finder overlay's
+/// result rows.
+fn entry_kind_label(entry: &Entry) -> &'static str {
+    match entry {
+        Entry::Session(_) => "SESSION",
+        Entry::Prompt(_) => "PROMPT",
+        Entry::Thinking(_) => "THINKING",
+        Entry::ToolCall(_) => "TOOL CALL",
+        Entry::ToolResult(_) => "RESULT",
+        Entry::Response(_) => "RESPONSE",
+        Entry::Error(_) => "ERROR",
+        Entry::SubagentStart(_) => "SUBAGENT",
+        Entry::SubagentEnd(_) => "SUBAGENT",
+        Entry::Annotation(_) => "ANNOTATION",
+        Entry::RedactionMarker(_) => "REDACTED",
+        Entry::Terminal(_) => "TERMINAL",
+        Entry::Unknown(_) => "UNKNOWN",
     }
 }
 
-/// Extract context text after a match position.
-fn extract_context_after(text: &str, pos: usize, max_len: usize) -> String {
-    let end = (pos + max_len).min(text.len());
-    let slice = &text[pos..end];
-    // Find a good end boundary (word/line break if possible)
-    if let Some(nl) = slice.find('\n') {
-        slice[..nl].to_string()
-    } else {
-        slice.to_string()
-    }
+/// Load user-defined redaction rules from `<config dir>/spool/redaction.toml`
+/// (a [`RedactionProfile`]), the same `dirs`-based location convention as
+/// the session cache. A missing file means "no custom rules"; a malformed
+/// one is also treated that way here rather than surfaced, since the editor
+/// re-reads this on every [`EditorState::detect_secrets_in_range`] call and
+/// a parse error would otherwise spam the status line on every redaction
+/// pass. `RedactionProfileWatcher` is the variant that surfaces load errors,
+/// for long-running non-interactive callers.
+fn load_custom_redaction_rules() -> Vec<CustomRule> {
+    let path = match dirs::config_dir() {
+        Some(dir) => dir.join("spool").join("redaction.toml"),
+        None => return Vec::new(),
+    };
+    RedactionProfile::load(&path).unwrap_or_default().rules
 }
 
 /// Apply confirmed redactions to entries in a SpoolFile.
@@ -1367,7 +2488,7 @@ fn apply_redactions_to_text(text: &str, redactions: &[&RedactionCandidate]) -> S
         let det = &redaction.detection;
         if det.start < result.len() && det.end <= result.len() {
             let replacement = det.reason.replacement();
-            result.replace_range(det.start..det.end, replacement);
+            result.replace_range(det.start..det.end, &replacement);
         }
     }
     result
@@ -1397,10 +2518,15 @@ mod tests {
             entry_count: Some(5),
             tools_used: None,
             files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
             first_prompt: None,
             schema_url: None,
             trimmed: None,
             ended: None,
+            content_hash: None,
             extra: HashMap::new(),
         })
     }
@@ -1456,7 +2582,7 @@ mod tests {
 
     #[test]
     fn test_build_timeline_empty() {
-        let timeline = build_timeline(&[]);
+        let timeline = build_timeline(&[], &CompressionConfig::default());
         assert!(timeline.is_empty());
     }
 
@@ -1467,7 +2593,7 @@ mod tests {
             make_prompt(0, "hello"),
             make_response(1000, "hi there"),
         ];
-        let timeline = build_timeline(&entries);
+        let timeline = build_timeline(&entries, &CompressionConfig::default());
         assert_eq!(timeline.len(), 3);
         assert_eq!(timeline[0].playback_ms, 0);
         assert_eq!(timeline[1].playback_ms, 0);
@@ -1481,10 +2607,17 @@ mod tests {
             make_response(1000, "first response"),
             make_prompt(31_000, "second prompt"),
         ];
-        let timeline = build_timeline(&entries);
+        let timeline = build_timeline(&entries, &CompressionConfig::default());
         assert_eq!(timeline[0].playback_ms, 0);
         assert_eq!(timeline[1].playback_ms, 1000);
-        assert_eq!(timeline[2].playback_ms, 3000);
+        // Below the threshold, the 30s gap is compressed but still much
+        // longer than a clamped-to-2s gap would be - diminishing growth,
+        // not a hard ceiling.
+        assert_eq!(
+            timeline[2].playback_ms,
+            1000 + compress_gap(30_000, MAX_IDLE_GAP_MS, MAX_IDLE_GAP_MS as f64)
+        );
+        assert!(timeline[2].playback_ms > 1000 + MAX_IDLE_GAP_MS);
     }
 
     #[test]
@@ -1494,10 +2627,14 @@ mod tests {
             make_thinking(1000, "thinking..."),
             make_response(61_000, "done"),
         ];
-        let timeline = build_timeline(&entries);
+        let timeline = build_timeline(&entries, &CompressionConfig::default());
         assert_eq!(timeline[0].playback_ms, 0);
         assert_eq!(timeline[1].playback_ms, 1000);
-        assert_eq!(timeline[2].playback_ms, 3000);
+        assert_eq!(
+            timeline[2].playback_ms,
+            1000 + compress_gap(60_000, MAX_THINKING_MS, MAX_THINKING_MS as f64)
+        );
+        assert!(timeline[2].playback_ms > 1000 + MAX_THINKING_MS);
     }
 
     #[test]
@@ -1507,10 +2644,20 @@ mod tests {
             make_response(500, "response"),
             make_prompt(1000, "prompt"),
         ];
-        let timeline = build_timeline(&entries);
+        let timeline = build_timeline(&entries, &CompressionConfig::default());
         assert_eq!(timeline[2].playback_ms, 1000);
     }
 
+    #[test]
+    fn test_compress_gap_is_monotonic_and_non_decreasing() {
+        let mut prev = compress_gap(0, 2_000, 2_000.0);
+        for gap in [500, 2_000, 5_000, 30_000, 1_800_000] {
+            let compressed = compress_gap(gap, 2_000, 2_000.0);
+            assert!(compressed >= prev, "compression must not decrease as the real gap grows");
+            prev = compressed;
+        }
+    }
+
     #[test]
     fn test_trim_preview_counts_entries() {
         let session = match make_session_entry() {
@@ -1523,9 +2670,75 @@ mod tests {
         file.add_entry(make_prompt(3000, "later"));
 
         let app = EditorState::new(file, PathBuf::from("session.spool"), 1.0);
-        let (kept, duration) = app.trim_preview(1500, 2500);
-        assert_eq!(kept, 2);
-        assert_eq!(duration, 1000);
+        let preview = app.trim_preview(1500, 2500);
+        assert_eq!(preview.kept, 2);
+        assert_eq!(preview.duration_ms, 1000);
+    }
+
+    #[test]
+    fn test_trim_preview_reports_removed_token_cost() {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "hello"));
+        file.add_entry(Entry::Response(spool_format::ResponseEntry {
+            id: Uuid::new_v4(),
+            ts: 2000,
+            content: "ok".to_string(),
+            truncated: None,
+            original_bytes: None,
+            model: Some("claude-sonnet-4".to_string()),
+            token_usage: Some(spool_format::TokenUsage {
+                input_tokens: 1_000_000,
+                output_tokens: 0,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            }),
+            subagent_id: None,
+            extra: HashMap::new(),
+        }));
+        file.add_entry(make_prompt(3000, "later"));
+
+        let app = EditorState::new(file, PathBuf::from("session.spool"), 1.0);
+        // Range [2500, 3500] excludes the response at ts=2000, so its
+        // tokens/cost show up as removed.
+        let preview = app.trim_preview(2500, 3500);
+        assert_eq!(preview.tokens_removed, 1_000_000);
+        assert_eq!(preview.cost_removed, 3.0);
+    }
+
+    #[test]
+    fn test_cost_summary_totals_response_token_usage() {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "hello"));
+        file.add_entry(Entry::Response(spool_format::ResponseEntry {
+            id: Uuid::new_v4(),
+            ts: 2000,
+            content: "ok".to_string(),
+            truncated: None,
+            original_bytes: None,
+            model: Some("claude-opus-4".to_string()),
+            token_usage: Some(spool_format::TokenUsage {
+                input_tokens: 1_000_000,
+                output_tokens: 0,
+                cache_read_tokens: None,
+                cache_creation_tokens: None,
+            }),
+            subagent_id: None,
+            extra: HashMap::new(),
+        }));
+
+        let app = EditorState::new(file, PathBuf::from("session.spool"), 1.0);
+        let summary = app.cost_summary();
+        assert_eq!(summary.total_input_tokens, 1_000_000);
+        assert_eq!(summary.total_cost, 15.0);
+        assert_eq!(summary.most_expensive.len(), 1);
     }
 
     #[test]
@@ -1588,4 +2801,84 @@ mod tests {
             _ => panic!("Expected annotation entry"),
         }
     }
+
+    #[test]
+    fn test_seek_index_finds_last_point_at_or_before() {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "hello"));
+        file.add_entry(make_response(2000, "ok"));
+        file.add_entry(make_prompt(3000, "later"));
+
+        let timeline = build_timeline(&file.entries, &CompressionConfig::default());
+        let index = SeekIndex::build(&timeline);
+
+        assert_eq!(index.seek_to(0), Some(0));
+        assert_eq!(index.seek_to(1500), Some(1));
+        assert_eq!(index.seek_to(2000), Some(2));
+        assert_eq!(index.seek_to(999_999), Some(3));
+    }
+
+    #[test]
+    fn test_seek_jumps_to_position_without_replaying_ticks() {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "hello"));
+        file.add_entry(make_response(2000, "ok"));
+        file.add_entry(make_prompt(3000, "later"));
+
+        let mut app = EditorState::new(file, PathBuf::from("session.spool"), 1.0);
+        app.seek(2000);
+
+        assert_eq!(app.visible_count, 3);
+        assert_eq!(app.playback_elapsed_ms, 2000);
+        assert!(!app.playing);
+    }
+
+    #[test]
+    fn test_tick_advances_with_mock_clock() {
+        let session = match make_session_entry() {
+            Entry::Session(s) => s,
+            _ => unreachable!(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(1000, "hello"));
+        file.add_entry(make_response(2000, "ok"));
+
+        let clock = std::rc::Rc::new(crate::tui::clock::MockClock::new());
+        let mut app = EditorState::with_clock(
+            file,
+            PathBuf::from("session.spool"),
+            1.0,
+            Box::new(RcClock(clock.clone())),
+        );
+
+        app.start_playing();
+        clock.advance(2500);
+        app.tick();
+
+        assert_eq!(app.visible_count, 3);
+        assert!(!app.playing);
+    }
+
+    /// Wraps a shared [`crate::tui::clock::MockClock`] so the test can keep
+    /// advancing it after handing ownership of a `Box<dyn Clocks>` to
+    /// [`EditorState::with_clock`].
+    struct RcClock(std::rc::Rc<crate::tui::clock::MockClock>);
+
+    impl Clocks for RcClock {
+        fn now_ms(&self) -> u64 {
+            self.0.now_ms()
+        }
+
+        fn sleep_until(&self, deadline_ms: u64) {
+            self.0.sleep_until(deadline_ms);
+        }
+    }
 }
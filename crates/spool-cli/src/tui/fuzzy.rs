@@ -0,0 +1,276 @@
+//! Fuzzy string matching for the Library session filter.
+//!
+//! Two stages, modeled on the usual fuzzy-finder approach (fzf, Sublime's
+//! "Goto Anything"):
+//! 1. [`CharBag`] cheaply rejects any candidate that can't possibly contain
+//!    the query as a subsequence, before the scoring pass ever runs.
+//! 2. [`fuzzy_match`] walks the query as a subsequence of the candidate via
+//!    a small DP, scoring word-boundary starts and consecutive-match runs
+//!    above scattered single-character hits, and returns the matched
+//!    character positions so callers can highlight them.
+
+const WORD_BOUNDARY_BONUS: i32 = 30;
+const CAMEL_CASE_BONUS: i32 = 25;
+const CONSECUTIVE_BONUS: i32 = 15;
+const GAP_PENALTY: i32 = 2;
+const NEG: i32 = i32::MIN / 2;
+
+/// A 64-bit presence bitset over lowercased ASCII letters and digits. Used
+/// to reject, in O(1), any candidate whose characters are a strict subset
+/// of what the query needs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CharBag(u64);
+
+impl CharBag {
+    pub fn of(s: &str) -> Self {
+        let mut bits = 0u64;
+        for c in s.chars() {
+            if let Some(bit) = bag_bit(c) {
+                bits |= 1 << bit;
+            }
+        }
+        CharBag(bits)
+    }
+
+    /// Whether every bit set in `query` is also set in `self`.
+    pub fn contains(&self, query: &CharBag) -> bool {
+        self.0 & query.0 == query.0
+    }
+}
+
+fn bag_bit(c: char) -> Option<u32> {
+    match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => Some(c as u32 - 'a' as u32),
+        c @ '0'..='9' => Some(26 + (c as u32 - '0' as u32)),
+        _ => None,
+    }
+}
+
+/// The result of a successful fuzzy match: a score (higher is better) and
+/// the candidate's char indices that matched the query, in query order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub positions: Vec<usize>,
+}
+
+/// Fuzzy-match `query` as a subsequence of `candidate`, case-insensitively.
+/// Returns `None` if `query` isn't a subsequence of `candidate` at all. An
+/// empty query matches everything with a score of 0 and no positions.
+pub fn fuzzy_match(candidate: &str, query: &str) -> Option<FuzzyMatch> {
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    if !CharBag::of(candidate).contains(&CharBag::of(query)) {
+        return None;
+    }
+
+    let cand: Vec<char> = candidate.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+    if cand.len() < query.len() {
+        return None;
+    }
+
+    best_subsequence_match(&cand, &query)
+}
+
+fn chars_eq(a: char, b: char) -> bool {
+    a.to_ascii_lowercase() == b.to_ascii_lowercase()
+}
+
+/// Per-position bonus for starting a match there: start of string, right
+/// after a separator, or a lowercase→uppercase camelCase transition.
+fn positional_bonus(cand: &[char]) -> Vec<i32> {
+    cand.iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            if i == 0 {
+                WORD_BOUNDARY_BONUS
+            } else {
+                let prev = cand[i - 1];
+                if matches!(prev, '-' | '_' | '/' | ' ') {
+                    WORD_BOUNDARY_BONUS
+                } else if prev.is_lowercase() && c.is_uppercase() {
+                    CAMEL_CASE_BONUS
+                } else {
+                    0
+                }
+            }
+        })
+        .collect()
+}
+
+/// DP over `(query position, candidate position)`: `dp[i]` holds the best
+/// score of matching the query so far with the last matched character at
+/// candidate index `i`, recomputed one query character at a time so only
+/// the previous layer needs to be kept around.
+fn best_subsequence_match(cand: &[char], query: &[char]) -> Option<FuzzyMatch> {
+    let n = cand.len();
+    let m = query.len();
+    let bonus = positional_bonus(cand);
+
+    let mut dp: Vec<i32> = (0..n)
+        .map(|i| {
+            if chars_eq(cand[i], query[0]) {
+                bonus[i]
+            } else {
+                NEG
+            }
+        })
+        .collect();
+    let mut pred: Vec<Vec<usize>> = vec![vec![usize::MAX; n]];
+
+    for j in 1..m {
+        let prev_dp = dp.clone();
+
+        // adjusted[k] folds the "distance from 0" into dp[k] so that, for
+        // any later position i, (adjusted[k] - GAP_PENALTY*(i-1)) equals
+        // prev_dp[k] minus the gap penalty for skipping from k to i. That
+        // makes "best non-consecutive predecessor before i" a running
+        // prefix max instead of an O(n) rescan per i.
+        let adjusted: Vec<i32> = (0..n)
+            .map(|k| {
+                if prev_dp[k] > NEG {
+                    prev_dp[k] + GAP_PENALTY * k as i32
+                } else {
+                    NEG
+                }
+            })
+            .collect();
+
+        let mut prefix_max = vec![NEG; n];
+        let mut prefix_max_idx = vec![usize::MAX; n];
+        for k in 0..n {
+            let (carry_max, carry_idx) = if k == 0 {
+                (NEG, usize::MAX)
+            } else {
+                (prefix_max[k - 1], prefix_max_idx[k - 1])
+            };
+            if adjusted[k] > carry_max {
+                prefix_max[k] = adjusted[k];
+                prefix_max_idx[k] = k;
+            } else {
+                prefix_max[k] = carry_max;
+                prefix_max_idx[k] = carry_idx;
+            }
+        }
+
+        let mut new_dp = vec![NEG; n];
+        let mut new_pred = vec![usize::MAX; n];
+
+        for i in 0..n {
+            if !chars_eq(cand[i], query[j]) {
+                continue;
+            }
+
+            let mut best = NEG;
+            let mut best_pred = usize::MAX;
+
+            // Consecutive match: predecessor is immediately before `i`.
+            if i > 0 && prev_dp[i - 1] > NEG {
+                let score = prev_dp[i - 1] + CONSECUTIVE_BONUS;
+                if score > best {
+                    best = score;
+                    best_pred = i - 1;
+                }
+            }
+
+            // Best predecessor with at least one skipped character.
+            if i >= 2 && prefix_max[i - 2] > NEG {
+                let score = prefix_max[i - 2] - GAP_PENALTY * (i as i32 - 1);
+                if score > best {
+                    best = score;
+                    best_pred = prefix_max_idx[i - 2];
+                }
+            }
+
+            if best > NEG {
+                new_dp[i] = best + bonus[i];
+                new_pred[i] = best_pred;
+            }
+        }
+
+        dp = new_dp;
+        pred.push(new_pred);
+    }
+
+    let (best_i, &best_score) = dp
+        .iter()
+        .enumerate()
+        .filter(|&(_, &score)| score > NEG)
+        .max_by_key(|&(_, &score)| score)?;
+
+    let mut positions = vec![0usize; m];
+    let mut i = best_i;
+    for j in (0..m).rev() {
+        positions[j] = i;
+        if j > 0 {
+            i = pred[j][i];
+        }
+    }
+
+    Some(FuzzyMatch {
+        score: best_score,
+        positions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_bag_rejects_missing_characters() {
+        let bag = CharBag::of("claude-migration");
+        assert!(!bag.contains(&CharBag::of("xyz")));
+        assert!(bag.contains(&CharBag::of("clmig")));
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_non_subsequence() {
+        assert!(fuzzy_match("claude-migration", "xyz").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_empty_query_matches_everything() {
+        let m = fuzzy_match("anything", "").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.positions.is_empty());
+    }
+
+    #[test]
+    fn fuzzy_match_finds_subsequence_positions() {
+        let m = fuzzy_match("claude-migration", "clmig").unwrap();
+        let chars: Vec<char> = "claude-migration".chars().collect();
+        let matched: String = m.positions.iter().map(|&i| chars[i]).collect();
+        assert_eq!(matched.to_lowercase(), "clmig");
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_consecutive_runs() {
+        // "mig" is a contiguous run in "zmigz" but scattered with plain
+        // filler letters (no separators, so no boundary bonus) in
+        // "zmzzzizzzg"; only the gap penalty should separate the two.
+        let contiguous = fuzzy_match("zmigz", "mig").unwrap();
+        let scattered = fuzzy_match("zmzzzizzzg", "mig").unwrap();
+        assert!(contiguous.score > scattered.score);
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_word_boundary_starts() {
+        // Same consecutive-run shape in both candidates; only whether the
+        // run starts right after a separator differs.
+        let boundary = fuzzy_match("-mig", "mig").unwrap();
+        let mid_word = fuzzy_match("zmig", "mig").unwrap();
+        assert!(boundary.score > mid_word.score);
+    }
+
+    #[test]
+    fn fuzzy_match_is_case_insensitive() {
+        assert!(fuzzy_match("Claude-Migration", "clmig").is_some());
+    }
+}
@@ -0,0 +1,294 @@
+//! Configurable key bindings for the Editor TUI.
+//!
+//! `Editor::handle_key` used to hardwire every binding directly in its
+//! `match key.code` arms. This module turns that into data: a [`Keymap`]
+//! maps a [`KeySpec`] (key code + modifiers) to a named command enum, with
+//! one table per mode so each resolves its own bindings independently. A
+//! user can override any entry from `<config dir>/spool/keymap.json`; any
+//! key not mentioned there keeps its default binding.
+//!
+//! Only modes whose input is mostly discrete commands get a table here -
+//! the normal playback mode and the redaction review modal. Annotation
+//! text entry, the live search query, and the fuzzy finder query are
+//! mostly free-form typing (every `Char` is text, not a command), so they
+//! keep their literal `KeyCode` handling in `editor.rs`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Every command the Editor's normal (non-modal) mode can dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditorCommand {
+    Back,
+    PlayPause,
+    StepForward,
+    StepBack,
+    SpeedUp,
+    SpeedDown,
+    JumpStart,
+    JumpEnd,
+    ScrollUp,
+    ScrollDown,
+    MarkTrimStart,
+    MarkTrimEnd,
+    StartRedactionReview,
+    StartAnnotation,
+    StartSearch,
+    JumpNextMatch,
+    JumpPrevMatch,
+    StartFinder,
+    ToggleVtMode,
+    ToggleDurationOverlay,
+    ToggleMarkdownMode,
+    Undo,
+    Redo,
+    ToggleInfo,
+}
+
+/// Every command the redaction review modal can dispatch. Shared across
+/// its Review and Preview stages; `editor.rs` interprets `Cancel`/`Confirm`
+/// differently depending on which stage is active (e.g. `Confirm` advances
+/// Review to Preview, but triggers the export from Preview).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionCommand {
+    Cancel,
+    Up,
+    Down,
+    ToggleConfirm,
+    AcceptAll,
+    DismissAll,
+    Confirm,
+}
+
+/// A key press as a lookup key: code plus modifiers, hashable so it can
+/// key a [`Keymap`]'s binding table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct KeySpec {
+    code: KeyCode,
+    mods: KeyModifiers,
+}
+
+impl KeySpec {
+    fn new(code: KeyCode, mods: KeyModifiers) -> Self {
+        KeySpec { code, mods }
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        KeySpec::new(key.code, key.modifiers)
+    }
+
+    /// Parse a config-file key string like `"h"`, `"ctrl+f"`, `"shift+g"`,
+    /// `"esc"`, `"space"`. Returns `None` for anything unrecognized rather
+    /// than failing the whole config load.
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let last = parts.pop()?;
+        let mut mods = KeyModifiers::NONE;
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => mods |= KeyModifiers::CONTROL,
+                "alt" => mods |= KeyModifiers::ALT,
+                "shift" => mods |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+        }
+        let code = match last.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ if last.chars().count() == 1 => KeyCode::Char(last.chars().next()?),
+            _ => return None,
+        };
+        Some(KeySpec::new(code, mods))
+    }
+
+    /// Render back to the same syntax [`KeySpec::parse`] accepts, used both
+    /// to round-trip config keys and to label `draw_controls`' hint line.
+    fn display(&self) -> String {
+        let mut s = String::new();
+        if self.mods.contains(KeyModifiers::CONTROL) {
+            s.push_str("ctrl+");
+        }
+        if self.mods.contains(KeyModifiers::ALT) {
+            s.push_str("alt+");
+        }
+        if self.mods.contains(KeyModifiers::SHIFT) {
+            s.push_str("shift+");
+        }
+        match self.code {
+            KeyCode::Esc => s.push_str("esc"),
+            KeyCode::Enter => s.push_str("enter"),
+            KeyCode::Tab => s.push_str("tab"),
+            KeyCode::Backspace => s.push_str("backspace"),
+            KeyCode::Up => s.push_str("up"),
+            KeyCode::Down => s.push_str("down"),
+            KeyCode::Left => s.push_str("left"),
+            KeyCode::Right => s.push_str("right"),
+            KeyCode::Home => s.push_str("home"),
+            KeyCode::End => s.push_str("end"),
+            KeyCode::PageUp => s.push_str("pageup"),
+            KeyCode::PageDown => s.push_str("pagedown"),
+            KeyCode::Char(' ') => s.push_str("space"),
+            KeyCode::Char(c) => s.push(c),
+            _ => s.push('?'),
+        }
+        s
+    }
+}
+
+/// Key bindings for one mode: a physical key maps to at most one command.
+/// Several keys may map to the same command (e.g. both `h` and Left to
+/// `StepBack`).
+pub struct Keymap<C> {
+    bindings: HashMap<KeySpec, C>,
+}
+
+impl<C: Copy + Eq> Keymap<C> {
+    fn new(bindings: HashMap<KeySpec, C>) -> Self {
+        Keymap { bindings }
+    }
+
+    /// Look up the command bound to this key press, if any.
+    pub fn lookup(&self, key: &KeyEvent) -> Option<C> {
+        self.bindings.get(&KeySpec::from_event(key)).copied()
+    }
+
+    /// The first key (in arbitrary map order) bound to `cmd`, formatted for
+    /// display - used by `draw_controls` to render the hint line from
+    /// whatever's actually bound, rather than a hardcoded string.
+    pub fn label_for(&self, cmd: C) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| **bound == cmd)
+            .map(|(key, _)| key.display())
+            .unwrap_or_else(|| "?".to_string())
+    }
+
+    /// Overlay `overrides` (key string -> command) onto this keymap's
+    /// existing bindings, in place. Unparseable key strings are skipped
+    /// rather than failing the whole load.
+    fn apply_overrides(&mut self, overrides: HashMap<String, C>) {
+        for (key_str, cmd) in overrides {
+            if let Some(spec) = KeySpec::parse(&key_str) {
+                self.bindings.insert(spec, cmd);
+            }
+        }
+    }
+}
+
+fn default_editor_bindings() -> HashMap<KeySpec, EditorCommand> {
+    use EditorCommand::*;
+    let raw: &[(KeyCode, KeyModifiers, EditorCommand)] = &[
+        (KeyCode::Char('q'), KeyModifiers::NONE, Back),
+        (KeyCode::Esc, KeyModifiers::NONE, Back),
+        (KeyCode::Char(' '), KeyModifiers::NONE, PlayPause),
+        (KeyCode::Right, KeyModifiers::NONE, StepForward),
+        (KeyCode::Char('l'), KeyModifiers::NONE, StepForward),
+        (KeyCode::Left, KeyModifiers::NONE, StepBack),
+        (KeyCode::Char('h'), KeyModifiers::NONE, StepBack),
+        (KeyCode::Char('+'), KeyModifiers::NONE, SpeedUp),
+        (KeyCode::Char('='), KeyModifiers::NONE, SpeedUp),
+        (KeyCode::Char('-'), KeyModifiers::NONE, SpeedDown),
+        (KeyCode::Char('_'), KeyModifiers::NONE, SpeedDown),
+        (KeyCode::Home, KeyModifiers::NONE, JumpStart),
+        (KeyCode::Char('g'), KeyModifiers::NONE, JumpStart),
+        (KeyCode::End, KeyModifiers::NONE, JumpEnd),
+        (KeyCode::Char('G'), KeyModifiers::NONE, JumpEnd),
+        (KeyCode::PageUp, KeyModifiers::NONE, ScrollUp),
+        (KeyCode::Char('k'), KeyModifiers::NONE, ScrollUp),
+        (KeyCode::PageDown, KeyModifiers::NONE, ScrollDown),
+        (KeyCode::Char('j'), KeyModifiers::NONE, ScrollDown),
+        (KeyCode::Char('['), KeyModifiers::NONE, MarkTrimStart),
+        (KeyCode::Char(']'), KeyModifiers::NONE, MarkTrimEnd),
+        (KeyCode::Char('x'), KeyModifiers::NONE, StartRedactionReview),
+        (KeyCode::Char('a'), KeyModifiers::NONE, StartAnnotation),
+        (KeyCode::Char('/'), KeyModifiers::NONE, StartSearch),
+        (KeyCode::Char('n'), KeyModifiers::NONE, JumpNextMatch),
+        (KeyCode::Char('N'), KeyModifiers::NONE, JumpPrevMatch),
+        (KeyCode::Char('f'), KeyModifiers::CONTROL, StartFinder),
+        (KeyCode::Char('t'), KeyModifiers::NONE, ToggleVtMode),
+        (KeyCode::Char('d'), KeyModifiers::NONE, ToggleDurationOverlay),
+        (KeyCode::Char('m'), KeyModifiers::NONE, ToggleMarkdownMode),
+        (KeyCode::Char('u'), KeyModifiers::NONE, Undo),
+        (KeyCode::Char('r'), KeyModifiers::CONTROL, Redo),
+        (KeyCode::Char('i'), KeyModifiers::NONE, ToggleInfo),
+    ];
+    raw.iter()
+        .map(|(code, mods, cmd)| (KeySpec::new(*code, *mods), *cmd))
+        .collect()
+}
+
+fn default_redaction_bindings() -> HashMap<KeySpec, RedactionCommand> {
+    use RedactionCommand::*;
+    let raw: &[(KeyCode, KeyModifiers, RedactionCommand)] = &[
+        (KeyCode::Esc, KeyModifiers::NONE, Cancel),
+        (KeyCode::Down, KeyModifiers::NONE, Down),
+        (KeyCode::Char('j'), KeyModifiers::NONE, Down),
+        (KeyCode::Up, KeyModifiers::NONE, Up),
+        (KeyCode::Char('k'), KeyModifiers::NONE, Up),
+        (KeyCode::Char(' '), KeyModifiers::NONE, ToggleConfirm),
+        (KeyCode::Char('a'), KeyModifiers::NONE, AcceptAll),
+        (KeyCode::Char('d'), KeyModifiers::NONE, DismissAll),
+        (KeyCode::Enter, KeyModifiers::NONE, Confirm),
+    ];
+    raw.iter()
+        .map(|(code, mods, cmd)| (KeySpec::new(*code, *mods), *cmd))
+        .collect()
+}
+
+/// Raw shape of `<config dir>/spool/keymap.json`: per-mode maps of key
+/// string to command name. Missing or malformed config is treated as "no
+/// overrides" rather than an error.
+#[derive(Debug, Default, Deserialize)]
+struct KeymapConfigFile {
+    #[serde(default)]
+    normal: HashMap<String, EditorCommand>,
+    #[serde(default)]
+    redaction: HashMap<String, RedactionCommand>,
+}
+
+fn load_config_file() -> KeymapConfigFile {
+    let Some(dir) = dirs::config_dir() else {
+        return KeymapConfigFile::default();
+    };
+    let path = dir.join("spool").join("keymap.json");
+    let Ok(json) = std::fs::read_to_string(&path) else {
+        return KeymapConfigFile::default();
+    };
+    serde_json::from_str(&json).unwrap_or_default()
+}
+
+/// Every mode's keymap, bundled together for [`crate::tui::editor::EditorState`].
+pub struct Keymaps {
+    pub normal: Keymap<EditorCommand>,
+    pub redaction: Keymap<RedactionCommand>,
+}
+
+impl Keymaps {
+    /// Load the default bindings, then overlay any overrides from
+    /// `<config dir>/spool/keymap.json`.
+    pub fn load() -> Self {
+        let config = load_config_file();
+
+        let mut normal = Keymap::new(default_editor_bindings());
+        normal.apply_overrides(config.normal);
+
+        let mut redaction = Keymap::new(default_redaction_bindings());
+        redaction.apply_overrides(config.redaction);
+
+        Keymaps { normal, redaction }
+    }
+}
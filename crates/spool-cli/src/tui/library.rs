@@ -1,7 +1,7 @@
 //! Library view - Interactive session browser.
 
-use anyhow::{Context, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -9,27 +9,63 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
-use spool_adapters::{claude_code, codex, AgentType, SessionInfo};
-use spool_format::Entry;
+use spool_adapters::{aichat, claude_code, codex, AgentType, SessionInfo};
+use spool_format::{Entry, EntryId};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
-use super::common::{format_tool_input, truncate_str_with_ellipsis};
+use super::common::{self, centered_rect, format_duration_ms, format_tool_input, truncate_str_with_ellipsis};
+use super::fuzzy::fuzzy_match;
+use super::library_search::{self, SearchHit};
+use super::AppEvent;
+use crate::ansi;
+use crate::diff;
+use crate::highlight;
 
 /// Action returned by the Library view to the top-level app loop.
 pub enum LibraryAction {
     /// Open a session in the Editor.
     OpenEditor(PathBuf, AgentType),
+    /// Open a `.spool` file in the Editor positioned at a specific entry -
+    /// the result of selecting a library-search hit.
+    OpenEditorAt(PathBuf, usize),
+    /// Export every marked session (converted via `convert_session`) as a
+    /// `.spool` file under the given directory.
+    ExportMarked(Vec<SessionInfo>, PathBuf),
+    /// Add a note to every marked session, as an `Entry::Annotation`
+    /// targeting its `Entry::Session`, and re-export it as `.spool`.
+    AnnotateMarked(Vec<SessionInfo>, String),
     /// Quit the application.
     Quit,
     /// No action; continue rendering.
     None,
 }
 
-/// Cached preview data for the selected session.
+/// Cached preview data for the selected session. Keyed by `path` (rather
+/// than an index into `sessions`, which can be reshuffled by
+/// [`LibraryState::refresh_sessions`]) plus `modified_at`, so a session
+/// whose file changed on disk since the preview was built is detected and
+/// reloaded rather than shown stale.
 struct PreviewData {
-    session_index: usize,
+    path: PathBuf,
+    modified_at: Option<chrono::DateTime<chrono::Utc>>,
     entries: Vec<Entry>,
+    /// Each `ToolCall`'s inferred syntax-highlighting language, keyed by
+    /// its id, so the paired `ToolResult` (referencing it via `call_id`)
+    /// can look up what to highlight its output as.
+    tool_languages: HashMap<EntryId, String>,
+    /// Rendered image previews for `ToolResult` entries whose output is an
+    /// image, keyed by the `ToolResult`'s own id. Populated lazily from
+    /// `draw_preview` (rather than eagerly in `load_preview`, like
+    /// `tool_languages`) since rendering needs the preview pane's actual
+    /// cell dimensions, which aren't known until draw time; a `RefCell`
+    /// lets `draw_preview` fill it in despite only holding `&self`. Caches
+    /// by `(cols, rows)` too, so a resize re-renders at the new size
+    /// instead of reusing a stale one.
+    image_previews: std::cell::RefCell<HashMap<EntryId, ((u16, u16), Option<Vec<Line<'static>>>)>>,
 }
 
 /// Input mode.
@@ -37,12 +73,90 @@ struct PreviewData {
 enum Mode {
     Normal,
     Search,
+    /// Vim-style line-visual selection: `j`/`k` extend the marked range
+    /// from `anchor` (the row Visual mode was entered on) to the current
+    /// selection; `V`/space commits it into `marked`, Esc discards it.
+    Visual { anchor: usize },
+    /// Reusing `search_input` as a scratch buffer for the export
+    /// destination directory.
+    ExportPath,
+    /// Reusing `search_input` as a scratch buffer for the annotation note.
+    AnnotateText,
+    /// Reusing `search_input` as a scratch buffer for the library-search
+    /// root directory.
+    LibrarySearchRoot,
+    /// Reusing `search_input` as a scratch buffer for the library-search
+    /// query, once a root has been chosen.
+    LibrarySearchQuery,
+    /// Browsing a library search's streamed-in hits (`j`/`k`/Enter/Esc).
+    LibrarySearchResults,
+}
+
+/// State of an in-flight or finished library-wide search (`Ctrl-l`), shown
+/// as a picker overlay over the normal session list/preview. Replaced
+/// wholesale (rather than mutated in place) each time a new search starts,
+/// so a late event from a superseded search is easy to detect by comparing
+/// `search_id`.
+struct LibrarySearchState {
+    search_id: u64,
+    root: PathBuf,
+    query: String,
+    total_files: usize,
+    hits: Vec<SearchHit>,
+    selected: usize,
+    done: bool,
+}
+
+/// How `draw_session_list` lays sessions out.
+#[derive(PartialEq, Clone, Copy)]
+enum ViewMode {
+    /// One row per session, in `filtered_indices` order.
+    Flat,
+    /// Sessions grouped under collapsible `project_dir` headers.
+    Tree,
+}
+
+/// One row of the session list, uniform across both [`ViewMode`]s so
+/// `move_up`/`move_down`/`jump_top`/`jump_bottom`/Enter don't need to
+/// special-case which one is active.
+enum Row {
+    /// A project-dir group header (`None` groups sessions with no known
+    /// project directory), with the number of sessions under it.
+    Group {
+        project_dir: Option<PathBuf>,
+        count: usize,
+    },
+    /// A session, indexed into `filtered_indices` (itself an index into
+    /// `sessions`).
+    Session(usize),
+}
+
+/// What a search-mode query is matched against.
+#[derive(PartialEq, Clone, Copy)]
+enum SearchScope {
+    /// Title and project directory (the original, fast filter).
+    Title,
+    /// Every searchable `Entry` body — see [`LibraryState::ensure_content_index`].
+    Content,
+}
+
+/// A content-search hit for one session, derived from
+/// [`LibraryState::content_cache`].
+struct ContentMatch {
+    /// Number of times the query occurs in the session's searchable text.
+    count: usize,
+    /// The first matching line, for display in the session list and preview.
+    snippet: String,
 }
 
 /// Library view state (the session browser).
 pub struct LibraryState {
     sessions: Vec<SessionInfo>,
     filtered_indices: Vec<usize>,
+    /// Matched title character positions for the session at the same
+    /// position in `filtered_indices`, for highlighting. Empty (no
+    /// highlight) when there's no active search.
+    match_positions: Vec<Vec<usize>>,
     selected: usize,
     preview: Option<PreviewData>,
     preview_scroll: usize,
@@ -50,10 +164,47 @@ pub struct LibraryState {
     search_input: String,
     agent_filter: Option<String>,
     status_message: Option<(String, Instant)>,
+    /// Sender the background content-index build (see
+    /// [`LibraryState::ensure_content_index`]) reports its result through.
+    content_tx: mpsc::Sender<AppEvent>,
+    /// Whether `/` in Search mode matches against title/project or against
+    /// `content_cache`.
+    search_scope: SearchScope,
+    /// Lazily-built index: session path -> lowercased concatenation of its
+    /// searchable entry text. `None` until the first content search builds
+    /// it; cleared on `refresh_sessions` so a changed file is re-scanned.
+    content_cache: Option<HashMap<PathBuf, String>>,
+    /// Whether the background pass building `content_cache` is running.
+    content_building: bool,
+    /// Current query's content-search hits, keyed by session path. `None`
+    /// outside [`SearchScope::Content`].
+    content_matches: Option<HashMap<PathBuf, ContentMatch>>,
+    view_mode: ViewMode,
+    /// Project-dir groups currently expanded in [`ViewMode::Tree`]. A `None`
+    /// key is the group of sessions with no known project directory.
+    expanded_groups: std::collections::HashSet<Option<PathBuf>>,
+    /// Flattened rows `move_up`/`move_down`/`jump_top`/`jump_bottom`/Enter
+    /// operate over; `selected` indexes into this. Rebuilt by
+    /// [`LibraryState::rebuild_rows`] whenever `filtered_indices`,
+    /// `view_mode`, or `expanded_groups` changes.
+    visible_rows: Vec<Row>,
+    /// Indices into `sessions` (not `filtered_indices`, so a mark survives
+    /// re-filtering/re-sorting) marked for a bulk export or annotate action.
+    marked: std::collections::HashSet<usize>,
+    /// The active library-wide search (`Ctrl-l`) and its results, if one
+    /// has been started. `None` when the picker overlay isn't shown.
+    library_search: Option<LibrarySearchState>,
+    /// Monotonically increasing id handed to each new library search, so a
+    /// result tagged with an older id (from a search the user has since
+    /// restarted) can be told apart from the current one and ignored.
+    next_search_id: u64,
+    /// The root directory chosen in `Mode::LibrarySearchRoot`, held here
+    /// while `Mode::LibrarySearchQuery` gathers the query text.
+    pending_search_root: Option<PathBuf>,
 }
 
 impl LibraryState {
-    pub fn new(agent_filter: Option<String>) -> Result<Self> {
+    pub fn new(agent_filter: Option<String>, content_tx: mpsc::Sender<AppEvent>) -> Result<Self> {
         let sessions: Vec<SessionInfo> = find_all_sessions()?
             .into_iter()
             .filter(|s| s.message_count.map(|c| c > 0).unwrap_or(true))
@@ -71,9 +222,13 @@ impl LibraryState {
             .map(|(i, _)| i)
             .collect();
 
+        let match_positions = vec![Vec::new(); filtered_indices.len()];
+        let visible_rows = (0..filtered_indices.len()).map(Row::Session).collect();
+
         Ok(LibraryState {
             sessions,
             filtered_indices,
+            match_positions,
             selected: 0,
             preview: None,
             preview_scroll: 0,
@@ -81,21 +236,76 @@ impl LibraryState {
             search_input: String::new(),
             agent_filter,
             status_message: None,
+            content_tx,
+            search_scope: SearchScope::Title,
+            content_cache: None,
+            content_building: false,
+            content_matches: None,
+            view_mode: ViewMode::Flat,
+            expanded_groups: std::collections::HashSet::new(),
+            visible_rows,
+            marked: std::collections::HashSet::new(),
+            library_search: None,
+            next_search_id: 0,
+            pending_search_root: None,
         })
     }
 
     fn selected_session(&self) -> Option<&SessionInfo> {
-        self.filtered_indices
-            .get(self.selected)
-            .map(|&i| &self.sessions[i])
+        match self.visible_rows.get(self.selected) {
+            Some(&Row::Session(fi)) => self.filtered_indices.get(fi).map(|&i| &self.sessions[i]),
+            _ => None,
+        }
+    }
+
+    /// Select the row for the session at `path`, if it's currently visible
+    /// (in `filtered_indices` and, in Tree mode, in an expanded group).
+    fn select_path(&mut self, path: &std::path::Path) {
+        if let Some(pos) = self.visible_rows.iter().position(|row| match row {
+            Row::Session(fi) => self
+                .filtered_indices
+                .get(*fi)
+                .map(|&i| self.sessions[i].path == *path)
+                .unwrap_or(false),
+            Row::Group { .. } => false,
+        }) {
+            self.selected = pos;
+        }
+    }
+
+    /// Toggle the mark on the session at the current row (a no-op on a
+    /// group header).
+    fn toggle_mark(&mut self) {
+        if let Some(&Row::Session(fi)) = self.visible_rows.get(self.selected) {
+            if let Some(&session_idx) = self.filtered_indices.get(fi) {
+                if !self.marked.remove(&session_idx) {
+                    self.marked.insert(session_idx);
+                }
+            }
+        }
     }
 
-    fn selected_session_index(&self) -> Option<usize> {
-        self.filtered_indices.get(self.selected).copied()
+    /// Mark every session row between `anchor` and the current selection
+    /// (inclusive), by row position in `visible_rows`. Group headers in the
+    /// range are skipped.
+    fn commit_visual_range(&mut self, anchor: usize) {
+        let lo = anchor.min(self.selected);
+        let hi = anchor.max(self.selected).min(self.visible_rows.len().saturating_sub(1));
+        for row in &self.visible_rows[lo..=hi] {
+            if let Row::Session(fi) = row {
+                if let Some(&session_idx) = self.filtered_indices.get(*fi) {
+                    self.marked.insert(session_idx);
+                }
+            }
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
     }
 
     fn move_down(&mut self) {
-        if !self.filtered_indices.is_empty() && self.selected < self.filtered_indices.len() - 1 {
+        if !self.visible_rows.is_empty() && self.selected < self.visible_rows.len() - 1 {
             self.selected += 1;
             self.preview_scroll = 0;
         }
@@ -114,64 +324,344 @@ impl LibraryState {
     }
 
     fn jump_bottom(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.selected = self.filtered_indices.len() - 1;
+        if !self.visible_rows.is_empty() {
+            self.selected = self.visible_rows.len() - 1;
             self.preview_scroll = 0;
         }
     }
 
+    /// Switch between [`ViewMode::Flat`] and [`ViewMode::Tree`], preserving
+    /// the current selection by session path. Entering Tree mode auto-
+    /// expands the group containing the previously selected session so
+    /// focus is never lost behind a collapsed header.
+    fn toggle_view_mode(&mut self) {
+        let selected_path = self.selected_session().map(|s| s.path.clone());
+
+        self.view_mode = match self.view_mode {
+            ViewMode::Flat => ViewMode::Tree,
+            ViewMode::Tree => ViewMode::Flat,
+        };
+
+        if self.view_mode == ViewMode::Tree {
+            let project_dir = selected_path
+                .as_ref()
+                .and_then(|p| self.sessions.iter().find(|s| s.path == *p))
+                .and_then(|s| s.project_dir.clone());
+            self.expanded_groups.insert(project_dir);
+        }
+
+        self.rebuild_rows();
+        if let Some(path) = selected_path {
+            self.select_path(&path);
+        }
+        self.preview_scroll = 0;
+    }
+
+    /// Recompute `visible_rows` from `filtered_indices`/`view_mode`/
+    /// `expanded_groups`, clamping `selected` back into range if it shrank.
+    fn rebuild_rows(&mut self) {
+        self.visible_rows = match self.view_mode {
+            ViewMode::Flat => (0..self.filtered_indices.len()).map(Row::Session).collect(),
+            ViewMode::Tree => self.build_tree_rows(),
+        };
+        if self.selected >= self.visible_rows.len() {
+            self.selected = self.visible_rows.len().saturating_sub(1);
+        }
+    }
+
+    /// Group `filtered_indices` by `project_dir`, preserving the order
+    /// groups first appear in (which, since `filtered_indices` is already
+    /// sorted by `modified_at` descending, puts each group's most-recently-
+    /// modified session first — and sessions within a group stay in that
+    /// same order).
+    fn build_tree_rows(&self) -> Vec<Row> {
+        let mut group_order: Vec<Option<PathBuf>> = Vec::new();
+        let mut groups: HashMap<Option<PathBuf>, Vec<usize>> = HashMap::new();
+
+        for (fi, &session_idx) in self.filtered_indices.iter().enumerate() {
+            let key = self.sessions[session_idx].project_dir.clone();
+            groups.entry(key.clone()).or_insert_with(|| {
+                group_order.push(key.clone());
+                Vec::new()
+            });
+            groups.get_mut(&key).unwrap().push(fi);
+        }
+
+        let mut rows = Vec::new();
+        for key in group_order {
+            let members = &groups[&key];
+            rows.push(Row::Group {
+                project_dir: key.clone(),
+                count: members.len(),
+            });
+            if self.expanded_groups.contains(&key) {
+                rows.extend(members.iter().map(|&fi| Row::Session(fi)));
+            }
+        }
+        rows
+    }
+
     fn update_filter(&mut self) {
-        let search = self.search_input.to_lowercase();
-        self.filtered_indices = self
+        if self.search_scope == SearchScope::Content {
+            self.filter_by_content();
+            self.rebuild_rows();
+            self.preview_scroll = 0;
+            return;
+        }
+        self.content_matches = None;
+
+        let query = self.search_input.trim();
+
+        let mut matches: Vec<(usize, Vec<usize>, i32)> = self
             .sessions
             .iter()
             .enumerate()
             .filter(|(_, s)| {
-                let agent_ok = self
-                    .agent_filter
+                self.agent_filter
                     .as_ref()
                     .map(|f| s.agent.as_str() == f.as_str())
-                    .unwrap_or(true);
-                let search_ok = search.is_empty()
-                    || s.title
-                        .as_ref()
-                        .map(|t| t.to_lowercase().contains(&search))
-                        .unwrap_or(false)
-                    || s.project_dir
-                        .as_ref()
-                        .map(|p| p.to_string_lossy().to_lowercase().contains(&search))
-                        .unwrap_or(false);
-                agent_ok && search_ok
+                    .unwrap_or(true)
+            })
+            .filter_map(|(i, s)| {
+                if query.is_empty() {
+                    return Some((i, Vec::new(), 0));
+                }
+                // Fuzzy-match against the title, falling back to the
+                // project dir or agent name (whichever scores highest
+                // wins), so `/clmig` finds a session by its title, its
+                // folder name, or e.g. `/codex` by agent.
+                let title_match = s.title.as_deref().and_then(|t| fuzzy_match(t, query));
+                let project_match = s
+                    .project_dir
+                    .as_ref()
+                    .and_then(|p| fuzzy_match(&p.to_string_lossy(), query));
+                let agent_match = fuzzy_match(s.agent.as_str(), query);
+
+                // Positions are only kept for a title match: that's the
+                // only text the session list actually highlights, so a
+                // project-dir or agent match contributes its score but no
+                // highlight.
+                let best_fallback = [&project_match, &agent_match]
+                    .into_iter()
+                    .flatten()
+                    .max_by_key(|m| m.score);
+
+                match (title_match, best_fallback) {
+                    (Some(t), Some(f)) if f.score > t.score => Some((i, Vec::new(), f.score)),
+                    (Some(t), _) => Some((i, t.positions, t.score)),
+                    (None, Some(f)) => Some((i, Vec::new(), f.score)),
+                    (None, None) => None,
+                }
             })
-            .map(|(i, _)| i)
             .collect();
 
-        if self.filtered_indices.is_empty() {
-            self.selected = 0;
-        } else if self.selected >= self.filtered_indices.len() {
-            self.selected = self.filtered_indices.len() - 1;
-        }
+        // Highest fuzzy score first, ties broken by most-recently-modified
+        // so a tie doesn't depend on `self.sessions`' incidental order.
+        matches.sort_by(|&(i_a, _, score_a), &(i_b, _, score_b)| {
+            score_b.cmp(&score_a).then_with(|| {
+                self.sessions[i_b]
+                    .modified_at
+                    .cmp(&self.sessions[i_a].modified_at)
+            })
+        });
+
+        self.filtered_indices = matches.iter().map(|(i, _, _)| *i).collect();
+        self.match_positions = matches.into_iter().map(|(_, positions, _)| positions).collect();
+
+        self.rebuild_rows();
         self.preview_scroll = 0;
     }
 
+    /// Filter `filtered_indices` against `content_cache`, a session at a
+    /// time, recording a [`ContentMatch`] (count + first-line snippet) for
+    /// each hit. While the cache hasn't been built yet (or is being
+    /// rebuilt), this matches nothing — [`LibraryState::apply_content_index`]
+    /// re-runs it once the background pass reports in.
+    fn filter_by_content(&mut self) {
+        let query = self.search_input.trim().to_lowercase();
+
+        if query.is_empty() {
+            self.filtered_indices = (0..self.sessions.len())
+                .filter(|&i| {
+                    self.agent_filter
+                        .as_ref()
+                        .map(|f| self.sessions[i].agent.as_str() == f.as_str())
+                        .unwrap_or(true)
+                })
+                .collect();
+            self.match_positions = vec![Vec::new(); self.filtered_indices.len()];
+            self.content_matches = None;
+            return;
+        }
+
+        self.ensure_content_index();
+
+        let Some(cache) = self.content_cache.as_ref() else {
+            self.filtered_indices = Vec::new();
+            self.match_positions = Vec::new();
+            self.content_matches = None;
+            return;
+        };
+
+        let mut indices = Vec::new();
+        let mut matches = HashMap::new();
+        for (i, session) in self.sessions.iter().enumerate() {
+            if let Some(f) = &self.agent_filter {
+                if session.agent.as_str() != f.as_str() {
+                    continue;
+                }
+            }
+            let Some(text) = cache.get(&session.path) else {
+                continue;
+            };
+            let count = text.matches(&query).count();
+            if count == 0 {
+                continue;
+            }
+            indices.push(i);
+            matches.insert(
+                session.path.clone(),
+                ContentMatch {
+                    count,
+                    snippet: extract_snippet_line(text, &query),
+                },
+            );
+        }
+
+        self.filtered_indices = indices;
+        self.match_positions = vec![Vec::new(); self.filtered_indices.len()];
+        self.content_matches = Some(matches);
+    }
+
+    /// Spawn a background pass building `content_cache` if it hasn't been
+    /// built yet (and isn't already being built). Converts every session
+    /// once, extracts its searchable text, and reports the whole cache back
+    /// through `content_tx` — see [`LibraryState::apply_content_index`].
+    fn ensure_content_index(&mut self) {
+        if self.content_cache.is_some() || self.content_building {
+            return;
+        }
+        self.content_building = true;
+
+        let sessions = self.sessions.clone();
+        let tx = self.content_tx.clone();
+        std::thread::spawn(move || {
+            let mut cache = HashMap::new();
+            for session in &sessions {
+                if let Ok(spool_file) = convert_session(session) {
+                    cache.insert(session.path.clone(), extract_searchable_text(&spool_file.entries));
+                }
+            }
+            let _ = tx.send(AppEvent::ContentIndexBuilt(cache));
+        });
+    }
+
+    /// Apply a freshly built `content_cache`, re-running the current
+    /// filter against it if still in [`SearchScope::Content`].
+    pub fn apply_content_index(&mut self, cache: HashMap<PathBuf, String>) {
+        self.content_building = false;
+        self.content_cache = Some(cache);
+        if self.search_scope == SearchScope::Content {
+            self.update_filter();
+            self.rebuild_rows();
+            // Force a reload so load_preview re-applies the scroll-to-match logic.
+            self.preview = None;
+        }
+    }
+
+    /// Record the file count reported once a library search's directory
+    /// walk finishes, if it's still the active search.
+    pub fn apply_library_search_started(&mut self, started: library_search::SearchStarted) {
+        if let Some(ref mut search) = self.library_search {
+            if search.search_id == started.search_id {
+                search.total_files = started.total_files;
+            }
+        }
+    }
+
+    /// Append a streamed-in hit, if it belongs to the active search.
+    pub fn push_library_search_hit(&mut self, search_id: u64, hit: SearchHit) {
+        if let Some(ref mut search) = self.library_search {
+            if search.search_id == search_id {
+                search.hits.push(hit);
+            }
+        }
+    }
+
+    /// Mark the active search as finished, if it's still the one that
+    /// completed.
+    pub fn finish_library_search(&mut self, search_id: u64) {
+        if let Some(ref mut search) = self.library_search {
+            if search.search_id == search_id {
+                search.done = true;
+            }
+        }
+    }
+
+    /// Start a fresh library-wide search, replacing whatever search (if
+    /// any) was previously active.
+    fn start_library_search(&mut self, root: PathBuf, query: String) {
+        self.next_search_id += 1;
+        let search_id = self.next_search_id;
+        self.library_search = Some(LibrarySearchState {
+            search_id,
+            root: root.clone(),
+            query: query.clone(),
+            total_files: 0,
+            hits: Vec::new(),
+            selected: 0,
+            done: false,
+        });
+        self.mode = Mode::LibrarySearchResults;
+        library_search::spawn_search(root, query, search_id, self.content_tx.clone());
+    }
+
     fn load_preview(&mut self) {
-        let Some(idx) = self.selected_session_index() else {
+        let Some(session) = self.selected_session() else {
             self.preview = None;
             return;
         };
 
         if let Some(ref p) = self.preview {
-            if p.session_index == idx {
+            if p.path == session.path && p.modified_at == session.modified_at {
                 return;
             }
         }
 
-        let session = &self.sessions[idx];
+        let path = session.path.clone();
+        let modified_at = session.modified_at;
         match convert_session(session) {
             Ok(spool_file) => {
+                let tool_languages = spool_file
+                    .entries
+                    .iter()
+                    .filter_map(|e| match e {
+                        Entry::ToolCall(tc) => highlight::infer_tool_language(&tc.tool, &tc.input)
+                            .map(|lang| (tc.id, lang)),
+                        _ => None,
+                    })
+                    .collect();
+
+                if self.content_matches.as_ref().is_some_and(|m| m.contains_key(&path)) {
+                    let query = self.search_input.trim();
+                    if let Some((first_entry_index, _)) =
+                        scan_entries_for_query(&spool_file.entries, query)
+                    {
+                        // No exact line-layout math here — each entry
+                        // renders a few lines in the preview, so this is
+                        // an approximation, just enough to land the match
+                        // on screen rather than requiring the user to
+                        // scroll down to find it themselves.
+                        self.preview_scroll = first_entry_index.saturating_mul(3);
+                    }
+                }
+
                 self.preview = Some(PreviewData {
-                    session_index: idx,
+                    path,
+                    modified_at,
                     entries: spool_file.entries,
+                    tool_languages,
+                    image_previews: std::cell::RefCell::new(HashMap::new()),
                 });
             }
             Err(_) => {
@@ -180,6 +670,58 @@ impl LibraryState {
         }
     }
 
+    /// Re-run session discovery (in response to an `FsChange` event from
+    /// the filesystem watcher), merging the refreshed list in while
+    /// preserving the current selection by path. The cached preview isn't
+    /// touched here directly — the next [`LibraryState::tick`] reloads it
+    /// automatically if the selected session's `modified_at` no longer
+    /// matches what it was built from (see [`LibraryState::load_preview`]).
+    pub fn refresh_sessions(&mut self) {
+        let Ok(mut sessions) = find_all_sessions() else {
+            return;
+        };
+        sessions.retain(|s| s.message_count.map(|c| c > 0).unwrap_or(true));
+
+        let previous_paths: std::collections::HashSet<PathBuf> =
+            self.sessions.iter().map(|s| s.path.clone()).collect();
+        let new_count = sessions
+            .iter()
+            .filter(|s| !previous_paths.contains(&s.path))
+            .count();
+
+        let selected_path = self.selected_session().map(|s| s.path.clone());
+
+        self.sessions = sessions;
+        // A session file may have changed on disk; drop the stale content
+        // index rather than try to patch individual entries. The next
+        // content search lazily rebuilds it.
+        self.content_cache = None;
+        self.content_building = false;
+        self.update_filter();
+
+        if let Some(path) = selected_path {
+            self.select_path(&path);
+        }
+
+        if new_count > 0 {
+            let label = if new_count == 1 { "session" } else { "sessions" };
+            self.set_status(format!("{} new {}", new_count, label));
+        }
+    }
+
+    /// Parent directories of every discovered session's log file, for a
+    /// filesystem watcher to subscribe to (deduplicated).
+    pub fn session_directories(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = self
+            .sessions
+            .iter()
+            .filter_map(|s| s.path.parent().map(PathBuf::from))
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        dirs
+    }
+
     pub fn set_status(&mut self, msg: String) {
         self.status_message = Some((msg, Instant::now()));
     }
@@ -210,8 +752,36 @@ impl LibraryState {
                 KeyCode::Char('/') => {
                     self.mode = Mode::Search;
                 }
+                KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.search_input = std::env::current_dir()
+                        .map(|p| p.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    self.mode = Mode::LibrarySearchRoot;
+                }
+                KeyCode::Char('t') => self.toggle_view_mode(),
+                KeyCode::Char(' ') => self.toggle_mark(),
+                KeyCode::Char('V') => {
+                    self.mode = Mode::Visual {
+                        anchor: self.selected,
+                    };
+                }
+                KeyCode::Char('e') if !self.marked.is_empty() => {
+                    self.mode = Mode::ExportPath;
+                }
+                KeyCode::Char('a') if !self.marked.is_empty() => {
+                    self.mode = Mode::AnnotateText;
+                }
+                KeyCode::Char('c') if !self.marked.is_empty() => self.clear_marks(),
                 KeyCode::Enter => {
-                    if let Some(session) = self.selected_session() {
+                    if let Some(&Row::Group { ref project_dir, .. }) =
+                        self.visible_rows.get(self.selected)
+                    {
+                        let key = project_dir.clone();
+                        if !self.expanded_groups.remove(&key) {
+                            self.expanded_groups.insert(key);
+                        }
+                        self.rebuild_rows();
+                    } else if let Some(session) = self.selected_session() {
                         let path = session.path.clone();
                         let agent = session.agent;
                         return LibraryAction::OpenEditor(path, agent);
@@ -229,6 +799,7 @@ impl LibraryState {
                 KeyCode::Esc => {
                     self.mode = Mode::Normal;
                     self.search_input.clear();
+                    self.search_scope = SearchScope::Title;
                     self.update_filter();
                 }
                 KeyCode::Enter => {
@@ -238,12 +809,159 @@ impl LibraryState {
                     self.search_input.pop();
                     self.update_filter();
                 }
+                // `/` doesn't type into the search box — it toggles what
+                // the query is matched against.
+                KeyCode::Char('/') => {
+                    self.search_scope = match self.search_scope {
+                        SearchScope::Title => SearchScope::Content,
+                        SearchScope::Content => SearchScope::Title,
+                    };
+                    if self.search_scope == SearchScope::Content {
+                        self.ensure_content_index();
+                    }
+                    self.update_filter();
+                }
                 KeyCode::Char(c) => {
                     self.search_input.push(c);
                     self.update_filter();
                 }
                 _ => {}
             },
+            Mode::Visual { anchor } => match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.move_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.move_up(),
+                KeyCode::Char('V') | KeyCode::Char(' ') | KeyCode::Enter => {
+                    self.commit_visual_range(anchor);
+                    self.mode = Mode::Normal;
+                }
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                }
+                _ => {}
+            },
+            Mode::ExportPath => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    self.search_input.clear();
+                }
+                KeyCode::Enter => {
+                    let dir = PathBuf::from(self.search_input.trim());
+                    let sessions: Vec<SessionInfo> = self
+                        .marked
+                        .iter()
+                        .filter_map(|&i| self.sessions.get(i).cloned())
+                        .collect();
+                    self.search_input.clear();
+                    self.mode = Mode::Normal;
+                    if !sessions.is_empty() {
+                        return LibraryAction::ExportMarked(sessions, dir);
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.search_input.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.search_input.push(c);
+                }
+                _ => {}
+            },
+            Mode::AnnotateText => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    self.search_input.clear();
+                }
+                KeyCode::Enter => {
+                    let note = self.search_input.trim().to_string();
+                    let sessions: Vec<SessionInfo> = self
+                        .marked
+                        .iter()
+                        .filter_map(|&i| self.sessions.get(i).cloned())
+                        .collect();
+                    self.search_input.clear();
+                    self.mode = Mode::Normal;
+                    if !sessions.is_empty() && !note.is_empty() {
+                        return LibraryAction::AnnotateMarked(sessions, note);
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.search_input.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.search_input.push(c);
+                }
+                _ => {}
+            },
+            Mode::LibrarySearchRoot => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    self.search_input.clear();
+                }
+                KeyCode::Enter => {
+                    let root = PathBuf::from(self.search_input.trim());
+                    self.search_input.clear();
+                    self.mode = Mode::LibrarySearchQuery;
+                    self.pending_search_root = Some(root);
+                }
+                KeyCode::Backspace => {
+                    self.search_input.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.search_input.push(c);
+                }
+                _ => {}
+            },
+            Mode::LibrarySearchQuery => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    self.search_input.clear();
+                    self.pending_search_root = None;
+                }
+                KeyCode::Enter => {
+                    let query = self.search_input.trim().to_string();
+                    let root = self.pending_search_root.take().unwrap_or_default();
+                    self.search_input.clear();
+                    if !query.is_empty() {
+                        self.start_library_search(root, query);
+                    } else {
+                        self.mode = Mode::Normal;
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.search_input.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.search_input.push(c);
+                }
+                _ => {}
+            },
+            Mode::LibrarySearchResults => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.library_search = None;
+                    self.mode = Mode::Normal;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if let Some(ref mut search) = self.library_search {
+                        if search.selected + 1 < search.hits.len() {
+                            search.selected += 1;
+                        }
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if let Some(ref mut search) = self.library_search {
+                        search.selected = search.selected.saturating_sub(1);
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(search) = self.library_search.take() {
+                        if let Some(hit) = search.hits.into_iter().nth(search.selected) {
+                            self.mode = Mode::Normal;
+                            return LibraryAction::OpenEditorAt(hit.path, hit.entry_index);
+                        }
+                    }
+                    self.mode = Mode::Normal;
+                }
+                _ => {}
+            },
         }
 
         LibraryAction::None
@@ -273,66 +991,192 @@ impl LibraryState {
         self.draw_session_list(f, main_chunks[0]);
         self.draw_preview(f, main_chunks[1]);
         self.draw_status_bar(f, status_area);
+
+        if self.mode == Mode::LibrarySearchResults {
+            self.draw_library_search_overlay(f);
+        }
+    }
+
+    fn draw_library_search_overlay(&self, f: &mut Frame) {
+        let Some(ref search) = self.library_search else {
+            return;
+        };
+
+        let area = centered_rect(80, 70, f.area());
+        let status = if search.done {
+            format!("{} hit(s) in {} file(s)", search.hits.len(), search.total_files)
+        } else {
+            format!(
+                "{} hit(s), scanning {} file(s)...",
+                search.hits.len(),
+                search.total_files
+            )
+        };
+        let title = format!(" Search \"{}\" in {} - {} ", search.query, search.root.display(), status);
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+
+        if search.hits.is_empty() {
+            let msg = if search.done { "No matches." } else { "Searching..." };
+            let paragraph = Paragraph::new(msg)
+                .block(block)
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(paragraph, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = search
+            .hits
+            .iter()
+            .map(|hit| {
+                let elapsed = format_duration_ms(hit.elapsed_ms);
+                let snippet = format!("{}[MATCH]{}", hit.context_before, hit.context_after);
+                let lines = vec![
+                    Line::from(Span::styled(
+                        format!("{} ({})", hit.title, elapsed),
+                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(Span::styled(
+                        format!(
+                            "  {}",
+                            truncate_str_with_ellipsis(&snippet, area.width.saturating_sub(4) as usize)
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ];
+                ListItem::new(lines)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(search.selected));
+        f.render_stateful_widget(list, area, &mut state);
     }
 
     fn draw_session_list(&self, f: &mut Frame, area: Rect) {
         let count = self.filtered_indices.len();
+        let mode_tag = match self.view_mode {
+            ViewMode::Flat => "",
+            ViewMode::Tree => " [tree]",
+        };
+        let scope_tag = match (self.search_scope, self.content_building) {
+            (SearchScope::Title, _) => "",
+            (SearchScope::Content, true) => " [content, indexing...]",
+            (SearchScope::Content, false) => " [content]",
+        };
         let title = if self.search_input.is_empty() {
-            format!(" Sessions ({}) ", count)
+            format!(" Sessions ({}){}{} ", count, mode_tag, scope_tag)
         } else {
-            format!(" Sessions ({}) [/{}] ", count, self.search_input)
+            format!(
+                " Sessions ({}){}{} [{}] ",
+                count, mode_tag, scope_tag, self.search_input
+            )
         };
 
+        let indent = self.view_mode == ViewMode::Tree;
         let items: Vec<ListItem> = self
-            .filtered_indices
+            .visible_rows
             .iter()
             .enumerate()
-            .map(|(i, &session_idx)| {
-                let session = &self.sessions[session_idx];
-                let title_text = session.title.as_deref().unwrap_or("Untitled");
-                let agent = session.agent.as_str();
-                let date = session
-                    .modified_at
-                    .map(|d| {
-                        d.with_timezone(&chrono::Local)
-                            .format("%m/%d %H:%M")
-                            .to_string()
-                    })
-                    .unwrap_or_default();
+            .map(|(i, row)| {
+                let is_selected = i == self.selected;
+                match row {
+                    Row::Group { project_dir, count } => {
+                        let expanded = self.expanded_groups.contains(project_dir);
+                        let marker = if expanded { "v" } else { ">" };
+                        let label = project_dir
+                            .as_ref()
+                            .map(|p| p.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| "(no project)".to_string());
 
-                let agent_badge = match agent {
-                    "claude-code" => "CC",
-                    "codex" => "CX",
-                    "cursor" => "CU",
-                    "aider" => "AI",
-                    _ => "??",
-                };
+                        let prefix = if is_selected { "> " } else { "  " };
+                        let text = format!("{}{} {} ({})", prefix, marker, label, count);
+                        let style = if is_selected {
+                            Style::default()
+                                .fg(Color::Cyan)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(Color::Cyan)
+                        };
+                        ListItem::new(Line::from(Span::styled(text, style)))
+                    }
+                    Row::Session(fi) => {
+                        let session_idx = self.filtered_indices[*fi];
+                        let session = &self.sessions[session_idx];
+                        let title_text = session.title.as_deref().unwrap_or("Untitled");
+                        let agent = session.agent.as_str();
+                        let date = session
+                            .modified_at
+                            .map(|d| {
+                                d.with_timezone(&chrono::Local)
+                                    .format("%m/%d %H:%M")
+                                    .to_string()
+                            })
+                            .unwrap_or_default();
 
-                let is_selected = i == self.selected;
+                        let agent_badge = match agent {
+                            "claude-code" => "CC",
+                            "codex" => "CX",
+                            "cursor" => "CU",
+                            "aider" => "AI",
+                            "aichat" => "AC",
+                            _ => "??",
+                        };
 
-                let max_title_len = area.width as usize - agent_badge.len() - date.len() - 6;
-                let display_title = truncate_str_with_ellipsis(title_text, max_title_len);
+                        let match_badge = self
+                            .content_matches
+                            .as_ref()
+                            .and_then(|m| m.get(&session.path))
+                            .map(|cm| format!("{}x ", cm.count));
+                        let match_badge_len = match_badge.as_ref().map(|b| b.len()).unwrap_or(0);
+                        let indent_len = if indent { 2 } else { 0 };
+                        let marked = self.marked.contains(&session_idx);
 
-                let line = Line::from(vec![
-                    Span::styled(
-                        if is_selected { "> " } else { "  " },
-                        Style::default().fg(Color::Cyan),
-                    ),
-                    Span::styled(
-                        display_title,
-                        if is_selected {
-                            Style::default().add_modifier(Modifier::BOLD)
-                        } else {
-                            Style::default()
-                        },
-                    ),
-                    Span::raw(" "),
-                    Span::styled(agent_badge, Style::default().fg(Color::DarkGray)),
-                    Span::raw(" "),
-                    Span::styled(date, Style::default().fg(Color::DarkGray)),
-                ]);
-
-                ListItem::new(line)
+                        let max_title_len = area.width as usize
+                            - agent_badge.len()
+                            - date.len()
+                            - match_badge_len
+                            - indent_len
+                            - if marked { 2 } else { 0 }
+                            - 6;
+                        let positions = self
+                            .match_positions
+                            .get(*fi)
+                            .map(|v| v.as_slice())
+                            .unwrap_or(&[]);
+
+                        let mut spans = vec![Span::styled(
+                            if is_selected { "> " } else { "  " },
+                            Style::default().fg(Color::Cyan),
+                        )];
+                        if indent {
+                            spans.push(Span::raw("  "));
+                        }
+                        if marked {
+                            spans.push(Span::styled(
+                                "* ",
+                                Style::default()
+                                    .fg(Color::Magenta)
+                                    .add_modifier(Modifier::BOLD),
+                            ));
+                        }
+                        if let Some(badge) = match_badge {
+                            spans.push(Span::styled(badge, Style::default().fg(Color::Yellow)));
+                        }
+                        spans.extend(title_spans(title_text, positions, max_title_len, is_selected));
+                        spans.push(Span::raw(" "));
+                        spans.push(Span::styled(agent_badge, Style::default().fg(Color::DarkGray)));
+                        spans.push(Span::raw(" "));
+                        spans.push(Span::styled(date, Style::default().fg(Color::DarkGray)));
+
+                        ListItem::new(Line::from(spans))
+                    }
+                }
             })
             .collect();
 
@@ -360,7 +1204,7 @@ impl LibraryState {
         let block = Block::default().borders(Borders::ALL).title(" Preview ");
 
         let Some(ref preview) = self.preview else {
-            let msg = if self.filtered_indices.is_empty() {
+            let msg = if self.selected_session().is_none() {
                 "No session selected"
             } else {
                 "Loading preview..."
@@ -374,6 +1218,20 @@ impl LibraryState {
 
         let mut lines: Vec<Line> = Vec::new();
 
+        if let Some(cm) = self
+            .content_matches
+            .as_ref()
+            .and_then(|m| m.get(&preview.path))
+        {
+            lines.push(Line::from(Span::styled(
+                format!("MATCH: {}", cm.snippet),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+        }
+
         for entry in &preview.entries {
             match entry {
                 Entry::Session(s) => {
@@ -431,7 +1289,6 @@ impl LibraryState {
                     lines.push(Line::from(""));
                 }
                 Entry::ToolCall(tc) => {
-                    let input_preview = format_tool_input(&tc.input, area.width as usize - 12);
                     let tool_display = if tc.tool == "Task" {
                         if let Some(subagent_type) =
                             tc.input.get("subagent_type").and_then(|v| v.as_str())
@@ -452,7 +1309,14 @@ impl LibraryState {
                         ),
                         Span::styled(tool_display, Style::default().fg(Color::Blue)),
                     ]));
-                    lines.push(Line::from(format!("  {}", input_preview)));
+                    if let Some(rows) = diff::diff_for_tool_call(&tc.tool, &tc.input) {
+                        for (style, text) in diff::diff_to_spans(&rows).into_iter().take(8) {
+                            lines.push(highlighted_line("  ", vec![(style, text)], area.width as usize - 4));
+                        }
+                    } else {
+                        let input_preview = format_tool_input(&tc.input, area.width as usize - 12);
+                        lines.push(Line::from(format!("  {}", input_preview)));
+                    }
                     lines.push(Line::from(""));
                 }
                 Entry::ToolResult(tr) => {
@@ -472,15 +1336,74 @@ impl LibraryState {
                             Style::default().fg(Color::Red),
                         )));
                     } else if let Some(ref output) = tr.output {
-                        let text = match output {
-                            spool_format::ToolOutput::Text(t) => t.as_str(),
-                            spool_format::ToolOutput::Binary(_) => "<binary>",
+                        let image_rows = match output {
+                            spool_format::ToolOutput::Binary(bin) => bin
+                                .decoded_bytes()
+                                .ok()
+                                .filter(|b| super::image_preview::detect_image_format(b).is_some())
+                                .map(|decoded| {
+                                    let cols = (area.width as usize).saturating_sub(4) as u16;
+                                    let rows = 4u16;
+                                    let mut cache = preview.image_previews.borrow_mut();
+                                    let stale = cache.get(&tr.id).map(|(dims, _)| *dims != (cols, rows)).unwrap_or(true);
+                                    if stale {
+                                        let rendered = super::image_preview::render_image(&decoded, cols, rows);
+                                        cache.insert(tr.id, ((cols, rows), rendered));
+                                    }
+                                    cache[&tr.id].1.clone()
+                                })
+                                .flatten(),
+                            spool_format::ToolOutput::Text(_) => None,
                         };
-                        let preview_lines: Vec<&str> = text.lines().take(3).collect();
-                        for line in preview_lines {
-                            let truncated =
-                                truncate_str_with_ellipsis(line, area.width as usize - 4);
-                            lines.push(Line::from(format!("  {}", truncated)));
+
+                        if let Some(image_lines) = image_rows {
+                            lines.extend(image_lines);
+                            lines.push(Line::from(""));
+                            continue;
+                        }
+
+                        let autoview_rows: Option<Vec<(Style, String)>> =
+                            match common::tool_result_view(output) {
+                                common::ToolResultView::Hex => match output {
+                                    spool_format::ToolOutput::Binary(bin) => {
+                                        bin.decoded_bytes().ok().map(|b| common::hex_dump_lines(&b, 3))
+                                    }
+                                    spool_format::ToolOutput::Text(_) => None,
+                                },
+                                common::ToolResultView::Table => match output {
+                                    spool_format::ToolOutput::Text(t) => {
+                                        common::json_table_rows(t, 3)
+                                    }
+                                    spool_format::ToolOutput::Binary(_) => None,
+                                },
+                                common::ToolResultView::Lines => None,
+                            };
+
+                        if let Some(rows) = autoview_rows {
+                            for (style, text) in rows {
+                                lines.push(highlighted_line("  ", vec![(style, text)], area.width as usize - 4));
+                            }
+                        } else {
+                            let text = match output {
+                                spool_format::ToolOutput::Text(t) => t.as_str(),
+                                spool_format::ToolOutput::Binary(_) => "<binary>",
+                            };
+                            let rendered_lines = if text.contains('\x1b') {
+                                ansi::parse_ansi_lines(text)
+                            } else {
+                                let lang = preview
+                                    .tool_languages
+                                    .get(&tr.call_id)
+                                    .map(|s| s.as_str());
+                                highlight::highlight_to_spans(text, lang)
+                            };
+                            for segments in rendered_lines.into_iter().take(3) {
+                                lines.push(highlighted_line(
+                                    "  ",
+                                    segments,
+                                    area.width as usize - 4,
+                                ));
+                            }
                         }
                     }
                     lines.push(Line::from(""));
@@ -492,10 +1415,11 @@ impl LibraryState {
                             .fg(Color::Magenta)
                             .add_modifier(Modifier::BOLD),
                     )));
-                    let preview_lines: Vec<&str> = r.content.lines().take(5).collect();
-                    for line in preview_lines {
-                        let truncated = truncate_str_with_ellipsis(line, area.width as usize - 4);
-                        lines.push(Line::from(format!("  {}", truncated)));
+                    for segments in highlight::highlight_response_lines(&r.content)
+                        .into_iter()
+                        .take(5)
+                    {
+                        lines.push(highlighted_line("  ", segments, area.width as usize - 4));
                     }
                     if r.content.lines().count() > 5 {
                         lines.push(Line::from(Span::styled(
@@ -552,6 +1476,20 @@ impl LibraryState {
                     )));
                     lines.push(Line::from(""));
                 }
+                Entry::Terminal(t) => {
+                    lines.push(Line::from(Span::styled(
+                        "TERMINAL",
+                        Style::default()
+                            .fg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    let text = String::from_utf8_lossy(&t.decoded_bytes()).into_owned();
+                    for line in text.lines().take(3) {
+                        let truncated = truncate_str_with_ellipsis(line, area.width as usize - 4);
+                        lines.push(Line::from(format!("  {}", truncated)));
+                    }
+                    lines.push(Line::from(""));
+                }
                 Entry::Unknown => {}
             }
         }
@@ -568,16 +1506,73 @@ impl LibraryState {
         let (left_text, style) = if let Some((ref msg, _)) = self.status_message {
             (msg.clone(), Style::default().fg(Color::Green))
         } else if self.mode == Mode::Search {
+            let scope = match self.search_scope {
+                SearchScope::Title => "title",
+                SearchScope::Content => "content",
+            };
+            (
+                format!(
+                    "Search [{}]: {}_ | Esc: cancel  Enter: confirm  /: toggle title/content",
+                    scope, self.search_input
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if matches!(self.mode, Mode::Visual { .. }) {
+            (
+                "VISUAL j/k: extend  V/space/Enter: mark range  Esc: cancel".to_string(),
+                Style::default().fg(Color::Magenta),
+            )
+        } else if self.mode == Mode::ExportPath {
+            (
+                format!(
+                    "Export {} session(s) to: {}_ | Esc: cancel  Enter: confirm",
+                    self.marked.len(),
+                    self.search_input
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if self.mode == Mode::AnnotateText {
+            (
+                format!(
+                    "Annotate {} session(s): {}_ | Esc: cancel  Enter: confirm",
+                    self.marked.len(),
+                    self.search_input
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if self.mode == Mode::LibrarySearchRoot {
+            (
+                format!(
+                    "Library search root: {}_ | Esc: cancel  Enter: next",
+                    self.search_input
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if self.mode == Mode::LibrarySearchQuery {
             (
                 format!(
-                    "Search: {}_ | Esc: cancel  Enter: confirm",
+                    "Library search query: {}_ | Esc: cancel  Enter: search",
                     self.search_input
                 ),
                 Style::default().fg(Color::Yellow),
             )
+        } else if self.mode == Mode::LibrarySearchResults {
+            (
+                "j/k: navigate  Enter: open at match  Esc: close".to_string(),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if !self.marked.is_empty() {
+            (
+                format!(
+                    "{} marked  space: toggle  V: visual  e: export  a: annotate  Esc: clear",
+                    self.marked.len()
+                ),
+                Style::default().fg(Color::Magenta),
+            )
         } else {
             (
-                "j/k: navigate  /: search  Enter: open  q: quit".to_string(),
+                "j/k: navigate  /: search  t: tree view  space: mark  Ctrl-l: library search  Enter: open  q: quit"
+                    .to_string(),
                 Style::default().fg(Color::DarkGray),
             )
         };
@@ -587,18 +1582,162 @@ impl LibraryState {
     }
 }
 
+/// Render one line of `highlight::highlight_to_spans`/`highlight_response_lines`
+/// output as ratatui `Span`s behind a plain `prefix`, truncating to
+/// `max_width` display characters (appending a dim `"..."`) rather than
+/// splitting a styled segment mid-way and losing its color.
+fn highlighted_line(prefix: &str, segments: Vec<(Style, String)>, max_width: usize) -> Line<'static> {
+    let mut spans = vec![Span::raw(prefix.to_string())];
+    let mut used = 0usize;
+    let mut truncated = false;
+
+    for (style, text) in segments {
+        if used >= max_width {
+            truncated = true;
+            break;
+        }
+        let remaining = max_width - used;
+        let char_count = text.chars().count();
+        if char_count <= remaining {
+            used += char_count;
+            spans.push(Span::styled(text, style));
+        } else {
+            let cut: String = text.chars().take(remaining).collect();
+            used = max_width;
+            spans.push(Span::styled(cut, style));
+            truncated = true;
+            break;
+        }
+    }
+
+    if truncated {
+        spans.push(Span::styled("...", Style::default().fg(Color::DarkGray)));
+    }
+    Line::from(spans)
+}
+
+/// Render `title` as per-character spans up to `max_len` chars (appending
+/// `"..."` if it doesn't fit), highlighting the char indices in `positions`
+/// (matched fuzzy-search characters) in a distinct style.
+fn title_spans(
+    title: &str,
+    positions: &[usize],
+    max_len: usize,
+    selected: bool,
+) -> Vec<Span<'static>> {
+    let chars: Vec<char> = title.chars().collect();
+    let truncated = chars.len() > max_len;
+    let budget = if truncated {
+        max_len.saturating_sub(3)
+    } else {
+        max_len
+    };
+    let shown = &chars[..budget.min(chars.len())];
+    let matched: std::collections::HashSet<usize> = positions.iter().copied().collect();
+
+    let base_style = if selected {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let match_style = base_style.fg(Color::Yellow);
+
+    let mut spans: Vec<Span<'static>> = shown
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let style = if matched.contains(&i) {
+                match_style
+            } else {
+                base_style
+            };
+            Span::styled(c.to_string(), style)
+        })
+        .collect();
+
+    if truncated {
+        spans.push(Span::styled("...".to_string(), base_style));
+    }
+    spans
+}
+
+/// Scan `entries`' prompt/response/thinking/tool-result text for a
+/// case-insensitive substring match on `query`, returning the index of the
+/// first matching entry and the total number of matching entries.
+fn scan_entries_for_query(entries: &[Entry], query: &str) -> Option<(usize, usize)> {
+    let needle = query.to_lowercase();
+    let mut first_index = None;
+    let mut count = 0;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let text: Option<Cow<str>> = match entry {
+            Entry::Prompt(p) => Some(Cow::Borrowed(&p.content)),
+            Entry::Response(r) => Some(Cow::Borrowed(&r.content)),
+            Entry::Thinking(t) => Some(Cow::Borrowed(&t.content)),
+            Entry::ToolCall(tc) => Some(Cow::Owned(tc.input.to_string())),
+            Entry::ToolResult(tr) => match &tr.output {
+                Some(spool_format::ToolOutput::Text(t)) => Some(Cow::Borrowed(t.as_str())),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(text) = text {
+            if text.to_lowercase().contains(&needle) {
+                count += 1;
+                first_index.get_or_insert(i);
+            }
+        }
+    }
+
+    first_index.map(|idx| (idx, count))
+}
+
+/// Extract the lowercased, newline-joined text of every searchable entry
+/// (prompt/response/thinking/tool-call input/tool-result output) in a
+/// session, for [`LibraryState::content_cache`].
+fn extract_searchable_text(entries: &[Entry]) -> String {
+    let mut text = String::new();
+    for entry in entries {
+        let piece: Option<Cow<str>> = match entry {
+            Entry::Prompt(p) => Some(Cow::Borrowed(p.content.as_str())),
+            Entry::Response(r) => Some(Cow::Borrowed(r.content.as_str())),
+            Entry::Thinking(t) => Some(Cow::Borrowed(t.content.as_str())),
+            Entry::ToolCall(tc) => Some(Cow::Owned(tc.input.to_string())),
+            Entry::ToolResult(tr) => match &tr.output {
+                Some(spool_format::ToolOutput::Text(t)) => Some(Cow::Borrowed(t.as_str())),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(piece) = piece {
+            text.push_str(&piece.to_lowercase());
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Find the first line of `text` (already lowercased) containing `query`
+/// and return it trimmed and length-capped, for display as a match snippet.
+fn extract_snippet_line(text: &str, query: &str) -> String {
+    let line = text
+        .lines()
+        .find(|line| line.contains(query))
+        .unwrap_or("")
+        .trim();
+    truncate_str_with_ellipsis(line, 80)
+}
+
 fn find_all_sessions() -> Result<Vec<SessionInfo>> {
-    let mut sessions =
-        claude_code::find_sessions().context("Failed to discover Claude Code sessions")?;
-    sessions.extend(codex::find_sessions().context("Failed to discover Codex sessions")?);
-    sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
-    Ok(sessions)
+    crate::commands::cache::catalog::scan()
 }
 
 pub fn convert_session(session: &SessionInfo) -> Result<spool_format::SpoolFile> {
     match session.agent {
         AgentType::ClaudeCode => claude_code::convert(session),
         AgentType::Codex => codex::convert(session),
+        AgentType::Aichat => aichat::convert(session),
         _ => anyhow::bail!("Unsupported agent: {}", session.agent.as_str()),
     }
 }
@@ -0,0 +1,138 @@
+//! Cross-session search across a whole library of `.spool` files, for the
+//! Library view's search-picker overlay (see
+//! [`super::library::LibraryState::handle_key`]'s `Mode::LibrarySearchQuery`
+//! handling).
+//!
+//! Unlike [`super::library::LibraryState::ensure_content_index`] (which only
+//! covers sessions this run's agent-discovery already found), this walks an
+//! arbitrary root directory the user names, respecting `.gitignore`-style
+//! ignore files via the `ignore` crate - the same crate ripgrep itself is
+//! built on - so pointing it at a project checkout doesn't also scan
+//! `target/` or `node_modules/`. Each `.spool` file under that root is
+//! scanned for `query` on a small worker pool, streaming hits back through
+//! the main event channel as they're found rather than collecting them all
+//! before the picker can show anything.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use ignore::WalkBuilder;
+use spool_format::{Entry, SpoolFile};
+
+use super::common::{entry_search_text, extract_context_after, extract_context_before};
+use super::AppEvent;
+
+/// Reported once a search starts, after the initial directory walk has
+/// enumerated every `.spool` file under the root - lets the picker show
+/// "scanning N files" before any hits arrive.
+pub struct SearchStarted {
+    pub search_id: u64,
+    pub total_files: usize,
+}
+
+/// One matching entry, found while scanning a single `.spool` file.
+pub struct SearchHit {
+    pub path: PathBuf,
+    pub title: String,
+    /// Index into that file's `entries`, for [`super::editor::EditorState::reveal_entry`].
+    pub entry_index: usize,
+    /// Time since the session's first entry, for display alongside the hit.
+    pub elapsed_ms: u64,
+    pub context_before: String,
+    pub context_after: String,
+}
+
+/// Walk `root` for `.spool` files and scan each for `query` (case-
+/// insensitive substring), reporting progress and hits back through `tx`
+/// tagged with `search_id` so a superseded search's stale results can be
+/// discarded by the receiver. Runs entirely on background threads; returns
+/// immediately.
+pub fn spawn_search(root: PathBuf, query: String, search_id: u64, tx: mpsc::Sender<AppEvent>) {
+    std::thread::spawn(move || {
+        let paths: Vec<PathBuf> = WalkBuilder::new(&root)
+            .hidden(false)
+            .build()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "spool"))
+            .collect();
+
+        if tx
+            .send(AppEvent::LibrarySearchStarted(SearchStarted {
+                search_id,
+                total_files: paths.len(),
+            }))
+            .is_err()
+        {
+            return;
+        }
+
+        let worker_count = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(paths.len().max(1));
+
+        // Round-robin the file list across `worker_count` threads so each
+        // worker's share is spread across the whole list rather than one
+        // contiguous (and possibly much larger or smaller) chunk.
+        let mut shares: Vec<Vec<PathBuf>> = vec![Vec::new(); worker_count];
+        for (i, path) in paths.into_iter().enumerate() {
+            shares[i % worker_count].push(path);
+        }
+
+        std::thread::scope(|scope| {
+            for share in shares {
+                let query = query.as_str();
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    for path in share {
+                        scan_file(&path, query, search_id, &tx);
+                    }
+                });
+            }
+        });
+
+        let _ = tx.send(AppEvent::LibrarySearchDone(search_id));
+    });
+}
+
+/// Scan one `.spool` file for `query`, sending a [`SearchHit`] for every
+/// entry whose searchable text contains it. A file that fails to parse is
+/// silently skipped - the same stance `library::convert_session` callers
+/// already take for a bad session rather than aborting the whole search.
+fn scan_file(path: &Path, query: &str, search_id: u64, tx: &mpsc::Sender<AppEvent>) {
+    let Ok(file) = SpoolFile::from_path(path) else {
+        return;
+    };
+    let needle = query.to_lowercase();
+    let title = file
+        .session
+        .title
+        .clone()
+        .unwrap_or_else(|| "Untitled".to_string());
+    let base_ts = file.entries.first().and_then(|e| e.timestamp()).unwrap_or(0);
+
+    for (entry_index, entry) in file.entries.iter().enumerate() {
+        let Some(text) = entry_search_text(entry) else {
+            continue;
+        };
+        let lowered = text.to_lowercase();
+        let Some(byte_pos) = lowered.find(&needle) else {
+            continue;
+        };
+
+        let elapsed_ms = entry.timestamp().unwrap_or(base_ts).saturating_sub(base_ts);
+        let hit = SearchHit {
+            path: path.to_path_buf(),
+            title: title.clone(),
+            entry_index,
+            elapsed_ms,
+            context_before: extract_context_before(&text, byte_pos, 40),
+            context_after: extract_context_after(&text, byte_pos + needle.len(), 40),
+        };
+
+        if tx.send(AppEvent::LibrarySearchHit(search_id, hit)).is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Minimal line-based Markdown rendering for `Response`/`Thinking` entry
+//! content in the Editor, toggled by
+//! [`crate::tui::keymap::EditorCommand::ToggleMarkdownMode`].
+//!
+//! Hand-rolled rather than pulled in from a Markdown-parsing crate, in
+//! keeping with this module's siblings ([`crate::vt`], [`crate::diff`],
+//! [`crate::ansi`]): headings, list markers, and inline emphasis/code only
+//! need a single pass over each line. Fenced code blocks already have a
+//! home in [`highlight::highlight_response_lines`]'s per-language coloring,
+//! which this module defers to rather than reimplementing.
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::highlight;
+
+/// Heading color, one per level 1-6 (repeating the last for anything
+/// deeper, though Markdown rarely nests that far).
+const HEADING_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+];
+
+/// Render `content` (a `Response`/`Thinking` entry's markdown text) into one
+/// `Vec<(Style, String)>` of styled segments per output line, mirroring
+/// [`highlight::highlight_response_lines`]'s shape so callers in
+/// [`super::common::render_entry_lines`] can treat the two interchangeably.
+/// Fenced code blocks are delegated to [`highlight::highlight_to_spans`] for
+/// per-language coloring; everything else is scanned line-by-line for
+/// headings, list markers, and inline code/emphasis.
+pub fn render_markdown_lines(content: &str) -> Vec<Vec<(Style, String)>> {
+    let blocks = spool_format::extract_code_blocks(content);
+    if blocks.is_empty() {
+        return content.lines().map(render_prose_line).collect();
+    }
+
+    let mut result = Vec::new();
+    let mut pos = 0;
+    for block in &blocks {
+        if block.byte_range.0 > pos {
+            result.extend(content[pos..block.byte_range.0].lines().map(render_prose_line));
+        }
+        let code = &content[block.byte_range.0..block.byte_range.1];
+        result.extend(highlight::highlight_to_spans(code, block.language.as_deref()));
+        pos = block.byte_range.1;
+    }
+    if pos < content.len() {
+        result.extend(content[pos..].lines().map(render_prose_line));
+    }
+    result
+}
+
+/// Render one non-code-block line: a heading, a list item, or plain prose
+/// (all three still get inline code/emphasis styling applied).
+fn render_prose_line(line: &str) -> Vec<(Style, String)> {
+    if let Some((level, text)) = heading(line) {
+        let color = HEADING_COLORS[(level - 1).min(HEADING_COLORS.len() - 1)];
+        return vec![(
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+            text.to_string(),
+        )];
+    }
+
+    if let Some((indent, marker, text)) = list_item(line) {
+        let mut spans = vec![(Style::default(), format!("{}{} ", indent, marker))];
+        spans.extend(inline_spans(text));
+        return spans;
+    }
+
+    inline_spans(line)
+}
+
+/// Recognize a `#`-`######` ATX heading, returning its level and the
+/// trimmed heading text.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+    let rest = &trimmed[hashes..];
+    rest.strip_prefix(' ').map(|text| (hashes, text.trim_start()))
+}
+
+/// Recognize a bullet (`-`/`*`/`+`) or numbered (`1.`) list item, returning
+/// its leading indent, a display marker, and the remaining text.
+fn list_item(line: &str) -> Option<(&str, String, &str)> {
+    let indent_len = line.len() - line.trim_start().len();
+    let indent = &line[..indent_len];
+    let rest = &line[indent_len..];
+
+    if let Some(text) = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))
+    {
+        return Some((indent, "\u{2022}".to_string(), text));
+    }
+
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if !digits.is_empty() {
+        if let Some(text) = rest[digits.len()..].strip_prefix(". ") {
+            return Some((indent, format!("{}.", digits), text));
+        }
+    }
+    None
+}
+
+/// Style for `` `inline code` `` spans.
+fn inline_code_style() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+
+/// Split `text` into spans styled for `**bold**`, `*italic*`/`_italic_`, and
+/// `` `inline code` ``. A single forward pass with no nesting - response
+/// text emphasis is overwhelmingly flat, and an unmatched marker (a lone
+/// `` ` `` or `*`) is just left as a literal character rather than failing
+/// the whole line.
+fn inline_spans(text: &str) -> Vec<(Style, String)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '`' {
+            if let Some(end) = find_marker(&chars, i + 1, chars[i]) {
+                flush_plain(&mut plain, &mut spans);
+                spans.push((inline_code_style(), chars[i + 1..end].iter().collect()));
+                i = end + 1;
+                continue;
+            }
+        } else if chars[i] == '*' && chars.get(i + 1) == Some(&'*') {
+            if let Some(end) = find_marker_pair(&chars, i + 2, '*') {
+                flush_plain(&mut plain, &mut spans);
+                spans.push((
+                    Style::default().add_modifier(Modifier::BOLD),
+                    chars[i + 2..end].iter().collect(),
+                ));
+                i = end + 2;
+                continue;
+            }
+        } else if chars[i] == '*' || chars[i] == '_' {
+            let marker = chars[i];
+            if let Some(end) = find_marker(&chars, i + 1, marker) {
+                flush_plain(&mut plain, &mut spans);
+                spans.push((
+                    Style::default().add_modifier(Modifier::ITALIC),
+                    chars[i + 1..end].iter().collect(),
+                ));
+                i = end + 1;
+                continue;
+            }
+        }
+        plain.push(chars[i]);
+        i += 1;
+    }
+    flush_plain(&mut plain, &mut spans);
+    if spans.is_empty() {
+        spans.push((Style::default(), String::new()));
+    }
+    spans
+}
+
+fn flush_plain(plain: &mut String, spans: &mut Vec<(Style, String)>) {
+    if !plain.is_empty() {
+        spans.push((Style::default(), std::mem::take(plain)));
+    }
+}
+
+/// The index of the next single `marker` char at/after `from`.
+fn find_marker(chars: &[char], from: usize, marker: char) -> Option<usize> {
+    (from..chars.len()).find(|&j| chars[j] == marker)
+}
+
+/// The index of the first char of the next `marker` pair (e.g. `**`) at/after `from`.
+fn find_marker_pair(chars: &[char], from: usize, marker: char) -> Option<usize> {
+    (from..chars.len().saturating_sub(1)).find(|&j| chars[j] == marker && chars[j + 1] == marker)
+}
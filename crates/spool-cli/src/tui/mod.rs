@@ -3,21 +3,36 @@
 //! The top-level app manages the terminal and dispatches between:
 //! - **Library**: session browser (browse, search, preview)
 //! - **Editor**: session replay (playback, trim, annotate, info overlay)
+//!
+//! All input to the main loop — key presses, terminal resizes, the redraw
+//! timer, and filesystem change notifications — arrives through one
+//! `mpsc` channel fed by independent producer threads (see [`AppEvent`]),
+//! rather than the loop polling each source itself.
 
+pub mod clock;
 pub mod common;
+pub mod cost;
 pub mod editor;
+pub mod fuzzy;
+pub mod image_preview;
+pub mod keymap;
 pub mod library;
+pub mod library_search;
+pub mod markdown;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event},
+    event::{self, Event as TermEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::prelude::*;
 use spool_adapters::AgentType;
+use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
+use std::sync::mpsc;
 use std::time::Duration;
 
 use editor::{EditorAction, EditorState};
@@ -31,11 +46,42 @@ enum AppView {
     Editor(Box<EditorState>),
 }
 
+/// One event driving the main loop, delivered through a single channel by
+/// independent producers: [`spawn_input_thread`] (key presses, resizes),
+/// [`spawn_tick_thread`] (the redraw timer), [`spawn_fs_watcher`] (session
+/// file changes on disk), and Library's own background content-index
+/// build (see `library::LibraryState::ensure_content_index`).
+pub(crate) enum AppEvent {
+    Key(event::KeyEvent),
+    Resize(u16, u16),
+    FsChange,
+    Tick,
+    /// The background content-search index has finished building: path ->
+    /// lowercased concatenation of that session's searchable entry text
+    /// (see `library::LibraryState::ensure_content_index`).
+    ContentIndexBuilt(HashMap<PathBuf, String>),
+    /// A library-wide search (see `library_search::spawn_search`) has
+    /// finished its initial directory walk and reports how many files it
+    /// will scan.
+    LibrarySearchStarted(library_search::SearchStarted),
+    /// A library-wide search found a match. Tagged with the search's id so
+    /// a hit from a search the user has since restarted or cancelled is
+    /// discarded rather than appended to the current results.
+    LibrarySearchHit(u64, library_search::SearchHit),
+    /// A library-wide search has scanned every file. Tagged with the
+    /// search's id for the same reason as `LibrarySearchHit`.
+    LibrarySearchDone(u64),
+}
+
 /// Run the TUI application.
 ///
 /// - `initial_path: None` → start in Library view
 /// - `initial_path: Some(path)` → start directly in Editor view
 pub fn run_tui(initial_path: Option<PathBuf>) -> Result<()> {
+    // Library needs a sender to report background content-search results
+    // back through the main loop, so the channel is built before the view.
+    let (tx, rx) = mpsc::channel();
+
     // Build initial view
     let mut view = match initial_path {
         Some(ref path) => {
@@ -50,7 +96,7 @@ pub fn run_tui(initial_path: Option<PathBuf>) -> Result<()> {
             AppView::Editor(Box::new(editor))
         }
         None => {
-            let library = LibraryState::new(None)?;
+            let library = LibraryState::new(None, tx.clone())?;
             AppView::Library(library)
         }
     };
@@ -73,7 +119,17 @@ pub fn run_tui(initial_path: Option<PathBuf>) -> Result<()> {
     // Preserve library state across transitions
     let mut saved_library: Option<LibraryState> = None;
 
-    let result = run_app_loop(&mut terminal, &mut view, &mut saved_library);
+    spawn_input_thread(tx.clone());
+    spawn_tick_thread(tx.clone());
+    let watch_dirs = match &view {
+        AppView::Library(lib) => lib.session_directories(),
+        AppView::Editor(_) => Vec::new(),
+    };
+    // Keeping the watcher alive (even unused by name) keeps it watching;
+    // dropping it tears the watch down, so it must outlive the loop.
+    let _watcher = spawn_fs_watcher(tx, watch_dirs);
+
+    let result = run_app_loop(&mut terminal, &mut view, &mut saved_library, &rx);
 
     // Restore terminal (once)
     disable_raw_mode()?;
@@ -82,10 +138,96 @@ pub fn run_tui(initial_path: Option<PathBuf>) -> Result<()> {
     result
 }
 
+/// Forward terminal key/resize events onto `tx` as they arrive. Exits
+/// quietly once the receiver is gone (the app is shutting down).
+fn spawn_input_thread(tx: mpsc::Sender<AppEvent>) {
+    std::thread::spawn(move || loop {
+        match event::poll(Duration::from_millis(50)) {
+            Ok(true) => match event::read() {
+                Ok(TermEvent::Key(key)) => {
+                    if tx.send(AppEvent::Key(key)).is_err() {
+                        return;
+                    }
+                }
+                Ok(TermEvent::Resize(w, h)) => {
+                    if tx.send(AppEvent::Resize(w, h)).is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => return,
+            },
+            Ok(false) => {}
+            Err(_) => return,
+        }
+    });
+}
+
+/// Drive redraws at a fixed cadence, independent of whether any other
+/// event arrived — this is what makes Editor playback and Library's
+/// status-message expiry advance even when the user isn't pressing keys.
+fn spawn_tick_thread(tx: mpsc::Sender<AppEvent>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(50));
+        if tx.send(AppEvent::Tick).is_err() {
+            return;
+        }
+    });
+}
+
+/// How long to wait for the dust to settle after a filesystem event before
+/// acting on it. An agent session file gets touched repeatedly in a short
+/// burst while a run is in progress; without this, each touch would queue
+/// its own `FsChange` and re-run session discovery once per write.
+const FS_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watch `dirs` non-recursively for session file creation/modification/
+/// removal, debouncing a burst of events into a single `FsChange` the main
+/// loop uses to re-run session discovery. Returns `None` (rather than
+/// erroring the whole TUI) if watching isn't available in this
+/// environment, e.g. an inotify instance limit — auto-refresh is a
+/// nicety, not a requirement.
+fn spawn_fs_watcher(tx: mpsc::Sender<AppEvent>, dirs: Vec<PathBuf>) -> Option<RecommendedWatcher> {
+    if dirs.is_empty() {
+        return None;
+    }
+
+    // Raw notify events land on `raw_tx`/`raw_rx`; the debouncer thread
+    // below coalesces a burst of them into one `AppEvent::FsChange`.
+    let (raw_tx, raw_rx) = mpsc::channel::<()>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = raw_tx.send(());
+        }
+    })
+    .ok()?;
+
+    for dir in &dirs {
+        let _ = watcher.watch(dir, RecursiveMode::NonRecursive);
+    }
+
+    std::thread::spawn(move || loop {
+        // Block for the first event in a burst, then keep draining until
+        // the stream goes quiet for a full debounce window.
+        if raw_rx.recv().is_err() {
+            return;
+        }
+        while raw_rx.recv_timeout(FS_DEBOUNCE).is_ok() {}
+
+        if tx.send(AppEvent::FsChange).is_err() {
+            return;
+        }
+    });
+
+    Some(watcher)
+}
+
 fn run_app_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     view: &mut AppView,
     saved_library: &mut Option<LibraryState>,
+    rx: &mpsc::Receiver<AppEvent>,
 ) -> Result<()> {
     loop {
         // Tick and draw
@@ -100,60 +242,175 @@ fn run_app_loop(
             }
         }
 
-        // Poll for events
-        let poll_duration = match view {
-            AppView::Library(_) => Duration::from_millis(200),
-            AppView::Editor(_) => Duration::from_millis(50),
+        let Ok(event) = rx.recv() else {
+            // Every producer thread is gone; nothing left to drive the loop.
+            break;
         };
 
-        if event::poll(poll_duration)? {
-            if let Event::Key(key) = event::read()? {
-                match view {
-                    AppView::Library(ref mut lib) => match lib.handle_key(key) {
-                        LibraryAction::OpenEditor(path, agent) => {
-                            match load_session_for_editor(&path, agent) {
-                                Ok(spool_file) => {
-                                    let mut editor = EditorState::new(spool_file, path, 1.0);
-                                    editor.has_library = true;
-                                    editor.start_playing();
-
-                                    // Save library state
-                                    let old_view =
-                                        std::mem::replace(view, AppView::Editor(Box::new(editor)));
-                                    if let AppView::Library(lib_state) = old_view {
-                                        *saved_library = Some(lib_state);
-                                    }
-                                }
-                                Err(e) => {
-                                    lib.set_status(format!("Failed to open: {}", e));
+        match event {
+            AppEvent::Tick => {}
+            AppEvent::Resize(_, _) => {
+                // Terminal already reports its new size on the next draw;
+                // this just wakes the loop up promptly instead of waiting
+                // for the tick.
+            }
+            AppEvent::FsChange => {
+                if let AppView::Library(ref mut lib) = view {
+                    lib.refresh_sessions();
+                }
+            }
+            AppEvent::ContentIndexBuilt(cache) => {
+                if let AppView::Library(ref mut lib) = view {
+                    lib.apply_content_index(cache);
+                }
+            }
+            AppEvent::LibrarySearchStarted(started) => {
+                if let AppView::Library(ref mut lib) = view {
+                    lib.apply_library_search_started(started);
+                }
+            }
+            AppEvent::LibrarySearchHit(search_id, hit) => {
+                if let AppView::Library(ref mut lib) = view {
+                    lib.push_library_search_hit(search_id, hit);
+                }
+            }
+            AppEvent::LibrarySearchDone(search_id) => {
+                if let AppView::Library(ref mut lib) = view {
+                    lib.finish_library_search(search_id);
+                }
+            }
+            AppEvent::Key(key) => match view {
+                AppView::Library(ref mut lib) => match lib.handle_key(key) {
+                    LibraryAction::OpenEditor(path, agent) => {
+                        match load_session_for_editor(&path, agent) {
+                            Ok(spool_file) => {
+                                let mut editor = EditorState::new(spool_file, path, 1.0);
+                                editor.has_library = true;
+                                editor.start_playing();
+
+                                // Save library state
+                                let old_view =
+                                    std::mem::replace(view, AppView::Editor(Box::new(editor)));
+                                if let AppView::Library(lib_state) = old_view {
+                                    *saved_library = Some(lib_state);
                                 }
                             }
+                            Err(e) => {
+                                lib.set_status(format!("Failed to open: {}", e));
+                            }
                         }
-                        LibraryAction::Quit => break,
-                        LibraryAction::None => {}
-                    },
-                    AppView::Editor(ref mut ed) => match ed.handle_key(key) {
-                        EditorAction::Back => {
-                            // Restore library state
-                            if let Some(lib_state) = saved_library.take() {
-                                *view = AppView::Library(lib_state);
-                            } else {
-                                break;
+                    }
+                    LibraryAction::OpenEditorAt(path, entry_index) => {
+                        match spool_format::SpoolFile::from_path(&path) {
+                            Ok(spool_file) => {
+                                let mut editor = EditorState::new(spool_file, path, 1.0);
+                                editor.has_library = true;
+                                editor.reveal_entry(entry_index);
+
+                                let old_view =
+                                    std::mem::replace(view, AppView::Editor(Box::new(editor)));
+                                if let AppView::Library(lib_state) = old_view {
+                                    *saved_library = Some(lib_state);
+                                }
+                            }
+                            Err(e) => {
+                                lib.set_status(format!("Failed to open: {}", e));
                             }
                         }
-                        EditorAction::Quit => break,
-                        EditorAction::None => {}
-                    },
-                }
-            }
+                    }
+                    LibraryAction::ExportMarked(sessions, dir) => {
+                        let total = sessions.len();
+                        let exported = export_marked_sessions(&sessions, &dir);
+                        lib.set_status(format!("Exported {} of {} session(s)", exported, total));
+                        lib.clear_marks();
+                    }
+                    LibraryAction::AnnotateMarked(sessions, note) => {
+                        let total = sessions.len();
+                        let annotated = annotate_marked_sessions(&sessions, &note);
+                        lib.set_status(format!("Annotated {} of {} session(s)", annotated, total));
+                        lib.clear_marks();
+                    }
+                    LibraryAction::Quit => break,
+                    LibraryAction::None => {}
+                },
+                AppView::Editor(ref mut ed) => match ed.handle_key(key) {
+                    EditorAction::Back => {
+                        // Restore library state
+                        if let Some(lib_state) = saved_library.take() {
+                            *view = AppView::Library(lib_state);
+                        } else {
+                            break;
+                        }
+                    }
+                    EditorAction::Quit => break,
+                    EditorAction::None => {}
+                },
+            },
         }
     }
 
     Ok(())
 }
 
+/// Convert and write each marked session as a `.spool` file into `dir`.
+/// Returns how many succeeded; failures are skipped rather than aborting
+/// the whole batch, since one bad session shouldn't block the rest.
+fn export_marked_sessions(sessions: &[spool_adapters::SessionInfo], dir: &std::path::Path) -> usize {
+    let mut exported = 0;
+    for session in sessions {
+        let Ok(spool_file) = library::convert_session(session) else {
+            continue;
+        };
+        let stem = session
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "session".to_string());
+        let output = dir.join(format!("{}.spool", stem));
+        if spool_file.write_to_path(&output).is_ok() {
+            exported += 1;
+        }
+    }
+    exported
+}
+
+/// Convert each marked session, append an annotation on its session entry,
+/// and re-export it alongside the source file. Returns how many succeeded.
+fn annotate_marked_sessions(sessions: &[spool_adapters::SessionInfo], note: &str) -> usize {
+    let mut annotated = 0;
+    for session in sessions {
+        let Ok(mut spool_file) = library::convert_session(session) else {
+            continue;
+        };
+        let annotation = spool_format::AnnotationEntry {
+            id: uuid::Uuid::new_v4(),
+            ts: 0,
+            target_id: spool_file.session.id,
+            content: note.to_string(),
+            author: None,
+            style: None,
+            created_at: Some(chrono::Utc::now()),
+            extra: HashMap::new(),
+        };
+        spool_file
+            .entries
+            .insert(0, spool_format::Entry::Annotation(annotation));
+
+        let stem = session
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "session".to_string());
+        let output = session.path.with_file_name(format!("{}.spool", stem));
+        if spool_file.write_to_path(&output).is_ok() {
+            annotated += 1;
+        }
+    }
+    annotated
+}
+
 fn load_session_for_editor(path: &PathBuf, agent: AgentType) -> Result<spool_format::SpoolFile> {
-    use spool_adapters::{claude_code, codex, SessionInfo};
+    use spool_adapters::{aichat, claude_code, codex, SessionInfo};
 
     if path.extension().map(|e| e == "spool").unwrap_or(false) {
         return Ok(spool_format::SpoolFile::from_path(path)?);
@@ -172,6 +429,7 @@ fn load_session_for_editor(path: &PathBuf, agent: AgentType) -> Result<spool_for
     match agent {
         AgentType::ClaudeCode => claude_code::convert(&session_info),
         AgentType::Codex => codex::convert(&session_info),
+        AgentType::Aichat => aichat::convert(&session_info),
         _ => anyhow::bail!("Unsupported agent: {}", agent.as_str()),
     }
 }
@@ -0,0 +1,261 @@
+//! Terminal-emulator replay of raw CLI tool output for the Editor view.
+//!
+//! [`crate::ansi::parse_ansi_spans`] treats escape-laden text as a flat,
+//! append-only stream: fine for `spool view`'s one-shot dump, but it renders
+//! captured `\r`-driven progress bars and cursor-repositioning output as
+//! scrolling garbage rather than the single updating line a real terminal
+//! would show. [`render_vt`] instead feeds the text through a small VT grid
+//! (cursor + cells), the same approach nbsh's `pty`/`vt` modules use to turn
+//! a PTY's byte stream into a snapshot-able screen, so a `ToolResult`'s raw
+//! output can be replayed the way it actually looked.
+//!
+//! The grid is fixed-width (wrapping at the caller's `width`) but grows
+//! rows on demand rather than being a bounded viewport — this is a one-shot
+//! replay of already-captured text, not a live PTY with a scrollback limit,
+//! so there's no reason to discard rows.
+
+use ratatui::style::Style;
+
+use crate::ansi::SgrState;
+
+type Cell = (char, Style);
+
+fn blank_cell() -> Cell {
+    (' ', Style::default())
+}
+
+struct VtGrid {
+    width: usize,
+    rows: Vec<Vec<Cell>>,
+    cursor_row: usize,
+    cursor_col: usize,
+    sgr: SgrState,
+}
+
+impl VtGrid {
+    fn new(width: usize) -> Self {
+        let width = width.max(1);
+        VtGrid {
+            width,
+            rows: vec![vec![blank_cell(); width]],
+            cursor_row: 0,
+            cursor_col: 0,
+            sgr: SgrState::default(),
+        }
+    }
+
+    fn ensure_row(&mut self, row: usize) {
+        while self.rows.len() <= row {
+            self.rows.push(vec![blank_cell(); self.width]);
+        }
+    }
+
+    fn put_char(&mut self, c: char) {
+        if self.cursor_col >= self.width {
+            self.cursor_col = 0;
+            self.cursor_row += 1;
+        }
+        self.ensure_row(self.cursor_row);
+        self.rows[self.cursor_row][self.cursor_col] = (c, self.sgr.to_style());
+        self.cursor_col += 1;
+    }
+
+    fn carriage_return(&mut self) {
+        self.cursor_col = 0;
+    }
+
+    fn line_feed(&mut self) {
+        self.cursor_row += 1;
+        self.ensure_row(self.cursor_row);
+    }
+
+    fn backspace(&mut self) {
+        self.cursor_col = self.cursor_col.saturating_sub(1);
+    }
+
+    /// `ESC [ <mode> K` - erase in line. `0` (default): cursor to end. `1`:
+    /// start to cursor. `2`: whole line.
+    fn erase_in_line(&mut self, mode: u32) {
+        self.ensure_row(self.cursor_row);
+        let col = self.cursor_col.min(self.width);
+        let row = &mut self.rows[self.cursor_row];
+        match mode {
+            1 => row[..col].fill(blank_cell()),
+            2 => row.fill(blank_cell()),
+            _ => row[col..].fill(blank_cell()),
+        }
+    }
+
+    /// `ESC [ <mode> J` - erase in display. `0` (default): cursor to end of
+    /// screen. `1`: start of screen to cursor. `2`/`3`: entire screen.
+    fn erase_in_display(&mut self, mode: u32) {
+        match mode {
+            2 | 3 => {
+                for row in &mut self.rows {
+                    row.fill(blank_cell());
+                }
+            }
+            1 => {
+                for row in self.rows.iter_mut().take(self.cursor_row) {
+                    row.fill(blank_cell());
+                }
+                self.erase_in_line(1);
+            }
+            _ => {
+                self.erase_in_line(0);
+                for row in self.rows.iter_mut().skip(self.cursor_row + 1) {
+                    row.fill(blank_cell());
+                }
+            }
+        }
+    }
+
+    /// `ESC [ <row> ; <col> H` / `f` - cursor position, 1-indexed with both
+    /// params defaulting to `1`.
+    fn cursor_position(&mut self, row: u32, col: u32) {
+        self.cursor_row = row.saturating_sub(1) as usize;
+        self.cursor_col = col.saturating_sub(1) as usize;
+        self.ensure_row(self.cursor_row);
+    }
+
+    fn feed(&mut self, text: &str) {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' => self.carriage_return(),
+                '\n' => self.line_feed(),
+                '\x08' => self.backspace(),
+                '\x1b' if chars.peek() == Some(&'[') => {
+                    chars.next(); // consume '['
+                    let mut raw = String::new();
+                    let mut final_byte = None;
+                    for c2 in chars.by_ref() {
+                        if c2.is_ascii_alphabetic() {
+                            final_byte = Some(c2);
+                            break;
+                        }
+                        raw.push(c2);
+                    }
+                    let params: Vec<u32> =
+                        raw.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+                    match final_byte {
+                        Some('m') => {
+                            let params = if raw.is_empty() { vec![0] } else { params };
+                            self.sgr.apply_sgr(&params);
+                        }
+                        Some('H') | Some('f') => {
+                            let row = params.first().copied().unwrap_or(1).max(1);
+                            let col = params.get(1).copied().unwrap_or(1).max(1);
+                            self.cursor_position(row, col);
+                        }
+                        Some('K') => self.erase_in_line(params.first().copied().unwrap_or(0)),
+                        Some('J') => self.erase_in_display(params.first().copied().unwrap_or(0)),
+                        // Other CSI sequences (cursor up/down/forward, scroll
+                        // regions, ...) don't affect a replayed snapshot's
+                        // final contents enough to justify implementing;
+                        // just consumed above and otherwise ignored.
+                        _ => {}
+                    }
+                }
+                other => self.put_char(other),
+            }
+        }
+    }
+
+    /// Snapshot the grid into one styled span list per row, trimming
+    /// trailing blank cells and coalescing consecutive same-style cells.
+    fn snapshot(&self) -> Vec<Vec<(Style, String)>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut end = row.len();
+                while end > 0 && row[end - 1] == blank_cell() {
+                    end -= 1;
+                }
+                let mut spans: Vec<(Style, String)> = Vec::new();
+                for &(ch, style) in &row[..end] {
+                    match spans.last_mut() {
+                        Some((last_style, text)) if *last_style == style => text.push(ch),
+                        _ => spans.push((style, ch.to_string())),
+                    }
+                }
+                spans
+            })
+            .collect()
+    }
+}
+
+/// Replay `text` through a VT grid `width` cells wide and snapshot the
+/// result as one styled span list per row - the VT-emulated analog of
+/// [`crate::ansi::parse_ansi_lines`]. `\r`-terminated progress-bar updates
+/// overwrite the same row instead of scrolling, `\x1b[K`/`\x1b[2J` erase
+/// in place, and `\x1b[H`-style cursor moves reposition future writes.
+pub fn render_vt(text: &str, width: usize) -> Vec<Vec<(Style, String)>> {
+    let mut grid = VtGrid::new(width);
+    grid.feed(text);
+    grid.snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn line_text(lines: &[Vec<(Style, String)>], row: usize) -> String {
+        lines[row].iter().map(|(_, s)| s.as_str()).collect()
+    }
+
+    #[test]
+    fn render_vt_passes_through_plain_multiline_text() {
+        let lines = render_vt("hello\nworld", 80);
+        assert_eq!(line_text(&lines, 0), "hello");
+        assert_eq!(line_text(&lines, 1), "world");
+    }
+
+    #[test]
+    fn render_vt_collapses_carriage_return_progress_updates() {
+        // Three \r-terminated updates on the same line, each padded with
+        // enough trailing content (via \x1b[K) that the final frame doesn't
+        // show leftovers from a longer earlier update.
+        let lines = render_vt("10%\r\x1b[K50%\r\x1b[K100%", 80);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(line_text(&lines, 0), "100%");
+    }
+
+    #[test]
+    fn render_vt_erase_in_line_default_clears_from_cursor() {
+        let lines = render_vt("hello\r\x1b[Khi", 80);
+        assert_eq!(line_text(&lines, 0), "hi");
+    }
+
+    #[test]
+    fn render_vt_erase_in_display_clears_whole_screen() {
+        let lines = render_vt("first\nsecond\x1b[2J", 80);
+        assert!(lines.iter().all(|l| l.is_empty()));
+    }
+
+    #[test]
+    fn render_vt_cursor_position_overwrites_in_place() {
+        let lines = render_vt("abcdef\x1b[1;1Hxy", 80);
+        assert_eq!(line_text(&lines, 0), "xydef");
+    }
+
+    #[test]
+    fn render_vt_backspace_moves_cursor_back_for_overwrite() {
+        let lines = render_vt("abc\x08\x08x", 80);
+        assert_eq!(line_text(&lines, 0), "axc");
+    }
+
+    #[test]
+    fn render_vt_applies_sgr_color_through_grid() {
+        let lines = render_vt("\x1b[31mred\x1b[0m", 80);
+        assert_eq!(lines[0], vec![(Style::default().fg(Color::Red), "red".to_string())]);
+    }
+
+    #[test]
+    fn render_vt_wraps_at_width() {
+        let lines = render_vt("abcdef", 3);
+        assert_eq!(line_text(&lines, 0), "abc");
+        assert_eq!(line_text(&lines, 1), "def");
+    }
+}
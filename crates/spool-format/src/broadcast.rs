@@ -0,0 +1,226 @@
+//! Live publish/subscribe of a session being recorded.
+//!
+//! Modeled on pub/sub media tracks: as entries are appended (the
+//! [`crate::SpoolFile::add_entry`] path), [`Broadcaster::publish`] tags each
+//! one with a sequence number and a `playback_ms` compressed the same way
+//! `spool-cli`'s local editor timeline is (idle gaps before a `Prompt`
+//! clamped to [`MAX_IDLE_GAP_MS`], thinking spans clamped to
+//! [`MAX_THINKING_MS`]), then forwards it to every subscribed [`Transport`].
+//! A late-joining subscriber calls [`Broadcaster::backfill_from`] for
+//! everything it missed before switching to live follow, so it ends up with
+//! the same compressed replay a local viewer would see.
+//!
+//! This crate ships no concrete transport (spool is a single-process CLI
+//! today); [`Transport`] is the extension point a server process would wire
+//! a websocket/SSE connection into.
+
+use crate::{Entry, SpoolResult};
+
+/// Gap before a `Prompt` longer than this is compressed down to it, matching
+/// `spool-cli`'s local timeline so a remote viewer's replay looks the same.
+const MAX_IDLE_GAP_MS: u64 = 2_000;
+
+/// Gap after a `Thinking` entry longer than this is compressed down to it,
+/// matching `spool-cli`'s local timeline.
+const MAX_THINKING_MS: u64 = 2_000;
+
+/// One published update: an appended [`Entry`] tagged with its sequence
+/// number and its compressed `playback_ms`.
+#[derive(Debug, Clone)]
+pub struct BroadcastEntry {
+    /// Position in publish order, starting at 0. Stable once assigned.
+    pub seq: u64,
+    /// The entry as appended to the session.
+    pub entry: Entry,
+    /// Compressed playback offset, in the same units and with the same
+    /// idle/thinking clamping as `spool-cli`'s local timeline.
+    pub playback_ms: u64,
+}
+
+/// Where a [`Broadcaster`] forwards published entries, for a remote viewer
+/// to receive.
+pub trait Transport {
+    /// Send one entry downstream, in publish order.
+    fn send(&mut self, update: &BroadcastEntry) -> SpoolResult<()>;
+}
+
+/// Publishes entries as they're appended to a recording session, keeping a
+/// full log so subscribers can backfill from any earlier point.
+pub struct Broadcaster {
+    log: Vec<BroadcastEntry>,
+    subscribers: Vec<Box<dyn Transport>>,
+    compressed_time: u64,
+    prev_original_ts: u64,
+}
+
+impl Broadcaster {
+    /// Create a broadcaster with no subscribers and an empty log.
+    pub fn new() -> Self {
+        Self {
+            log: Vec::new(),
+            subscribers: Vec::new(),
+            compressed_time: 0,
+            prev_original_ts: 0,
+        }
+    }
+
+    /// Attach a transport that receives every subsequent [`publish`](Self::publish).
+    /// It does not receive entries published before it subscribed - call
+    /// [`send_backfill`](Self::send_backfill) first to catch it up.
+    pub fn subscribe(&mut self, transport: Box<dyn Transport>) {
+        self.subscribers.push(transport);
+    }
+
+    /// Publish a newly-appended entry: compute its compressed `playback_ms`
+    /// from the running clock kept across calls, append it to the log, and
+    /// forward it to every subscribed transport.
+    pub fn publish(&mut self, entry: Entry) -> SpoolResult<BroadcastEntry> {
+        let original_ts = entry.timestamp().unwrap_or(0);
+        let raw_gap = original_ts.saturating_sub(self.prev_original_ts);
+
+        let compressed_gap = if self.log.is_empty() {
+            0
+        } else {
+            let mut gap = raw_gap;
+            if matches!(entry, Entry::Prompt(_)) && gap > MAX_IDLE_GAP_MS {
+                gap = MAX_IDLE_GAP_MS;
+            }
+            if let Some(prev) = self.log.last() {
+                if matches!(prev.entry, Entry::Thinking(_)) && gap > MAX_THINKING_MS {
+                    gap = MAX_THINKING_MS;
+                }
+            }
+            gap
+        };
+        self.compressed_time += compressed_gap;
+        self.prev_original_ts = original_ts;
+
+        let update = BroadcastEntry {
+            seq: self.log.len() as u64,
+            entry,
+            playback_ms: self.compressed_time,
+        };
+        self.log.push(update.clone());
+
+        for subscriber in &mut self.subscribers {
+            subscriber.send(&update)?;
+        }
+
+        Ok(update)
+    }
+
+    /// The sequence number the next published entry will receive. A
+    /// subscriber compares this against its own high-water mark to know
+    /// whether it's caught up to the live edge.
+    pub fn live_seq(&self) -> u64 {
+        self.log.len() as u64
+    }
+
+    /// Every published entry with sequence number `>= from_seq`, for a
+    /// subscriber catching up after missing some live updates (or a late
+    /// joiner passing 0).
+    pub fn backfill_from(&self, from_seq: u64) -> &[BroadcastEntry] {
+        let start = from_seq.min(self.log.len() as u64) as usize;
+        &self.log[start..]
+    }
+
+    /// Send everything from `from_seq` onward to `transport` directly,
+    /// without registering it as a subscriber.
+    pub fn send_backfill(&self, transport: &mut dyn Transport, from_seq: u64) -> SpoolResult<()> {
+        for update in self.backfill_from(from_seq) {
+            transport.send(update)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Broadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PromptEntry;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn prompt(ts: u64, content: &str) -> Entry {
+        Entry::Prompt(PromptEntry {
+            id: Uuid::new_v4(),
+            ts,
+            content: content.to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        received: Vec<u64>,
+    }
+
+    impl Transport for RecordingTransport {
+        fn send(&mut self, update: &BroadcastEntry) -> SpoolResult<()> {
+            self.received.push(update.seq);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn publish_assigns_sequential_seq_numbers() {
+        let mut broadcaster = Broadcaster::new();
+        let first = broadcaster.publish(prompt(0, "hi")).unwrap();
+        let second = broadcaster.publish(prompt(100, "again")).unwrap();
+        assert_eq!(first.seq, 0);
+        assert_eq!(second.seq, 1);
+        assert_eq!(broadcaster.live_seq(), 2);
+    }
+
+    #[test]
+    fn publish_compresses_idle_gaps_before_a_prompt() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.publish(prompt(0, "hi")).unwrap();
+        let second = broadcaster.publish(prompt(31_000, "later")).unwrap();
+        assert_eq!(second.playback_ms, MAX_IDLE_GAP_MS);
+    }
+
+    #[test]
+    fn subscriber_receives_only_entries_published_after_subscribing() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.publish(prompt(0, "before")).unwrap();
+
+        let mut transport = Box::new(RecordingTransport::default());
+        // Back-fill first, then subscribe, mirroring a real late joiner.
+        broadcaster
+            .send_backfill(transport.as_mut(), 0)
+            .unwrap();
+        broadcaster.subscribe(transport);
+
+        broadcaster.publish(prompt(1000, "after")).unwrap();
+        assert_eq!(broadcaster.live_seq(), 2);
+    }
+
+    #[test]
+    fn backfill_from_returns_entries_at_or_after_seq() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.publish(prompt(0, "a")).unwrap();
+        broadcaster.publish(prompt(1000, "b")).unwrap();
+        broadcaster.publish(prompt(2000, "c")).unwrap();
+
+        let backfill = broadcaster.backfill_from(1);
+        assert_eq!(backfill.len(), 2);
+        assert_eq!(backfill[0].seq, 1);
+        assert_eq!(backfill[1].seq, 2);
+    }
+
+    #[test]
+    fn backfill_from_beyond_the_log_is_empty() {
+        let mut broadcaster = Broadcaster::new();
+        broadcaster.publish(prompt(0, "a")).unwrap();
+        assert!(broadcaster.backfill_from(99).is_empty());
+    }
+}
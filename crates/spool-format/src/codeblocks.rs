@@ -0,0 +1,267 @@
+//! Fenced code block detection for prompt/response text.
+//!
+//! `ResponseEntry.content`/`PromptEntry.content` stay opaque markdown
+//! strings so existing renderers keep working unchanged; this module is an
+//! optional pass that records where the fenced code lives within that
+//! string so viewers and search/indexing can treat code separately from
+//! prose without reparsing markdown themselves.
+
+use crate::{Entry, SpoolFile};
+use serde::{Deserialize, Serialize};
+
+/// A fenced (` ``` `) code block found in an entry's text content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CodeBlock {
+    /// Byte range of the code (excluding the fence lines themselves)
+    /// within the entry's `content` string.
+    pub byte_range: (usize, usize),
+    /// The block's language, either taken from the fence's info string
+    /// (e.g. ` ```rust `) or guessed from the code's contents.
+    pub language: Option<String>,
+    /// `true` if `language` was guessed rather than taken from the fence.
+    pub guessed: bool,
+}
+
+/// Extract fenced code blocks from `text`.
+///
+/// Only well-formed ` ``` `-delimited blocks are recognized; an unclosed
+/// trailing fence is ignored rather than treated as extending to the end
+/// of the text, since that's far more often a truncated response than a
+/// real unterminated block.
+pub fn extract_code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(open_rel) = text[search_from..].find("```") {
+        let open_start = search_from + open_rel;
+        let info_start = open_start + 3;
+        let info_end = text[info_start..]
+            .find('\n')
+            .map(|i| info_start + i)
+            .unwrap_or(text.len());
+        let info = text[info_start..info_end].trim();
+
+        let code_start = (info_end + 1).min(text.len());
+        let Some(close_rel) = text[code_start..].find("```") else {
+            break;
+        };
+        let code_end = code_start + close_rel;
+        let fence_end = code_end + 3;
+
+        let language = if info.is_empty() {
+            guess_language(&text[code_start..code_end])
+        } else {
+            Some(info.to_string())
+        };
+
+        blocks.push(CodeBlock {
+            byte_range: (code_start, code_end),
+            guessed: info.is_empty() && language.is_some(),
+            language,
+        });
+
+        search_from = fence_end;
+    }
+
+    blocks
+}
+
+/// Run [`extract_code_blocks`] over every `Prompt`/`Response` entry in
+/// `file` and stash non-empty results in `extra["x_code_blocks"]`.
+pub fn annotate_code_blocks(file: &mut SpoolFile) {
+    for entry in &mut file.entries {
+        let content = match entry {
+            Entry::Prompt(p) => &p.content,
+            Entry::Response(r) => &r.content,
+            _ => continue,
+        };
+
+        let blocks = extract_code_blocks(content);
+        if blocks.is_empty() {
+            continue;
+        }
+
+        if let Ok(value) = serde_json::to_value(&blocks) {
+            if let Some(extra) = entry.extra_mut() {
+                extra.insert("x_code_blocks".to_string(), value);
+            }
+        }
+    }
+}
+
+/// Guess a fenced block's language from lightweight extension/keyword
+/// heuristics when the fence has no explicit info string.
+fn guess_language(code: &str) -> Option<String> {
+    let trimmed = code.trim_start();
+
+    if trimmed.starts_with("#!/usr/bin/env python") || trimmed.starts_with("#!/usr/bin/python") {
+        return Some("python".to_string());
+    }
+    if trimmed.starts_with("#!/bin/bash") || trimmed.starts_with("#!/bin/sh") {
+        return Some("bash".to_string());
+    }
+    if trimmed.starts_with("<?php") {
+        return Some("php".to_string());
+    }
+    if trimmed.starts_with("<!DOCTYPE html") || trimmed.starts_with("<html") {
+        return Some("html".to_string());
+    }
+    if trimmed.starts_with("package main") || trimmed.contains("func main()") {
+        return Some("go".to_string());
+    }
+
+    const RUST_MARKERS: &[&str] = &["fn main(", "let mut ", "impl ", "pub fn ", "pub struct"];
+    const PYTHON_MARKERS: &[&str] = &["def ", "import ", "elif ", "    self."];
+    const JS_MARKERS: &[&str] = &["function ", "const ", "=> {", "console.log("];
+    const TS_MARKERS: &[&str] = &["interface ", ": string", ": number", "export type "];
+    const CPP_MARKERS: &[&str] = &["#include <", "std::", "int main("];
+
+    if TS_MARKERS.iter().any(|m| code.contains(m)) {
+        return Some("typescript".to_string());
+    }
+    if RUST_MARKERS.iter().any(|m| code.contains(m)) {
+        return Some("rust".to_string());
+    }
+    if PYTHON_MARKERS.iter().any(|m| code.contains(m)) {
+        return Some("python".to_string());
+    }
+    if CPP_MARKERS.iter().any(|m| code.contains(m)) {
+        return Some("cpp".to_string());
+    }
+    if JS_MARKERS.iter().any(|m| code.contains(m)) {
+        return Some("javascript".to_string());
+    }
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(code).is_ok()
+    {
+        return Some("json".to_string());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Entry, PromptEntry, ResponseEntry, SessionEntry, SpoolFile};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn test_session() -> SessionEntry {
+        SessionEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            version: "1.0".to_string(),
+            agent: "test".to_string(),
+            recorded_at: Utc::now(),
+            agent_version: None,
+            title: None,
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn prompt_entry(content: &str) -> Entry {
+        Entry::Prompt(PromptEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            content: content.to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    fn response_entry(content: &str) -> Entry {
+        Entry::Response(ResponseEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            content: content.to_string(),
+            truncated: None,
+            original_bytes: None,
+            model: None,
+            token_usage: None,
+            subagent_id: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_extract_explicit_language() {
+        let text = "Here you go:\n```rust\nfn main() {}\n```\nDone.";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert!(!blocks[0].guessed);
+        assert_eq!(&text[blocks[0].byte_range.0..blocks[0].byte_range.1], "fn main() {}\n");
+    }
+
+    #[test]
+    fn test_extract_guesses_language_when_absent() {
+        let text = "```\ndef hello():\n    return 1\n```";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+        assert!(blocks[0].guessed);
+    }
+
+    #[test]
+    fn test_extract_multiple_blocks() {
+        let text = "```python\nprint(1)\n```\ntext\n```js\nconsole.log(1)\n```";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+        assert_eq!(blocks[1].language.as_deref(), Some("js"));
+    }
+
+    #[test]
+    fn test_extract_no_blocks() {
+        assert!(extract_code_blocks("just prose, no fences here").is_empty());
+    }
+
+    #[test]
+    fn test_extract_ignores_unclosed_fence() {
+        let text = "some text\n```rust\nfn main() {}\n";
+        assert!(extract_code_blocks(text).is_empty());
+    }
+
+    #[test]
+    fn test_extract_unknown_language_when_unrecognized() {
+        let text = "```\nxyzzy plugh\n```";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language, None);
+        assert!(!blocks[0].guessed);
+    }
+
+    #[test]
+    fn test_annotate_sets_extra_on_prompt_and_response() {
+        let mut file = SpoolFile::new(test_session());
+        file.entries.push(prompt_entry("```rust\nfn a() {}\n```"));
+        file.entries.push(response_entry("no code here"));
+        file.entries
+            .push(response_entry("```python\nimport os\n```"));
+        file.entry_spans.resize(file.entries.len(), None);
+
+        annotate_code_blocks(&mut file);
+
+        assert!(file.entries[1].extra().unwrap().contains_key("x_code_blocks"));
+        assert!(!file.entries[2].extra().unwrap().contains_key("x_code_blocks"));
+        assert!(file.entries[3].extra().unwrap().contains_key("x_code_blocks"));
+    }
+}
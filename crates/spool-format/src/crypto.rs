@@ -0,0 +1,225 @@
+//! Encryption-at-rest for `.spool` files (the `.spool.enc` container).
+//!
+//! A session's content can stay sensitive even after [`crate::SecretDetector`]
+//! redaction (the shape of a conversation is itself information), so this
+//! gives callers a way to archive a `SpoolFile` under a passphrase instead of
+//! plaintext JSONL. Layout, in order: an 8-byte magic, a version byte, the
+//! Argon2id salt and params, the AEAD nonce, then the XChaCha20-Poly1305
+//! ciphertext - a deliberately simple container in the spirit of
+//! Aerogramme's cryptoblob layer, not a general-purpose envelope format.
+
+use crate::{SpoolError, SpoolFile, SpoolResult};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+/// Identifies an encrypted container up front, so callers can sniff the
+/// first bytes rather than relying solely on the `.spool.enc` extension.
+pub const MAGIC: &[u8; 8] = b"SPOOLENC";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Argon2id cost parameters, stored in the container header rather than
+/// hardcoded so a future default can change without breaking old files -
+/// the header from whichever file is being decrypted is always what's used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct KdfParams {
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+impl Default for KdfParams {
+    /// OWASP's current minimum recommendation for Argon2id: 19 MiB, 2
+    /// passes, 1 degree of parallelism.
+    fn default() -> Self {
+        Self {
+            m_cost: 19_456,
+            t_cost: 2,
+            p_cost: 1,
+        }
+    }
+}
+
+/// Returns `true` if `data` starts with the encrypted-container magic.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: KdfParams) -> SpoolResult<[u8; KEY_LEN]> {
+    let argon2 = Argon2::new(
+        Algorithm::Argon2id,
+        Version::V0x13,
+        Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_LEN))
+            .map_err(|e| SpoolError::Crypto(format!("invalid KDF params: {e}")))?,
+    );
+    let mut key = [0u8; KEY_LEN];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| SpoolError::Crypto(format!("key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+/// Serialize `file` and encrypt it under `passphrase`, returning the full
+/// container (header + ciphertext) ready to write to a `.spool.enc` file.
+pub fn encrypt_spool_file(file: &SpoolFile, passphrase: &str) -> SpoolResult<Vec<u8>> {
+    let mut plaintext = Vec::new();
+    file.write_to(&mut plaintext)?;
+    encrypt_bytes(&plaintext, passphrase)
+}
+
+/// Encrypt arbitrary plaintext bytes into a container. Split out from
+/// [`encrypt_spool_file`] so the container format can be tested directly.
+pub fn encrypt_bytes(plaintext: &[u8], passphrase: &str) -> SpoolResult<Vec<u8>> {
+    let params = KdfParams::default();
+
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt, params)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XNonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| SpoolError::Crypto(format!("encryption failed: {e}")))?;
+
+    let mut out = Vec::with_capacity(8 + 1 + SALT_LEN + NONCE_LEN + 12 + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a container produced by [`encrypt_spool_file`] and parse the
+/// result as a [`SpoolFile`].
+pub fn decrypt_spool_file(data: &[u8], passphrase: &str) -> SpoolResult<SpoolFile> {
+    let plaintext = decrypt_bytes(data, passphrase)?;
+    SpoolFile::from_reader(plaintext.as_slice())
+}
+
+/// Decrypt a container back to its plaintext bytes.
+pub fn decrypt_bytes(data: &[u8], passphrase: &str) -> SpoolResult<Vec<u8>> {
+    const HEADER_LEN: usize = 8 + 1 + SALT_LEN + NONCE_LEN + 12;
+    if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+        return Err(SpoolError::Crypto(
+            "not a spool encrypted container".to_string(),
+        ));
+    }
+
+    let mut offset = MAGIC.len();
+    let version = data[offset];
+    offset += 1;
+    if version != VERSION {
+        return Err(SpoolError::Crypto(format!(
+            "unsupported container version: {version}"
+        )));
+    }
+
+    let salt = &data[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce_bytes = &data[offset..offset + NONCE_LEN];
+    offset += NONCE_LEN;
+    let m_cost = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let t_cost = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let p_cost = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let ciphertext = &data[offset..];
+
+    let params = KdfParams {
+        m_cost,
+        t_cost,
+        p_cost,
+    };
+    let key = derive_key(passphrase, salt, params)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XNonce::from_slice(nonce_bytes);
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| SpoolError::Crypto("decryption failed (wrong passphrase?)".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionEntry;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn sample_file() -> SpoolFile {
+        SpoolFile::new(SessionEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            version: "1.0".to_string(),
+            agent: "test".to_string(),
+            recorded_at: chrono::Utc::now(),
+            agent_version: None,
+            title: Some("Test Session".to_string()),
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_is_encrypted_detects_magic() {
+        let blob = encrypt_bytes(b"hello", "correct horse").unwrap();
+        assert!(is_encrypted(&blob));
+        assert!(!is_encrypted(b"not encrypted"));
+    }
+
+    #[test]
+    fn test_roundtrip_with_correct_passphrase() {
+        let blob = encrypt_bytes(b"some secret transcript", "hunter2").unwrap();
+        let plaintext = decrypt_bytes(&blob, "hunter2").unwrap();
+        assert_eq!(plaintext, b"some secret transcript");
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails_to_decrypt() {
+        let blob = encrypt_bytes(b"some secret transcript", "hunter2").unwrap();
+        let err = decrypt_bytes(&blob, "wrong passphrase").unwrap_err();
+        assert!(matches!(err, SpoolError::Crypto(_)));
+    }
+
+    #[test]
+    fn test_spool_file_roundtrips_through_encryption() {
+        let file = sample_file();
+        let blob = encrypt_spool_file(&file, "hunter2").unwrap();
+        let decrypted = decrypt_spool_file(&blob, "hunter2").unwrap();
+        assert_eq!(decrypted.entries.len(), file.entries.len());
+        assert_eq!(decrypted.session.id, file.session.id);
+    }
+
+    #[test]
+    fn test_corrupted_ciphertext_is_rejected() {
+        let mut blob = encrypt_bytes(b"some secret transcript", "hunter2").unwrap();
+        let last = blob.len() - 1;
+        blob[last] ^= 0xFF;
+        assert!(decrypt_bytes(&blob, "hunter2").is_err());
+    }
+}
@@ -62,6 +62,9 @@ pub enum Entry {
     Annotation(AnnotationEntry),
     /// Marker indicating redaction occurred
     RedactionMarker(RedactionMarkerEntry),
+    /// Raw PTY/terminal output, captured as a sequence of timestamped byte
+    /// frames rather than a single structured text field
+    Terminal(TerminalEntry),
     /// Unknown entry type (for forward compatibility)
     #[serde(other)]
     #[schemars(skip)]
@@ -83,6 +86,7 @@ impl Entry {
             Entry::SubagentEnd(e) => Some(&e.id),
             Entry::Annotation(e) => Some(&e.id),
             Entry::RedactionMarker(e) => Some(&e.id),
+            Entry::Terminal(e) => Some(&e.id),
             Entry::Unknown => None,
         }
     }
@@ -101,6 +105,70 @@ impl Entry {
             Entry::SubagentEnd(e) => Some(e.ts),
             Entry::Annotation(e) => Some(e.ts),
             Entry::RedactionMarker(e) => Some(e.ts),
+            Entry::Terminal(e) => Some(e.ts),
+            Entry::Unknown => None,
+        }
+    }
+
+    /// Get the subagent that owns this entry, for entry types that can be
+    /// attributed to one (see [`crate::SpoolFile::subagent_tree`]).
+    pub fn subagent_id(&self) -> Option<EntryId> {
+        match self {
+            Entry::Prompt(e) => e.subagent_id,
+            Entry::Thinking(e) => e.subagent_id,
+            Entry::ToolCall(e) => e.subagent_id,
+            Entry::ToolResult(e) => e.subagent_id,
+            Entry::Response(e) => e.subagent_id,
+            Entry::Error(e) => e.subagent_id,
+            Entry::Terminal(e) => e.subagent_id,
+            Entry::Session(_)
+            | Entry::SubagentStart(_)
+            | Entry::SubagentEnd(_)
+            | Entry::Annotation(_)
+            | Entry::RedactionMarker(_)
+            | Entry::Unknown => None,
+        }
+    }
+
+    /// Get the entry's extension fields (the `x_`-prefixed bag every variant
+    /// carries for forward compatibility). `Unknown` entries have no
+    /// extension bag of their own.
+    pub fn extra(&self) -> Option<&HashMap<String, serde_json::Value>> {
+        match self {
+            Entry::Session(e) => Some(&e.extra),
+            Entry::Prompt(e) => Some(&e.extra),
+            Entry::Thinking(e) => Some(&e.extra),
+            Entry::ToolCall(e) => Some(&e.extra),
+            Entry::ToolResult(e) => Some(&e.extra),
+            Entry::Response(e) => Some(&e.extra),
+            Entry::Error(e) => Some(&e.extra),
+            Entry::SubagentStart(e) => Some(&e.extra),
+            Entry::SubagentEnd(e) => Some(&e.extra),
+            Entry::Annotation(e) => Some(&e.extra),
+            Entry::RedactionMarker(e) => Some(&e.extra),
+            Entry::Terminal(e) => Some(&e.extra),
+            Entry::Unknown => None,
+        }
+    }
+
+    /// Get a mutable reference to the entry's extension fields, for code
+    /// like [`crate::seal_integrity`] that stamps values such as
+    /// `x_content_hash` into existing entries. `Unknown` entries have no
+    /// extension bag.
+    pub fn extra_mut(&mut self) -> Option<&mut HashMap<String, serde_json::Value>> {
+        match self {
+            Entry::Session(e) => Some(&mut e.extra),
+            Entry::Prompt(e) => Some(&mut e.extra),
+            Entry::Thinking(e) => Some(&mut e.extra),
+            Entry::ToolCall(e) => Some(&mut e.extra),
+            Entry::ToolResult(e) => Some(&mut e.extra),
+            Entry::Response(e) => Some(&mut e.extra),
+            Entry::Error(e) => Some(&mut e.extra),
+            Entry::SubagentStart(e) => Some(&mut e.extra),
+            Entry::SubagentEnd(e) => Some(&mut e.extra),
+            Entry::Annotation(e) => Some(&mut e.extra),
+            Entry::RedactionMarker(e) => Some(&mut e.extra),
+            Entry::Terminal(e) => Some(&mut e.extra),
             Entry::Unknown => None,
         }
     }
@@ -145,6 +213,26 @@ pub struct SessionEntry {
     /// File paths modified during the session
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files_modified: Option<Vec<String>>,
+    /// Total time spent per tool (by tool name), in milliseconds, measured
+    /// from each `ToolCall`'s timestamp to its matching `ToolResult`'s.
+    /// Tool calls with no matching result contribute nothing, and
+    /// concurrent calls are measured independently, so these sums can
+    /// legitimately exceed `duration_ms`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_time_ms: Option<HashMap<String, u64>>,
+    /// Number of times each tool was invoked (by tool name).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_invocations: Option<HashMap<String, u64>>,
+    /// Total time spent per subagent, in milliseconds, keyed by the
+    /// subagent's `SubagentStart` id (as a string). Computed the same way
+    /// as `tool_time_ms`, attributed via each `ToolCall`'s `subagent_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subagent_time_ms: Option<HashMap<String, u64>>,
+    /// Per-path summary of line-level edits made during the session. The
+    /// deduped path list in `files_modified` stays available alongside
+    /// this for callers that just need "was this path touched".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_changes: Option<Vec<FileChangeSummary>>,
     /// First user prompt text (truncated), useful for browsing/indexing
     #[serde(skip_serializing_if = "Option::is_none")]
     pub first_prompt: Option<String>,
@@ -157,6 +245,11 @@ pub struct SessionEntry {
     /// How the session ended
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ended: Option<SessionEndState>,
+    /// Accumulated integrity hash over the whole entry chain, set by
+    /// [`crate::seal_integrity`]. See [`crate::verify_integrity`] for how
+    /// it's checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
 
     /// Extension fields (prefixed with x_)
     #[serde(flatten)]
@@ -164,6 +257,27 @@ pub struct SessionEntry {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+/// Aggregated line-level edit stats for a single file path, computed by
+/// summing every tool call that touched it over the course of a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct FileChangeSummary {
+    /// The path these stats are for.
+    pub path: String,
+    /// Number of tool calls that touched this path.
+    pub edits: u64,
+    /// Total lines added across all edits to this path.
+    pub lines_added: u64,
+    /// Total lines removed across all edits to this path.
+    pub lines_removed: u64,
+    /// Whether the first tool call seen for this path created it (a
+    /// `Write`-family call) as opposed to an `Edit`. `None` if that can't
+    /// be determined (e.g. the path was only ever edited, never written).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<bool>,
+    /// Timestamp of the last tool call that touched this path.
+    pub last_touched_ts: Timestamp,
+}
+
 /// Metadata about trimming
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct TrimmedMetadata {
@@ -181,7 +295,14 @@ pub enum SessionEndState {
     Cancelled,
     Error,
     Timeout,
+    /// The end state could not be determined (not the same as `Other`,
+    /// which preserves an end state this binary doesn't recognize at all).
     Unknown,
+    /// An end state not known to this version of `spool`, preserved
+    /// verbatim so older binaries can read, validate, and re-emit files
+    /// written by a newer one without data loss.
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// User prompt entry
@@ -217,6 +338,31 @@ pub struct Attachment {
     pub size_bytes: Option<usize>,
 }
 
+impl Attachment {
+    /// Build an attachment from raw bytes, canonicalizing to standard
+    /// padded base64 regardless of what alphabet a reader might later
+    /// need to tolerate on decode.
+    pub fn from_bytes(media_type: impl Into<String>, filename: Option<String>, data: &[u8]) -> Self {
+        use base64::Engine;
+        Attachment {
+            attachment_type: "binary".to_string(),
+            media_type: media_type.into(),
+            encoding: "base64".to_string(),
+            data: base64::engine::general_purpose::STANDARD.encode(data),
+            filename,
+            size_bytes: Some(data.len()),
+        }
+    }
+
+    /// Decode `data` back into raw bytes, tolerating whichever base64
+    /// variant the producing agent actually used (standard, URL-safe,
+    /// padded/unpadded, or MIME with embedded line breaks) rather than
+    /// assuming every producer emits standard padded base64.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        decode_base64_tolerant(&self.data)
+    }
+}
+
 /// Agent thinking entry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct ThinkingEntry {
@@ -302,6 +448,70 @@ pub struct BinaryContent {
     pub truncated: Option<bool>,
 }
 
+impl BinaryContent {
+    /// Build binary tool output from raw bytes, canonicalizing to
+    /// standard padded base64 regardless of what alphabet a reader might
+    /// later need to tolerate on decode.
+    pub fn from_bytes(media_type: impl Into<String>, data: &[u8]) -> Self {
+        use base64::Engine;
+        BinaryContent {
+            content_type: "binary".to_string(),
+            media_type: media_type.into(),
+            encoding: "base64".to_string(),
+            data: base64::engine::general_purpose::STANDARD.encode(data),
+            size_bytes: Some(data.len()),
+            filename: None,
+            truncated: None,
+        }
+    }
+
+    /// Decode `data` back into raw bytes, tolerating whichever base64
+    /// variant the producing agent actually used (standard, URL-safe,
+    /// padded/unpadded, or MIME with embedded line breaks) rather than
+    /// assuming every producer emits standard padded base64.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        decode_base64_tolerant(&self.data)
+    }
+}
+
+/// Try decoding `data` against, in order, standard, URL-safe (padded and
+/// unpadded), unpadded standard, and MIME (tolerating embedded
+/// whitespace/newlines) base64, returning the first alphabet that
+/// succeeds. Different agents encode inline attachments and binary tool
+/// output with different alphabets, so a single hard-coded engine drops
+/// payloads from anything that isn't standard padded base64.
+fn decode_base64_tolerant(data: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    use base64::engine::general_purpose::{
+        STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD,
+    };
+    use base64::engine::GeneralPurpose;
+    use base64::Engine;
+
+    let engines: [&GeneralPurpose; 4] = [&STANDARD, &URL_SAFE, &URL_SAFE_NO_PAD, &STANDARD_NO_PAD];
+
+    let mut last_err = None;
+    for engine in engines {
+        match engine.decode(data) {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    // MIME-style base64 may have embedded whitespace/newlines that none of
+    // the strict engines above tolerate; strip it and try once more.
+    let stripped: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    if stripped.len() != data.len() {
+        if let Ok(bytes) = STANDARD.decode(&stripped) {
+            return Ok(bytes);
+        }
+        if let Ok(bytes) = STANDARD_NO_PAD.decode(&stripped) {
+            return Ok(bytes);
+        }
+    }
+
+    Err(last_err.expect("at least one decode attempt was made"))
+}
+
 /// Inline redaction information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct RedactionInfo {
@@ -432,6 +642,22 @@ pub enum SubagentStatus {
     Completed,
     Failed,
     Cancelled,
+    /// A status not known to this version of `spool`, preserved verbatim
+    /// so older binaries can read, validate, and re-emit files written by
+    /// a newer one without data loss.
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl std::fmt::Display for SubagentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubagentStatus::Completed => write!(f, "completed"),
+            SubagentStatus::Failed => write!(f, "failed"),
+            SubagentStatus::Cancelled => write!(f, "cancelled"),
+            SubagentStatus::Other(raw) => write!(f, "{}", raw),
+        }
+    }
 }
 
 /// Annotation entry
@@ -461,6 +687,11 @@ pub enum AnnotationStyle {
     Pin,
     Warning,
     Success,
+    /// A style not known to this version of `spool`, preserved verbatim
+    /// so older binaries can read, validate, and re-emit files written by
+    /// a newer one without data loss.
+    #[serde(untagged)]
+    Other(String),
 }
 
 /// Redaction marker entry
@@ -492,6 +723,79 @@ pub enum RedactionReason {
     IpAddress,
     Pii,
     Custom,
+    /// A reason not known to this version of `spool` (not the same as
+    /// `Custom`, which marks a reason the detector itself doesn't
+    /// further categorize), preserved verbatim so older binaries can
+    /// read, validate, and re-emit files written by a newer one without
+    /// data loss.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Raw PTY/terminal output captured during a tool invocation, as a sequence
+/// of timestamped byte frames - exactly like a PTY reader thread would
+/// capture them while draining the master fd. Unlike `ToolResult`'s single
+/// settled-text `output` field, this preserves the original pacing so
+/// playback can replay the terminal frame-by-frame instead of dumping the
+/// whole capture at once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct TerminalEntry {
+    pub id: EntryId,
+    pub ts: Timestamp,
+    /// The `ToolCall` (or subagent) this terminal output belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<EntryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subagent_id: Option<EntryId>,
+    /// The frames, in capture order. Each frame's `offset_ms` is relative to
+    /// `ts`, matching how `build_timeline` derives sub-entry playback times.
+    pub frames: Vec<TerminalFrame>,
+    #[serde(flatten)]
+    #[schemars(schema_with = "schema::extras_schema")]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl TerminalEntry {
+    /// Decode and concatenate every frame's bytes in order, yielding the
+    /// full raw terminal output as it would have looked written to a single
+    /// stream. Frames that fail to decode (corrupt or hand-edited base64)
+    /// are skipped rather than failing the whole entry.
+    pub fn decoded_bytes(&self) -> Vec<u8> {
+        self.decoded_bytes_upto(None)
+    }
+
+    /// Like [`Self::decoded_bytes`], but only concatenates frames up to and
+    /// including `frame_limit` (0-indexed) - `None` means every frame. Used
+    /// by the Editor's playback to reveal a capture frame-by-frame.
+    pub fn decoded_bytes_upto(&self, frame_limit: Option<usize>) -> Vec<u8> {
+        let end = frame_limit.map_or(self.frames.len(), |f| (f + 1).min(self.frames.len()));
+        let mut out = Vec::new();
+        for frame in &self.frames[..end] {
+            if let Ok(mut bytes) = frame.decode() {
+                out.append(&mut bytes);
+            }
+        }
+        out
+    }
+}
+
+/// One timestamped chunk of raw terminal output, exactly as written to the
+/// PTY master fd in a single read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct TerminalFrame {
+    /// Milliseconds after the entry's `ts` that this frame was captured.
+    pub offset_ms: u64,
+    /// Raw bytes written during this frame, base64-encoded (terminal output
+    /// is not guaranteed to be valid UTF-8).
+    pub data: String,
+}
+
+impl TerminalFrame {
+    /// Decode `data` from base64 back into the raw captured bytes.
+    pub fn decode(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.decode(&self.data)
+    }
 }
 
 #[cfg(test)]
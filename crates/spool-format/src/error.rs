@@ -29,6 +29,47 @@ pub enum SpoolError {
     /// Invalid entry reference
     #[error("Entry {0} references non-existent entry {1}")]
     InvalidReference(String, String),
+
+    /// Integrity check failure
+    #[error("Integrity error: {0}")]
+    Integrity(#[from] IntegrityError),
+
+    /// Encryption/decryption failure - see [`crate::crypto`]. A `String`
+    /// rather than a dedicated enum since the underlying AEAD/KDF errors
+    /// don't carry much more than "it failed" (e.g. wrong passphrase and a
+    /// corrupted ciphertext produce the same authentication failure).
+    #[error("Encryption error: {0}")]
+    Crypto(String),
+}
+
+/// Errors surfaced by [`crate::verify_integrity`] when a file's content
+/// hashes don't match its entries.
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    /// The rolling hash stored in an entry doesn't match the hash computed
+    /// over the entries seen so far.
+    #[error("Entry {index} ({id}) has a content hash mismatch: expected {expected}, computed {actual}")]
+    HashMismatch {
+        index: usize,
+        id: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// An entry is missing its `x_content_hash` extension field even though
+    /// the file as a whole has been sealed.
+    #[error("Entry {index} ({id}) is missing its content hash")]
+    MissingEntryHash { index: usize, id: String },
+
+    /// The session's `content_hash` doesn't match the final rolling hash
+    /// computed over the file's entries.
+    #[error("Session content hash mismatch: expected {expected}, computed {actual}")]
+    SessionHashMismatch { expected: String, actual: String },
+
+    /// The file has fewer entries than `SessionEntry::entry_count` claims,
+    /// suggesting the chain was truncated after sealing.
+    #[error("File was truncated: expected {expected} entries, found {actual}")]
+    TruncatedChain { expected: usize, actual: usize },
 }
 
 /// Validation errors for Spool entries.
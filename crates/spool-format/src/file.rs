@@ -1,10 +1,86 @@
 //! Reading and writing Spool files.
 
-use crate::{Entry, SessionEntry, SpoolError, SpoolResult};
+use crate::{Entry, EntryId, SessionEntry, SpoolError, SpoolResult, SubagentStatus};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
+/// Location of an entry within the originating JSONL file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// 1-indexed line number.
+    pub line: usize,
+    /// Byte offset range of the line's content within the file.
+    pub byte_range: (usize, usize),
+}
+
+/// A line that failed to parse as JSON, pinpointed for `spool validate`'s
+/// "line N, column C" + source line + caret rendering. Kept separate from
+/// `SpoolFile::unparsed_lines` (which exists purely for round-tripping raw
+/// text back out on write) since this also carries the column `serde_json`
+/// reported and a ready-to-render message.
+#[derive(Debug, Clone)]
+pub struct LineParseError {
+    /// 1-indexed line number.
+    pub line: usize,
+    /// 1-indexed column within that line where `serde_json`'s parser
+    /// stopped.
+    pub col: usize,
+    /// Width, in characters, of the caret underline. Always 1 -
+    /// `serde_json` only reports a single error position, not a range.
+    pub span_len: usize,
+    pub message: String,
+    /// The raw source line, so the CLI can reproduce it under the error
+    /// without re-reading the file.
+    pub source_line: String,
+}
+
+/// Token counts aggregated for a single model, as part of a
+/// [`TokenUsageSummary`].
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ModelTokenUsage {
+    pub responses: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+}
+
+/// Token usage aggregated across every response in a session, with a
+/// per-model breakdown. Built by [`SpoolFile::token_usage_summary`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TokenUsageSummary {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub by_model: BTreeMap<String, ModelTokenUsage>,
+}
+
+/// One node in the subagent call tree reconstructed by
+/// [`SpoolFile::subagent_tree`]. Not part of the on-disk format itself -
+/// derived from `SubagentStart`/`SubagentEnd` entries and whatever other
+/// entries reference them via `subagent_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubagentNode {
+    pub id: EntryId,
+    pub agent: String,
+    pub context: Option<String>,
+    pub status: Option<SubagentStatus>,
+    pub start_ts: crate::Timestamp,
+    pub end_ts: Option<crate::Timestamp>,
+    /// `None` if no matching `SubagentEnd` was found (the subagent never
+    /// finished, or the file was trimmed past its end).
+    pub duration_ms: Option<u64>,
+    pub prompts: usize,
+    pub responses: usize,
+    pub tool_calls: usize,
+    pub errors: usize,
+    pub children: Vec<SubagentNode>,
+}
+
 /// A parsed Spool file.
 #[derive(Debug, Clone)]
 pub struct SpoolFile {
@@ -14,6 +90,15 @@ pub struct SpoolFile {
     pub entries: Vec<Entry>,
     /// Lines that failed to parse (for round-tripping)
     pub unparsed_lines: Vec<(usize, String)>,
+    /// Source location of each entry in `entries`, parallel by index.
+    /// `None` for entries with no known origin (e.g. added programmatically,
+    /// or converted from a non-Spool log format).
+    pub entry_spans: Vec<Option<SourceSpan>>,
+    /// Structured, line/column-pinpointed errors for each entry in
+    /// `unparsed_lines`, parallel by content rather than index (a line can
+    /// fail to parse for a reason `serde_json` can't locate a column for,
+    /// though in practice that's rare). Surfaced by `spool validate`.
+    pub parse_errors: Vec<LineParseError>,
 }
 
 impl SpoolFile {
@@ -23,6 +108,8 @@ impl SpoolFile {
             session: session.clone(),
             entries: vec![Entry::Session(session)],
             unparsed_lines: Vec::new(),
+            entry_spans: vec![None],
+            parse_errors: Vec::new(),
         }
     }
 
@@ -36,12 +123,18 @@ impl SpoolFile {
     /// Read a Spool file from a reader.
     pub fn from_reader<R: BufRead>(reader: R) -> SpoolResult<Self> {
         let mut entries = Vec::new();
+        let mut entry_spans = Vec::new();
         let mut unparsed_lines = Vec::new();
+        let mut parse_errors = Vec::new();
         let mut session: Option<SessionEntry> = None;
+        let mut byte_offset: usize = 0;
 
         for (line_num, line_result) in reader.lines().enumerate() {
             let line = line_result?;
             let line_num = line_num + 1; // 1-indexed
+            let line_start = byte_offset;
+            let line_end = line_start + line.len();
+            byte_offset = line_end + 1; // account for the stripped '\n'
 
             // Skip blank lines
             if line.trim().is_empty() {
@@ -60,8 +153,21 @@ impl SpoolFile {
                         }
                     }
                     entries.push(entry);
+                    entry_spans.push(Some(SourceSpan {
+                        line: line_num,
+                        byte_range: (line_start, line_end),
+                    }));
                 }
                 Err(e) => {
+                    // Pinpoint the failure for `spool validate` before
+                    // `line` is moved into `unparsed_lines` below.
+                    parse_errors.push(LineParseError {
+                        line: line_num,
+                        col: e.column().max(1),
+                        span_len: 1,
+                        message: e.to_string(),
+                        source_line: line.clone(),
+                    });
                     // Store unparsed line for round-tripping
                     unparsed_lines.push((line_num, line));
                     // Log but don't fail - forward compatibility
@@ -76,6 +182,8 @@ impl SpoolFile {
             session,
             entries,
             unparsed_lines,
+            entry_spans,
+            parse_errors,
         })
     }
 
@@ -99,9 +207,15 @@ impl SpoolFile {
         Ok(())
     }
 
-    /// Add an entry to the file.
+    /// Add an entry to the file. It has no known source location.
     pub fn add_entry(&mut self, entry: Entry) {
         self.entries.push(entry);
+        self.entry_spans.push(None);
+    }
+
+    /// Look up the source location of the entry at `index`, if known.
+    pub fn entry_span(&self, index: usize) -> Option<SourceSpan> {
+        self.entry_spans.get(index).copied().flatten()
     }
 
     /// Get all entries of a specific type.
@@ -173,22 +287,155 @@ impl SpoolFile {
         tools
     }
 
+    /// Aggregate token usage across all responses, broken down by `model`.
+    /// Responses with no `token_usage` are ignored; responses with no
+    /// `model` are grouped under `"unknown"`.
+    pub fn token_usage_summary(&self) -> TokenUsageSummary {
+        let mut summary = TokenUsageSummary::default();
+
+        for response in self.responses() {
+            let Some(usage) = &response.token_usage else {
+                continue;
+            };
+            let cache_read = usage.cache_read_tokens.unwrap_or(0);
+            let cache_creation = usage.cache_creation_tokens.unwrap_or(0);
+
+            let model = response.model.clone().unwrap_or_else(|| "unknown".to_string());
+            let entry = summary.by_model.entry(model).or_default();
+            entry.responses += 1;
+            entry.input_tokens += usage.input_tokens;
+            entry.output_tokens += usage.output_tokens;
+            entry.cache_read_tokens += cache_read;
+            entry.cache_creation_tokens += cache_creation;
+
+            summary.input_tokens += usage.input_tokens;
+            summary.output_tokens += usage.output_tokens;
+            summary.cache_read_tokens += cache_read;
+            summary.cache_creation_tokens += cache_creation;
+        }
+
+        summary
+    }
+
+    /// Reconstruct the subagent call tree from `SubagentStart`/`SubagentEnd`
+    /// pairs, nested by `parent_subagent_id` and with every other
+    /// entry type that carries a `subagent_id` attributed to its owner.
+    /// Roots and each node's children are ordered by start time.
+    pub fn subagent_tree(&self) -> Vec<SubagentNode> {
+        let starts: Vec<&crate::SubagentStartEntry> = self.entries_of_type(|e| match e {
+            Entry::SubagentStart(s) => Some(s),
+            _ => None,
+        });
+
+        let ends_by_start: HashMap<EntryId, &crate::SubagentEndEntry> = self
+            .entries_of_type(|e| match e {
+                Entry::SubagentEnd(end) => Some(end),
+                _ => None,
+            })
+            .into_iter()
+            .map(|end| (end.start_id, end))
+            .collect();
+
+        #[derive(Default, Clone, Copy)]
+        struct Counts {
+            prompts: usize,
+            responses: usize,
+            tool_calls: usize,
+            errors: usize,
+        }
+        let mut counts: HashMap<EntryId, Counts> = HashMap::new();
+        for entry in &self.entries {
+            let Some(subagent_id) = entry.subagent_id() else {
+                continue;
+            };
+            let c = counts.entry(subagent_id).or_default();
+            match entry {
+                Entry::Prompt(_) => c.prompts += 1,
+                Entry::Response(_) => c.responses += 1,
+                Entry::ToolCall(_) => c.tool_calls += 1,
+                Entry::Error(_) => c.errors += 1,
+                _ => {}
+            }
+        }
+
+        let mut nodes: HashMap<EntryId, SubagentNode> = starts
+            .iter()
+            .map(|s| {
+                let end = ends_by_start.get(&s.id);
+                let c = counts.get(&s.id).copied().unwrap_or_default();
+                (
+                    s.id,
+                    SubagentNode {
+                        id: s.id,
+                        agent: s.agent.clone(),
+                        context: s.context.clone(),
+                        status: end.and_then(|e| e.status.clone()),
+                        start_ts: s.ts,
+                        end_ts: end.map(|e| e.ts),
+                        duration_ms: end.map(|e| e.ts.saturating_sub(s.ts)),
+                        prompts: c.prompts,
+                        responses: c.responses,
+                        tool_calls: c.tool_calls,
+                        errors: c.errors,
+                        children: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+
+        let mut child_ids: HashMap<EntryId, Vec<EntryId>> = HashMap::new();
+        let mut root_ids: Vec<EntryId> = Vec::new();
+        for s in &starts {
+            match s.parent_subagent_id {
+                Some(parent) if nodes.contains_key(&parent) => {
+                    child_ids.entry(parent).or_default().push(s.id);
+                }
+                _ => root_ids.push(s.id),
+            }
+        }
+
+        fn build(
+            id: EntryId,
+            nodes: &mut HashMap<EntryId, SubagentNode>,
+            child_ids: &HashMap<EntryId, Vec<EntryId>>,
+        ) -> SubagentNode {
+            let mut node = nodes.remove(&id).expect("node was indexed by its own id");
+            if let Some(children) = child_ids.get(&id) {
+                node.children = children.iter().map(|c| build(*c, nodes, child_ids)).collect();
+                node.children.sort_by_key(|c| c.start_ts);
+            }
+            node
+        }
+
+        let mut roots: Vec<SubagentNode> = root_ids
+            .into_iter()
+            .map(|id| build(id, &mut nodes, &child_ids))
+            .collect();
+        roots.sort_by_key(|n| n.start_ts);
+        roots
+    }
+
     /// Trim the file to a time range.
     pub fn trim(&mut self, start_ms: u64, end_ms: u64) {
-        // Keep session entry always
+        // Keep session entry (and its span) always
         let session = self.entries.remove(0);
+        let session_span = self.entry_spans.remove(0);
 
-        // Filter entries within range
-        self.entries.retain(|e| {
-            if let Some(ts) = e.timestamp() {
-                ts >= start_ms && ts <= end_ms
-            } else {
-                false
+        // Filter entries (and their spans in lockstep) within range
+        let mut kept_entries = Vec::with_capacity(self.entries.len());
+        let mut kept_spans = Vec::with_capacity(self.entry_spans.len());
+        for (entry, span) in self.entries.drain(..).zip(self.entry_spans.drain(..)) {
+            if entry.timestamp().is_some_and(|ts| ts >= start_ms && ts <= end_ms) {
+                kept_entries.push(entry);
+                kept_spans.push(span);
             }
-        });
+        }
 
         // Re-add session at start
-        self.entries.insert(0, session);
+        kept_entries.insert(0, session);
+        kept_spans.insert(0, session_span);
+        self.entries = kept_entries;
+        self.entry_spans = kept_spans;
 
         // Update session metadata
         if let Entry::Session(ref mut s) = self.entries[0] {
@@ -230,9 +477,16 @@ mod tests {
             duration_ms: None,
             entry_count: None,
             tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
             schema_url: None,
             trimmed: None,
             ended: None,
+            content_hash: None,
             extra: HashMap::new(),
         }
     }
@@ -260,4 +514,61 @@ mod tests {
         let result = SpoolFile::from_reader(reader);
         assert!(matches!(result, Err(SpoolError::MissingSessionEntry)));
     }
+
+    #[test]
+    fn test_entry_spans_track_line_numbers() {
+        let session_line = r#"{"id":"00000000-0000-0000-0000-000000000000","ts":0,"type":"session","version":"1.0","agent":"test","recorded_at":"2025-01-01T00:00:00Z"}"#;
+        let prompt_line = r#"{"id":"00000000-0000-0000-0000-000000000001","ts":100,"type":"prompt","content":"Hi"}"#;
+        let content = format!("{}\n\n{}\n", session_line, prompt_line);
+        let reader = std::io::Cursor::new(content);
+        let file = SpoolFile::from_reader(reader).unwrap();
+
+        assert_eq!(file.entries.len(), 2);
+        assert_eq!(file.entry_span(0).map(|s| s.line), Some(1));
+        assert_eq!(file.entry_span(1).map(|s| s.line), Some(3));
+    }
+
+    #[test]
+    fn test_entry_span_byte_range_covers_line() {
+        let session_line = r#"{"id":"00000000-0000-0000-0000-000000000000","ts":0,"type":"session","version":"1.0","agent":"test","recorded_at":"2025-01-01T00:00:00Z"}"#;
+        let reader = std::io::Cursor::new(session_line.to_string());
+        let file = SpoolFile::from_reader(reader).unwrap();
+
+        let span = file.entry_span(0).unwrap();
+        assert_eq!(span.byte_range, (0, session_line.len()));
+    }
+
+    #[test]
+    fn test_malformed_line_reports_pinpointed_parse_error() {
+        let session_line = r#"{"id":"00000000-0000-0000-0000-000000000000","ts":0,"type":"session","version":"1.0","agent":"test","recorded_at":"2025-01-01T00:00:00Z"}"#;
+        let bad_line = r#"{"id": "oops", "type": "prompt", "content": }"#;
+        let content = format!("{}\n{}\n", session_line, bad_line);
+        let reader = std::io::Cursor::new(content);
+        let file = SpoolFile::from_reader(reader).unwrap();
+
+        assert_eq!(file.entries.len(), 1); // only the session entry parsed
+        assert_eq!(file.parse_errors.len(), 1);
+        let err = &file.parse_errors[0];
+        assert_eq!(err.line, 2);
+        assert_eq!(err.source_line, bad_line);
+        assert!(err.col > 0);
+        assert_eq!(err.span_len, 1);
+    }
+
+    #[test]
+    fn test_added_entries_have_no_span() {
+        let session = create_test_session();
+        let mut file = SpoolFile::new(session);
+        file.add_entry(Entry::Prompt(crate::PromptEntry {
+            id: Uuid::new_v4(),
+            ts: 100,
+            content: "Hello".to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: HashMap::new(),
+        }));
+
+        assert_eq!(file.entry_span(0), None);
+        assert_eq!(file.entry_span(1), None);
+    }
 }
@@ -0,0 +1,296 @@
+//! Content integrity hashing for Spool files.
+//!
+//! [`seal_integrity`] chains every entry into a rolling SHA-256 hash: each
+//! entry's hash covers a canonical JSON encoding of itself plus the hash of
+//! the entry before it. The per-entry hash is stamped into that entry's
+//! `extra` bag under `x_content_hash`, reusing the format's existing
+//! forward-compatible extension mechanism rather than adding a field to
+//! every entry type. The *final* rolling hash — covering the whole file —
+//! is stored once, on [`crate::SessionEntry::content_hash`], so a reader
+//! can confirm the whole chain is intact from a single field, while
+//! [`verify_integrity`] can still point at the exact entry where a chain
+//! breaks.
+//!
+//! `HashMap`'s iteration order isn't guaranteed, so entries are hashed via
+//! a canonical JSON encoding (object keys sorted) rather than via
+//! `serde_json::to_vec` directly, which would make the hash depend on
+//! incidental map ordering.
+
+use crate::{Entry, IntegrityError, SpoolFile};
+use sha2::{Digest, Sha256};
+
+const CONTENT_HASH_KEY: &str = "x_content_hash";
+
+/// Serialize `value` to JSON with object keys sorted, so the result is
+/// stable regardless of the source map's iteration order.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", canonical_json(&serde_json::Value::String(k.clone())), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let elements: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", elements.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Build the value that gets hashed for `entry`: its JSON encoding with any
+/// previously-stamped `x_content_hash` stripped out, so sealing is
+/// idempotent and a hash never covers itself.
+fn hashable_value(entry: &Entry) -> serde_json::Value {
+    let mut value = serde_json::to_value(entry).expect("Entry always serializes");
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove(CONTENT_HASH_KEY);
+    }
+    value
+}
+
+/// Compute the next link in the chain: `sha256(prev_hash || canonical(entry))`.
+fn chain_hash(prev_hash: &str, entry: &Entry) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(canonical_json(&hashable_value(entry)).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// The hash that precedes the first entry in the chain.
+fn genesis_hash() -> String {
+    "0".repeat(64)
+}
+
+/// Stamp every entry in `file` with a chained `x_content_hash`, and set
+/// [`crate::SessionEntry::content_hash`] to the final accumulated hash.
+///
+/// Sealing is idempotent: calling it again recomputes the same chain,
+/// since each entry's previous hash is stripped before it's re-hashed.
+pub fn seal_integrity(file: &mut SpoolFile) {
+    let mut prev_hash = genesis_hash();
+    for entry in file.entries.iter_mut() {
+        let hash = chain_hash(&prev_hash, entry);
+        if let Some(extra) = entry.extra_mut() {
+            extra.insert(
+                CONTENT_HASH_KEY.to_string(),
+                serde_json::Value::String(hash.clone()),
+            );
+        }
+        prev_hash = hash;
+    }
+
+    file.session.content_hash = Some(prev_hash.clone());
+    if let Some(Entry::Session(session)) = file.entries.first_mut() {
+        session.content_hash = Some(prev_hash);
+    }
+}
+
+/// Recompute the hash chain over `file` and compare it against the
+/// `x_content_hash` stamps and the session's `content_hash`, reporting
+/// every mismatch found rather than stopping at the first one.
+///
+/// Returns `Ok(())` if the file has never been sealed (no
+/// `content_hash` on the session entry) — there's nothing to verify.
+pub fn verify_integrity(file: &SpoolFile) -> Result<(), Vec<IntegrityError>> {
+    if file.session.content_hash.is_none() {
+        return Ok(());
+    }
+
+    let mut errors = Vec::new();
+
+    if let Some(expected_count) = file.session.entry_count {
+        if expected_count != file.entries.len() {
+            errors.push(IntegrityError::TruncatedChain {
+                expected: expected_count,
+                actual: file.entries.len(),
+            });
+        }
+    }
+
+    let mut prev_hash = genesis_hash();
+    for (index, entry) in file.entries.iter().enumerate() {
+        let expected = chain_hash(&prev_hash, entry);
+        let id = entry
+            .id()
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "?".to_string());
+
+        match entry.extra().and_then(|extra| extra.get(CONTENT_HASH_KEY)) {
+            Some(serde_json::Value::String(actual)) if *actual == expected => {}
+            Some(serde_json::Value::String(actual)) => {
+                errors.push(IntegrityError::HashMismatch {
+                    index,
+                    id,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+            _ => errors.push(IntegrityError::MissingEntryHash { index, id }),
+        }
+
+        prev_hash = expected;
+    }
+
+    if let Some(expected) = &file.session.content_hash {
+        if *expected != prev_hash {
+            errors.push(IntegrityError::SessionHashMismatch {
+                expected: expected.clone(),
+                actual: prev_hash,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PromptEntry, SessionEntry};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn test_session() -> SessionEntry {
+        SessionEntry {
+            id: Uuid::new_v4(),
+            ts: 0,
+            version: "1.0".to_string(),
+            agent: "test".to_string(),
+            recorded_at: Utc::now(),
+            agent_version: None,
+            title: None,
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn test_prompt(ts: u64, content: &str) -> Entry {
+        Entry::Prompt(PromptEntry {
+            id: Uuid::new_v4(),
+            ts,
+            content: content.to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_seal_sets_session_content_hash() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+
+        assert!(file.session.content_hash.is_none());
+        seal_integrity(&mut file);
+        assert!(file.session.content_hash.is_some());
+    }
+
+    #[test]
+    fn test_seal_stamps_every_entry() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+
+        for entry in &file.entries {
+            assert!(entry.extra().unwrap().contains_key(CONTENT_HASH_KEY));
+        }
+    }
+
+    #[test]
+    fn test_verify_passes_after_seal() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+
+        assert!(verify_integrity(&file).is_ok());
+    }
+
+    #[test]
+    fn test_verify_ok_when_never_sealed() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+
+        assert!(verify_integrity(&file).is_ok());
+    }
+
+    #[test]
+    fn test_verify_detects_tampered_entry() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+
+        if let Entry::Prompt(p) = &mut file.entries[1] {
+            p.content = "tampered".to_string();
+        }
+
+        let errors = verify_integrity(&file).unwrap_err();
+        assert!(matches!(
+            errors[0],
+            IntegrityError::HashMismatch { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_detects_missing_entry_hash() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+
+        if let Entry::Prompt(p) = &mut file.entries[1] {
+            p.extra.remove(CONTENT_HASH_KEY);
+        }
+
+        let errors = verify_integrity(&file).unwrap_err();
+        assert!(matches!(
+            errors[0],
+            IntegrityError::MissingEntryHash { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_detects_truncated_chain() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+        file.session.entry_count = Some(file.entries.len() + 1);
+
+        let errors = verify_integrity(&file).unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, IntegrityError::TruncatedChain { .. })));
+    }
+
+    #[test]
+    fn test_seal_is_idempotent() {
+        let mut file = SpoolFile::new(test_session());
+        file.add_entry(test_prompt(100, "hello"));
+        seal_integrity(&mut file);
+        let first_hash = file.session.content_hash.clone();
+        seal_integrity(&mut file);
+        assert_eq!(file.session.content_hash, first_hash);
+    }
+}
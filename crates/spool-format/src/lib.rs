@@ -26,16 +26,34 @@
 //! }
 //! ```
 
+mod broadcast;
+mod codeblocks;
+mod crypto;
 mod entry;
 mod error;
 mod file;
+mod integrity;
+mod pool;
+mod queue;
 mod redaction;
+mod repair;
+mod source_sink;
+mod tokens;
 mod validation;
 
+pub use broadcast::*;
+pub use codeblocks::*;
+pub use crypto::*;
 pub use entry::*;
 pub use error::*;
 pub use file::*;
+pub use integrity::*;
+pub use pool::*;
+pub use queue::*;
 pub use redaction::*;
+pub use source_sink::*;
+pub use repair::*;
+pub use tokens::*;
 pub use validation::*;
 
 /// The current version of the Spool format
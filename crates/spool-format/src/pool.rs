@@ -0,0 +1,200 @@
+//! Bounded worker pool with reusable, recreatable worker state.
+//!
+//! Unlike spawning a thread per item, [`Pool`] holds a fixed number of
+//! worker slots up front, each backed by its own long-lived state (e.g. a
+//! reusable buffer or scratch allocator). Callers check out a slot with
+//! [`Pool::get`] (or the `act` convenience), and it's handed back to the
+//! pool on drop -- rebuilt from the original constructor first if the
+//! caller flagged it invalid. Checking out beyond `capacity` blocks until a
+//! slot is returned, so a pipeline fanning out across cores can never spawn
+//! unbounded threads or hold unbounded worker state.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// A bounded pool of reusable worker state, capped at `capacity` in-flight
+/// checkouts.
+pub struct Pool<T> {
+    constructor: Arc<dyn Fn() -> T + Send + Sync>,
+    // `mpsc::Sender` isn't `Sync`, so it's wrapped in a `Mutex` (like the
+    // receiver) purely to let `Pool` itself be shared across threads; the
+    // lock is only ever held for the instant it takes to clone the sender.
+    sender: Mutex<Sender<T>>,
+    receiver: Arc<Mutex<Receiver<T>>>,
+}
+
+impl<T: Send + 'static> Pool<T> {
+    /// Create a pool with `capacity` workers, each built up front by
+    /// calling `constructor` once.
+    pub fn new<F>(capacity: usize, constructor: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..capacity {
+            sender
+                .send(constructor())
+                .expect("receiver is held by this Pool and can't be dropped yet");
+        }
+        Self {
+            constructor: Arc::new(constructor),
+            sender: Mutex::new(sender),
+            receiver: Arc::new(Mutex::new(receiver)),
+        }
+    }
+
+    /// Check out a worker, blocking until one is available.
+    pub fn get(&self) -> Worker<T> {
+        let item = self
+            .receiver
+            .lock()
+            .expect("pool receiver mutex poisoned")
+            .recv()
+            .expect("pool sender is held by this Pool and can't be dropped yet");
+        let sender = self.sender.lock().expect("pool sender mutex poisoned").clone();
+        Worker {
+            item: Some(item),
+            sender,
+            constructor: self.constructor.clone(),
+            invalid: false,
+        }
+    }
+
+    /// Check out a worker and run `f` on it, returning `f`'s result. Call
+    /// `worker.invalidate()` inside `f` if the worker's state was left
+    /// unusable (e.g. by an error), so it's rebuilt from the constructor
+    /// instead of recycled as-is.
+    pub fn act<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Worker<T>) -> R,
+    {
+        let mut worker = self.get();
+        f(&mut worker)
+    }
+}
+
+/// A checked-out worker. Returned to its pool on drop, rebuilt from the
+/// pool's constructor first if [`invalidate`](Worker::invalidate) was called.
+pub struct Worker<T> {
+    item: Option<T>,
+    sender: Sender<T>,
+    constructor: Arc<dyn Fn() -> T + Send + Sync>,
+    invalid: bool,
+}
+
+impl<T> Worker<T> {
+    /// Mark this worker's state as unusable; on drop it is rebuilt from the
+    /// pool's constructor rather than recycled.
+    pub fn invalidate(&mut self) {
+        self.invalid = true;
+    }
+}
+
+impl<T> std::ops::Deref for Worker<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.item.as_ref().expect("worker state taken before drop")
+    }
+}
+
+impl<T> std::ops::DerefMut for Worker<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.item.as_mut().expect("worker state taken before drop")
+    }
+}
+
+impl<T> Drop for Worker<T> {
+    fn drop(&mut self) {
+        let item = if self.invalid {
+            (self.constructor)()
+        } else {
+            self.item.take().expect("worker state taken before drop")
+        };
+        // The Pool holds the other end of this channel for as long as any
+        // Worker can exist, so a send failure here would mean the Pool was
+        // already dropped; ignore it rather than panicking in a destructor.
+        let _ = self.sender.send(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn get_returns_constructed_value() {
+        let pool = Pool::new(2, || 42);
+        let worker = pool.get();
+        assert_eq!(*worker, 42);
+    }
+
+    #[test]
+    fn worker_is_recycled_on_drop() {
+        let built = Arc::new(AtomicUsize::new(0));
+        let built_clone = built.clone();
+        let pool = Pool::new(1, move || built_clone.fetch_add(1, Ordering::SeqCst));
+
+        {
+            let _worker = pool.get();
+        }
+        let worker = pool.get();
+        // Only one worker was ever constructed: the second `get()` reused
+        // the first worker's slot instead of building a new one.
+        assert_eq!(*worker, 0);
+        assert_eq!(built.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn invalidated_worker_is_rebuilt() {
+        let built = Arc::new(AtomicUsize::new(0));
+        let built_clone = built.clone();
+        let pool = Pool::new(1, move || built_clone.fetch_add(1, Ordering::SeqCst));
+
+        {
+            let mut worker = pool.get();
+            worker.invalidate();
+        }
+        let worker = pool.get();
+        assert_eq!(*worker, 1);
+        assert_eq!(built.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn act_runs_closure_on_checked_out_worker() {
+        let pool = Pool::new(1, Vec::<i32>::new);
+        pool.act(|worker| worker.push(1));
+        pool.act(|worker| worker.push(2));
+        let worker = pool.get();
+        assert_eq!(*worker, vec![1, 2]);
+    }
+
+    #[test]
+    fn act_rebuilds_worker_invalidated_inside_closure() {
+        let built = Arc::new(AtomicUsize::new(0));
+        let built_clone = built.clone();
+        let pool = Pool::new(1, move || built_clone.fetch_add(1, Ordering::SeqCst));
+
+        pool.act(|worker| worker.invalidate());
+        let worker = pool.get();
+        assert_eq!(*worker, 1);
+    }
+
+    #[test]
+    fn get_blocks_until_capacity_is_returned() {
+        let pool = Arc::new(Pool::new(1, || 0));
+        let first = pool.get();
+
+        let pool_clone = pool.clone();
+        let handle = std::thread::spawn(move || {
+            // Blocks until `first` is dropped below.
+            let _second = pool_clone.get();
+        });
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        drop(first);
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,296 @@
+//! Durable, file-based sequential job queue.
+//!
+//! True to the crate's name, a [`Spool`] is a literal spool directory in the
+//! print-spooler sense: jobs are dropped in as individual files, consumed in
+//! deterministic order, and leased by renaming (a single atomic filesystem
+//! operation) so a crash mid-lease leaves the job recoverable rather than
+//! lost or double-processed. Nothing is held in memory between calls --
+//! `Spool::open` can be called again after a crash and will pick up exactly
+//! where the directory's contents say it left off.
+
+use crate::SpoolResult;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Stable identifier for an enqueued job: its sequence number in the queue.
+pub type JobId = u64;
+
+const QUEUED_EXT: &str = "job";
+const LEASED_EXT: &str = "processing";
+
+/// A durable job queue backed by a directory of files, consumed in
+/// deterministic (sequence-number) order.
+pub struct Spool {
+    dir: PathBuf,
+}
+
+impl Spool {
+    /// Open (creating if needed) a spool directory, re-queuing any job left
+    /// `.processing` by a run that crashed mid-lease.
+    pub fn open<P: AsRef<Path>>(dir: P) -> SpoolResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some(LEASED_EXT) {
+                if let Some(id) = parse_job_id(&path) {
+                    fs::rename(&path, job_path(&dir, id))?;
+                }
+            }
+        }
+
+        Ok(Self { dir })
+    }
+
+    /// Enqueue `bytes` as a new job, returning its stable id. The job is
+    /// written to a temp file and renamed into place so a reader never
+    /// observes a partially-written job file.
+    pub fn enqueue(&self, bytes: &[u8]) -> SpoolResult<JobId> {
+        let id = self.next_id()?;
+        let tmp_path = self.dir.join(format!("{:020}.tmp", id));
+        {
+            let mut f = fs::File::create(&tmp_path)?;
+            f.write_all(bytes)?;
+            f.sync_all()?;
+        }
+        fs::rename(&tmp_path, job_path(&self.dir, id))?;
+        Ok(id)
+    }
+
+    /// Lease the lowest-numbered queued job, if any. The returned guard
+    /// must be resolved with [`JobGuard::commit`] or [`JobGuard::abort`];
+    /// dropping it unresolved re-queues the job.
+    pub fn next_job(&self) -> SpoolResult<Option<JobGuard>> {
+        let Some(id) = self.lowest_queued_id()? else {
+            return Ok(None);
+        };
+
+        let queued_path = job_path(&self.dir, id);
+        let leased_path = leased_path(&self.dir, id);
+        // Rename, not copy-then-delete: the lease transition is a single
+        // atomic filesystem operation, so another caller (or a crash) never
+        // observes the job as both queued and leased, or as neither.
+        fs::rename(&queued_path, &leased_path)?;
+        let bytes = fs::read(&leased_path)?;
+
+        Ok(Some(JobGuard {
+            id,
+            bytes,
+            queued_path,
+            leased_path,
+            resolved: false,
+        }))
+    }
+
+    /// Lease jobs one at a time until the queue is empty. Each item must
+    /// still be resolved by the caller via `commit()`/`abort()`.
+    pub fn jobs(&self) -> impl Iterator<Item = SpoolResult<JobGuard>> + '_ {
+        std::iter::from_fn(move || self.next_job().transpose())
+    }
+
+    fn next_id(&self) -> SpoolResult<JobId> {
+        let mut max_id: Option<JobId> = None;
+        for entry in fs::read_dir(&self.dir)? {
+            if let Some(id) = parse_job_id(&entry?.path()) {
+                max_id = Some(max_id.map_or(id, |m| m.max(id)));
+            }
+        }
+        Ok(max_id.map_or(0, |m| m + 1))
+    }
+
+    fn lowest_queued_id(&self) -> SpoolResult<Option<JobId>> {
+        let mut min_id = None;
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some(QUEUED_EXT) {
+                if let Some(id) = parse_job_id(&path) {
+                    min_id = Some(min_id.map_or(id, |m: JobId| m.min(id)));
+                }
+            }
+        }
+        Ok(min_id)
+    }
+}
+
+fn job_path(dir: &Path, id: JobId) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, QUEUED_EXT))
+}
+
+fn leased_path(dir: &Path, id: JobId) -> PathBuf {
+    dir.join(format!("{:020}.{}", id, LEASED_EXT))
+}
+
+fn parse_job_id(path: &Path) -> Option<JobId> {
+    let ext = path.extension().and_then(|e| e.to_str())?;
+    if ext != QUEUED_EXT && ext != LEASED_EXT {
+        return None;
+    }
+    path.file_stem().and_then(|s| s.to_str())?.parse().ok()
+}
+
+/// A leased job. Must be resolved via [`commit`](JobGuard::commit) or
+/// [`abort`](JobGuard::abort); dropping it unresolved re-queues the job so a
+/// panicking worker can't lose it silently.
+pub struct JobGuard {
+    id: JobId,
+    bytes: Vec<u8>,
+    queued_path: PathBuf,
+    leased_path: PathBuf,
+    resolved: bool,
+}
+
+impl JobGuard {
+    /// This job's stable sequence id.
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// The job's enqueued payload.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Mark the job as successfully processed, deleting its lease file.
+    pub fn commit(mut self) -> SpoolResult<()> {
+        fs::remove_file(&self.leased_path)?;
+        self.resolved = true;
+        Ok(())
+    }
+
+    /// Re-queue the job so a later `next_job()` leases it again.
+    pub fn abort(mut self) -> SpoolResult<()> {
+        fs::rename(&self.leased_path, &self.queued_path)?;
+        self.resolved = true;
+        Ok(())
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if !self.resolved {
+            // Best-effort: an unresolved guard (e.g. the worker panicked)
+            // re-queues the job rather than silently losing it.
+            let _ = fs::rename(&self.leased_path, &self.queued_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn test_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("spool-queue-test-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn enqueue_and_lease_in_order() {
+        let dir = test_dir();
+        let spool = Spool::open(&dir).unwrap();
+
+        let first = spool.enqueue(b"first").unwrap();
+        let second = spool.enqueue(b"second").unwrap();
+        assert!(second > first);
+
+        let job = spool.next_job().unwrap().unwrap();
+        assert_eq!(job.id(), first);
+        assert_eq!(job.bytes(), b"first");
+        job.commit().unwrap();
+
+        let job = spool.next_job().unwrap().unwrap();
+        assert_eq!(job.id(), second);
+        job.commit().unwrap();
+
+        assert!(spool.next_job().unwrap().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn commit_removes_the_job_file() {
+        let dir = test_dir();
+        let spool = Spool::open(&dir).unwrap();
+        spool.enqueue(b"payload").unwrap();
+
+        let job = spool.next_job().unwrap().unwrap();
+        job.commit().unwrap();
+
+        let remaining: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert!(remaining.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn abort_requeues_the_job() {
+        let dir = test_dir();
+        let spool = Spool::open(&dir).unwrap();
+        let id = spool.enqueue(b"payload").unwrap();
+
+        let job = spool.next_job().unwrap().unwrap();
+        job.abort().unwrap();
+
+        let job = spool.next_job().unwrap().unwrap();
+        assert_eq!(job.id(), id);
+        assert_eq!(job.bytes(), b"payload");
+        job.commit().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dropping_an_unresolved_guard_requeues_the_job() {
+        let dir = test_dir();
+        let spool = Spool::open(&dir).unwrap();
+        spool.enqueue(b"payload").unwrap();
+
+        {
+            let _job = spool.next_job().unwrap().unwrap();
+            // Dropped without commit/abort.
+        }
+
+        let job = spool.next_job().unwrap().unwrap();
+        assert_eq!(job.bytes(), b"payload");
+        job.commit().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reopening_recovers_a_job_left_processing_by_a_crash() {
+        let dir = test_dir();
+        {
+            let spool = Spool::open(&dir).unwrap();
+            spool.enqueue(b"payload").unwrap();
+            let job = spool.next_job().unwrap().unwrap();
+            // Simulate a crash: `job` is forgotten instead of committed or
+            // aborted, so its lease file is left on disk when the process
+            // (and thus this `Spool`) goes away.
+            std::mem::forget(job);
+        }
+
+        let spool = Spool::open(&dir).unwrap();
+        let job = spool.next_job().unwrap().unwrap();
+        assert_eq!(job.bytes(), b"payload");
+        job.commit().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn jobs_iterator_drains_the_queue() {
+        let dir = test_dir();
+        let spool = Spool::open(&dir).unwrap();
+        spool.enqueue(b"a").unwrap();
+        spool.enqueue(b"b").unwrap();
+        spool.enqueue(b"c").unwrap();
+
+        let mut seen = Vec::new();
+        for job in spool.jobs() {
+            let job = job.unwrap();
+            seen.push(job.bytes().to_vec());
+            job.commit().unwrap();
+        }
+
+        assert_eq!(seen, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,453 @@
+//! Repair (autofix) for Spool files.
+//!
+//! [`repair`] mechanically fixes a subset of the problems [`crate::validate_default`]
+//! can detect: renaming duplicated entry IDs (rewriting any later references
+//! to the renamed ID), resolving or flagging annotations that target
+//! nothing, forcing the session entry's `ts` to 0, and optionally
+//! stable-sorting entries into timestamp order. Every change is recorded as
+//! an [`AppliedFix`] so the repair is auditable; set [`RepairOptions::dry_run`]
+//! to preview the fixes without mutating the file.
+
+use crate::{Entry, EntryId, SourceSpan, SpoolFile};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// A single mechanical fix applied (or, in dry-run mode, that would be
+/// applied) by [`repair`].
+#[derive(Debug, Clone)]
+pub struct AppliedFix {
+    /// Name of the validation rule this fix addresses, e.g. `"duplicate_ids"`.
+    pub rule: &'static str,
+    /// ID of the entry the fix was applied to. For a renamed duplicate ID,
+    /// this is the new ID.
+    pub entry_id: EntryId,
+    /// Description of the entry's state before the fix.
+    pub before: String,
+    /// Description of the entry's state after the fix.
+    pub after: String,
+}
+
+/// What to do with an [`Entry::Annotation`] whose `target_id` doesn't match
+/// any known entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrphanedAnnotationStrategy {
+    /// Leave the annotation in place but mark it with `extra["x_orphaned"]`.
+    Flag,
+    /// Remove the annotation entirely.
+    Drop,
+}
+
+/// Options controlling which fixes [`repair`] applies.
+#[derive(Debug, Clone)]
+pub struct RepairOptions {
+    /// Stable-sort entries (other than the session entry) by timestamp,
+    /// eliminating `timestamp_ordering` warnings. Off by default, since
+    /// reordering content is more invasive than the other fixes.
+    pub sort_by_timestamp: bool,
+    /// How to handle annotations whose `target_id` can't be resolved.
+    pub orphaned_annotations: OrphanedAnnotationStrategy,
+    /// Compute fixes without mutating the returned file; the returned
+    /// [`SpoolFile`] is then an unmodified clone of the input.
+    pub dry_run: bool,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            sort_by_timestamp: false,
+            orphaned_annotations: OrphanedAnnotationStrategy::Flag,
+            dry_run: false,
+        }
+    }
+}
+
+/// Mechanically repair `file`, applying the fixes `options` enables, and
+/// return the repaired file (or, in dry-run mode, an unchanged clone)
+/// alongside every fix that was (or would have been) applied.
+pub fn repair(file: &SpoolFile, options: &RepairOptions) -> (SpoolFile, Vec<AppliedFix>) {
+    let mut working = file.clone();
+    let mut fixes = Vec::new();
+
+    fix_session_timestamp(&mut working, &mut fixes);
+    fix_duplicate_ids(&mut working, &mut fixes);
+    fix_orphaned_annotations(&mut working, options.orphaned_annotations, &mut fixes);
+    if options.sort_by_timestamp {
+        fix_ordering(&mut working, &mut fixes);
+    }
+
+    if options.dry_run {
+        (file.clone(), fixes)
+    } else {
+        (working, fixes)
+    }
+}
+
+fn fix_session_timestamp(file: &mut SpoolFile, fixes: &mut Vec<AppliedFix>) {
+    if file.session.ts != 0 {
+        fixes.push(AppliedFix {
+            rule: "session_timestamp",
+            entry_id: file.session.id,
+            before: format!("ts={}", file.session.ts),
+            after: "ts=0".to_string(),
+        });
+        file.session.ts = 0;
+        if let Some(Entry::Session(session)) = file.entries.first_mut() {
+            session.ts = 0;
+        }
+    }
+}
+
+fn set_entry_id(entry: &mut Entry, new_id: Uuid) {
+    match entry {
+        Entry::Session(e) => e.id = new_id,
+        Entry::Prompt(e) => e.id = new_id,
+        Entry::Thinking(e) => e.id = new_id,
+        Entry::ToolCall(e) => e.id = new_id,
+        Entry::ToolResult(e) => e.id = new_id,
+        Entry::Response(e) => e.id = new_id,
+        Entry::Error(e) => e.id = new_id,
+        Entry::SubagentStart(e) => e.id = new_id,
+        Entry::SubagentEnd(e) => e.id = new_id,
+        Entry::Annotation(e) => e.id = new_id,
+        Entry::RedactionMarker(e) => e.id = new_id,
+        Entry::Terminal(e) => e.id = new_id,
+        Entry::Unknown => {}
+    }
+}
+
+/// If `entry` holds a reference to another entry's ID (a tool result's
+/// `call_id`, a subagent end's `start_id`, or an annotation's `target_id`)
+/// and that ID has since been renamed, update the reference in place.
+/// Returns the referencing entry's own ID and a description of the stale
+/// reference, if a rewrite happened.
+fn rewrite_reference(entry: &mut Entry, rename: &HashMap<Uuid, Uuid>) -> Option<(Uuid, String)> {
+    let (this_id, reference, label): (Uuid, &mut Uuid, &str) = match entry {
+        Entry::ToolResult(tr) => (tr.id, &mut tr.call_id, "call_id"),
+        Entry::SubagentEnd(se) => (se.id, &mut se.start_id, "start_id"),
+        Entry::Annotation(a) => (a.id, &mut a.target_id, "target_id"),
+        _ => return None,
+    };
+
+    let mapped = *rename.get(reference)?;
+    if mapped == *reference {
+        return None;
+    }
+    let before = format!("{}={}", label, *reference);
+    *reference = mapped;
+    Some((this_id, before))
+}
+
+fn fix_duplicate_ids(file: &mut SpoolFile, fixes: &mut Vec<AppliedFix>) {
+    // Maps an entry's original ID to its current (possibly renamed) ID.
+    let mut rename: HashMap<Uuid, Uuid> = HashMap::new();
+
+    for entry in file.entries.iter_mut() {
+        if let Some(&id) = entry.id() {
+            if rename.contains_key(&id) {
+                let new_id = Uuid::new_v4();
+                fixes.push(AppliedFix {
+                    rule: "duplicate_ids",
+                    entry_id: new_id,
+                    before: format!("id={}", id),
+                    after: format!("id={}", new_id),
+                });
+                set_entry_id(entry, new_id);
+                rename.insert(id, new_id);
+            } else {
+                rename.insert(id, id);
+            }
+        }
+
+        if let Some((this_id, before)) = rewrite_reference(entry, &rename) {
+            fixes.push(AppliedFix {
+                rule: "duplicate_ids",
+                entry_id: this_id,
+                before,
+                after: "reference updated to renamed entry".to_string(),
+            });
+        }
+    }
+}
+
+fn fix_orphaned_annotations(
+    file: &mut SpoolFile,
+    strategy: OrphanedAnnotationStrategy,
+    fixes: &mut Vec<AppliedFix>,
+) {
+    let known_ids: HashSet<EntryId> = file.entries.iter().filter_map(|e| e.id().copied()).collect();
+
+    let mut to_drop = Vec::new();
+    for (idx, entry) in file.entries.iter_mut().enumerate() {
+        if let Entry::Annotation(a) = entry {
+            if known_ids.contains(&a.target_id) {
+                continue;
+            }
+
+            match strategy {
+                OrphanedAnnotationStrategy::Flag => {
+                    a.extra
+                        .insert("x_orphaned".to_string(), serde_json::Value::Bool(true));
+                    fixes.push(AppliedFix {
+                        rule: "annotation_targets",
+                        entry_id: a.id,
+                        before: format!("target_id={}", a.target_id),
+                        after: "flagged as orphaned (x_orphaned=true)".to_string(),
+                    });
+                }
+                OrphanedAnnotationStrategy::Drop => {
+                    fixes.push(AppliedFix {
+                        rule: "annotation_targets",
+                        entry_id: a.id,
+                        before: format!("target_id={}", a.target_id),
+                        after: "dropped".to_string(),
+                    });
+                    to_drop.push(idx);
+                }
+            }
+        }
+    }
+
+    // Remove back-to-front so earlier indices stay valid, keeping
+    // `entries` and `entry_spans` in lockstep.
+    for idx in to_drop.into_iter().rev() {
+        file.entries.remove(idx);
+        file.entry_spans.remove(idx);
+    }
+}
+
+fn fix_ordering(file: &mut SpoolFile, fixes: &mut Vec<AppliedFix>) {
+    if file.entries.len() <= 1 {
+        return;
+    }
+    let original_order: Vec<Option<EntryId>> = file.entries.iter().map(|e| e.id().copied()).collect();
+
+    let session = file.entries.remove(0);
+    let session_span = file.entry_spans.remove(0);
+
+    let mut rest: Vec<(Entry, Option<SourceSpan>)> = file
+        .entries
+        .drain(..)
+        .zip(file.entry_spans.drain(..))
+        .collect();
+    rest.sort_by_key(|(entry, _)| entry.timestamp().unwrap_or(u64::MAX));
+
+    file.entries.push(session);
+    file.entry_spans.push(session_span);
+    for (entry, span) in rest {
+        file.entries.push(entry);
+        file.entry_spans.push(span);
+    }
+
+    let new_order: Vec<Option<EntryId>> = file.entries.iter().map(|e| e.id().copied()).collect();
+    if new_order != original_order {
+        fixes.push(AppliedFix {
+            rule: "timestamp_ordering",
+            entry_id: file.session.id,
+            before: "entries in original (possibly out-of-order) order".to_string(),
+            after: "entries stable-sorted by timestamp".to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use chrono::Utc;
+    use std::collections::HashMap as StdHashMap;
+
+    fn make_session(ts: u64) -> SessionEntry {
+        SessionEntry {
+            id: Uuid::new_v4(),
+            ts,
+            version: "1.0".to_string(),
+            agent: "test".to_string(),
+            recorded_at: Utc::now(),
+            agent_version: None,
+            title: None,
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: StdHashMap::new(),
+        }
+    }
+
+    fn make_prompt(id: Uuid, ts: u64, content: &str) -> Entry {
+        Entry::Prompt(PromptEntry {
+            id,
+            ts,
+            content: content.to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: StdHashMap::new(),
+        })
+    }
+
+    fn make_tool_call(id: Uuid, ts: u64) -> Entry {
+        Entry::ToolCall(ToolCallEntry {
+            id,
+            ts,
+            tool: "bash".to_string(),
+            input: serde_json::json!({}),
+            subagent_id: None,
+            extra: StdHashMap::new(),
+        })
+    }
+
+    fn make_tool_result(id: Uuid, ts: u64, call_id: Uuid) -> Entry {
+        Entry::ToolResult(ToolResultEntry {
+            id,
+            ts,
+            call_id,
+            output: None,
+            error: None,
+            truncated: None,
+            original_bytes: None,
+            subagent_id: None,
+            redacted: None,
+            extra: StdHashMap::new(),
+        })
+    }
+
+    fn make_annotation(id: Uuid, ts: u64, target_id: Uuid) -> Entry {
+        Entry::Annotation(AnnotationEntry {
+            id,
+            ts,
+            target_id,
+            content: "note".to_string(),
+            author: None,
+            style: None,
+            created_at: None,
+            extra: StdHashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_repair_fixes_session_timestamp() {
+        let file = SpoolFile::new(make_session(500));
+        let (repaired, fixes) = repair(&file, &RepairOptions::default());
+
+        assert_eq!(repaired.session.ts, 0);
+        assert!(fixes.iter().any(|f| f.rule == "session_timestamp"));
+    }
+
+    #[test]
+    fn test_repair_renames_duplicate_ids_and_rewrites_references() {
+        let mut file = SpoolFile::new(make_session(0));
+        let shared_id = Uuid::new_v4();
+        file.add_entry(make_tool_call(shared_id, 100));
+        file.add_entry(make_tool_call(shared_id, 200)); // duplicate, gets renamed
+        file.add_entry(make_tool_result(Uuid::new_v4(), 300, shared_id));
+
+        let (repaired, fixes) = repair(&file, &RepairOptions::default());
+
+        let first_call_id = *repaired.entries[1].id().unwrap();
+        let second_call_id = *repaired.entries[2].id().unwrap();
+        assert_eq!(first_call_id, shared_id);
+        assert_ne!(second_call_id, shared_id);
+
+        let Entry::ToolResult(result) = &repaired.entries[3] else {
+            panic!("expected tool result");
+        };
+        assert_eq!(result.call_id, second_call_id);
+
+        assert!(fixes.iter().any(|f| f.rule == "duplicate_ids" && f.before == format!("id={}", shared_id)));
+        assert!(fixes
+            .iter()
+            .any(|f| f.rule == "duplicate_ids" && f.before == format!("call_id={}", shared_id)));
+
+        assert!(validate_default(&repaired).is_valid());
+    }
+
+    #[test]
+    fn test_repair_flags_orphaned_annotation_by_default() {
+        let mut file = SpoolFile::new(make_session(0));
+        file.add_entry(make_annotation(Uuid::new_v4(), 100, Uuid::new_v4()));
+
+        let (repaired, fixes) = repair(&file, &RepairOptions::default());
+
+        assert_eq!(repaired.entries.len(), 2);
+        let Entry::Annotation(a) = &repaired.entries[1] else {
+            panic!("expected annotation");
+        };
+        assert_eq!(a.extra.get("x_orphaned"), Some(&serde_json::Value::Bool(true)));
+        assert!(fixes.iter().any(|f| f.rule == "annotation_targets"));
+    }
+
+    #[test]
+    fn test_repair_drops_orphaned_annotation_when_configured() {
+        let mut file = SpoolFile::new(make_session(0));
+        file.add_entry(make_prompt(Uuid::new_v4(), 50, "hi"));
+        file.add_entry(make_annotation(Uuid::new_v4(), 100, Uuid::new_v4()));
+
+        let options = RepairOptions {
+            orphaned_annotations: OrphanedAnnotationStrategy::Drop,
+            ..RepairOptions::default()
+        };
+        let (repaired, fixes) = repair(&file, &options);
+
+        assert_eq!(repaired.entries.len(), 2);
+        assert!(!repaired.entries.iter().any(|e| matches!(e, Entry::Annotation(_))));
+        assert_eq!(repaired.entry_spans.len(), repaired.entries.len());
+        assert!(fixes.iter().any(|f| f.rule == "annotation_targets" && f.after == "dropped"));
+    }
+
+    #[test]
+    fn test_repair_sorts_by_timestamp_when_enabled() {
+        let mut file = SpoolFile::new(make_session(0));
+        file.add_entry(make_prompt(Uuid::new_v4(), 200, "second"));
+        file.add_entry(make_prompt(Uuid::new_v4(), 100, "first but later in file"));
+
+        let options = RepairOptions {
+            sort_by_timestamp: true,
+            ..RepairOptions::default()
+        };
+        let (repaired, fixes) = repair(&file, &options);
+
+        let Entry::Prompt(first) = &repaired.entries[1] else {
+            panic!("expected prompt");
+        };
+        assert_eq!(first.content, "first but later in file");
+        assert!(fixes.iter().any(|f| f.rule == "timestamp_ordering"));
+    }
+
+    #[test]
+    fn test_repair_leaves_order_untouched_by_default() {
+        let mut file = SpoolFile::new(make_session(0));
+        file.add_entry(make_prompt(Uuid::new_v4(), 200, "second"));
+        file.add_entry(make_prompt(Uuid::new_v4(), 100, "first but later in file"));
+
+        let (repaired, fixes) = repair(&file, &RepairOptions::default());
+
+        let Entry::Prompt(first) = &repaired.entries[1] else {
+            panic!("expected prompt");
+        };
+        assert_eq!(first.content, "second");
+        assert!(!fixes.iter().any(|f| f.rule == "timestamp_ordering"));
+    }
+
+    #[test]
+    fn test_repair_dry_run_does_not_mutate() {
+        let file = SpoolFile::new(make_session(500));
+        let options = RepairOptions {
+            dry_run: true,
+            ..RepairOptions::default()
+        };
+        let (unchanged, fixes) = repair(&file, &options);
+
+        assert_eq!(unchanged.session.ts, 500);
+        assert!(fixes.iter().any(|f| f.rule == "session_timestamp"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Token counting over a [`SpoolFile`]'s entry text.
+//!
+//! This runs an actual BPE tokenizer (via `tiktoken-rs`, the same
+//! cl100k/o200k vocabularies OpenAI's API uses) over every entry's text, so
+//! it produces a count even for agents or log formats that never recorded
+//! [`crate::TokenUsageSummary`]'s model-reported `token_usage` fields. It's
+//! an estimate, not ground truth - different models tokenize differently,
+//! and this only approximates whichever one actually produced the session.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tiktoken_rs::CoreBPE;
+
+use crate::entry::{Entry, ToolOutput};
+use crate::file::SpoolFile;
+
+/// Which BPE vocabulary to tokenize with. `Cl100kBase` (GPT-3.5/4-era) is
+/// the default - close enough for an estimate across agents that don't
+/// disclose their exact tokenizer; `O200kBase` is the newer GPT-4o-era
+/// vocabulary for callers who know that's the better match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenModel {
+    #[default]
+    Cl100kBase,
+    O200kBase,
+}
+
+/// Per-entry-type token totals produced by [`TokenCounter::count_file`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TokenCountSummary {
+    pub total: usize,
+    pub by_entry_type: BTreeMap<String, usize>,
+}
+
+/// Counts BPE tokens in entry text using a selectable encoder.
+pub struct TokenCounter {
+    bpe: CoreBPE,
+}
+
+impl TokenCounter {
+    /// Build a counter for `model`. The underlying tokenizer tables are
+    /// bundled with `tiktoken-rs`, so this never touches the network.
+    pub fn new(model: TokenModel) -> Self {
+        let bpe = match model {
+            TokenModel::Cl100kBase => {
+                tiktoken_rs::cl100k_base().expect("cl100k_base is a built-in encoding")
+            }
+            TokenModel::O200kBase => {
+                tiktoken_rs::o200k_base().expect("o200k_base is a built-in encoding")
+            }
+        };
+        Self { bpe }
+    }
+
+    /// Token count for a single string of text.
+    pub fn count(&self, text: &str) -> usize {
+        self.bpe.encode_with_special_tokens(text).len()
+    }
+
+    /// Token counts for every text-bearing entry in `file`, aggregated
+    /// overall and per entry type (same type names as
+    /// [`crate::redaction`]'s detection pipeline: `prompt`, `response`,
+    /// `thinking`, `tool_result`, `error`, `annotation`).
+    pub fn count_file(&self, file: &SpoolFile) -> TokenCountSummary {
+        let mut summary = TokenCountSummary::default();
+        for entry in &file.entries {
+            let Some((entry_type, text)) = entry_text(entry) else {
+                continue;
+            };
+            let count = self.count(text);
+            summary.total += count;
+            *summary.by_entry_type.entry(entry_type.to_string()).or_insert(0) += count;
+        }
+        summary
+    }
+}
+
+impl Default for TokenCounter {
+    fn default() -> Self {
+        Self::new(TokenModel::default())
+    }
+}
+
+fn entry_text(entry: &Entry) -> Option<(&'static str, &str)> {
+    match entry {
+        Entry::Prompt(p) => Some(("prompt", p.content.as_str())),
+        Entry::Response(r) => Some(("response", r.content.as_str())),
+        Entry::Thinking(t) => Some(("thinking", t.content.as_str())),
+        Entry::Error(e) => Some(("error", e.message.as_str())),
+        Entry::Annotation(a) => Some(("annotation", a.content.as_str())),
+        Entry::ToolResult(tr) => match &tr.output {
+            Some(ToolOutput::Text(t)) => Some(("tool_result", t.as_str())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PromptEntry, SessionEntry};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn make_test_file() -> SpoolFile {
+        let session = SessionEntry {
+            id: Uuid::nil(),
+            ts: 0,
+            version: "1.0".to_string(),
+            agent: "test".to_string(),
+            recorded_at: chrono::Utc::now(),
+            agent_version: None,
+            title: None,
+            author: None,
+            tags: None,
+            duration_ms: None,
+            entry_count: None,
+            tools_used: None,
+            files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
+            first_prompt: None,
+            schema_url: None,
+            trimmed: None,
+            ended: None,
+            content_hash: None,
+            extra: HashMap::new(),
+        };
+        let mut file = SpoolFile::new(session);
+        file.add_entry(Entry::Prompt(PromptEntry {
+            id: Uuid::new_v4(),
+            ts: 1000,
+            content: "hello world, this is a test prompt".to_string(),
+            subagent_id: None,
+            attachments: None,
+            extra: HashMap::new(),
+        }));
+        file
+    }
+
+    #[test]
+    fn test_count_is_nonzero_for_nonempty_text() {
+        let counter = TokenCounter::default();
+        assert!(counter.count("hello world") > 0);
+    }
+
+    #[test]
+    fn test_count_file_aggregates_by_entry_type() {
+        let file = make_test_file();
+        let counter = TokenCounter::default();
+        let summary = counter.count_file(&file);
+        assert_eq!(summary.total, summary.by_entry_type["prompt"]);
+        assert!(summary.total > 0);
+    }
+}
@@ -1,142 +1,662 @@
 //! Validation utilities for Spool files.
+//!
+//! Validation is organized as a set of independent [`Rule`]s, similar to how a
+//! linter organizes checks. Each rule inspects a [`SpoolFile`] and reports
+//! [`Diagnostic`]s into a [`DiagnosticSink`]. The default [`Validator`] registers
+//! all built-in rules, but callers can disable rules by name, override a rule's
+//! severity, or register their own [`Rule`] implementations.
 
-use crate::{Entry, SpoolFile, ValidationError};
-use std::collections::HashSet;
+use crate::{Entry, EntryId, SourceSpan, SpoolFile};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
 
-/// Validation options.
+/// Severity of a validation diagnostic.
+///
+/// Ordered so that `Severity::Error` is the most severe; [`ValidationResult::is_valid`]
+/// treats any diagnostic at `Severity::Error` as making the file invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single finding reported by a [`Rule`].
 #[derive(Debug, Clone)]
-pub struct ValidationOptions {
-    /// Check for duplicate entry IDs
-    pub check_duplicate_ids: bool,
-    /// Check that tool results reference valid tool calls
-    pub check_tool_references: bool,
-    /// Check that subagent ends reference valid starts
-    pub check_subagent_references: bool,
-    /// Check that annotations reference valid entries
-    pub check_annotation_references: bool,
-    /// Warn about out-of-order timestamps (not an error per spec)
-    pub warn_out_of_order_timestamps: bool,
-}
-
-impl Default for ValidationOptions {
-    fn default() -> Self {
+pub struct Diagnostic {
+    /// Stable, machine-readable name of the rule that produced this diagnostic
+    /// (see [`Rule::name`]).
+    pub rule: &'static str,
+    /// Severity of this diagnostic, after any overrides are applied.
+    pub severity: Severity,
+    /// Human-readable description of the problem.
+    pub message: String,
+    /// Location of the offending entry in the originating JSONL file, if known.
+    pub location: Option<SourceSpan>,
+    /// Zero-based index into `SpoolFile::entries` of the offending entry, if
+    /// the diagnostic is attributable to one.
+    pub entry_index: Option<usize>,
+    /// ID of the offending entry, if attributable to one.
+    pub entry_id: Option<EntryId>,
+}
+
+/// Collects diagnostics emitted by a single rule during a validation pass.
+pub struct DiagnosticSink<'a> {
+    rule_name: &'static str,
+    severity: Severity,
+    file: &'a SpoolFile,
+    out: &'a mut Vec<Diagnostic>,
+}
+
+impl<'a> DiagnosticSink<'a> {
+    /// Report a finding with no associated entry.
+    pub fn report(&mut self, message: impl Into<String>) {
+        self.push(None, None, None, message);
+    }
+
+    /// Report a finding located at `entry_index` in `file.entries`, attaching
+    /// its source location and entry ID when the file has one (e.g. not for
+    /// programmatically-constructed entries).
+    pub fn report_at(&mut self, entry_index: usize, message: impl Into<String>) {
+        let location = self.file.entry_span(entry_index);
+        let entry_id = self.file.entries.get(entry_index).and_then(|e| e.id()).copied();
+        self.push(location, Some(entry_index), entry_id, message);
+    }
+
+    fn push(
+        &mut self,
+        location: Option<SourceSpan>,
+        entry_index: Option<usize>,
+        entry_id: Option<EntryId>,
+        message: impl Into<String>,
+    ) {
+        self.out.push(Diagnostic {
+            rule: self.rule_name,
+            severity: self.severity,
+            message: message.into(),
+            location,
+            entry_index,
+            entry_id,
+        });
+    }
+}
+
+/// A single validation check that can be registered with a [`Validator`].
+pub trait Rule {
+    /// Stable identifier used for disabling this rule or overriding its severity.
+    fn name(&self) -> &'static str;
+
+    /// Severity applied to diagnostics from this rule unless overridden
+    /// via [`Validator::set_severity`].
+    fn default_severity(&self) -> Severity;
+
+    /// Inspect `file`, reporting any findings into `ctx`.
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink);
+}
+
+/// Session entry must have `ts == 0`.
+struct SessionTimestampRule;
+
+impl Rule for SessionTimestampRule {
+    fn name(&self) -> &'static str {
+        "session_timestamp"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        if file.session.ts != 0 {
+            ctx.report_at(
+                0,
+                format!("Session entry must have ts=0, found ts={}", file.session.ts),
+            );
+        }
+    }
+}
+
+/// No two entries may share an entry ID.
+struct DuplicateIdsRule;
+
+impl Rule for DuplicateIdsRule {
+    fn name(&self) -> &'static str {
+        "duplicate_ids"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        let mut seen_ids = HashSet::new();
+        for (idx, entry) in file.entries.iter().enumerate() {
+            if let Some(id) = entry.id() {
+                if !seen_ids.insert(*id) {
+                    ctx.report_at(idx, format!("Duplicate entry ID: {}", id));
+                }
+            }
+        }
+    }
+}
+
+/// Every `ToolResult` must reference a `ToolCall` earlier in the file.
+struct OrphanedToolResultsRule;
+
+impl Rule for OrphanedToolResultsRule {
+    fn name(&self) -> &'static str {
+        "orphaned_tool_results"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        let mut tool_call_ids = HashSet::new();
+        for (idx, entry) in file.entries.iter().enumerate() {
+            match entry {
+                Entry::ToolCall(tc) => {
+                    tool_call_ids.insert(tc.id);
+                }
+                Entry::ToolResult(tr) if !tool_call_ids.contains(&tr.call_id) => {
+                    ctx.report_at(
+                        idx,
+                        format!(
+                            "Tool result {} references unknown tool call {}",
+                            tr.id, tr.call_id
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Every `SubagentEnd` must reference a `SubagentStart` earlier in the file.
+struct OrphanedSubagentEndsRule;
+
+impl Rule for OrphanedSubagentEndsRule {
+    fn name(&self) -> &'static str {
+        "orphaned_subagent_ends"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        let mut subagent_start_ids = HashSet::new();
+        for (idx, entry) in file.entries.iter().enumerate() {
+            match entry {
+                Entry::SubagentStart(ss) => {
+                    subagent_start_ids.insert(ss.id);
+                }
+                Entry::SubagentEnd(se) if !subagent_start_ids.contains(&se.start_id) => {
+                    ctx.report_at(
+                        idx,
+                        format!(
+                            "Subagent end {} references unknown subagent start {}",
+                            se.id, se.start_id
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Every `Annotation` should target a known entry ID.
+struct AnnotationTargetsRule;
+
+impl Rule for AnnotationTargetsRule {
+    fn name(&self) -> &'static str {
+        "annotation_targets"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        let mut seen_ids = HashSet::new();
+        for (idx, entry) in file.entries.iter().enumerate() {
+            if let Some(id) = entry.id() {
+                seen_ids.insert(*id);
+            }
+            if let Entry::Annotation(a) = entry {
+                if !seen_ids.contains(&a.target_id) {
+                    ctx.report_at(
+                        idx,
+                        format!("Annotation {} references unknown entry {}", a.id, a.target_id),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Entries should appear in non-decreasing timestamp order.
+struct TimestampOrderingRule;
+
+impl Rule for TimestampOrderingRule {
+    fn name(&self) -> &'static str {
+        "timestamp_ordering"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+        let mut last_ts: Option<u64> = None;
+        for (idx, entry) in file.entries.iter().enumerate() {
+            if let Some(ts) = entry.timestamp() {
+                if let Some(last) = last_ts {
+                    if ts < last {
+                        ctx.report_at(
+                            idx,
+                            format!(
+                                "Entry {:?} has timestamp {} which is before previous entry's {}",
+                                entry.id(),
+                                ts,
+                                last
+                            ),
+                        );
+                    }
+                }
+                last_ts = Some(ts);
+            }
+        }
+    }
+}
+
+fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(SessionTimestampRule),
+        Box::new(DuplicateIdsRule),
+        Box::new(OrphanedToolResultsRule),
+        Box::new(OrphanedSubagentEndsRule),
+        Box::new(AnnotationTargetsRule),
+        Box::new(TimestampOrderingRule),
+    ]
+}
+
+/// Runs a configurable set of [`Rule`]s against a [`SpoolFile`].
+///
+/// Construct with [`Validator::new`] to get all built-in rules, then customize
+/// with [`Validator::with_rule`], [`Validator::disable_rule`], and
+/// [`Validator::set_severity`] before calling [`Validator::validate`].
+pub struct Validator {
+    rules: Vec<Box<dyn Rule>>,
+    disabled: HashSet<&'static str>,
+    severity_overrides: HashMap<&'static str, Severity>,
+}
+
+impl Validator {
+    /// Create a validator with all built-in rules registered.
+    pub fn new() -> Self {
+        Self {
+            rules: default_rules(),
+            disabled: HashSet::new(),
+            severity_overrides: HashMap::new(),
+        }
+    }
+
+    /// Create a validator with no rules registered.
+    pub fn empty() -> Self {
         Self {
-            check_duplicate_ids: true,
-            check_tool_references: true,
-            check_subagent_references: true,
-            check_annotation_references: true,
-            warn_out_of_order_timestamps: true,
+            rules: Vec::new(),
+            disabled: HashSet::new(),
+            severity_overrides: HashMap::new(),
         }
     }
+
+    /// Register an additional rule (built-in or custom).
+    pub fn with_rule(mut self, rule: Box<dyn Rule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Disable a rule by name; it will not run during [`Validator::validate`].
+    pub fn disable_rule(mut self, name: &'static str) -> Self {
+        self.disabled.insert(name);
+        self
+    }
+
+    /// Override the severity a named rule reports at, e.g. to promote
+    /// `timestamp_ordering` from a warning to a hard error.
+    pub fn set_severity(mut self, name: &'static str, severity: Severity) -> Self {
+        self.severity_overrides.insert(name, severity);
+        self
+    }
+
+    /// Run all enabled rules against `file`.
+    pub fn validate(&self, file: &SpoolFile) -> ValidationResult {
+        let mut diagnostics = Vec::new();
+
+        for rule in &self.rules {
+            if self.disabled.contains(rule.name()) {
+                continue;
+            }
+
+            let severity = self
+                .severity_overrides
+                .get(rule.name())
+                .copied()
+                .unwrap_or_else(|| rule.default_severity());
+
+            let mut sink = DiagnosticSink {
+                rule_name: rule.name(),
+                severity,
+                file,
+                out: &mut diagnostics,
+            };
+            rule.check(file, &mut sink);
+        }
+
+        ValidationResult { diagnostics }
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-/// Validation result.
+/// Result of running a [`Validator`] against a [`SpoolFile`].
 #[derive(Debug)]
 pub struct ValidationResult {
-    /// Hard errors that make the file invalid
-    pub errors: Vec<ValidationError>,
-    /// Warnings that don't make the file invalid
-    pub warnings: Vec<String>,
+    /// All diagnostics reported by the rules that ran, in rule-registration order.
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl ValidationResult {
-    /// Returns true if there are no errors.
+    /// Returns true if no diagnostic has `Severity::Error`.
     pub fn is_valid(&self) -> bool {
-        self.errors.is_empty()
+        !self.diagnostics.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    /// Diagnostics at `Severity::Error`.
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    /// Diagnostics at `Severity::Warning`.
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
     }
 }
 
-/// Validate a Spool file.
-pub fn validate(file: &SpoolFile, options: &ValidationOptions) -> ValidationResult {
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
+/// Validate a Spool file with all built-in rules at their default severities.
+pub fn validate_default(file: &SpoolFile) -> ValidationResult {
+    Validator::new().validate(file)
+}
+
+/// A reference to an entry that has not been defined yet, recorded so it can
+/// be resolved (or reported as orphaned) later in the stream.
+struct PendingRef {
+    rule: &'static str,
+    location: Option<SourceSpan>,
+    entry_index: Option<usize>,
+    entry_id: Option<EntryId>,
+    message: String,
+}
 
-    // Collect all entry IDs
-    let mut seen_ids = HashSet::new();
-    let mut tool_call_ids = HashSet::new();
-    let mut subagent_start_ids = HashSet::new();
+/// Validates a stream of [`Entry`] values in a single pass, without holding
+/// the whole file in memory.
+///
+/// Unlike [`Validator`], which requires a fully materialized [`SpoolFile`],
+/// this only tracks the set of live entry IDs and any references seen before
+/// their target was defined. Memory use is bounded by the number of
+/// outstanding tool calls / subagent starts and unresolved references, not by
+/// the length of the file, which matters for multi-gigabyte session logs.
+pub struct StreamingValidator {
+    position: usize,
+    seen_ids: HashSet<crate::EntryId>,
+    tool_call_ids: HashSet<crate::EntryId>,
+    subagent_start_ids: HashSet<crate::EntryId>,
+    pending_tool_results: HashMap<crate::EntryId, PendingRef>,
+    pending_subagent_ends: HashMap<crate::EntryId, PendingRef>,
+    last_ts: Option<u64>,
+    diagnostics: Vec<Diagnostic>,
+}
 
-    // Check session entry
-    if file.session.ts != 0 {
-        errors.push(ValidationError::SessionTimestampNotZero(file.session.ts));
+impl StreamingValidator {
+    /// Create a new streaming validator.
+    pub fn new() -> Self {
+        Self {
+            position: 0,
+            seen_ids: HashSet::new(),
+            tool_call_ids: HashSet::new(),
+            subagent_start_ids: HashSet::new(),
+            pending_tool_results: HashMap::new(),
+            pending_subagent_ends: HashMap::new(),
+            last_ts: None,
+            diagnostics: Vec::new(),
+        }
     }
 
-    let mut last_ts: Option<u64> = None;
+    /// Feed the next entry in stream order into the validator.
+    /// `location` is the entry's source location, if known.
+    pub fn push(&mut self, entry: &Entry, location: Option<SourceSpan>) {
+        let entry_index = self.position;
+        let entry_id = entry.id().copied();
 
-    for entry in &file.entries {
-        // Check for duplicate IDs
-        if options.check_duplicate_ids {
-            if let Some(id) = entry.id() {
-                if !seen_ids.insert(*id) {
-                    errors.push(ValidationError::DuplicateId(id.to_string()));
+        if self.position == 0 {
+            if let Entry::Session(session) = entry {
+                if session.ts != 0 {
+                    self.report(
+                        "session_timestamp",
+                        Severity::Error,
+                        location,
+                        entry_index,
+                        entry_id,
+                        format!("Session entry must have ts=0, found ts={}", session.ts),
+                    );
                 }
             }
         }
 
-        // Track tool calls and subagent starts
-        match entry {
-            Entry::ToolCall(tc) => {
-                tool_call_ids.insert(tc.id);
-            }
-            Entry::SubagentStart(ss) => {
-                subagent_start_ids.insert(ss.id);
+        if let Some(id) = entry.id() {
+            if !self.seen_ids.insert(*id) {
+                self.report(
+                    "duplicate_ids",
+                    Severity::Error,
+                    location,
+                    entry_index,
+                    entry_id,
+                    format!("Duplicate entry ID: {}", id),
+                );
             }
-            _ => {}
         }
 
-        // Check references
         match entry {
-            Entry::ToolResult(tr) => {
-                if options.check_tool_references && !tool_call_ids.contains(&tr.call_id) {
-                    errors.push(ValidationError::OrphanedToolResult {
-                        result_id: tr.id.to_string(),
-                        call_id: tr.call_id.to_string(),
-                    });
-                }
+            Entry::ToolCall(tc) => {
+                self.tool_call_ids.insert(tc.id);
+                self.pending_tool_results.remove(&tc.id);
             }
-            Entry::SubagentEnd(se) => {
-                if options.check_subagent_references && !subagent_start_ids.contains(&se.start_id) {
-                    errors.push(ValidationError::OrphanedSubagentEnd {
-                        end_id: se.id.to_string(),
-                        start_id: se.start_id.to_string(),
-                    });
-                }
+            Entry::ToolResult(tr) if !self.tool_call_ids.contains(&tr.call_id) => {
+                self.pending_tool_results.insert(
+                    tr.call_id,
+                    PendingRef {
+                        rule: "orphaned_tool_results",
+                        location,
+                        entry_index: Some(entry_index),
+                        entry_id,
+                        message: format!(
+                            "Tool result {} references unknown tool call {}",
+                            tr.id, tr.call_id
+                        ),
+                    },
+                );
             }
-            Entry::Annotation(a) => {
-                if options.check_annotation_references && !seen_ids.contains(&a.target_id) {
-                    warnings.push(format!(
-                        "Annotation {} references unknown entry {}",
-                        a.id, a.target_id
-                    ));
-                }
+            Entry::SubagentStart(ss) => {
+                self.subagent_start_ids.insert(ss.id);
+                self.pending_subagent_ends.remove(&ss.id);
+            }
+            Entry::SubagentEnd(se) if !self.subagent_start_ids.contains(&se.start_id) => {
+                self.pending_subagent_ends.insert(
+                    se.start_id,
+                    PendingRef {
+                        rule: "orphaned_subagent_ends",
+                        location,
+                        entry_index: Some(entry_index),
+                        entry_id,
+                        message: format!(
+                            "Subagent end {} references unknown subagent start {}",
+                            se.id, se.start_id
+                        ),
+                    },
+                );
+            }
+            Entry::Annotation(a) if !self.seen_ids.contains(&a.target_id) => {
+                self.report(
+                    "annotation_targets",
+                    Severity::Warning,
+                    location,
+                    entry_index,
+                    entry_id,
+                    format!("Annotation {} references unknown entry {}", a.id, a.target_id),
+                );
             }
             _ => {}
         }
 
-        // Check timestamp ordering
-        if options.warn_out_of_order_timestamps {
-            if let Some(ts) = entry.timestamp() {
-                if let Some(last) = last_ts {
-                    if ts < last {
-                        warnings.push(format!(
+        if let Some(ts) = entry.timestamp() {
+            if let Some(last) = self.last_ts {
+                if ts < last {
+                    self.report(
+                        "timestamp_ordering",
+                        Severity::Warning,
+                        location,
+                        entry_index,
+                        entry_id,
+                        format!(
                             "Entry {:?} has timestamp {} which is before previous entry's {}",
                             entry.id(),
                             ts,
                             last
-                        ));
-                    }
+                        ),
+                    );
                 }
-                last_ts = Some(ts);
             }
+            self.last_ts = Some(ts);
         }
+
+        self.position += 1;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn report(
+        &mut self,
+        rule: &'static str,
+        severity: Severity,
+        location: Option<SourceSpan>,
+        entry_index: usize,
+        entry_id: Option<EntryId>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity,
+            message,
+            location,
+            entry_index: Some(entry_index),
+            entry_id,
+        });
+    }
+
+    /// Diagnostics reported so far, without consuming the validator - for a
+    /// caller that needs to react to a bad entry the moment it arrives
+    /// (e.g. a live recording server) rather than waiting for [`finish`](Self::finish).
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
     }
 
-    ValidationResult { errors, warnings }
+    /// Finish the stream, turning any references still unresolved into
+    /// orphan diagnostics, and return the accumulated result.
+    pub fn finish(mut self) -> ValidationResult {
+        for pending in self.pending_tool_results.into_values() {
+            self.diagnostics.push(Diagnostic {
+                rule: pending.rule,
+                severity: Severity::Error,
+                message: pending.message,
+                location: pending.location,
+                entry_index: pending.entry_index,
+                entry_id: pending.entry_id,
+            });
+        }
+        for pending in self.pending_subagent_ends.into_values() {
+            self.diagnostics.push(Diagnostic {
+                rule: pending.rule,
+                severity: Severity::Error,
+                message: pending.message,
+                location: pending.location,
+                entry_index: pending.entry_index,
+                entry_id: pending.entry_id,
+            });
+        }
+
+        ValidationResult {
+            diagnostics: self.diagnostics,
+        }
+    }
 }
 
-/// Validate a Spool file with default options.
-pub fn validate_default(file: &SpoolFile) -> ValidationResult {
-    validate(file, &ValidationOptions::default())
+impl Default for StreamingValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validate a stream of entries in a single pass without materializing a [`SpoolFile`].
+pub fn validate_streaming(entries: impl Iterator<Item = Entry>) -> ValidationResult {
+    let mut validator = StreamingValidator::new();
+    for entry in entries {
+        validator.push(&entry, None);
+    }
+    validator.finish()
+}
+
+/// Validate a JSONL reader in a single pass without materializing a [`SpoolFile`].
+///
+/// Lines that fail to parse are skipped (for forward compatibility), matching
+/// [`SpoolFile::from_reader`]'s behavior.
+pub fn validate_reader<R: std::io::BufRead>(reader: R) -> crate::SpoolResult<ValidationResult> {
+    let mut validator = StreamingValidator::new();
+    let mut byte_offset: usize = 0;
+
+    for (line_num, line_result) in reader.lines().enumerate() {
+        let line = line_result?;
+        let line_num = line_num + 1; // 1-indexed
+        let line_start = byte_offset;
+        let line_end = line_start + line.len();
+        byte_offset = line_end + 1;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Ok(entry) = serde_json::from_str::<Entry>(&line) {
+            validator.push(
+                &entry,
+                Some(SourceSpan {
+                    line: line_num,
+                    byte_range: (line_start, line_end),
+                }),
+            );
+        }
+    }
+
+    Ok(validator.finish())
 }
 
 #[cfg(test)]
@@ -162,10 +682,15 @@ mod tests {
             entry_count: None,
             tools_used: None,
             files_modified: None,
+            tool_time_ms: None,
+            tool_invocations: None,
+            subagent_time_ms: None,
+            file_changes: None,
             first_prompt: None,
             schema_url: None,
             trimmed: None,
             ended: None,
+            content_hash: None,
             extra: HashMap::new(),
         }
     }
@@ -202,10 +727,7 @@ mod tests {
 
         let result = validate_default(&file);
         assert!(!result.is_valid());
-        assert!(result
-            .errors
-            .iter()
-            .any(|e| matches!(e, ValidationError::DuplicateId(_))));
+        assert!(result.errors().any(|d| d.rule == "duplicate_ids"));
     }
 
     #[test]
@@ -217,6 +739,200 @@ mod tests {
 
         let result = validate_default(&file);
         assert!(result.is_valid()); // Out of order is a warning, not error
-        assert!(!result.warnings.is_empty());
+        assert!(result.warnings().any(|d| d.rule == "timestamp_ordering"));
+    }
+
+    #[test]
+    fn test_disable_rule() {
+        let session = make_session();
+        let mut file = SpoolFile::new(session);
+
+        let id = Uuid::new_v4();
+        file.add_entry(make_prompt(id, 100, "First"));
+        file.add_entry(make_prompt(id, 200, "Duplicate"));
+
+        let result = Validator::new().disable_rule("duplicate_ids").validate(&file);
+        assert!(result.is_valid());
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_promote_warning_to_error() {
+        let session = make_session();
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(Uuid::new_v4(), 200, "Second"));
+        file.add_entry(make_prompt(Uuid::new_v4(), 100, "First but later"));
+
+        let result = Validator::new()
+            .set_severity("timestamp_ordering", Severity::Error)
+            .validate(&file);
+
+        assert!(!result.is_valid());
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "timestamp_ordering" && d.severity == Severity::Error));
+    }
+
+    struct NoPromptsRule;
+
+    impl Rule for NoPromptsRule {
+        fn name(&self) -> &'static str {
+            "custom_no_prompts"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Info
+        }
+
+        fn check(&self, file: &SpoolFile, ctx: &mut DiagnosticSink) {
+            for entry in &file.entries {
+                if matches!(entry, Entry::Prompt(_)) {
+                    ctx.report("File contains a Prompt entry");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let session = make_session();
+        let mut file = SpoolFile::new(session);
+        file.add_entry(make_prompt(Uuid::new_v4(), 100, "Hello"));
+
+        let result = Validator::empty().with_rule(Box::new(NoPromptsRule)).validate(&file);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule, "custom_no_prompts");
+        assert_eq!(result.diagnostics[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_diagnostics_carry_source_location() {
+        let session_line = r#"{"id":"00000000-0000-0000-0000-000000000000","ts":0,"type":"session","version":"1.0","agent":"test","recorded_at":"2025-01-01T00:00:00Z"}"#;
+        let dup_id = "00000000-0000-0000-0000-000000000001";
+        let prompt_a = format!(
+            r#"{{"id":"{}","ts":100,"type":"prompt","content":"First"}}"#,
+            dup_id
+        );
+        let prompt_b = format!(
+            r#"{{"id":"{}","ts":200,"type":"prompt","content":"Duplicate"}}"#,
+            dup_id
+        );
+        let content = format!("{}\n{}\n{}\n", session_line, prompt_a, prompt_b);
+        let file = SpoolFile::from_reader(std::io::Cursor::new(content)).unwrap();
+
+        let result = validate_default(&file);
+        let dup = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "duplicate_ids")
+            .expect("duplicate_ids diagnostic");
+
+        assert_eq!(dup.location.map(|l| l.line), Some(3));
+    }
+
+    #[test]
+    fn test_programmatic_entries_have_no_location() {
+        let session = make_session();
+        let mut file = SpoolFile::new(session);
+        let id = Uuid::new_v4();
+        file.add_entry(make_prompt(id, 100, "First"));
+        file.add_entry(make_prompt(id, 200, "Duplicate"));
+
+        let result = validate_default(&file);
+        let dup = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "duplicate_ids")
+            .expect("duplicate_ids diagnostic");
+
+        assert!(dup.location.is_none());
+    }
+
+    fn make_tool_call(id: Uuid, ts: u64) -> Entry {
+        Entry::ToolCall(ToolCallEntry {
+            id,
+            ts,
+            tool: "bash".to_string(),
+            input: serde_json::json!({}),
+            subagent_id: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    fn make_tool_result(id: Uuid, ts: u64, call_id: Uuid) -> Entry {
+        Entry::ToolResult(ToolResultEntry {
+            id,
+            ts,
+            call_id,
+            output: None,
+            error: None,
+            truncated: None,
+            original_bytes: None,
+            subagent_id: None,
+            redacted: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_streaming_resolves_forward_tool_reference() {
+        let call_id = Uuid::new_v4();
+        let entries = vec![
+            Entry::Session(make_session()),
+            make_tool_result(Uuid::new_v4(), 100, call_id),
+            make_tool_call(call_id, 50),
+        ];
+
+        let result = validate_streaming(entries.into_iter());
+        assert!(!result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "orphaned_tool_results"));
+    }
+
+    #[test]
+    fn test_streaming_reports_unresolved_tool_reference_at_end() {
+        let entries = vec![
+            Entry::Session(make_session()),
+            make_tool_result(Uuid::new_v4(), 100, Uuid::new_v4()),
+        ];
+
+        let result = validate_streaming(entries.into_iter());
+        assert!(!result.is_valid());
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "orphaned_tool_results" && d.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_streaming_matches_eager_validator_on_duplicate_ids() {
+        let id = Uuid::new_v4();
+        let entries = vec![
+            Entry::Session(make_session()),
+            make_prompt(id, 100, "First"),
+            make_prompt(id, 200, "Duplicate"),
+        ];
+
+        let result = validate_streaming(entries.into_iter());
+        assert!(!result.is_valid());
+        assert!(result.diagnostics.iter().any(|d| d.rule == "duplicate_ids"));
+    }
+
+    #[test]
+    fn test_validate_reader_tracks_line_numbers() {
+        let session_line = r#"{"id":"00000000-0000-0000-0000-000000000000","ts":0,"type":"session","version":"1.0","agent":"test","recorded_at":"2025-01-01T00:00:00Z"}"#;
+        let bad_result = r#"{"id":"00000000-0000-0000-0000-000000000001","ts":100,"type":"tool_result","call_id":"00000000-0000-0000-0000-000000000099"}"#;
+        let content = format!("{}\n{}\n", session_line, bad_result);
+
+        let result = validate_reader(std::io::Cursor::new(content)).unwrap();
+        let orphan = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "orphaned_tool_results")
+            .expect("orphaned_tool_results diagnostic");
+
+        assert_eq!(orphan.location.map(|l| l.line), Some(2));
     }
 }
@@ -0,0 +1,69 @@
+//! Client side of the live recording transport: forwards entries to a
+//! [`crate::SessionServer`] as they're produced.
+
+use crate::frame::{read_frame, write_frame};
+use crate::protocol::{Handshake, HandshakeAck, Message, FORMAT_VERSION};
+use anyhow::{Context, Result};
+use spool_format::{BroadcastEntry, Entry, EntryId, SpoolError, SpoolResult, Transport};
+use std::io::BufWriter;
+use std::net::{TcpStream, ToSocketAddrs};
+
+fn json_err(e: serde_json::Error) -> SpoolError {
+    SpoolError::Json { line: 0, message: e.to_string(), source: e }
+}
+
+/// A live connection to a `spool serve` listener, handed to a
+/// [`spool_format::Broadcaster`] (via [`Transport`]) or driven directly
+/// with [`send_entry`](Self::send_entry).
+pub struct NetClient {
+    writer: BufWriter<TcpStream>,
+    /// How many entries the server already had for this session before
+    /// this connection - e.g. after a reconnect - so the caller knows how
+    /// many of its own already-produced entries to skip re-sending rather
+    /// than duplicating them.
+    pub resume_from: usize,
+}
+
+impl NetClient {
+    /// Connect to `addr` and hand off `session_id` (the same UUID across
+    /// reconnects resumes the recording instead of starting a new one).
+    pub fn connect(addr: impl ToSocketAddrs, agent: &str, session_id: EntryId) -> Result<Self> {
+        let stream = TcpStream::connect(addr).context("Failed to connect")?;
+        let mut writer = BufWriter::new(stream.try_clone().context("Failed to clone stream")?);
+
+        let handshake = Handshake {
+            format_version: FORMAT_VERSION,
+            agent: agent.to_string(),
+            session_id,
+        };
+        write_frame(&mut writer, &serde_json::to_vec(&handshake)?).context("Failed to send handshake")?;
+
+        let mut reader = std::io::BufReader::new(stream);
+        let ack_bytes = read_frame(&mut reader).context("Failed to read handshake ack")?;
+        let ack: HandshakeAck = serde_json::from_slice(&ack_bytes).context("Malformed handshake ack")?;
+
+        Ok(Self { writer, resume_from: ack.resume_from })
+    }
+
+    /// Forward one entry to the server.
+    pub fn send_entry(&mut self, entry: &Entry) -> SpoolResult<()> {
+        let message = Message::Entry(Box::new(entry.clone()));
+        let payload = serde_json::to_vec(&message).map_err(json_err)?;
+        write_frame(&mut self.writer, &payload)?;
+        Ok(())
+    }
+
+    /// Tell the server no more entries are coming and close the
+    /// connection.
+    pub fn close(mut self) -> SpoolResult<()> {
+        let payload = serde_json::to_vec(&Message::End).map_err(json_err)?;
+        write_frame(&mut self.writer, &payload)?;
+        Ok(())
+    }
+}
+
+impl Transport for NetClient {
+    fn send(&mut self, update: &BroadcastEntry) -> SpoolResult<()> {
+        self.send_entry(&update.entry)
+    }
+}
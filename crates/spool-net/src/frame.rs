@@ -0,0 +1,92 @@
+//! Length-prefixed framing: a 4-byte big-endian length followed by that
+//! many bytes of payload. Both the handshake and the per-entry messages in
+//! [`crate::protocol`] are JSON-encoded then sent through the same
+//! `write_frame`/`read_frame` pair, so the wire format has exactly one
+//! place that knows where a message starts and ends.
+
+use std::io::{self, Read, Write};
+
+/// Largest frame accepted from the wire - a sanity bound so a corrupt (or
+/// hostile) length prefix can't turn into an attempt to allocate gigabytes.
+const MAX_FRAME_BYTES: u32 = 64 * 1024 * 1024;
+
+/// Write `payload` as one frame and flush, so the receiver sees it
+/// immediately rather than sitting in a `BufWriter`.
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame exceeds u32::MAX bytes"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Read one frame's payload, or an `UnexpectedEof` error if the connection
+/// closed before a complete length prefix arrived (the caller's signal
+/// that the other side is done and it's time to stop reading).
+pub fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len_buf = [0u8; 4];
+    reader.read_exact(&mut len_buf)?;
+    let len = u32::from_be_bytes(len_buf);
+    if len > MAX_FRAME_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds {MAX_FRAME_BYTES} byte limit"),
+        ));
+    }
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trips_a_frame() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, b"hello").unwrap();
+
+        let mut cursor = Cursor::new(buf);
+        let payload = read_frame(&mut cursor).unwrap();
+        assert_eq!(payload, b"hello");
+    }
+
+    #[test]
+    fn round_trips_an_empty_frame() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, &[]).unwrap();
+
+        let mut cursor = Cursor::new(buf);
+        let payload = read_frame(&mut cursor).unwrap();
+        assert!(payload.is_empty());
+    }
+
+    #[test]
+    fn two_frames_in_sequence_dont_interfere() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, b"first").unwrap();
+        write_frame(&mut buf, b"second").unwrap();
+
+        let mut cursor = Cursor::new(buf);
+        assert_eq!(read_frame(&mut cursor).unwrap(), b"first");
+        assert_eq!(read_frame(&mut cursor).unwrap(), b"second");
+    }
+
+    #[test]
+    fn an_oversized_length_prefix_is_rejected_before_allocating() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(MAX_FRAME_BYTES + 1).to_be_bytes());
+
+        let mut cursor = Cursor::new(buf);
+        assert!(read_frame(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn a_truncated_connection_reads_as_unexpected_eof() {
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let err = read_frame(&mut cursor).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
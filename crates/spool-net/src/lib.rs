@@ -0,0 +1,29 @@
+//! # spool-net
+//!
+//! Network transport for live remote session recording.
+//!
+//! `spool_format::broadcast` ships a [`spool_format::Transport`] extension
+//! point but no concrete implementation, since `spool-format` itself has no
+//! business knowing about sockets. This crate is that implementation: an
+//! agent can stream entries to a `spool serve` listener as they're
+//! produced, instead of only writing a complete `.spool` file once the
+//! session ends.
+//!
+//! - [`frame`] - length-prefixed JSON framing shared by both directions.
+//! - [`protocol`] - the handshake and per-entry messages sent over a frame.
+//! - [`SessionServer`] - binds a TCP listener, appends each session's
+//!   entries to an open `.spool` file, and resumes a dropped connection by
+//!   session UUID.
+//! - [`NetClient`] - connects to a listener and forwards entries,
+//!   implementing [`spool_format::Transport`] so it can be handed straight
+//!   to a [`spool_format::Broadcaster`].
+
+mod client;
+mod frame;
+mod protocol;
+mod server;
+
+pub use client::NetClient;
+pub use frame::{read_frame, write_frame};
+pub use protocol::{Handshake, HandshakeAck, Message, FORMAT_VERSION};
+pub use server::SessionServer;
@@ -0,0 +1,42 @@
+//! Wire messages exchanged over a [`crate::frame`]d connection.
+
+use serde::{Deserialize, Serialize};
+use spool_format::{Entry, EntryId};
+
+/// Bumped whenever a breaking change is made to [`Handshake`], [`HandshakeAck`],
+/// or [`Message`]. [`crate::SessionServer`] rejects a handshake carrying a
+/// version it doesn't recognize rather than guessing at compatibility.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Sent once by the client immediately after connecting, before any
+/// [`Message`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handshake {
+    pub format_version: u32,
+    /// Agent identifier, matching [`spool_format::SessionEntry::agent`].
+    pub agent: String,
+    /// Stable identifier for the session being recorded. Reusing the same
+    /// `session_id` on a later connection is how a dropped connection
+    /// resumes rather than starting a second, duplicate recording.
+    pub session_id: EntryId,
+}
+
+/// The server's reply to a [`Handshake`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeAck {
+    /// How many entries (including the session entry) the server already
+    /// has on disk for this `session_id` - `0` for a session it's never
+    /// seen before. The client skips re-sending anything at or before this
+    /// count, so a reconnect after a crash doesn't duplicate entries.
+    pub resume_from: usize,
+}
+
+/// One message sent by the client after the handshake completes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// The next entry in recording order.
+    Entry(Box<Entry>),
+    /// No more entries are coming; the server should flush and close.
+    End,
+}
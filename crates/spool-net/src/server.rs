@@ -0,0 +1,137 @@
+//! TCP listener that accepts client connections and appends each session's
+//! entries to an open `.spool` file in real time.
+
+use crate::frame::{read_frame, write_frame};
+use crate::protocol::{Handshake, HandshakeAck, Message, FORMAT_VERSION};
+use anyhow::{bail, Context, Result};
+use spool_format::{Entry, EntryId, SpoolFile, StreamingValidator};
+use std::fs::OpenOptions;
+use std::io::{BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// How many newly-appended entries accumulate before the writer is flushed
+/// to disk. `1` means every entry is flushed as it arrives - the whole
+/// point of live recording is surviving a crash with as little as possible
+/// unflushed, so this trades a little more `fsync` pressure for that
+/// guarantee.
+const FLUSH_EVERY: usize = 1;
+
+/// Accepts connections on a TCP address and appends each session's entries
+/// to `<base_dir>/<session_id>.spool`, creating the file on first contact
+/// and reopening it in append mode on a reconnect.
+pub struct SessionServer {
+    base_dir: PathBuf,
+}
+
+impl SessionServer {
+    /// A server that writes sessions under `base_dir`, creating it (and
+    /// any missing parents) the first time [`serve`](Self::serve) runs.
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self { base_dir: base_dir.into() }
+    }
+
+    /// Bind `addr` and serve forever, blocking the calling thread. Each
+    /// accepted connection is handled on its own thread, so one stalled
+    /// agent can't block the others from flushing.
+    pub fn serve(&self, addr: impl ToSocketAddrs) -> Result<()> {
+        std::fs::create_dir_all(&self.base_dir)
+            .with_context(|| format!("Failed to create {:?}", self.base_dir))?;
+        let listener = TcpListener::bind(addr).context("Failed to bind listener")?;
+        for stream in listener.incoming() {
+            let stream = stream.context("Failed to accept connection")?;
+            let base_dir = self.base_dir.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &base_dir) {
+                    eprintln!("spool serve: connection error: {e:#}");
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+fn session_path(base_dir: &Path, session_id: &EntryId) -> PathBuf {
+    base_dir.join(format!("{session_id}.spool"))
+}
+
+fn handle_connection(stream: TcpStream, base_dir: &Path) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone().context("Failed to clone stream")?);
+    let mut writer = BufWriter::new(stream);
+
+    let handshake_bytes = read_frame(&mut reader).context("Failed to read handshake")?;
+    let handshake: Handshake =
+        serde_json::from_slice(&handshake_bytes).context("Malformed handshake")?;
+    if handshake.format_version != FORMAT_VERSION {
+        bail!(
+            "unsupported format_version {} (expected {FORMAT_VERSION})",
+            handshake.format_version
+        );
+    }
+
+    let path = session_path(base_dir, &handshake.session_id);
+    let existing = path.exists().then(|| SpoolFile::from_path(&path)).transpose()?;
+    let resume_from = existing.as_ref().map(|f| f.entries.len()).unwrap_or(0);
+
+    // Seed the validator with whatever's already on disk so duplicate-id
+    // and ordering checks see the whole session, not just this
+    // connection's tail.
+    let mut validator = StreamingValidator::new();
+    if let Some(file) = &existing {
+        for entry in &file.entries {
+            validator.push(entry, None);
+        }
+    }
+
+    write_frame(&mut writer, &serde_json::to_vec(&HandshakeAck { resume_from })?)
+        .context("Failed to send handshake ack")?;
+
+    let mut file = BufWriter::new(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {path:?}"))?,
+    );
+
+    let mut unflushed = 0;
+    loop {
+        let frame = match read_frame(&mut reader) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e).context("Failed to read message"),
+        };
+        let message: Message = serde_json::from_slice(&frame).context("Malformed message")?;
+        match message {
+            Message::Entry(entry) => {
+                let before = validator.diagnostics().len();
+                validator.push(&entry, None);
+                if validator.diagnostics().len() > before {
+                    // Forward-compatible, matching `SpoolFile::from_reader`'s
+                    // stance on a bad line: log but keep recording rather
+                    // than drop the connection over one invalid entry.
+                    eprintln!(
+                        "spool serve: entry failed validation: {}",
+                        validator.diagnostics().last().map(|d| d.message.as_str()).unwrap_or("")
+                    );
+                }
+                append_entry(&mut file, &entry)?;
+                unflushed += 1;
+                if unflushed >= FLUSH_EVERY {
+                    file.flush()?;
+                    unflushed = 0;
+                }
+            }
+            Message::End => break,
+        }
+    }
+    file.flush().context("Failed to flush on close")?;
+    Ok(())
+}
+
+fn append_entry<W: Write>(writer: &mut W, entry: &Entry) -> Result<()> {
+    let json = serde_json::to_string(entry).context("Failed to serialize entry")?;
+    writeln!(writer, "{json}").context("Failed to append entry")?;
+    Ok(())
+}